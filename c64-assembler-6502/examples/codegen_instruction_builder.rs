@@ -1,5 +1,5 @@
 use c64_assembler_6502::{
-    isa_6502,
+    isa_6502, isa_6502_illegal,
     opcodes::{
         NO_ABSOLUTE, NO_ABSOLUTE_X, NO_ABSOLUTE_Y, NO_ACCUMULATOR, NO_IMMEDIATE, NO_IMPLIED, NO_INDEXED_INDIRECT,
         NO_INDIRECT, NO_INDIRECT_INDEXED, NO_RELATIVE, NO_ZEROPAGE, NO_ZEROPAGE_X, NO_ZEROPAGE_Y,
@@ -9,12 +9,12 @@ use c64_assembler_6502::{
 fn main() {
     let mut lines = Vec::<String>::default();
 
-    for def in isa_6502() {
+    for def in isa_6502().iter().chain(&isa_6502_illegal()) {
         if def.implied != NO_IMPLIED {
             lines.push(format!(
                 "
                 /// Record a new {0} instruction (addressing mode is implied).
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -26,7 +26,7 @@ fn main() {
                     self.add_instruction(Operation::{1}, AddressMode::Implied);
                     self
                 }}",
-                def.instruction.to_string(),
+                def.instruction,
                 def.instruction.to_uppercase()
             ));
         } else {
@@ -36,9 +36,8 @@ fn main() {
                 fn {0}(&mut self, addressing_mode: AddressMode) -> &mut Self {{
                     self.add_instruction(Operation::{1}, addressing_mode);
                     self
-                }}
-                ",
-                def.instruction.to_string(),
+                }}",
+                def.instruction,
                 def.instruction.to_uppercase()
             ));
         }
@@ -58,7 +57,7 @@ fn main() {
                 }}
 
                 /// Record a {0} instruction with lower byte of an address.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -67,14 +66,12 @@ fn main() {
                 ///     .label(\"test_data\")
                 ///     .build();
                 /// ```
-                pub fn {0}_imm_low(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::Immediate(Immediate::Low(
-                        AddressReference::new(address_name)
-                    )))
+                pub fn {0}_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::Immediate(Immediate::Low(address.into())))
                 }}
 
                 /// Record a {0} instruction with higher byte of an address.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -83,20 +80,33 @@ fn main() {
                 ///     .label(\"test_data\")
                 ///     .build();
                 /// ```
-                pub fn {0}_imm_high(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::Immediate(Immediate::High(
-                        AddressReference::new(address_name)
-                    )))
+                pub fn {0}_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::Immediate(Immediate::High(address.into())))
                 }}
-                ",
-                def.instruction.to_string()
+
+                /// Record a {0} instruction with an expression that evaluates to a byte.
+                ///
+                /// # Example
+                /// ```
+                /// use c64_assembler::builder::InstructionBuilder;
+                /// use c64_assembler::memory::expression::Expression;
+                /// let instructions = InstructionBuilder::default()
+                ///     .{0}_imm_expr(Expression::name(\"test_end\") - Expression::name(\"test_start\"))
+                ///     .label(\"test_start\")
+                ///     .label(\"test_end\")
+                ///     .build();
+                /// ```
+                pub fn {0}_imm_expr(&mut self, expression: Expression) -> &mut Self {{
+                    self.{0}(AddressMode::Immediate(Immediate::Expression(expression)))
+                }}",
+                def.instruction
             ));
         }
         if def.accumulator != NO_ACCUMULATOR {
             lines.push(format!(
                 "
                 /// Record a {0} instruction that uses accumulator as address mode.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -106,17 +116,15 @@ fn main() {
                 /// ```
                 pub fn {0}_acc(&mut self) -> &mut Self {{
                     self.{0}(AddressMode::Accumulator)
-                }}
-                ",
-                def.instruction.to_string()
+                }}",
+                def.instruction
             ));
         }
-
         if def.absolute != NO_ABSOLUTE || def.zeropage != NO_ZEROPAGE {
             lines.push(format!(
                 "
-                /// Record a {0} instruction that use an absolute address. 
-                /// 
+                /// Record a {0} instruction that use an absolute address.
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -125,13 +133,13 @@ fn main() {
                 ///     .label(\"test_label\")
                 ///     .build();
                 /// ```
-                pub fn {0}_addr(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::Absolute(AddressReference::new(address_name)))
+                pub fn {0}_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::Absolute(address.into()))
                 }}
-        
+
                 /// Record a {0} instruction that use an absolute address with an offset.
                 /// Offset is in bytes.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -144,16 +152,15 @@ fn main() {
                     self.{0}(AddressMode::Absolute(AddressReference::with_offset(
                         address_name, offset
                     )))
-                }}
-                ",
-                def.instruction.to_string()
+                }}",
+                def.instruction
             ));
         }
         if def.absolute_x != NO_ABSOLUTE_X || def.zeropage_x != NO_ZEROPAGE_X {
             lines.push(format!(
                 "
                 /// Record a {0} instructon that use an absolute address with x-register as indexer.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -163,18 +170,17 @@ fn main() {
                 ///     .label(\"test_label\")
                 ///     .build();
                 /// ```
-                pub fn {0}_addr_x(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::AbsoluteX(AddressReference::new(address_name)))
-                }}
-                ",
-                def.instruction.to_string()
+                pub fn {0}_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::AbsoluteX(address.into()))
+                }}",
+                def.instruction
             ));
         }
         if def.absolute_y != NO_ABSOLUTE_Y || def.zeropage_y != NO_ZEROPAGE_Y {
             lines.push(format!(
                 "
                 /// Record a {0} instructon that use an absolute address with y-register as indexer.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -184,18 +190,17 @@ fn main() {
                 ///     .label(\"test_label\")
                 ///     .build();
                 /// ```
-                pub fn {0}_addr_y(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::AbsoluteY(AddressReference::new(address_name)))
-                }}
-                ",
-                def.instruction.to_string()
+                pub fn {0}_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::AbsoluteY(address.into()))
+                }}",
+                def.instruction
             ));
         }
         if def.relative != NO_RELATIVE {
             lines.push(format!(
                 "
-                /// Record a {0} instruction that use  relativeeeeeeeee address. 
-                /// 
+                /// Record a {0} instruction that use  relativeeeeeeeee address.
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -204,13 +209,13 @@ fn main() {
                 ///     .label(\"test_label\")
                 ///     .build();
                 /// ```
-                pub fn {0}_addr(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::Relative(AddressReference::new(address_name)))
+                pub fn {0}_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::Relative(address.into()))
                 }}
-        
+
                 /// Record a {0} instruction that use a relative address with an offset.
                 /// Offset is in bytes.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -223,16 +228,15 @@ fn main() {
                     self.{0}(AddressMode::Relative(AddressReference::with_offset(
                         address_name, offset
                     )))
-                }}
-                ",
-                def.instruction.to_string()
+                }}",
+                def.instruction
             ));
         }
         if def.indirect != NO_INDIRECT {
             lines.push(format!(
                 "
                 /// Record a {0} instruction that uses indirect addressing mode.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -241,18 +245,17 @@ fn main() {
                 ///     .label(\"test_label\")
                 ///     .build();
                 /// ```
-                pub fn {0}_ind(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::Indirect(AddressReference::new(address_name)))
-                }}
-                ",
-                def.instruction.to_string()
+                pub fn {0}_ind(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::Indirect(address.into()))
+                }}",
+                def.instruction
             ));
         }
         if def.indexed_indirect != NO_INDEXED_INDIRECT {
             lines.push(format!(
                 "
                 /// Record a {0} instruction that uses indexed indirect addressing mode.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -262,18 +265,17 @@ fn main() {
                 ///     .label(\"test_label\")
                 ///     .build();
                 /// ```
-                pub fn {0}_ind_x(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
-                }}
-                ",
-                def.instruction.to_string()
+                pub fn {0}_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::IndexedIndirect(address.into()))
+                }}",
+                def.instruction
             ));
         }
         if def.indirect_indexed != NO_INDIRECT_INDEXED {
             lines.push(format!(
                 "
                 /// Record a {0} instruction that uses indirect indexed addressing mode.
-                /// 
+                ///
                 /// # Example
                 /// ```
                 /// use c64_assembler::builder::InstructionBuilder;
@@ -283,11 +285,10 @@ fn main() {
                 ///     .label(\"test_label\")
                 ///     .build();
                 /// ```
-                pub fn {0}_ind_y(&mut self, address_name: &str) -> &mut Self {{
-                    self.{0}(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
-                }}
-                ",
-                def.instruction.to_string()
+                pub fn {0}_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {{
+                    self.{0}(AddressMode::IndirectIndexed(address.into()))
+                }}",
+                def.instruction
             ));
         }
     }
@@ -1,6 +1,6 @@
 use c64_assembler_6502::{
     instruction::InstructionDef,
-    isa_6502,
+    isa_6502, isa_6502_illegal,
     opcodes::{
         OpCode, NO_ABSOLUTE, NO_ABSOLUTE_X, NO_ABSOLUTE_Y, NO_ACCUMULATOR, NO_IMMEDIATE, NO_IMPLIED,
         NO_INDEXED_INDIRECT, NO_INDIRECT, NO_INDIRECT_INDEXED, NO_RELATIVE, NO_ZEROPAGE, NO_ZEROPAGE_X, NO_ZEROPAGE_Y,
@@ -8,11 +8,11 @@ use c64_assembler_6502::{
 };
 
 fn main() {
-    fn format(def: &InstructionDef, op_code: OpCode, no_opcode: OpCode, opcode_str: &str) -> String {
+    fn format(def: &InstructionDef, op_code: Option<OpCode>, no_opcode: Option<OpCode>, opcode_str: &str) -> String {
         if op_code == no_opcode {
             format!("NO_{}", opcode_str)
         } else {
-            format!("{}_{}", def.instruction.to_uppercase(), opcode_str)
+            format!("Some({}_{})", def.instruction.to_uppercase(), opcode_str)
         }
     }
 
@@ -37,6 +37,22 @@ pub const OPCODES_{0}:InstructionDef = InstructionDef {{
     indirect: {12},
     indexed_indirect: {13},
     indirect_indexed: {14},
+    cycles: Cycles {{
+        implied: {15},
+        immediate: {16},
+        accumulator: {17},
+        absolute: {18},
+        absolute_x: {19},
+        absolute_y: {20},
+        zeropage: {21},
+        zeropage_x: {22},
+        zeropage_y: {23},
+        relative: {24},
+        indirect: {25},
+        indexed_indirect: {26},
+        indirect_indexed: {27},
+    }},
+    page_penalty: PagePenalty::{28:?},
 }};",
             def.instruction.to_uppercase(),
             def.instruction.to_string(),
@@ -53,12 +69,26 @@ pub const OPCODES_{0}:InstructionDef = InstructionDef {{
             format(def, def.indirect, NO_INDIRECT, "INDIRECT"),
             format(def, def.indexed_indirect, NO_INDEXED_INDIRECT, "INDEXED_INDIRECT"),
             format(def, def.indirect_indexed, NO_INDIRECT_INDEXED, "INDIRECT_INDEXED"),
+            def.cycles.implied,
+            def.cycles.immediate,
+            def.cycles.accumulator,
+            def.cycles.absolute,
+            def.cycles.absolute_x,
+            def.cycles.absolute_y,
+            def.cycles.zeropage,
+            def.cycles.zeropage_x,
+            def.cycles.zeropage_y,
+            def.cycles.relative,
+            def.cycles.indirect,
+            def.cycles.indexed_indirect,
+            def.cycles.indirect_indexed,
+            def.page_penalty,
         );
         result.push(line);
     }
 
     let mut lines = Vec::<String>::default();
-    for def in &isa_6502() {
+    for def in isa_6502().iter().chain(&isa_6502_illegal()) {
         format_opcodes(&mut lines, def);
     }
 
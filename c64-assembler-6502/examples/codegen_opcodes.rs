@@ -1,11 +1,8 @@
-use c64_assembler_6502::{
-    isa_6502,
-    opcodes::{OpCode, NO_IMPLIED},
-};
+use c64_assembler_6502::{instruction::InstructionDef, isa_6502, isa_6502_illegal, opcodes::OpCode};
 
 fn main() {
-    fn format_opcode(result: &mut Vec<String>, instruction: &str, opcode: OpCode, post: &str) {
-        if opcode != NO_IMPLIED {
+    fn format_opcode(result: &mut Vec<String>, instruction: &str, opcode: Option<OpCode>, post: &str) {
+        if let Some(opcode) = opcode {
             result.push(format!(
                 "/// OpCode for the {} instruction in addressing mode {}
 pub const {}_{}:OpCode = 0x{:02x};",
@@ -18,21 +15,29 @@ pub const {}_{}:OpCode = 0x{:02x};",
         }
     }
 
+    fn format_opcodes(result: &mut Vec<String>, def: &InstructionDef) {
+        format_opcode(result, def.instruction, def.implied, "IMPLIED");
+        format_opcode(result, def.instruction, def.immediate, "IMMEDIATE");
+        format_opcode(result, def.instruction, def.accumulator, "ACCUMULATOR");
+        format_opcode(result, def.instruction, def.absolute, "ABSOLUTE");
+        format_opcode(result, def.instruction, def.absolute_x, "ABSOLUTE_X");
+        format_opcode(result, def.instruction, def.absolute_y, "ABSOLUTE_Y");
+        format_opcode(result, def.instruction, def.zeropage, "ZEROPAGE");
+        format_opcode(result, def.instruction, def.zeropage_x, "ZEROPAGE_X");
+        format_opcode(result, def.instruction, def.zeropage_y, "ZEROPAGE_Y");
+        format_opcode(result, def.instruction, def.relative, "RELATIVE");
+        format_opcode(result, def.instruction, def.indirect, "INDIRECT");
+        format_opcode(result, def.instruction, def.indexed_indirect, "INDEXED_INDIRECT");
+        format_opcode(result, def.instruction, def.indirect_indexed, "INDIRECT_INDEXED");
+    }
+
     let mut lines = Vec::<String>::default();
-    for def in isa_6502() {
-        format_opcode(&mut lines, def.instruction, def.implied, "IMPLIED");
-        format_opcode(&mut lines, def.instruction, def.immediate, "IMMEDIATE");
-        format_opcode(&mut lines, def.instruction, def.accumulator, "ACCUMULATOR");
-        format_opcode(&mut lines, def.instruction, def.absolute, "ABSOLUTE");
-        format_opcode(&mut lines, def.instruction, def.absolute_x, "ABSOLUTE_X");
-        format_opcode(&mut lines, def.instruction, def.absolute_y, "ABSOLUTE_Y");
-        format_opcode(&mut lines, def.instruction, def.zeropage, "ZEROPAGE");
-        format_opcode(&mut lines, def.instruction, def.zeropage_x, "ZEROPAGE_X");
-        format_opcode(&mut lines, def.instruction, def.zeropage_y, "ZEROPAGE_Y");
-        format_opcode(&mut lines, def.instruction, def.relative, "RELATIVE");
-        format_opcode(&mut lines, def.instruction, def.indirect, "INDIRECT");
-        format_opcode(&mut lines, def.instruction, def.indexed_indirect, "INDEXED_INDIRECT");
-        format_opcode(&mut lines, def.instruction, def.indirect_indexed, "INDIRECT_INDEXED");
+    for def in &isa_6502() {
+        format_opcodes(&mut lines, def);
+    }
+    lines.push("\n// Illegal (undocumented) instructions of the NMOS 6502.".to_string());
+    for def in &isa_6502_illegal() {
+        format_opcodes(&mut lines, def);
     }
 
     println!("{}", lines.join("\n"));
@@ -1,64 +1,225 @@
-use crate::opcodes::OpCode;
+use crate::opcodes::*;
 
 /// Instruction definition.
 ///
-/// Contains the instruction (as str) and the op-codes per addressing mode.
-#[derive(Debug)]
+/// Contains the instruction (as str), the op-codes and the cycles per addressing mode.
+#[derive(Debug, Copy, Clone)]
 pub struct InstructionDef {
     /// Instruction as lowercase str (lda, sta, ...)
     pub instruction: &'static str,
     /// OpCode for implied addressing mode.
     ///
     /// Contains [crate::opcodes::NO_IMPLIED] when no op-code exist.
-    pub implied: OpCode,
+    pub implied: Option<OpCode>,
     /// OpCode for immediate addressing mode.
     ///
     /// Contains [crate::opcodes::NO_IMMEDIATE] when no op-code exists.
-    pub immediate: OpCode,
+    pub immediate: Option<OpCode>,
     /// OpCode for accumualtor addressing mode.
     ///
     /// Contains [crate::opcodes::NO_ACCUMULATOR] when no op-code exists.
-    pub accumulator: OpCode,
+    pub accumulator: Option<OpCode>,
     /// OpCode for absolute addressing mode.
     ///
     /// Contains [crate::opcodes::NO_ABSOLUTE] when no op-code exists.
-    pub absolute: OpCode,
+    pub absolute: Option<OpCode>,
     /// OpCode for absolute-x addressing mode.
     ///
     /// Contains [crate::opcodes::NO_ABSOLUTE_X] when no op-code exists.
-    pub absolute_x: OpCode,
+    pub absolute_x: Option<OpCode>,
     /// OpCode for absolute-y addressing mode.
     ///
     /// Contains [crate::opcodes::NO_ABSOLUTE_Y] when no op-code exists.
-    pub absolute_y: OpCode,
+    pub absolute_y: Option<OpCode>,
     /// OpCode for zeropage addressing mode.
     ///
     /// Contains [crate::opcodes::NO_ZEROPAGE] when no op-code exists.
-    pub zeropage: OpCode,
+    pub zeropage: Option<OpCode>,
     /// OpCode for zeropage-x addressing mode.
     ///
     /// Contains [crate::opcodes::NO_ZEROPAGE_X] when no op-code exists.
-    pub zeropage_x: OpCode,
+    pub zeropage_x: Option<OpCode>,
     /// OpCode for zeropage-y addressing mode.
     ///
     /// Contains [crate::opcodes::NO_ZEROPAGE_Y] when no op-code exists.
-    pub zeropage_y: OpCode,
+    pub zeropage_y: Option<OpCode>,
     /// OpCode for relative addressing mode.
     ///
     /// Contains [crate::opcodes::NO_RELATIVE] when no op-code exists.
-    pub relative: OpCode,
+    pub relative: Option<OpCode>,
     /// OpCode for indirect addressing mode.
     ///
     /// Contains [crate::opcodes::NO_INDIRECT] when no op-code exists.
-    pub indirect: OpCode,
+    pub indirect: Option<OpCode>,
     /// OpCode for indexed indirect addressing mode.
     ///
     /// Contains [crate::opcodes::NO_INDEXED_INDIRECT] when no op-code exists.
-    pub indexed_indirect: OpCode,
+    pub indexed_indirect: Option<OpCode>,
     /// OpCode for indirect indexed addressing mode.
     ///
     /// Contains [crate::opcodes::NO_INDIRECT_INDEXED] when no op-code exists.
-    pub indirect_indexed: OpCode,
+    pub indirect_indexed: Option<OpCode>,
+    /// Base number of cycles per addressing mode.
+    pub cycles: Cycles,
+    /// Additional cycles that depend on the addresses that are accessed.
+    pub page_penalty: PagePenalty,
+}
+
+/// Number of cycles an instruction takes per addressing mode.
+///
+/// Penalties for crossing pages are not included, see [PagePenalty]. Contains 0 when the
+/// instruction has no op-code for the addressing mode.
+#[derive(Debug, Copy, Clone)]
+pub struct Cycles {
+    pub implied: u8,
+    pub immediate: u8,
+    pub accumulator: u8,
+    pub absolute: u8,
+    pub absolute_x: u8,
+    pub absolute_y: u8,
+    pub zeropage: u8,
+    pub zeropage_x: u8,
+    pub zeropage_y: u8,
+    pub relative: u8,
+    pub indirect: u8,
+    pub indexed_indirect: u8,
+    pub indirect_indexed: u8,
+}
+
+/// Additional cycles an instruction takes on top of its base [Cycles].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PagePenalty {
+    /// Instruction always takes its base number of cycles.
+    None,
+    /// One cycle is added when indexing (absolute,x, absolute,y or (indirect),y) crosses a
+    /// page boundary.
+    IndexedRead,
+    /// One cycle is added when the branch is taken and another one when the target is on a
+    /// different page than the next instruction.
+    Branch,
+}
+
+/// Addressing modes of the 6502 as they are encoded in the byte stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddressingMode {
+    Implied,
+    Immediate,
+    Accumulator,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    Zeropage,
+    ZeropageX,
+    ZeropageY,
+    Relative,
+    Indirect,
+    IndexedIndirect,
+    IndirectIndexed,
+}
+
+impl AddressingMode {
+    /// All addressing modes.
+    pub const ALL: [AddressingMode; 13] = [
+        AddressingMode::Implied,
+        AddressingMode::Immediate,
+        AddressingMode::Accumulator,
+        AddressingMode::Absolute,
+        AddressingMode::AbsoluteX,
+        AddressingMode::AbsoluteY,
+        AddressingMode::Zeropage,
+        AddressingMode::ZeropageX,
+        AddressingMode::ZeropageY,
+        AddressingMode::Relative,
+        AddressingMode::Indirect,
+        AddressingMode::IndexedIndirect,
+        AddressingMode::IndirectIndexed,
+    ];
+
+    /// Total number of bytes an instruction occupies, including its op-code.
+    ///
+    /// ```
+    /// use c64_assembler_6502::instruction::AddressingMode;
+    ///
+    /// assert_eq!(1, AddressingMode::Implied.byte_size());
+    /// assert_eq!(2, AddressingMode::Zeropage.byte_size());
+    /// assert_eq!(3, AddressingMode::Absolute.byte_size());
+    /// ```
+    pub fn byte_size(&self) -> u16 {
+        match self {
+            AddressingMode::Implied | AddressingMode::Accumulator => 1,
+            AddressingMode::Immediate
+            | AddressingMode::Zeropage
+            | AddressingMode::ZeropageX
+            | AddressingMode::ZeropageY
+            | AddressingMode::Relative
+            | AddressingMode::IndexedIndirect
+            | AddressingMode::IndirectIndexed => 2,
+            AddressingMode::Absolute
+            | AddressingMode::AbsoluteX
+            | AddressingMode::AbsoluteY
+            | AddressingMode::Indirect => 3,
+        }
+    }
+}
+
+impl InstructionDef {
+    /// Get the op-code of this instruction for the given addressing mode.
+    ///
+    /// Returns `None` when the instruction cannot be used with the addressing mode.
+    ///
+    /// ```
+    /// use c64_assembler_6502::instruction::{AddressingMode, OPCODES_LDA};
+    ///
+    /// assert_eq!(Some(0xA9), OPCODES_LDA.opcode(AddressingMode::Immediate));
+    /// assert_eq!(None, OPCODES_LDA.opcode(AddressingMode::Accumulator));
+    /// ```
+    pub fn opcode(&self, addressing_mode: AddressingMode) -> Option<OpCode> {
+        match addressing_mode {
+            AddressingMode::Implied => self.implied,
+            AddressingMode::Immediate => self.immediate,
+            AddressingMode::Accumulator => self.accumulator,
+            AddressingMode::Absolute => self.absolute,
+            AddressingMode::AbsoluteX => self.absolute_x,
+            AddressingMode::AbsoluteY => self.absolute_y,
+            AddressingMode::Zeropage => self.zeropage,
+            AddressingMode::ZeropageX => self.zeropage_x,
+            AddressingMode::ZeropageY => self.zeropage_y,
+            AddressingMode::Relative => self.relative,
+            AddressingMode::Indirect => self.indirect,
+            AddressingMode::IndexedIndirect => self.indexed_indirect,
+            AddressingMode::IndirectIndexed => self.indirect_indexed,
+        }
+    }
+
+    /// Get the base number of cycles of this instruction for the given addressing mode.
+    ///
+    /// Returns `None` when the instruction cannot be used with the addressing mode.
+    ///
+    /// ```
+    /// use c64_assembler_6502::instruction::{AddressingMode, OPCODES_LDA, OPCODES_STA};
+    ///
+    /// assert_eq!(Some(4), OPCODES_LDA.cycles(AddressingMode::AbsoluteX));
+    /// assert_eq!(Some(5), OPCODES_STA.cycles(AddressingMode::AbsoluteX));
+    /// assert_eq!(None, OPCODES_STA.cycles(AddressingMode::Immediate));
+    /// ```
+    pub fn cycles(&self, addressing_mode: AddressingMode) -> Option<u8> {
+        self.opcode(addressing_mode)?;
+        Some(match addressing_mode {
+            AddressingMode::Implied => self.cycles.implied,
+            AddressingMode::Immediate => self.cycles.immediate,
+            AddressingMode::Accumulator => self.cycles.accumulator,
+            AddressingMode::Absolute => self.cycles.absolute,
+            AddressingMode::AbsoluteX => self.cycles.absolute_x,
+            AddressingMode::AbsoluteY => self.cycles.absolute_y,
+            AddressingMode::Zeropage => self.cycles.zeropage,
+            AddressingMode::ZeropageX => self.cycles.zeropage_x,
+            AddressingMode::ZeropageY => self.cycles.zeropage_y,
+            AddressingMode::Relative => self.cycles.relative,
+            AddressingMode::Indirect => self.cycles.indirect,
+            AddressingMode::IndexedIndirect => self.cycles.indexed_indirect,
+            AddressingMode::IndirectIndexed => self.cycles.indirect_indexed,
+        })
+    }
 }
 
 pub use gen::*;
@@ -66,7 +227,7 @@ pub use gen::*;
 mod gen {
     use crate::opcodes::*;
 
-    use super::InstructionDef;
+    use super::{Cycles, InstructionDef, PagePenalty};
 
     /// Instruction definition for adc
     ///
@@ -74,18 +235,34 @@ mod gen {
     pub const OPCODES_ADC: InstructionDef = InstructionDef {
         instruction: "adc",
         implied: NO_IMPLIED,
-        immediate: ADC_IMMEDIATE,
+        immediate: Some(ADC_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: ADC_ABSOLUTE,
-        absolute_x: ADC_ABSOLUTE_X,
-        absolute_y: ADC_ABSOLUTE_Y,
-        zeropage: ADC_ZEROPAGE,
-        zeropage_x: ADC_ZEROPAGE_X,
+        absolute: Some(ADC_ABSOLUTE),
+        absolute_x: Some(ADC_ABSOLUTE_X),
+        absolute_y: Some(ADC_ABSOLUTE_Y),
+        zeropage: Some(ADC_ZEROPAGE),
+        zeropage_x: Some(ADC_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: ADC_INDEXED_INDIRECT,
-        indirect_indexed: ADC_INDIRECT_INDEXED,
+        indexed_indirect: Some(ADC_INDEXED_INDIRECT),
+        indirect_indexed: Some(ADC_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 4,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 5,
+        },
+        page_penalty: PagePenalty::IndexedRead,
     };
 
     /// Instruction definition for and
@@ -94,18 +271,34 @@ mod gen {
     pub const OPCODES_AND: InstructionDef = InstructionDef {
         instruction: "and",
         implied: NO_IMPLIED,
-        immediate: AND_IMMEDIATE,
+        immediate: Some(AND_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: AND_ABSOLUTE,
-        absolute_x: AND_ABSOLUTE_X,
-        absolute_y: AND_ABSOLUTE_Y,
-        zeropage: AND_ZEROPAGE,
-        zeropage_x: AND_ZEROPAGE_X,
+        absolute: Some(AND_ABSOLUTE),
+        absolute_x: Some(AND_ABSOLUTE_X),
+        absolute_y: Some(AND_ABSOLUTE_Y),
+        zeropage: Some(AND_ZEROPAGE),
+        zeropage_x: Some(AND_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: AND_INDEXED_INDIRECT,
-        indirect_indexed: AND_INDIRECT_INDEXED,
+        indexed_indirect: Some(AND_INDEXED_INDIRECT),
+        indirect_indexed: Some(AND_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 4,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 5,
+        },
+        page_penalty: PagePenalty::IndexedRead,
     };
 
     /// Instruction definition for asl
@@ -115,17 +308,33 @@ mod gen {
         instruction: "asl",
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
-        accumulator: ASL_ACCUMULATOR,
-        absolute: ASL_ABSOLUTE,
-        absolute_x: ASL_ABSOLUTE_X,
+        accumulator: Some(ASL_ACCUMULATOR),
+        absolute: Some(ASL_ABSOLUTE),
+        absolute_x: Some(ASL_ABSOLUTE_X),
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: ASL_ZEROPAGE,
-        zeropage_x: ASL_ZEROPAGE_X,
+        zeropage: Some(ASL_ZEROPAGE),
+        zeropage_x: Some(ASL_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 2,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 0,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for bcc
@@ -142,10 +351,26 @@ mod gen {
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
-        relative: BCC_RELATIVE,
+        relative: Some(BCC_RELATIVE),
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 2,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::Branch,
     };
 
     /// Instruction definition for bcs
@@ -162,10 +387,26 @@ mod gen {
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
-        relative: BCS_RELATIVE,
+        relative: Some(BCS_RELATIVE),
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 2,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::Branch,
     };
 
     /// Instruction definition for beq
@@ -182,10 +423,26 @@ mod gen {
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
-        relative: BEQ_RELATIVE,
+        relative: Some(BEQ_RELATIVE),
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 2,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::Branch,
     };
 
     /// Instruction definition for bit
@@ -196,16 +453,32 @@ mod gen {
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: BIT_ABSOLUTE,
+        absolute: Some(BIT_ABSOLUTE),
         absolute_x: NO_ABSOLUTE_X,
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: BIT_ZEROPAGE,
+        zeropage: Some(BIT_ZEROPAGE),
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 3,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for bmi
@@ -222,10 +495,26 @@ mod gen {
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
-        relative: BMI_RELATIVE,
+        relative: Some(BMI_RELATIVE),
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 2,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::Branch,
     };
 
     /// Instruction definition for bne
@@ -242,10 +531,26 @@ mod gen {
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
-        relative: BNE_RELATIVE,
+        relative: Some(BNE_RELATIVE),
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 2,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::Branch,
     };
 
     /// Instruction definition for bpl
@@ -262,10 +567,26 @@ mod gen {
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
-        relative: BPL_RELATIVE,
+        relative: Some(BPL_RELATIVE),
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 2,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::Branch,
     };
 
     /// Instruction definition for brk
@@ -273,7 +594,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_BRK: InstructionDef = InstructionDef {
         instruction: "brk",
-        implied: BRK_IMPLIED,
+        implied: Some(BRK_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -286,6 +607,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 7,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for bvc
@@ -302,10 +639,26 @@ mod gen {
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
-        relative: BVC_RELATIVE,
+        relative: Some(BVC_RELATIVE),
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 2,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::Branch,
     };
 
     /// Instruction definition for bvs
@@ -322,10 +675,62 @@ mod gen {
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
-        relative: BVS_RELATIVE,
+        relative: Some(BVS_RELATIVE),
+        indirect: NO_INDIRECT,
+        indexed_indirect: NO_INDEXED_INDIRECT,
+        indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 2,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::Branch,
+    };
+
+    /// Instruction definition for clc
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_CLC: InstructionDef = InstructionDef {
+        instruction: "clc",
+        implied: Some(CLC_IMPLIED),
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: NO_ABSOLUTE,
+        absolute_x: NO_ABSOLUTE_X,
+        absolute_y: NO_ABSOLUTE_Y,
+        zeropage: NO_ZEROPAGE,
+        zeropage_x: NO_ZEROPAGE_X,
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for cld
@@ -333,7 +738,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_CLD: InstructionDef = InstructionDef {
         instruction: "cld",
-        implied: CLD_IMPLIED,
+        implied: Some(CLD_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -346,6 +751,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for cli
@@ -353,7 +774,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_CLI: InstructionDef = InstructionDef {
         instruction: "cli",
-        implied: CLI_IMPLIED,
+        implied: Some(CLI_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -366,6 +787,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for clv
@@ -373,7 +810,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_CLV: InstructionDef = InstructionDef {
         instruction: "clv",
-        implied: CLV_IMPLIED,
+        implied: Some(CLV_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -386,6 +823,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for cmp
@@ -394,18 +847,34 @@ mod gen {
     pub const OPCODES_CMP: InstructionDef = InstructionDef {
         instruction: "cmp",
         implied: NO_IMPLIED,
-        immediate: CMP_IMMEDIATE,
+        immediate: Some(CMP_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: CMP_ABSOLUTE,
-        absolute_x: CMP_ABSOLUTE_X,
-        absolute_y: CMP_ABSOLUTE_Y,
-        zeropage: CMP_ZEROPAGE,
-        zeropage_x: CMP_ZEROPAGE_X,
+        absolute: Some(CMP_ABSOLUTE),
+        absolute_x: Some(CMP_ABSOLUTE_X),
+        absolute_y: Some(CMP_ABSOLUTE_Y),
+        zeropage: Some(CMP_ZEROPAGE),
+        zeropage_x: Some(CMP_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: CMP_INDEXED_INDIRECT,
-        indirect_indexed: CMP_INDIRECT_INDEXED,
+        indexed_indirect: Some(CMP_INDEXED_INDIRECT),
+        indirect_indexed: Some(CMP_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 4,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 5,
+        },
+        page_penalty: PagePenalty::IndexedRead,
     };
 
     /// Instruction definition for cpx
@@ -414,18 +883,34 @@ mod gen {
     pub const OPCODES_CPX: InstructionDef = InstructionDef {
         instruction: "cpx",
         implied: NO_IMPLIED,
-        immediate: CPX_IMMEDIATE,
+        immediate: Some(CPX_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: CPX_ABSOLUTE,
+        absolute: Some(CPX_ABSOLUTE),
         absolute_x: NO_ABSOLUTE_X,
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: CPX_ZEROPAGE,
+        zeropage: Some(CPX_ZEROPAGE),
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 3,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for cpy
@@ -434,18 +919,34 @@ mod gen {
     pub const OPCODES_CPY: InstructionDef = InstructionDef {
         instruction: "cpy",
         implied: NO_IMPLIED,
-        immediate: CPY_IMMEDIATE,
+        immediate: Some(CPY_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: CPY_ABSOLUTE,
+        absolute: Some(CPY_ABSOLUTE),
         absolute_x: NO_ABSOLUTE_X,
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: CPY_ZEROPAGE,
+        zeropage: Some(CPY_ZEROPAGE),
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 3,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for dec
@@ -456,16 +957,32 @@ mod gen {
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: DEC_ABSOLUTE,
-        absolute_x: DEC_ABSOLUTE_X,
+        absolute: Some(DEC_ABSOLUTE),
+        absolute_x: Some(DEC_ABSOLUTE_X),
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: DEC_ZEROPAGE,
-        zeropage_x: DEC_ZEROPAGE_X,
+        zeropage: Some(DEC_ZEROPAGE),
+        zeropage_x: Some(DEC_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 0,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for dex
@@ -473,7 +990,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_DEX: InstructionDef = InstructionDef {
         instruction: "dex",
-        implied: DEX_IMPLIED,
+        implied: Some(DEX_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -486,6 +1003,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for dey
@@ -493,7 +1026,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_DEY: InstructionDef = InstructionDef {
         instruction: "dey",
-        implied: DEY_IMPLIED,
+        implied: Some(DEY_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -506,6 +1039,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for eor
@@ -514,18 +1063,34 @@ mod gen {
     pub const OPCODES_EOR: InstructionDef = InstructionDef {
         instruction: "eor",
         implied: NO_IMPLIED,
-        immediate: EOR_IMMEDIATE,
+        immediate: Some(EOR_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: EOR_ABSOLUTE,
-        absolute_x: EOR_ABSOLUTE_X,
-        absolute_y: EOR_ABSOLUTE_Y,
-        zeropage: EOR_ZEROPAGE,
-        zeropage_x: EOR_ZEROPAGE_X,
+        absolute: Some(EOR_ABSOLUTE),
+        absolute_x: Some(EOR_ABSOLUTE_X),
+        absolute_y: Some(EOR_ABSOLUTE_Y),
+        zeropage: Some(EOR_ZEROPAGE),
+        zeropage_x: Some(EOR_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: EOR_INDEXED_INDIRECT,
-        indirect_indexed: EOR_INDIRECT_INDEXED,
+        indexed_indirect: Some(EOR_INDEXED_INDIRECT),
+        indirect_indexed: Some(EOR_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 4,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 5,
+        },
+        page_penalty: PagePenalty::IndexedRead,
     };
 
     /// Instruction definition for inc
@@ -536,16 +1101,32 @@ mod gen {
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: INC_ABSOLUTE,
-        absolute_x: INC_ABSOLUTE_X,
+        absolute: Some(INC_ABSOLUTE),
+        absolute_x: Some(INC_ABSOLUTE_X),
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: INC_ZEROPAGE,
-        zeropage_x: INC_ZEROPAGE_X,
+        zeropage: Some(INC_ZEROPAGE),
+        zeropage_x: Some(INC_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 0,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for inx
@@ -553,7 +1134,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_INX: InstructionDef = InstructionDef {
         instruction: "inx",
-        implied: INX_IMPLIED,
+        implied: Some(INX_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -566,6 +1147,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for iny
@@ -573,7 +1170,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_INY: InstructionDef = InstructionDef {
         instruction: "iny",
-        implied: INY_IMPLIED,
+        implied: Some(INY_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -586,6 +1183,130 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for jmp
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_JMP: InstructionDef = InstructionDef {
+        instruction: "jmp",
+        implied: NO_IMPLIED,
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(JMP_ABSOLUTE),
+        absolute_x: NO_ABSOLUTE_X,
+        absolute_y: NO_ABSOLUTE_Y,
+        zeropage: NO_ZEROPAGE,
+        zeropage_x: NO_ZEROPAGE_X,
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: Some(JMP_INDIRECT),
+        indexed_indirect: NO_INDEXED_INDIRECT,
+        indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 3,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 5,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for jsr
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_JSR: InstructionDef = InstructionDef {
+        instruction: "jsr",
+        implied: NO_IMPLIED,
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(JSR_ABSOLUTE),
+        absolute_x: NO_ABSOLUTE_X,
+        absolute_y: NO_ABSOLUTE_Y,
+        zeropage: NO_ZEROPAGE,
+        zeropage_x: NO_ZEROPAGE_X,
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: NO_INDEXED_INDIRECT,
+        indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for lda
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_LDA: InstructionDef = InstructionDef {
+        instruction: "lda",
+        implied: NO_IMPLIED,
+        immediate: Some(LDA_IMMEDIATE),
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(LDA_ABSOLUTE),
+        absolute_x: Some(LDA_ABSOLUTE_X),
+        absolute_y: Some(LDA_ABSOLUTE_Y),
+        zeropage: Some(LDA_ZEROPAGE),
+        zeropage_x: Some(LDA_ZEROPAGE_X),
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: Some(LDA_INDEXED_INDIRECT),
+        indirect_indexed: Some(LDA_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 4,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 5,
+        },
+        page_penalty: PagePenalty::IndexedRead,
     };
 
     /// Instruction definition for ldx
@@ -594,18 +1315,70 @@ mod gen {
     pub const OPCODES_LDX: InstructionDef = InstructionDef {
         instruction: "ldx",
         implied: NO_IMPLIED,
-        immediate: LDX_IMMEDIATE,
+        immediate: Some(LDX_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: LDX_ABSOLUTE,
+        absolute: Some(LDX_ABSOLUTE),
         absolute_x: NO_ABSOLUTE_X,
-        absolute_y: LDX_ABSOLUTE_Y,
-        zeropage: LDX_ZEROPAGE,
+        absolute_y: Some(LDX_ABSOLUTE_Y),
+        zeropage: Some(LDX_ZEROPAGE),
         zeropage_x: NO_ZEROPAGE_X,
-        zeropage_y: LDX_ZEROPAGE_Y,
+        zeropage_y: Some(LDX_ZEROPAGE_Y),
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 0,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 0,
+            zeropage_y: 4,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::IndexedRead,
+    };
+
+    /// Instruction definition for ldy
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_LDY: InstructionDef = InstructionDef {
+        instruction: "ldy",
+        implied: NO_IMPLIED,
+        immediate: Some(LDY_IMMEDIATE),
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(LDY_ABSOLUTE),
+        absolute_x: Some(LDY_ABSOLUTE_X),
+        absolute_y: NO_ABSOLUTE_Y,
+        zeropage: Some(LDY_ZEROPAGE),
+        zeropage_x: Some(LDY_ZEROPAGE_X),
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: NO_INDEXED_INDIRECT,
+        indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 4,
+            absolute_y: 0,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::IndexedRead,
     };
 
     /// Instruction definition for lsr
@@ -615,17 +1388,33 @@ mod gen {
         instruction: "lsr",
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
-        accumulator: LSR_ACCUMULATOR,
-        absolute: LSR_ABSOLUTE,
-        absolute_x: LSR_ABSOLUTE_X,
+        accumulator: Some(LSR_ACCUMULATOR),
+        absolute: Some(LSR_ABSOLUTE),
+        absolute_x: Some(LSR_ABSOLUTE_X),
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: LSR_ZEROPAGE,
-        zeropage_x: LSR_ZEROPAGE_X,
+        zeropage: Some(LSR_ZEROPAGE),
+        zeropage_x: Some(LSR_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 2,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 0,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for nop
@@ -633,7 +1422,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_NOP: InstructionDef = InstructionDef {
         instruction: "nop",
-        implied: NOP_IMPLIED,
+        implied: Some(NOP_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -646,6 +1435,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for ora
@@ -654,18 +1459,34 @@ mod gen {
     pub const OPCODES_ORA: InstructionDef = InstructionDef {
         instruction: "ora",
         implied: NO_IMPLIED,
-        immediate: ORA_IMMEDIATE,
+        immediate: Some(ORA_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: ORA_ABSOLUTE,
-        absolute_x: ORA_ABSOLUTE_X,
-        absolute_y: ORA_ABSOLUTE_Y,
-        zeropage: ORA_ZEROPAGE,
-        zeropage_x: ORA_ZEROPAGE_X,
+        absolute: Some(ORA_ABSOLUTE),
+        absolute_x: Some(ORA_ABSOLUTE_X),
+        absolute_y: Some(ORA_ABSOLUTE_Y),
+        zeropage: Some(ORA_ZEROPAGE),
+        zeropage_x: Some(ORA_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: ORA_INDEXED_INDIRECT,
-        indirect_indexed: ORA_INDIRECT_INDEXED,
+        indexed_indirect: Some(ORA_INDEXED_INDIRECT),
+        indirect_indexed: Some(ORA_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 4,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 5,
+        },
+        page_penalty: PagePenalty::IndexedRead,
     };
 
     /// Instruction definition for pha
@@ -673,7 +1494,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_PHA: InstructionDef = InstructionDef {
         instruction: "pha",
-        implied: PHA_IMPLIED,
+        implied: Some(PHA_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -686,6 +1507,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 3,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for php
@@ -693,7 +1530,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_PHP: InstructionDef = InstructionDef {
         instruction: "php",
-        implied: PHP_IMPLIED,
+        implied: Some(PHP_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -706,6 +1543,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 3,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for pla
@@ -713,7 +1566,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_PLA: InstructionDef = InstructionDef {
         instruction: "pla",
-        implied: PLA_IMPLIED,
+        implied: Some(PLA_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -726,6 +1579,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 4,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for plp
@@ -733,7 +1602,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_PLP: InstructionDef = InstructionDef {
         instruction: "plp",
-        implied: PLP_IMPLIED,
+        implied: Some(PLP_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -746,6 +1615,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 4,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for rol
@@ -755,17 +1640,33 @@ mod gen {
         instruction: "rol",
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
-        accumulator: ROL_ACCUMULATOR,
-        absolute: ROL_ABSOLUTE,
-        absolute_x: ROL_ABSOLUTE_X,
+        accumulator: Some(ROL_ACCUMULATOR),
+        absolute: Some(ROL_ABSOLUTE),
+        absolute_x: Some(ROL_ABSOLUTE_X),
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: ROL_ZEROPAGE,
-        zeropage_x: ROL_ZEROPAGE_X,
+        zeropage: Some(ROL_ZEROPAGE),
+        zeropage_x: Some(ROL_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 2,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 0,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for ror
@@ -775,17 +1676,33 @@ mod gen {
         instruction: "ror",
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
-        accumulator: ROR_ACCUMULATOR,
-        absolute: ROR_ABSOLUTE,
-        absolute_x: ROR_ABSOLUTE_X,
+        accumulator: Some(ROR_ACCUMULATOR),
+        absolute: Some(ROR_ABSOLUTE),
+        absolute_x: Some(ROR_ABSOLUTE_X),
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: ROR_ZEROPAGE,
-        zeropage_x: ROR_ZEROPAGE_X,
+        zeropage: Some(ROR_ZEROPAGE),
+        zeropage_x: Some(ROR_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 2,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 0,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for rti
@@ -793,7 +1710,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_RTI: InstructionDef = InstructionDef {
         instruction: "rti",
-        implied: RTI_IMPLIED,
+        implied: Some(RTI_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -806,6 +1723,58 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 6,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for rts
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_RTS: InstructionDef = InstructionDef {
+        instruction: "rts",
+        implied: Some(RTS_IMPLIED),
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: NO_ABSOLUTE,
+        absolute_x: NO_ABSOLUTE_X,
+        absolute_y: NO_ABSOLUTE_Y,
+        zeropage: NO_ZEROPAGE,
+        zeropage_x: NO_ZEROPAGE_X,
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: NO_INDEXED_INDIRECT,
+        indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 6,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for sbc
@@ -814,18 +1783,70 @@ mod gen {
     pub const OPCODES_SBC: InstructionDef = InstructionDef {
         instruction: "sbc",
         implied: NO_IMPLIED,
-        immediate: SBC_IMMEDIATE,
+        immediate: Some(SBC_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: SBC_ABSOLUTE,
-        absolute_x: SBC_ABSOLUTE_X,
-        absolute_y: SBC_ABSOLUTE_Y,
-        zeropage: SBC_ZEROPAGE,
-        zeropage_x: SBC_ZEROPAGE_X,
+        absolute: Some(SBC_ABSOLUTE),
+        absolute_x: Some(SBC_ABSOLUTE_X),
+        absolute_y: Some(SBC_ABSOLUTE_Y),
+        zeropage: Some(SBC_ZEROPAGE),
+        zeropage_x: Some(SBC_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: SBC_INDEXED_INDIRECT,
-        indirect_indexed: SBC_INDIRECT_INDEXED,
+        indexed_indirect: Some(SBC_INDEXED_INDIRECT),
+        indirect_indexed: Some(SBC_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 4,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 5,
+        },
+        page_penalty: PagePenalty::IndexedRead,
+    };
+
+    /// Instruction definition for sec
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_SEC: InstructionDef = InstructionDef {
+        instruction: "sec",
+        implied: Some(SEC_IMPLIED),
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: NO_ABSOLUTE,
+        absolute_x: NO_ABSOLUTE_X,
+        absolute_y: NO_ABSOLUTE_Y,
+        zeropage: NO_ZEROPAGE,
+        zeropage_x: NO_ZEROPAGE_X,
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: NO_INDEXED_INDIRECT,
+        indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for sed
@@ -833,7 +1854,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_SED: InstructionDef = InstructionDef {
         instruction: "sed",
-        implied: SED_IMPLIED,
+        implied: Some(SED_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -846,6 +1867,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for sei
@@ -853,7 +1890,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_SEI: InstructionDef = InstructionDef {
         instruction: "sei",
-        implied: SEI_IMPLIED,
+        implied: Some(SEI_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -866,6 +1903,58 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for sta
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_STA: InstructionDef = InstructionDef {
+        instruction: "sta",
+        implied: NO_IMPLIED,
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(STA_ABSOLUTE),
+        absolute_x: Some(STA_ABSOLUTE_X),
+        absolute_y: Some(STA_ABSOLUTE_Y),
+        zeropage: Some(STA_ZEROPAGE),
+        zeropage_x: Some(STA_ZEROPAGE_X),
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: Some(STA_INDEXED_INDIRECT),
+        indirect_indexed: Some(STA_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 5,
+            absolute_y: 5,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 6,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for stx
@@ -876,16 +1965,32 @@ mod gen {
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: STX_ABSOLUTE,
+        absolute: Some(STX_ABSOLUTE),
         absolute_x: NO_ABSOLUTE_X,
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: STX_ZEROPAGE,
+        zeropage: Some(STX_ZEROPAGE),
         zeropage_x: NO_ZEROPAGE_X,
-        zeropage_y: STX_ZEROPAGE_Y,
+        zeropage_y: Some(STX_ZEROPAGE_Y),
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 3,
+            zeropage_x: 0,
+            zeropage_y: 4,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for sty
@@ -896,16 +2001,32 @@ mod gen {
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: STY_ABSOLUTE,
+        absolute: Some(STY_ABSOLUTE),
         absolute_x: NO_ABSOLUTE_X,
         absolute_y: NO_ABSOLUTE_Y,
-        zeropage: STY_ZEROPAGE,
-        zeropage_x: STY_ZEROPAGE_X,
+        zeropage: Some(STY_ZEROPAGE),
+        zeropage_x: Some(STY_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 3,
+            zeropage_x: 4,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for tax
@@ -913,7 +2034,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_TAX: InstructionDef = InstructionDef {
         instruction: "tax",
-        implied: TAX_IMPLIED,
+        implied: Some(TAX_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -926,6 +2047,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for tay
@@ -933,7 +2070,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_TAY: InstructionDef = InstructionDef {
         instruction: "tay",
-        implied: TAY_IMPLIED,
+        implied: Some(TAY_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -946,6 +2083,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for tsx
@@ -953,7 +2106,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_TSX: InstructionDef = InstructionDef {
         instruction: "tsx",
-        implied: TSX_IMPLIED,
+        implied: Some(TSX_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -966,6 +2119,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for txa
@@ -973,7 +2142,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_TXA: InstructionDef = InstructionDef {
         instruction: "txa",
-        implied: TXA_IMPLIED,
+        implied: Some(TXA_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -986,6 +2155,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for txs
@@ -993,7 +2178,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_TXS: InstructionDef = InstructionDef {
         instruction: "txs",
-        implied: TXS_IMPLIED,
+        implied: Some(TXS_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -1006,6 +2191,22 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
     /// Instruction definition for tya
@@ -1013,7 +2214,7 @@ mod gen {
     /// Includes the instruction name and its [OpCode] for a address mode.
     pub const OPCODES_TYA: InstructionDef = InstructionDef {
         instruction: "tya",
-        implied: TYA_IMPLIED,
+        implied: Some(TYA_IMPLIED),
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
@@ -1026,97 +2227,357 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 2,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
-    /// Instruction definition for lda
+    /// Instruction definition for slo
     ///
     /// Includes the instruction name and its [OpCode] for a address mode.
-    pub const OPCODES_LDA: InstructionDef = InstructionDef {
-        instruction: "lda",
+    pub const OPCODES_SLO: InstructionDef = InstructionDef {
+        instruction: "slo",
         implied: NO_IMPLIED,
-        immediate: LDA_IMMEDIATE,
+        immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: LDA_ABSOLUTE,
-        absolute_x: LDA_ABSOLUTE_X,
-        absolute_y: LDA_ABSOLUTE_Y,
-        zeropage: LDA_ZEROPAGE,
-        zeropage_x: LDA_ZEROPAGE_X,
+        absolute: Some(SLO_ABSOLUTE),
+        absolute_x: Some(SLO_ABSOLUTE_X),
+        absolute_y: Some(SLO_ABSOLUTE_Y),
+        zeropage: Some(SLO_ZEROPAGE),
+        zeropage_x: Some(SLO_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: LDA_INDEXED_INDIRECT,
-        indirect_indexed: LDA_INDIRECT_INDEXED,
+        indexed_indirect: Some(SLO_INDEXED_INDIRECT),
+        indirect_indexed: Some(SLO_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 7,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 8,
+            indirect_indexed: 8,
+        },
+        page_penalty: PagePenalty::None,
     };
 
-    /// Instruction definition for ldy
+    /// Instruction definition for rla
     ///
     /// Includes the instruction name and its [OpCode] for a address mode.
-    pub const OPCODES_LDY: InstructionDef = InstructionDef {
-        instruction: "ldy",
+    pub const OPCODES_RLA: InstructionDef = InstructionDef {
+        instruction: "rla",
         implied: NO_IMPLIED,
-        immediate: LDY_IMMEDIATE,
+        immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: LDY_ABSOLUTE,
-        absolute_x: LDY_ABSOLUTE_X,
-        absolute_y: NO_ABSOLUTE_Y,
-        zeropage: LDY_ZEROPAGE,
-        zeropage_x: LDY_ZEROPAGE_X,
+        absolute: Some(RLA_ABSOLUTE),
+        absolute_x: Some(RLA_ABSOLUTE_X),
+        absolute_y: Some(RLA_ABSOLUTE_Y),
+        zeropage: Some(RLA_ZEROPAGE),
+        zeropage_x: Some(RLA_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: NO_INDEXED_INDIRECT,
+        indexed_indirect: Some(RLA_INDEXED_INDIRECT),
+        indirect_indexed: Some(RLA_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 7,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 8,
+            indirect_indexed: 8,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for sre
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_SRE: InstructionDef = InstructionDef {
+        instruction: "sre",
+        implied: NO_IMPLIED,
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(SRE_ABSOLUTE),
+        absolute_x: Some(SRE_ABSOLUTE_X),
+        absolute_y: Some(SRE_ABSOLUTE_Y),
+        zeropage: Some(SRE_ZEROPAGE),
+        zeropage_x: Some(SRE_ZEROPAGE_X),
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: Some(SRE_INDEXED_INDIRECT),
+        indirect_indexed: Some(SRE_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 7,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 8,
+            indirect_indexed: 8,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for rra
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_RRA: InstructionDef = InstructionDef {
+        instruction: "rra",
+        implied: NO_IMPLIED,
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(RRA_ABSOLUTE),
+        absolute_x: Some(RRA_ABSOLUTE_X),
+        absolute_y: Some(RRA_ABSOLUTE_Y),
+        zeropage: Some(RRA_ZEROPAGE),
+        zeropage_x: Some(RRA_ZEROPAGE_X),
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: Some(RRA_INDEXED_INDIRECT),
+        indirect_indexed: Some(RRA_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 7,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 8,
+            indirect_indexed: 8,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for sax
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_SAX: InstructionDef = InstructionDef {
+        instruction: "sax",
+        implied: NO_IMPLIED,
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(SAX_ABSOLUTE),
+        absolute_x: NO_ABSOLUTE_X,
+        absolute_y: NO_ABSOLUTE_Y,
+        zeropage: Some(SAX_ZEROPAGE),
+        zeropage_x: NO_ZEROPAGE_X,
+        zeropage_y: Some(SAX_ZEROPAGE_Y),
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: Some(SAX_INDEXED_INDIRECT),
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 3,
+            zeropage_x: 0,
+            zeropage_y: 4,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
-    /// Instruction definition for sta
+    /// Instruction definition for lax
     ///
     /// Includes the instruction name and its [OpCode] for a address mode.
-    pub const OPCODES_STA: InstructionDef = InstructionDef {
-        instruction: "sta",
+    pub const OPCODES_LAX: InstructionDef = InstructionDef {
+        instruction: "lax",
+        implied: NO_IMPLIED,
+        immediate: NO_IMMEDIATE,
+        accumulator: NO_ACCUMULATOR,
+        absolute: Some(LAX_ABSOLUTE),
+        absolute_x: NO_ABSOLUTE_X,
+        absolute_y: Some(LAX_ABSOLUTE_Y),
+        zeropage: Some(LAX_ZEROPAGE),
+        zeropage_x: NO_ZEROPAGE_X,
+        zeropage_y: Some(LAX_ZEROPAGE_Y),
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: Some(LAX_INDEXED_INDIRECT),
+        indirect_indexed: Some(LAX_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 4,
+            absolute_x: 0,
+            absolute_y: 4,
+            zeropage: 3,
+            zeropage_x: 0,
+            zeropage_y: 4,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 6,
+            indirect_indexed: 5,
+        },
+        page_penalty: PagePenalty::IndexedRead,
+    };
+
+    /// Instruction definition for dcp
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_DCP: InstructionDef = InstructionDef {
+        instruction: "dcp",
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: STA_ABSOLUTE,
-        absolute_x: STA_ABSOLUTE_X,
-        absolute_y: STA_ABSOLUTE_Y,
-        zeropage: STA_ZEROPAGE,
-        zeropage_x: STA_ZEROPAGE_X,
+        absolute: Some(DCP_ABSOLUTE),
+        absolute_x: Some(DCP_ABSOLUTE_X),
+        absolute_y: Some(DCP_ABSOLUTE_Y),
+        zeropage: Some(DCP_ZEROPAGE),
+        zeropage_x: Some(DCP_ZEROPAGE_X),
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
         indirect: NO_INDIRECT,
-        indexed_indirect: STA_INDEXED_INDIRECT,
-        indirect_indexed: STA_INDIRECT_INDEXED,
+        indexed_indirect: Some(DCP_INDEXED_INDIRECT),
+        indirect_indexed: Some(DCP_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 7,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 8,
+            indirect_indexed: 8,
+        },
+        page_penalty: PagePenalty::None,
     };
 
-    /// Instruction definition for jmp
+    /// Instruction definition for isc
     ///
     /// Includes the instruction name and its [OpCode] for a address mode.
-    pub const OPCODES_JMP: InstructionDef = InstructionDef {
-        instruction: "jmp",
+    pub const OPCODES_ISC: InstructionDef = InstructionDef {
+        instruction: "isc",
         implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
-        absolute: JMP_ABSOLUTE,
+        absolute: Some(ISC_ABSOLUTE),
+        absolute_x: Some(ISC_ABSOLUTE_X),
+        absolute_y: Some(ISC_ABSOLUTE_Y),
+        zeropage: Some(ISC_ZEROPAGE),
+        zeropage_x: Some(ISC_ZEROPAGE_X),
+        zeropage_y: NO_ZEROPAGE_Y,
+        relative: NO_RELATIVE,
+        indirect: NO_INDIRECT,
+        indexed_indirect: Some(ISC_INDEXED_INDIRECT),
+        indirect_indexed: Some(ISC_INDIRECT_INDEXED),
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 6,
+            absolute_x: 7,
+            absolute_y: 7,
+            zeropage: 5,
+            zeropage_x: 6,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 8,
+            indirect_indexed: 8,
+        },
+        page_penalty: PagePenalty::None,
+    };
+
+    /// Instruction definition for anc
+    ///
+    /// Includes the instruction name and its [OpCode] for a address mode.
+    pub const OPCODES_ANC: InstructionDef = InstructionDef {
+        instruction: "anc",
+        implied: NO_IMPLIED,
+        immediate: Some(ANC_IMMEDIATE),
+        accumulator: NO_ACCUMULATOR,
+        absolute: NO_ABSOLUTE,
         absolute_x: NO_ABSOLUTE_X,
         absolute_y: NO_ABSOLUTE_Y,
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
         relative: NO_RELATIVE,
-        indirect: JMP_INDIRECT,
+        indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
-    /// Instruction definition for jsr
+    /// Instruction definition for alr
     ///
     /// Includes the instruction name and its [OpCode] for a address mode.
-    pub const OPCODES_JSR: InstructionDef = InstructionDef {
-        instruction: "jsr",
+    pub const OPCODES_ALR: InstructionDef = InstructionDef {
+        instruction: "alr",
         implied: NO_IMPLIED,
-        immediate: NO_IMMEDIATE,
+        immediate: Some(ALR_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
-        absolute: JSR_ABSOLUTE,
+        absolute: NO_ABSOLUTE,
         absolute_x: NO_ABSOLUTE_X,
         absolute_y: NO_ABSOLUTE_Y,
         zeropage: NO_ZEROPAGE,
@@ -1126,15 +2587,31 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
-    /// Instruction definition for sec
+    /// Instruction definition for arr
     ///
     /// Includes the instruction name and its [OpCode] for a address mode.
-    pub const OPCODES_SEC: InstructionDef = InstructionDef {
-        instruction: "sec",
-        implied: SEC_IMPLIED,
-        immediate: NO_IMMEDIATE,
+    pub const OPCODES_ARR: InstructionDef = InstructionDef {
+        instruction: "arr",
+        implied: NO_IMPLIED,
+        immediate: Some(ARR_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
         absolute_x: NO_ABSOLUTE_X,
@@ -1146,15 +2623,31 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
-    /// Instruction definition for clc
+    /// Instruction definition for sbx
     ///
     /// Includes the instruction name and its [OpCode] for a address mode.
-    pub const OPCODES_CLC: InstructionDef = InstructionDef {
-        instruction: "clc",
-        implied: CLC_IMPLIED,
-        immediate: NO_IMMEDIATE,
+    pub const OPCODES_SBX: InstructionDef = InstructionDef {
+        instruction: "sbx",
+        implied: NO_IMPLIED,
+        immediate: Some(SBX_IMMEDIATE),
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
         absolute_x: NO_ABSOLUTE_X,
@@ -1166,19 +2659,35 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 2,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 0,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::None,
     };
 
-    /// Instruction definition for rts
+    /// Instruction definition for las
     ///
     /// Includes the instruction name and its [OpCode] for a address mode.
-    pub const OPCODES_RTS: InstructionDef = InstructionDef {
-        instruction: "rts",
-        implied: RTS_IMPLIED,
+    pub const OPCODES_LAS: InstructionDef = InstructionDef {
+        instruction: "las",
+        implied: NO_IMPLIED,
         immediate: NO_IMMEDIATE,
         accumulator: NO_ACCUMULATOR,
         absolute: NO_ABSOLUTE,
         absolute_x: NO_ABSOLUTE_X,
-        absolute_y: NO_ABSOLUTE_Y,
+        absolute_y: Some(LAS_ABSOLUTE_Y),
         zeropage: NO_ZEROPAGE,
         zeropage_x: NO_ZEROPAGE_X,
         zeropage_y: NO_ZEROPAGE_Y,
@@ -1186,5 +2695,21 @@ mod gen {
         indirect: NO_INDIRECT,
         indexed_indirect: NO_INDEXED_INDIRECT,
         indirect_indexed: NO_INDIRECT_INDEXED,
+        cycles: Cycles {
+            implied: 0,
+            immediate: 0,
+            accumulator: 0,
+            absolute: 0,
+            absolute_x: 0,
+            absolute_y: 4,
+            zeropage: 0,
+            zeropage_x: 0,
+            zeropage_y: 0,
+            relative: 0,
+            indirect: 0,
+            indexed_indirect: 0,
+            indirect_indexed: 0,
+        },
+        page_penalty: PagePenalty::IndexedRead,
     };
 }
@@ -7,7 +7,7 @@
 //! | **Instruction** | **Implied** | **Immediate** | **Accumulator** | **Absolute** | **Absolute,X** | **Absolute,Y** | **Zero Page** | **Zero Page,X** | **Zero Page,Y** | **Relative** | **Indirect** | **Indirect,X** | **Indirect,Y** |
 //! | --------------- | ----------- | ------------- | --------------- | ------------ | -------------- | -------------- | ------------- | --------------- | --------------- | ------------ | ------------ | -------------- | -------------- |
 //! | ADC             |             | 0x69          |                 | 0x6D         | 0x7D           | 0x79           | 0x65          | 0x75            |                 |              |              | 0x61           | 0x71           |
-//! | AND             |             | 0x29          |                 | 0x2D         | 0x3D           | 0x39           | 0x25          | 0x35            |                 |              |              | 0x21           | 0x01           |
+//! | AND             |             | 0x29          |                 | 0x2D         | 0x3D           | 0x39           | 0x25          | 0x35            |                 |              |              | 0x21           | 0x31           |
 //! | ASL             |             |               | 0x0A            | 0x0E         | 0x1E           |                | 0x06          | 0x16            |                 |              |              |                |                |
 //! | BCC             |             |               |                 |              |                |                |               |                 |                 | 0x90         |              |                |                |
 //! | BCS             |             |               |                 |              |                |                |               |                 |                 | 0xB0         |              |                |                |
@@ -62,6 +62,27 @@
 //! | TXA             | 0x8A        |               |                 |              |                |                |               |                 |                 |              |              |                |                |
 //! | TXS             | 0x9A        |               |                 |              |                |                |               |                 |                 |              |              |                |                |
 //! | TYA             | 0x98        |               |                 |              |                |                |               |                 |                 |              |              |                |                |
+//!
+//! # Illegal instructions
+//!
+//! The NMOS 6502 also executes op-codes that aren't part of the official instruction set.
+//! Only the stable ones are defined and they are returned by [isa_6502_illegal].
+//!
+//! | **Instruction** | **Implied** | **Immediate** | **Accumulator** | **Absolute** | **Absolute,X** | **Absolute,Y** | **Zero Page** | **Zero Page,X** | **Zero Page,Y** | **Relative** | **Indirect** | **Indirect,X** | **Indirect,Y** |
+//! | --------------- | ----------- | ------------- | --------------- | ------------ | -------------- | -------------- | ------------- | --------------- | --------------- | ------------ | ------------ | -------------- | -------------- |
+//! | SLO             |             |               |                 | 0x0F         | 0x1F           | 0x1B           | 0x07          | 0x17            |                 |              |              | 0x03           | 0x13           |
+//! | RLA             |             |               |                 | 0x2F         | 0x3F           | 0x3B           | 0x27          | 0x37            |                 |              |              | 0x23           | 0x33           |
+//! | SRE             |             |               |                 | 0x4F         | 0x5F           | 0x5B           | 0x47          | 0x57            |                 |              |              | 0x43           | 0x53           |
+//! | RRA             |             |               |                 | 0x6F         | 0x7F           | 0x7B           | 0x67          | 0x77            |                 |              |              | 0x63           | 0x73           |
+//! | SAX             |             |               |                 | 0x8F         |                |                | 0x87          |                 | 0x97            |              |              | 0x83           |                |
+//! | LAX             |             |               |                 | 0xAF         |                | 0xBF           | 0xA7          |                 | 0xB7            |              |              | 0xA3           | 0xB3           |
+//! | DCP             |             |               |                 | 0xCF         | 0xDF           | 0xDB           | 0xC7          | 0xD7            |                 |              |              | 0xC3           | 0xD3           |
+//! | ISC             |             |               |                 | 0xEF         | 0xFF           | 0xFB           | 0xE7          | 0xF7            |                 |              |              | 0xE3           | 0xF3           |
+//! | ANC             |             | 0x0B          |                 |              |                |                |               |                 |                 |              |              |                |                |
+//! | ALR             |             | 0x4B          |                 |              |                |                |               |                 |                 |              |              |                |                |
+//! | ARR             |             | 0x6B          |                 |              |                |                |               |                 |                 |              |              |                |                |
+//! | SBX             |             | 0xCB          |                 |              |                |                |               |                 |                 |              |              |                |                |
+//! | LAS             |             |               |                 |              |                | 0xBB           |               |                 |                 |              |              |                |                |
 
 pub mod instruction;
 pub mod opcodes;
@@ -129,3 +150,67 @@ pub fn isa_6502() -> Vec<InstructionDef> {
         OPCODES_TYA,
     ]
 }
+
+/// Return the illegal (undocumented) instruction definitions of the NMOS 6502.
+///
+/// Only the stable illegal instructions are included. Combine with [isa_6502] to
+/// decode programs that use them.
+///
+/// ```
+/// use c64_assembler_6502::{isa_6502, isa_6502_illegal, opcode_table};
+///
+/// let mut isa = isa_6502();
+/// isa.extend(isa_6502_illegal());
+/// let table = opcode_table(&isa);
+/// assert_eq!("lax", table[0xA7].unwrap().0.instruction);
+/// ```
+pub fn isa_6502_illegal() -> Vec<InstructionDef> {
+    vec![
+        OPCODES_SLO,
+        OPCODES_RLA,
+        OPCODES_SRE,
+        OPCODES_RRA,
+        OPCODES_SAX,
+        OPCODES_LAX,
+        OPCODES_DCP,
+        OPCODES_ISC,
+        OPCODES_ANC,
+        OPCODES_ALR,
+        OPCODES_ARR,
+        OPCODES_SBX,
+        OPCODES_LAS,
+    ]
+}
+
+/// Lookup table to decode op-codes.
+///
+/// Contains for each op-code the instruction definition and addressing mode it encodes.
+/// Op-codes that aren't part of the given instruction set are `None`.
+///
+/// ```
+/// use c64_assembler_6502::{instruction::AddressingMode, isa_6502, isa_6502_illegal, opcode_table};
+///
+/// let table = opcode_table(&isa_6502());
+/// let (definition, addressing_mode) = table[0xA9].unwrap();
+/// assert_eq!("lda", definition.instruction);
+/// assert_eq!(AddressingMode::Immediate, addressing_mode);
+/// assert!(table[0x02].is_none());
+///
+/// // Illegal instructions use all op-codes except the ones that halt the CPU.
+/// let table = opcode_table(&isa_6502_illegal());
+/// let (definition, addressing_mode) = table[0xFF].unwrap();
+/// assert_eq!("isc", definition.instruction);
+/// assert_eq!(AddressingMode::AbsoluteX, addressing_mode);
+/// assert!(table[0x02].is_none());
+/// ```
+pub fn opcode_table(isa: &[InstructionDef]) -> Vec<Option<(InstructionDef, AddressingMode)>> {
+    let mut table = vec![None; 256];
+    for definition in isa {
+        for addressing_mode in AddressingMode::ALL {
+            if let Some(opcode) = definition.opcode(addressing_mode) {
+                table[opcode as usize] = Some((*definition, addressing_mode));
+            }
+        }
+    }
+    table
+}
@@ -3,33 +3,32 @@ pub use gen::*;
 /// An OpCode is the first byte that encodes the instruction and type of addressing mode.
 pub type OpCode = u8;
 
-const UNUSED: OpCode = 0xFF;
-/// Special OpCode for instructions that don't have an op-code for implied addressing mode.
-pub const NO_IMPLIED: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for immediate addressing mode.
-pub const NO_IMMEDIATE: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for accumulator addressing mode.
-pub const NO_ACCUMULATOR: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for absolute addressing mode.
-pub const NO_ABSOLUTE: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for absolute-x addressing mode.
-pub const NO_ABSOLUTE_X: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for absolute-y addressing mode.
-pub const NO_ABSOLUTE_Y: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for zeropage addressing mode.
-pub const NO_ZEROPAGE: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for zeropage-x addressing mode.
-pub const NO_ZEROPAGE_X: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for zeropage-y addressing mode.
-pub const NO_ZEROPAGE_Y: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for indirect addressing mode.
-pub const NO_INDIRECT: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for indexed indirect addressing mode.
-pub const NO_INDEXED_INDIRECT: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for indirect indexed addressing mode.
-pub const NO_INDIRECT_INDEXED: OpCode = UNUSED;
-/// Special OpCode for instructions that don't have an op-code for relative addressing mode.
-pub const NO_RELATIVE: OpCode = UNUSED;
+/// Special value for instructions that don't have an op-code for implied addressing mode.
+pub const NO_IMPLIED: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for immediate addressing mode.
+pub const NO_IMMEDIATE: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for accumulator addressing mode.
+pub const NO_ACCUMULATOR: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for absolute addressing mode.
+pub const NO_ABSOLUTE: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for absolute-x addressing mode.
+pub const NO_ABSOLUTE_X: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for absolute-y addressing mode.
+pub const NO_ABSOLUTE_Y: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for zeropage addressing mode.
+pub const NO_ZEROPAGE: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for zeropage-x addressing mode.
+pub const NO_ZEROPAGE_X: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for zeropage-y addressing mode.
+pub const NO_ZEROPAGE_Y: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for indirect addressing mode.
+pub const NO_INDIRECT: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for indexed indirect addressing mode.
+pub const NO_INDEXED_INDIRECT: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for indirect indexed addressing mode.
+pub const NO_INDIRECT_INDEXED: Option<OpCode> = None;
+/// Special value for instructions that don't have an op-code for relative addressing mode.
+pub const NO_RELATIVE: Option<OpCode> = None;
 
 mod gen {
     use super::OpCode;
@@ -65,7 +64,7 @@ mod gen {
     /// OpCode for the and instruction in addressing mode indexed_indirect
     pub const AND_INDEXED_INDIRECT: OpCode = 0x21;
     /// OpCode for the and instruction in addressing mode indirect_indexed
-    pub const AND_INDIRECT_INDEXED: OpCode = 0x01;
+    pub const AND_INDIRECT_INDEXED: OpCode = 0x31;
     /// OpCode for the asl instruction in addressing mode accumulator
     pub const ASL_ACCUMULATOR: OpCode = 0x0a;
     /// OpCode for the asl instruction in addressing mode absolute
@@ -98,6 +97,8 @@ mod gen {
     pub const BVC_RELATIVE: OpCode = 0x50;
     /// OpCode for the bvs instruction in addressing mode relative
     pub const BVS_RELATIVE: OpCode = 0x70;
+    /// OpCode for the clc instruction in addressing mode implied
+    pub const CLC_IMPLIED: OpCode = 0x18;
     /// OpCode for the cld instruction in addressing mode implied
     pub const CLD_IMPLIED: OpCode = 0xd8;
     /// OpCode for the cli instruction in addressing mode implied
@@ -172,6 +173,28 @@ mod gen {
     pub const INX_IMPLIED: OpCode = 0xe8;
     /// OpCode for the iny instruction in addressing mode implied
     pub const INY_IMPLIED: OpCode = 0xc8;
+    /// OpCode for the jmp instruction in addressing mode absolute
+    pub const JMP_ABSOLUTE: OpCode = 0x4c;
+    /// OpCode for the jmp instruction in addressing mode indirect
+    pub const JMP_INDIRECT: OpCode = 0x6c;
+    /// OpCode for the jsr instruction in addressing mode absolute
+    pub const JSR_ABSOLUTE: OpCode = 0x20;
+    /// OpCode for the lda instruction in addressing mode immediate
+    pub const LDA_IMMEDIATE: OpCode = 0xa9;
+    /// OpCode for the lda instruction in addressing mode absolute
+    pub const LDA_ABSOLUTE: OpCode = 0xad;
+    /// OpCode for the lda instruction in addressing mode absolute_x
+    pub const LDA_ABSOLUTE_X: OpCode = 0xbd;
+    /// OpCode for the lda instruction in addressing mode absolute_y
+    pub const LDA_ABSOLUTE_Y: OpCode = 0xb9;
+    /// OpCode for the lda instruction in addressing mode zeropage
+    pub const LDA_ZEROPAGE: OpCode = 0xa5;
+    /// OpCode for the lda instruction in addressing mode zeropage_x
+    pub const LDA_ZEROPAGE_X: OpCode = 0xb5;
+    /// OpCode for the lda instruction in addressing mode indexed_indirect
+    pub const LDA_INDEXED_INDIRECT: OpCode = 0xa1;
+    /// OpCode for the lda instruction in addressing mode indirect_indexed
+    pub const LDA_INDIRECT_INDEXED: OpCode = 0xb1;
     /// OpCode for the ldx instruction in addressing mode immediate
     pub const LDX_IMMEDIATE: OpCode = 0xa2;
     /// OpCode for the ldx instruction in addressing mode absolute
@@ -182,6 +205,16 @@ mod gen {
     pub const LDX_ZEROPAGE: OpCode = 0xa6;
     /// OpCode for the ldx instruction in addressing mode zeropage_y
     pub const LDX_ZEROPAGE_Y: OpCode = 0xb6;
+    /// OpCode for the ldy instruction in addressing mode immediate
+    pub const LDY_IMMEDIATE: OpCode = 0xa0;
+    /// OpCode for the ldy instruction in addressing mode absolute
+    pub const LDY_ABSOLUTE: OpCode = 0xac;
+    /// OpCode for the ldy instruction in addressing mode absolute_x
+    pub const LDY_ABSOLUTE_X: OpCode = 0xbc;
+    /// OpCode for the ldy instruction in addressing mode zeropage
+    pub const LDY_ZEROPAGE: OpCode = 0xa4;
+    /// OpCode for the ldy instruction in addressing mode zeropage_x
+    pub const LDY_ZEROPAGE_X: OpCode = 0xb4;
     /// OpCode for the lsr instruction in addressing mode accumulator
     pub const LSR_ACCUMULATOR: OpCode = 0x4a;
     /// OpCode for the lsr instruction in addressing mode absolute
@@ -240,6 +273,8 @@ mod gen {
     pub const ROR_ZEROPAGE_X: OpCode = 0x76;
     /// OpCode for the rti instruction in addressing mode implied
     pub const RTI_IMPLIED: OpCode = 0x40;
+    /// OpCode for the rts instruction in addressing mode implied
+    pub const RTS_IMPLIED: OpCode = 0x60;
     /// OpCode for the sbc instruction in addressing mode immediate
     pub const SBC_IMMEDIATE: OpCode = 0xe9;
     /// OpCode for the sbc instruction in addressing mode absolute
@@ -256,10 +291,26 @@ mod gen {
     pub const SBC_INDEXED_INDIRECT: OpCode = 0xe1;
     /// OpCode for the sbc instruction in addressing mode indirect_indexed
     pub const SBC_INDIRECT_INDEXED: OpCode = 0xf1;
+    /// OpCode for the sec instruction in addressing mode implied
+    pub const SEC_IMPLIED: OpCode = 0x38;
     /// OpCode for the sed instruction in addressing mode implied
     pub const SED_IMPLIED: OpCode = 0xf8;
     /// OpCode for the sei instruction in addressing mode implied
     pub const SEI_IMPLIED: OpCode = 0x78;
+    /// OpCode for the sta instruction in addressing mode absolute
+    pub const STA_ABSOLUTE: OpCode = 0x8d;
+    /// OpCode for the sta instruction in addressing mode absolute_x
+    pub const STA_ABSOLUTE_X: OpCode = 0x9d;
+    /// OpCode for the sta instruction in addressing mode absolute_y
+    pub const STA_ABSOLUTE_Y: OpCode = 0x99;
+    /// OpCode for the sta instruction in addressing mode zeropage
+    pub const STA_ZEROPAGE: OpCode = 0x85;
+    /// OpCode for the sta instruction in addressing mode zeropage_x
+    pub const STA_ZEROPAGE_X: OpCode = 0x95;
+    /// OpCode for the sta instruction in addressing mode indexed_indirect
+    pub const STA_INDEXED_INDIRECT: OpCode = 0x81;
+    /// OpCode for the sta instruction in addressing mode indirect_indexed
+    pub const STA_INDIRECT_INDEXED: OpCode = 0x91;
     /// OpCode for the stx instruction in addressing mode absolute
     pub const STX_ABSOLUTE: OpCode = 0x8e;
     /// OpCode for the stx instruction in addressing mode zeropage
@@ -284,56 +335,120 @@ mod gen {
     pub const TXS_IMPLIED: OpCode = 0x9a;
     /// OpCode for the tya instruction in addressing mode implied
     pub const TYA_IMPLIED: OpCode = 0x98;
-    /// OpCode for the lda instruction in addressing mode immediate
-    pub const LDA_IMMEDIATE: OpCode = 0xa9;
-    /// OpCode for the lda instruction in addressing mode absolute
-    pub const LDA_ABSOLUTE: OpCode = 0xad;
-    /// OpCode for the lda instruction in addressing mode absolute_x
-    pub const LDA_ABSOLUTE_X: OpCode = 0xbd;
-    /// OpCode for the lda instruction in addressing mode absolute_y
-    pub const LDA_ABSOLUTE_Y: OpCode = 0xb9;
-    /// OpCode for the lda instruction in addressing mode zeropage
-    pub const LDA_ZEROPAGE: OpCode = 0xa5;
-    /// OpCode for the lda instruction in addressing mode zeropage_x
-    pub const LDA_ZEROPAGE_X: OpCode = 0xb5;
-    /// OpCode for the lda instruction in addressing mode indexed_indirect
-    pub const LDA_INDEXED_INDIRECT: OpCode = 0xa1;
-    /// OpCode for the lda instruction in addressing mode indirect_indexed
-    pub const LDA_INDIRECT_INDEXED: OpCode = 0xb1;
-    /// OpCode for the ldy instruction in addressing mode immediate
-    pub const LDY_IMMEDIATE: OpCode = 0xa0;
-    /// OpCode for the ldy instruction in addressing mode absolute
-    pub const LDY_ABSOLUTE: OpCode = 0xac;
-    /// OpCode for the ldy instruction in addressing mode absolute_x
-    pub const LDY_ABSOLUTE_X: OpCode = 0xbc;
-    /// OpCode for the ldy instruction in addressing mode zeropage
-    pub const LDY_ZEROPAGE: OpCode = 0xa4;
-    /// OpCode for the ldy instruction in addressing mode zeropage_x
-    pub const LDY_ZEROPAGE_X: OpCode = 0xb4;
-    /// OpCode for the sta instruction in addressing mode absolute
-    pub const STA_ABSOLUTE: OpCode = 0x8d;
-    /// OpCode for the sta instruction in addressing mode absolute_x
-    pub const STA_ABSOLUTE_X: OpCode = 0x9d;
-    /// OpCode for the sta instruction in addressing mode absolute_y
-    pub const STA_ABSOLUTE_Y: OpCode = 0x99;
-    /// OpCode for the sta instruction in addressing mode zeropage
-    pub const STA_ZEROPAGE: OpCode = 0x85;
-    /// OpCode for the sta instruction in addressing mode zeropage_x
-    pub const STA_ZEROPAGE_X: OpCode = 0x95;
-    /// OpCode for the sta instruction in addressing mode indexed_indirect
-    pub const STA_INDEXED_INDIRECT: OpCode = 0x81;
-    /// OpCode for the sta instruction in addressing mode indirect_indexed
-    pub const STA_INDIRECT_INDEXED: OpCode = 0x91;
-    /// OpCode for the jmp instruction in addressing mode absolute
-    pub const JMP_ABSOLUTE: OpCode = 0x4c;
-    /// OpCode for the jmp instruction in addressing mode indirect
-    pub const JMP_INDIRECT: OpCode = 0x6c;
-    /// OpCode for the jsr instruction in addressing mode absolute
-    pub const JSR_ABSOLUTE: OpCode = 0x20;
-    /// OpCode for the sec instruction in addressing mode implied
-    pub const SEC_IMPLIED: OpCode = 0x38;
-    /// OpCode for the clc instruction in addressing mode implied
-    pub const CLC_IMPLIED: OpCode = 0x18;
-    /// OpCode for the rts instruction in addressing mode implied
-    pub const RTS_IMPLIED: OpCode = 0x60;
+
+    // Illegal (undocumented) instructions of the NMOS 6502.
+    /// OpCode for the slo instruction in addressing mode absolute
+    pub const SLO_ABSOLUTE: OpCode = 0x0f;
+    /// OpCode for the slo instruction in addressing mode absolute_x
+    pub const SLO_ABSOLUTE_X: OpCode = 0x1f;
+    /// OpCode for the slo instruction in addressing mode absolute_y
+    pub const SLO_ABSOLUTE_Y: OpCode = 0x1b;
+    /// OpCode for the slo instruction in addressing mode zeropage
+    pub const SLO_ZEROPAGE: OpCode = 0x07;
+    /// OpCode for the slo instruction in addressing mode zeropage_x
+    pub const SLO_ZEROPAGE_X: OpCode = 0x17;
+    /// OpCode for the slo instruction in addressing mode indexed_indirect
+    pub const SLO_INDEXED_INDIRECT: OpCode = 0x03;
+    /// OpCode for the slo instruction in addressing mode indirect_indexed
+    pub const SLO_INDIRECT_INDEXED: OpCode = 0x13;
+    /// OpCode for the rla instruction in addressing mode absolute
+    pub const RLA_ABSOLUTE: OpCode = 0x2f;
+    /// OpCode for the rla instruction in addressing mode absolute_x
+    pub const RLA_ABSOLUTE_X: OpCode = 0x3f;
+    /// OpCode for the rla instruction in addressing mode absolute_y
+    pub const RLA_ABSOLUTE_Y: OpCode = 0x3b;
+    /// OpCode for the rla instruction in addressing mode zeropage
+    pub const RLA_ZEROPAGE: OpCode = 0x27;
+    /// OpCode for the rla instruction in addressing mode zeropage_x
+    pub const RLA_ZEROPAGE_X: OpCode = 0x37;
+    /// OpCode for the rla instruction in addressing mode indexed_indirect
+    pub const RLA_INDEXED_INDIRECT: OpCode = 0x23;
+    /// OpCode for the rla instruction in addressing mode indirect_indexed
+    pub const RLA_INDIRECT_INDEXED: OpCode = 0x33;
+    /// OpCode for the sre instruction in addressing mode absolute
+    pub const SRE_ABSOLUTE: OpCode = 0x4f;
+    /// OpCode for the sre instruction in addressing mode absolute_x
+    pub const SRE_ABSOLUTE_X: OpCode = 0x5f;
+    /// OpCode for the sre instruction in addressing mode absolute_y
+    pub const SRE_ABSOLUTE_Y: OpCode = 0x5b;
+    /// OpCode for the sre instruction in addressing mode zeropage
+    pub const SRE_ZEROPAGE: OpCode = 0x47;
+    /// OpCode for the sre instruction in addressing mode zeropage_x
+    pub const SRE_ZEROPAGE_X: OpCode = 0x57;
+    /// OpCode for the sre instruction in addressing mode indexed_indirect
+    pub const SRE_INDEXED_INDIRECT: OpCode = 0x43;
+    /// OpCode for the sre instruction in addressing mode indirect_indexed
+    pub const SRE_INDIRECT_INDEXED: OpCode = 0x53;
+    /// OpCode for the rra instruction in addressing mode absolute
+    pub const RRA_ABSOLUTE: OpCode = 0x6f;
+    /// OpCode for the rra instruction in addressing mode absolute_x
+    pub const RRA_ABSOLUTE_X: OpCode = 0x7f;
+    /// OpCode for the rra instruction in addressing mode absolute_y
+    pub const RRA_ABSOLUTE_Y: OpCode = 0x7b;
+    /// OpCode for the rra instruction in addressing mode zeropage
+    pub const RRA_ZEROPAGE: OpCode = 0x67;
+    /// OpCode for the rra instruction in addressing mode zeropage_x
+    pub const RRA_ZEROPAGE_X: OpCode = 0x77;
+    /// OpCode for the rra instruction in addressing mode indexed_indirect
+    pub const RRA_INDEXED_INDIRECT: OpCode = 0x63;
+    /// OpCode for the rra instruction in addressing mode indirect_indexed
+    pub const RRA_INDIRECT_INDEXED: OpCode = 0x73;
+    /// OpCode for the sax instruction in addressing mode absolute
+    pub const SAX_ABSOLUTE: OpCode = 0x8f;
+    /// OpCode for the sax instruction in addressing mode zeropage
+    pub const SAX_ZEROPAGE: OpCode = 0x87;
+    /// OpCode for the sax instruction in addressing mode zeropage_y
+    pub const SAX_ZEROPAGE_Y: OpCode = 0x97;
+    /// OpCode for the sax instruction in addressing mode indexed_indirect
+    pub const SAX_INDEXED_INDIRECT: OpCode = 0x83;
+    /// OpCode for the lax instruction in addressing mode absolute
+    pub const LAX_ABSOLUTE: OpCode = 0xaf;
+    /// OpCode for the lax instruction in addressing mode absolute_y
+    pub const LAX_ABSOLUTE_Y: OpCode = 0xbf;
+    /// OpCode for the lax instruction in addressing mode zeropage
+    pub const LAX_ZEROPAGE: OpCode = 0xa7;
+    /// OpCode for the lax instruction in addressing mode zeropage_y
+    pub const LAX_ZEROPAGE_Y: OpCode = 0xb7;
+    /// OpCode for the lax instruction in addressing mode indexed_indirect
+    pub const LAX_INDEXED_INDIRECT: OpCode = 0xa3;
+    /// OpCode for the lax instruction in addressing mode indirect_indexed
+    pub const LAX_INDIRECT_INDEXED: OpCode = 0xb3;
+    /// OpCode for the dcp instruction in addressing mode absolute
+    pub const DCP_ABSOLUTE: OpCode = 0xcf;
+    /// OpCode for the dcp instruction in addressing mode absolute_x
+    pub const DCP_ABSOLUTE_X: OpCode = 0xdf;
+    /// OpCode for the dcp instruction in addressing mode absolute_y
+    pub const DCP_ABSOLUTE_Y: OpCode = 0xdb;
+    /// OpCode for the dcp instruction in addressing mode zeropage
+    pub const DCP_ZEROPAGE: OpCode = 0xc7;
+    /// OpCode for the dcp instruction in addressing mode zeropage_x
+    pub const DCP_ZEROPAGE_X: OpCode = 0xd7;
+    /// OpCode for the dcp instruction in addressing mode indexed_indirect
+    pub const DCP_INDEXED_INDIRECT: OpCode = 0xc3;
+    /// OpCode for the dcp instruction in addressing mode indirect_indexed
+    pub const DCP_INDIRECT_INDEXED: OpCode = 0xd3;
+    /// OpCode for the isc instruction in addressing mode absolute
+    pub const ISC_ABSOLUTE: OpCode = 0xef;
+    /// OpCode for the isc instruction in addressing mode absolute_x
+    pub const ISC_ABSOLUTE_X: OpCode = 0xff;
+    /// OpCode for the isc instruction in addressing mode absolute_y
+    pub const ISC_ABSOLUTE_Y: OpCode = 0xfb;
+    /// OpCode for the isc instruction in addressing mode zeropage
+    pub const ISC_ZEROPAGE: OpCode = 0xe7;
+    /// OpCode for the isc instruction in addressing mode zeropage_x
+    pub const ISC_ZEROPAGE_X: OpCode = 0xf7;
+    /// OpCode for the isc instruction in addressing mode indexed_indirect
+    pub const ISC_INDEXED_INDIRECT: OpCode = 0xe3;
+    /// OpCode for the isc instruction in addressing mode indirect_indexed
+    pub const ISC_INDIRECT_INDEXED: OpCode = 0xf3;
+    /// OpCode for the anc instruction in addressing mode immediate
+    pub const ANC_IMMEDIATE: OpCode = 0x0b;
+    /// OpCode for the alr instruction in addressing mode immediate
+    pub const ALR_IMMEDIATE: OpCode = 0x4b;
+    /// OpCode for the arr instruction in addressing mode immediate
+    pub const ARR_IMMEDIATE: OpCode = 0x6b;
+    /// OpCode for the sbx instruction in addressing mode immediate
+    pub const SBX_IMMEDIATE: OpCode = 0xcb;
+    /// OpCode for the las instruction in addressing mode absolute_y
+    pub const LAS_ABSOLUTE_Y: OpCode = 0xbb;
 }
@@ -0,0 +1,624 @@
+//! 6502 CPU.
+use std::collections::BTreeSet;
+
+use c64_assembler_6502::{
+    instruction::{AddressingMode, InstructionDef, PagePenalty},
+    isa_6502, opcode_table,
+    opcodes::{BRK_IMPLIED, RTS_IMPLIED},
+};
+
+use crate::{memory::Bus, EmulatorResult, Error};
+
+/// Carry flag of the status register.
+pub const FLAG_CARRY: u8 = 0x01;
+/// Zero flag of the status register.
+pub const FLAG_ZERO: u8 = 0x02;
+/// Interrupt disable flag of the status register.
+pub const FLAG_INTERRUPT_DISABLE: u8 = 0x04;
+/// Decimal mode flag of the status register.
+pub const FLAG_DECIMAL: u8 = 0x08;
+/// Break flag; only exists on the stack after BRK or PHP.
+pub const FLAG_BREAK: u8 = 0x10;
+/// Unused flag; always reads as set.
+pub const FLAG_UNUSED: u8 = 0x20;
+/// Overflow flag of the status register.
+pub const FLAG_OVERFLOW: u8 = 0x40;
+/// Negative flag of the status register.
+pub const FLAG_NEGATIVE: u8 = 0x80;
+
+const STACK_PAGE: u16 = 0x0100;
+const IRQ_VECTOR: u16 = 0xFFFE;
+/// Address where BASIC programs start.
+const BASIC_START: u16 = 0x0801;
+/// Token of the BASIC `SYS` command.
+const TOKEN_SYS: u8 = 0x9E;
+
+/// Registers of the CPU.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Registers {
+    pub a: u8,
+    pub x: u8,
+    pub y: u8,
+    pub stack_pointer: u8,
+    pub program_counter: u16,
+    pub status: u8,
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Registers {
+            a: 0,
+            x: 0,
+            y: 0,
+            stack_pointer: 0xFD,
+            program_counter: 0,
+            status: FLAG_UNUSED | FLAG_INTERRUPT_DISABLE,
+        }
+    }
+}
+
+impl Registers {
+    /// Is the given flag of the status register set.
+    pub fn flag(&self, flag: u8) -> bool {
+        self.status & flag != 0
+    }
+
+    /// Set or clear the given flag of the status register.
+    pub fn set_flag(&mut self, flag: u8, value: bool) {
+        if value {
+            self.status |= flag;
+        } else {
+            self.status &= !flag;
+        }
+    }
+
+    fn set_zero_negative(&mut self, value: u8) {
+        self.set_flag(FLAG_ZERO, value == 0);
+        self.set_flag(FLAG_NEGATIVE, value & 0x80 != 0);
+    }
+}
+
+/// Reason why the CPU stopped executing instructions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StopReason {
+    /// The called subroutine returned (RTS).
+    Return,
+    /// A BRK instruction is about to be executed.
+    Break,
+    /// The program counter reached a breakpoint.
+    Breakpoint(u16),
+    /// The cycle limit has been reached.
+    CycleLimit,
+}
+
+/// 6502 CPU connected to a memory bus.
+pub struct Cpu<B: Bus> {
+    pub registers: Registers,
+    pub bus: B,
+    cycles: u64,
+    cycle_limit: Option<u64>,
+    breakpoints: BTreeSet<u16>,
+    opcodes: Vec<Option<(InstructionDef, AddressingMode)>>,
+}
+
+impl<B: Bus + Default> Default for Cpu<B> {
+    fn default() -> Self {
+        Cpu::new(B::default())
+    }
+}
+
+impl<B: Bus> Cpu<B> {
+    /// Create a new CPU connected to the given bus.
+    pub fn new(bus: B) -> Self {
+        Cpu {
+            registers: Registers::default(),
+            bus,
+            cycles: 0,
+            cycle_limit: None,
+            breakpoints: BTreeSet::new(),
+            opcodes: opcode_table(&isa_6502()),
+        }
+    }
+
+    /// Total number of cycles executed by this CPU.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Stop running after the given number of cycles.
+    ///
+    /// The limit is counted from the start of each [Cpu::run] or [Cpu::call]. It protects
+    /// tests against programs that never return.
+    pub fn set_cycle_limit(&mut self, cycle_limit: Option<u64>) {
+        self.cycle_limit = cycle_limit;
+    }
+
+    /// Stop running when the program counter reaches the given address.
+    pub fn add_breakpoint(&mut self, address: u16) {
+        self.breakpoints.insert(address);
+    }
+
+    /// Remove a breakpoint. Returns false when no breakpoint was set at the address.
+    pub fn remove_breakpoint(&mut self, address: u16) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    /// Load a .PRG byte stream into memory.
+    ///
+    /// The first two bytes contain the load address; returns the load address.
+    pub fn load_program(&mut self, program: &[u8]) -> EmulatorResult<u16> {
+        let [low, high, bytes @ ..] = program else {
+            return Err(Error::MissingLoadAddress);
+        };
+        let load_address = u16::from_le_bytes([*low, *high]);
+        self.bus.write_bytes(load_address, bytes);
+        Ok(load_address)
+    }
+
+    /// Load a .PRG byte stream into memory and call its entry point.
+    ///
+    /// Programs that start with a BASIC `SYS` line are called at the address of the `SYS`
+    /// command, other programs are called at their load address. Programs loaded at `$0800`
+    /// start with a zero byte before the BASIC line.
+    pub fn run_program(&mut self, program: &[u8]) -> EmulatorResult<StopReason> {
+        let load_address = self.load_program(program)?;
+        let entry_point = match (load_address, &program[2..]) {
+            (BASIC_START, bytes) | (0x0800, [0x00, bytes @ ..]) => basic_sys_address(bytes),
+            _ => None,
+        };
+        self.call(entry_point.unwrap_or(load_address))
+    }
+
+    /// Call the subroutine at the given address.
+    ///
+    /// Runs until the subroutine returns, a BRK instruction or a breakpoint is reached
+    /// or the cycle limit is exceeded.
+    pub fn call(&mut self, address: u16) -> EmulatorResult<StopReason> {
+        let return_stack_pointer = self.registers.stack_pointer;
+        self.push_u16(self.registers.program_counter.wrapping_sub(1));
+        self.registers.program_counter = address;
+        self.execute(Some(return_stack_pointer))
+    }
+
+    /// Run from the current program counter until a BRK instruction or breakpoint is
+    /// reached or the cycle limit is exceeded.
+    pub fn run(&mut self) -> EmulatorResult<StopReason> {
+        self.execute(None)
+    }
+
+    fn execute(&mut self, return_stack_pointer: Option<u8>) -> EmulatorResult<StopReason> {
+        let start_cycles = self.cycles;
+        let mut first_instruction = true;
+        loop {
+            let program_counter = self.registers.program_counter;
+            // Allow continuing from the breakpoint that stopped the previous run.
+            if !first_instruction && self.breakpoints.contains(&program_counter) {
+                return Ok(StopReason::Breakpoint(program_counter));
+            }
+            first_instruction = false;
+            if let Some(cycle_limit) = self.cycle_limit {
+                if self.cycles - start_cycles >= cycle_limit {
+                    return Ok(StopReason::CycleLimit);
+                }
+            }
+            let opcode = self.bus.read(program_counter);
+            if opcode == BRK_IMPLIED {
+                return Ok(StopReason::Break);
+            }
+            self.step()?;
+            if opcode == RTS_IMPLIED && Some(self.registers.stack_pointer) == return_stack_pointer {
+                return Ok(StopReason::Return);
+            }
+        }
+    }
+
+    /// Execute a single instruction. Returns the number of cycles it took.
+    pub fn step(&mut self) -> EmulatorResult<u8> {
+        let program_counter = self.registers.program_counter;
+        let opcode = self.bus.read(program_counter);
+        let Some((definition, addressing_mode)) = self.opcodes[opcode as usize] else {
+            return Err(Error::InvalidOpCode {
+                address: program_counter,
+                opcode,
+            });
+        };
+        let unsupported = || Error::UnsupportedInstruction {
+            address: program_counter,
+            opcode,
+        };
+        self.registers.program_counter = program_counter.wrapping_add(addressing_mode.byte_size());
+        let (address, page_crossed) = self.operand_address(addressing_mode, program_counter);
+
+        let mut cycles = definition.cycles(addressing_mode).ok_or_else(unsupported)?;
+        if page_crossed && definition.page_penalty == PagePenalty::IndexedRead {
+            cycles += 1;
+        }
+        match definition.instruction {
+            "adc" => {
+                let value = self.load(address);
+                self.add_with_carry(value);
+            }
+            "and" => {
+                self.registers.a &= self.load(address);
+                self.registers.set_zero_negative(self.registers.a);
+            }
+            "asl" => self.read_modify_write(address, |cpu, value| {
+                cpu.registers.set_flag(FLAG_CARRY, value & 0x80 != 0);
+                value << 1
+            }),
+            "bcc" => {
+                cycles += self
+                    .branch(address, page_crossed, !self.registers.flag(FLAG_CARRY))
+                    .ok_or_else(unsupported)?
+            }
+            "bcs" => {
+                cycles += self
+                    .branch(address, page_crossed, self.registers.flag(FLAG_CARRY))
+                    .ok_or_else(unsupported)?
+            }
+            "beq" => {
+                cycles += self
+                    .branch(address, page_crossed, self.registers.flag(FLAG_ZERO))
+                    .ok_or_else(unsupported)?
+            }
+            "bit" => {
+                let value = self.load(address);
+                self.registers.set_flag(FLAG_ZERO, self.registers.a & value == 0);
+                self.registers.set_flag(FLAG_NEGATIVE, value & 0x80 != 0);
+                self.registers.set_flag(FLAG_OVERFLOW, value & 0x40 != 0);
+            }
+            "bmi" => {
+                cycles += self
+                    .branch(address, page_crossed, self.registers.flag(FLAG_NEGATIVE))
+                    .ok_or_else(unsupported)?
+            }
+            "bne" => {
+                cycles += self
+                    .branch(address, page_crossed, !self.registers.flag(FLAG_ZERO))
+                    .ok_or_else(unsupported)?
+            }
+            "bpl" => {
+                cycles += self
+                    .branch(address, page_crossed, !self.registers.flag(FLAG_NEGATIVE))
+                    .ok_or_else(unsupported)?
+            }
+            "brk" => {
+                // BRK skips the byte after the op-code.
+                self.push_u16(self.registers.program_counter.wrapping_add(1));
+                self.push(self.registers.status | FLAG_BREAK | FLAG_UNUSED);
+                self.registers.set_flag(FLAG_INTERRUPT_DISABLE, true);
+                self.registers.program_counter = self.bus.read_u16(IRQ_VECTOR);
+            }
+            "bvc" => {
+                cycles += self
+                    .branch(address, page_crossed, !self.registers.flag(FLAG_OVERFLOW))
+                    .ok_or_else(unsupported)?
+            }
+            "bvs" => {
+                cycles += self
+                    .branch(address, page_crossed, self.registers.flag(FLAG_OVERFLOW))
+                    .ok_or_else(unsupported)?
+            }
+            "clc" => self.registers.set_flag(FLAG_CARRY, false),
+            "cld" => self.registers.set_flag(FLAG_DECIMAL, false),
+            "cli" => self.registers.set_flag(FLAG_INTERRUPT_DISABLE, false),
+            "clv" => self.registers.set_flag(FLAG_OVERFLOW, false),
+            "cmp" => self.compare(self.registers.a, address),
+            "cpx" => self.compare(self.registers.x, address),
+            "cpy" => self.compare(self.registers.y, address),
+            "dec" => self.read_modify_write(address, |_, value| value.wrapping_sub(1)),
+            "dex" => {
+                self.registers.x = self.registers.x.wrapping_sub(1);
+                self.registers.set_zero_negative(self.registers.x);
+            }
+            "dey" => {
+                self.registers.y = self.registers.y.wrapping_sub(1);
+                self.registers.set_zero_negative(self.registers.y);
+            }
+            "eor" => {
+                self.registers.a ^= self.load(address);
+                self.registers.set_zero_negative(self.registers.a);
+            }
+            "inc" => self.read_modify_write(address, |_, value| value.wrapping_add(1)),
+            "inx" => {
+                self.registers.x = self.registers.x.wrapping_add(1);
+                self.registers.set_zero_negative(self.registers.x);
+            }
+            "iny" => {
+                self.registers.y = self.registers.y.wrapping_add(1);
+                self.registers.set_zero_negative(self.registers.y);
+            }
+            "jmp" => self.registers.program_counter = address.ok_or_else(unsupported)?,
+            "jsr" => {
+                let address = address.ok_or_else(unsupported)?;
+                self.push_u16(self.registers.program_counter.wrapping_sub(1));
+                self.registers.program_counter = address;
+            }
+            "lda" => {
+                self.registers.a = self.load(address);
+                self.registers.set_zero_negative(self.registers.a);
+            }
+            "ldx" => {
+                self.registers.x = self.load(address);
+                self.registers.set_zero_negative(self.registers.x);
+            }
+            "ldy" => {
+                self.registers.y = self.load(address);
+                self.registers.set_zero_negative(self.registers.y);
+            }
+            "lsr" => self.read_modify_write(address, |cpu, value| {
+                cpu.registers.set_flag(FLAG_CARRY, value & 0x01 != 0);
+                value >> 1
+            }),
+            "nop" => {}
+            "ora" => {
+                self.registers.a |= self.load(address);
+                self.registers.set_zero_negative(self.registers.a);
+            }
+            "pha" => self.push(self.registers.a),
+            "php" => self.push(self.registers.status | FLAG_BREAK | FLAG_UNUSED),
+            "pla" => {
+                self.registers.a = self.pull();
+                self.registers.set_zero_negative(self.registers.a);
+            }
+            "plp" => self.registers.status = (self.pull() & !FLAG_BREAK) | FLAG_UNUSED,
+            "rol" => self.read_modify_write(address, |cpu, value| {
+                let carry = cpu.registers.flag(FLAG_CARRY) as u8;
+                cpu.registers.set_flag(FLAG_CARRY, value & 0x80 != 0);
+                (value << 1) | carry
+            }),
+            "ror" => self.read_modify_write(address, |cpu, value| {
+                let carry = cpu.registers.flag(FLAG_CARRY) as u8;
+                cpu.registers.set_flag(FLAG_CARRY, value & 0x01 != 0);
+                (value >> 1) | (carry << 7)
+            }),
+            "rti" => {
+                self.registers.status = (self.pull() & !FLAG_BREAK) | FLAG_UNUSED;
+                self.registers.program_counter = self.pull_u16();
+            }
+            "rts" => self.registers.program_counter = self.pull_u16().wrapping_add(1),
+            "sbc" => {
+                let value = self.load(address);
+                self.subtract_with_carry(value);
+            }
+            "sec" => self.registers.set_flag(FLAG_CARRY, true),
+            "sed" => self.registers.set_flag(FLAG_DECIMAL, true),
+            "sei" => self.registers.set_flag(FLAG_INTERRUPT_DISABLE, true),
+            "sta" => self.store(address, self.registers.a),
+            "stx" => self.store(address, self.registers.x),
+            "sty" => self.store(address, self.registers.y),
+            "tax" => {
+                self.registers.x = self.registers.a;
+                self.registers.set_zero_negative(self.registers.x);
+            }
+            "tay" => {
+                self.registers.y = self.registers.a;
+                self.registers.set_zero_negative(self.registers.y);
+            }
+            "tsx" => {
+                self.registers.x = self.registers.stack_pointer;
+                self.registers.set_zero_negative(self.registers.x);
+            }
+            "txa" => {
+                self.registers.a = self.registers.x;
+                self.registers.set_zero_negative(self.registers.a);
+            }
+            "txs" => self.registers.stack_pointer = self.registers.x,
+            "tya" => {
+                self.registers.a = self.registers.y;
+                self.registers.set_zero_negative(self.registers.a);
+            }
+            _ => {
+                self.registers.program_counter = program_counter;
+                return Err(Error::InvalidOpCode {
+                    address: program_counter,
+                    opcode,
+                });
+            }
+        }
+
+        self.cycles += cycles as u64;
+        Ok(cycles)
+    }
+
+    /// Determine the effective address of the operand.
+    ///
+    /// Immediate operands are addressed by their location in the instruction. Returns `None`
+    /// for implied and accumulator addressing and whether a page boundary was crossed
+    /// during indexing.
+    fn operand_address(&self, addressing_mode: AddressingMode, program_counter: u16) -> (Option<u16>, bool) {
+        let operand = program_counter.wrapping_add(1);
+        let indexed = |base: u16, index: u8| {
+            let address = base.wrapping_add(index as u16);
+            (Some(address), base & 0xFF00 != address & 0xFF00)
+        };
+        match addressing_mode {
+            AddressingMode::Implied | AddressingMode::Accumulator => (None, false),
+            AddressingMode::Immediate => (Some(operand), false),
+            AddressingMode::Zeropage => (Some(self.bus.read(operand) as u16), false),
+            AddressingMode::ZeropageX => (
+                Some(self.bus.read(operand).wrapping_add(self.registers.x) as u16),
+                false,
+            ),
+            AddressingMode::ZeropageY => (
+                Some(self.bus.read(operand).wrapping_add(self.registers.y) as u16),
+                false,
+            ),
+            AddressingMode::Absolute => (Some(self.bus.read_u16(operand)), false),
+            AddressingMode::AbsoluteX => indexed(self.bus.read_u16(operand), self.registers.x),
+            AddressingMode::AbsoluteY => indexed(self.bus.read_u16(operand), self.registers.y),
+            AddressingMode::Relative => {
+                let next_instruction = program_counter.wrapping_add(2);
+                let offset = self.bus.read(operand) as i8;
+                let target = next_instruction.wrapping_add_signed(offset as i16);
+                (Some(target), next_instruction & 0xFF00 != target & 0xFF00)
+            }
+            AddressingMode::Indirect => {
+                // The NMOS 6502 doesn't carry into the high byte of the pointer.
+                let pointer = self.bus.read_u16(operand);
+                let high_pointer = (pointer & 0xFF00) | (pointer.wrapping_add(1) & 0x00FF);
+                let address = u16::from_le_bytes([self.bus.read(pointer), self.bus.read(high_pointer)]);
+                (Some(address), false)
+            }
+            AddressingMode::IndexedIndirect => {
+                let pointer = self.bus.read(operand).wrapping_add(self.registers.x);
+                (Some(self.read_zeropage_u16(pointer)), false)
+            }
+            AddressingMode::IndirectIndexed => {
+                let pointer = self.bus.read(operand);
+                indexed(self.read_zeropage_u16(pointer), self.registers.y)
+            }
+        }
+    }
+
+    fn read_zeropage_u16(&self, pointer: u8) -> u16 {
+        u16::from_le_bytes([
+            self.bus.read(pointer as u16),
+            self.bus.read(pointer.wrapping_add(1) as u16),
+        ])
+    }
+
+    /// Read the operand; `None` refers to the accumulator.
+    fn load(&self, address: Option<u16>) -> u8 {
+        match address {
+            Some(address) => self.bus.read(address),
+            None => self.registers.a,
+        }
+    }
+
+    /// Write the operand; `None` refers to the accumulator.
+    fn store(&mut self, address: Option<u16>, value: u8) {
+        match address {
+            Some(address) => self.bus.write(address, value),
+            None => self.registers.a = value,
+        }
+    }
+
+    fn read_modify_write(&mut self, address: Option<u16>, modify: impl FnOnce(&mut Self, u8) -> u8) {
+        let value = self.load(address);
+        let result = modify(self, value);
+        self.store(address, result);
+        self.registers.set_zero_negative(result);
+    }
+
+    /// Take the branch when the condition holds. Returns the additional cycles or `None`
+    /// when there is no branch target.
+    fn branch(&mut self, address: Option<u16>, page_crossed: bool, condition: bool) -> Option<u8> {
+        if !condition {
+            return Some(0);
+        }
+        self.registers.program_counter = address?;
+        if page_crossed {
+            Some(2)
+        } else {
+            Some(1)
+        }
+    }
+
+    fn compare(&mut self, register: u8, address: Option<u16>) {
+        let value = self.load(address);
+        self.registers.set_flag(FLAG_CARRY, register >= value);
+        self.registers.set_zero_negative(register.wrapping_sub(value));
+    }
+
+    fn add_with_carry(&mut self, value: u8) {
+        let a = self.registers.a as u16;
+        let value = value as u16;
+        let carry = self.registers.flag(FLAG_CARRY) as u16;
+        let binary = a + value + carry;
+
+        if self.registers.flag(FLAG_DECIMAL) {
+            // NMOS behavior: zero flag is based on the binary result, negative and overflow
+            // on the intermediate result after adjusting the low nibble.
+            let mut low = (a & 0x0F) + (value & 0x0F) + carry;
+            if low > 0x09 {
+                low += 0x06;
+            }
+            let mut high = (a >> 4) + (value >> 4) + (low > 0x0F) as u16;
+            self.registers.set_flag(FLAG_ZERO, binary & 0xFF == 0);
+            self.registers.set_flag(FLAG_NEGATIVE, high & 0x08 != 0);
+            self.registers
+                .set_flag(FLAG_OVERFLOW, (a ^ value) & 0x80 == 0 && (a ^ (high << 4)) & 0x80 != 0);
+            if high > 0x09 {
+                high += 0x06;
+            }
+            self.registers.set_flag(FLAG_CARRY, high > 0x0F);
+            self.registers.a = ((high << 4) | (low & 0x0F)) as u8;
+        } else {
+            self.registers
+                .set_flag(FLAG_OVERFLOW, !(a ^ value) & (a ^ binary) & 0x80 != 0);
+            self.registers.set_flag(FLAG_CARRY, binary > 0xFF);
+            self.registers.a = binary as u8;
+            self.registers.set_zero_negative(self.registers.a);
+        }
+    }
+
+    fn subtract_with_carry(&mut self, value: u8) {
+        let a = self.registers.a as i16;
+        let value = value as i16;
+        let borrow = !self.registers.flag(FLAG_CARRY) as i16;
+        let binary = a - value - borrow;
+
+        // NMOS behavior: all flags are based on the binary result.
+        self.registers.set_flag(FLAG_CARRY, binary >= 0);
+        self.registers
+            .set_flag(FLAG_OVERFLOW, (a ^ value) & (a ^ binary) & 0x80 != 0);
+        self.registers.set_zero_negative(binary as u8);
+
+        if self.registers.flag(FLAG_DECIMAL) {
+            let mut low = (a & 0x0F) - (value & 0x0F) - borrow;
+            let mut high = (a >> 4) - (value >> 4);
+            if low < 0 {
+                low -= 0x06;
+                high -= 1;
+            }
+            if high < 0 {
+                high -= 0x06;
+            }
+            self.registers.a = ((high << 4) | (low & 0x0F)) as u8;
+        } else {
+            self.registers.a = binary as u8;
+        }
+    }
+
+    fn push(&mut self, value: u8) {
+        self.bus.write(STACK_PAGE | self.registers.stack_pointer as u16, value);
+        self.registers.stack_pointer = self.registers.stack_pointer.wrapping_sub(1);
+    }
+
+    fn pull(&mut self) -> u8 {
+        self.registers.stack_pointer = self.registers.stack_pointer.wrapping_add(1);
+        self.bus.read(STACK_PAGE | self.registers.stack_pointer as u16)
+    }
+
+    fn push_u16(&mut self, value: u16) {
+        let [low, high] = value.to_le_bytes();
+        self.push(high);
+        self.push(low);
+    }
+
+    fn pull_u16(&mut self) -> u16 {
+        let low = self.pull();
+        let high = self.pull();
+        u16::from_le_bytes([low, high])
+    }
+}
+
+/// Address of the `SYS` command when the bytes start with a BASIC line.
+fn basic_sys_address(bytes: &[u8]) -> Option<u16> {
+    // Skip the link to the next line and the line number.
+    let mut statements = bytes.get(4..)?.iter().skip_while(|byte| **byte == b' ');
+    if *statements.next()? != TOKEN_SYS {
+        return None;
+    }
+    let digits = statements
+        .skip_while(|byte| **byte == b' ')
+        .take_while(|byte| byte.is_ascii_digit())
+        .collect::<Vec<_>>();
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u16, |address, digit| {
+        address.checked_mul(10)?.checked_add((**digit - b'0') as u16)
+    })
+}
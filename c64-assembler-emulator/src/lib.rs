@@ -0,0 +1,37 @@
+//! 6502 CPU emulator.
+//!
+//! The emulator is used to execute programs that are generated by the c64-assembler crate
+//! inside tests. Op-codes are decoded using the instruction definitions of the
+//! c64-assembler-6502 crate, so the assembler and emulator always agree on the
+//! instruction set.
+//!
+//! ```
+//! use c64_assembler_emulator::cpu::{Cpu, StopReason};
+//! use c64_assembler_emulator::memory::{Bus, Ram};
+//!
+//! // lda #$00; sta $d020; rts
+//! let program = [0x00, 0x08, 0xA9, 0x00, 0x8D, 0x20, 0xD0, 0x60];
+//!
+//! let mut cpu = Cpu::<Ram>::default();
+//! cpu.bus.write(0xD020, 0xFF);
+//! let stop_reason = cpu.run_program(&program).unwrap();
+//! assert_eq!(StopReason::Return, stop_reason);
+//! assert_eq!(0x00, cpu.bus.read(0xD020));
+//! assert_eq!(12, cpu.cycles());
+//! ```
+
+pub mod cpu;
+pub mod memory;
+
+pub type EmulatorResult<T> = Result<T, Error>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The byte at the program counter isn't an op-code of the instruction set.
+    InvalidOpCode { address: u16, opcode: u8 },
+    /// The instruction definition of the op-code misses the cycles or operand that are
+    /// needed to execute it.
+    UnsupportedInstruction { address: u16, opcode: u8 },
+    /// The .PRG byte stream is shorter than its two byte load address.
+    MissingLoadAddress,
+}
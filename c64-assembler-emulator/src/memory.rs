@@ -0,0 +1,47 @@
+//! Memory that is accessible by the CPU.
+
+/// Bus connecting the CPU to memory and devices.
+///
+/// Implement this trait to emulate memory mapped devices or banking.
+pub trait Bus {
+    /// Read a byte from the given address.
+    fn read(&self, address: u16) -> u8;
+
+    /// Write a byte to the given address.
+    fn write(&mut self, address: u16, value: u8);
+
+    /// Read a little endian word from the given address.
+    fn read_u16(&self, address: u16) -> u16 {
+        u16::from_le_bytes([self.read(address), self.read(address.wrapping_add(1))])
+    }
+
+    /// Write multiple bytes starting at the given address.
+    fn write_bytes(&mut self, address: u16, bytes: &[u8]) {
+        for (offset, byte) in bytes.iter().enumerate() {
+            self.write(address.wrapping_add(offset as u16), *byte);
+        }
+    }
+}
+
+/// 64KB of RAM without any devices.
+pub struct Ram {
+    bytes: Vec<u8>,
+}
+
+impl Default for Ram {
+    fn default() -> Self {
+        Ram {
+            bytes: vec![0; 0x10000],
+        }
+    }
+}
+
+impl Bus for Ram {
+    fn read(&self, address: u16) -> u8 {
+        self.bytes[address as usize]
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.bytes[address as usize] = value;
+    }
+}
@@ -0,0 +1,148 @@
+use c64_assembler_emulator::{
+    cpu::{Cpu, StopReason, FLAG_CARRY, FLAG_DECIMAL, FLAG_OVERFLOW, FLAG_ZERO},
+    memory::{Bus, Ram},
+    Error,
+};
+
+fn cpu_with_program(program: &[u8]) -> Cpu<Ram> {
+    let mut cpu = Cpu::<Ram>::default();
+    cpu.load_program(program).unwrap();
+    cpu.set_cycle_limit(Some(10_000));
+    cpu
+}
+
+#[test]
+fn run_until_return() {
+    // ldx #$05; loop: dex; bne loop; rts
+    let mut cpu = cpu_with_program(&[0x00, 0x10, 0xA2, 0x05, 0xCA, 0xD0, 0xFD, 0x60]);
+    assert_eq!(Ok(StopReason::Return), cpu.call(0x1000));
+    assert_eq!(0x00, cpu.registers.x);
+    assert!(cpu.registers.flag(FLAG_ZERO));
+    assert_eq!(0xFD, cpu.registers.stack_pointer);
+    // ldx + 5 * dex + 4 taken branches + 1 not taken branch + rts
+    assert_eq!(2 + 5 * 2 + 4 * 3 + 2 + 6, cpu.cycles());
+}
+
+#[test]
+fn nested_subroutines() {
+    // jsr sub; rts; sub: lda #$42; pha; pla; sta $2000; rts
+    let mut cpu = cpu_with_program(&[
+        0x00, 0x10, 0x20, 0x04, 0x10, 0x60, 0xA9, 0x42, 0x48, 0x68, 0x8D, 0x00, 0x20, 0x60,
+    ]);
+    assert_eq!(Ok(StopReason::Return), cpu.call(0x1000));
+    assert_eq!(0x42, cpu.bus.read(0x2000));
+}
+
+#[test]
+fn break_and_breakpoints() {
+    // inx; inx; inx; brk
+    let mut cpu = cpu_with_program(&[0x00, 0x10, 0xE8, 0xE8, 0xE8, 0x00]);
+    cpu.add_breakpoint(0x1001);
+    cpu.registers.program_counter = 0x1000;
+    assert_eq!(Ok(StopReason::Breakpoint(0x1001)), cpu.run());
+    assert_eq!(1, cpu.registers.x);
+    assert_eq!(Ok(StopReason::Break), cpu.run());
+    assert_eq!(3, cpu.registers.x);
+    assert_eq!(0x1003, cpu.registers.program_counter);
+    assert!(cpu.remove_breakpoint(0x1001));
+}
+
+#[test]
+fn cycle_limit() {
+    // loop: jmp loop
+    let mut cpu = cpu_with_program(&[0x00, 0x10, 0x4C, 0x00, 0x10]);
+    cpu.set_cycle_limit(Some(30));
+    assert_eq!(Ok(StopReason::CycleLimit), cpu.call(0x1000));
+    assert_eq!(30, cpu.cycles());
+}
+
+#[test]
+fn page_crossing_cycles() {
+    // lda $10ff,x
+    let mut cpu = cpu_with_program(&[0x00, 0x10, 0xBD, 0xFF, 0x10]);
+    cpu.registers.program_counter = 0x1000;
+    assert_eq!(Ok(4), cpu.step());
+    cpu.registers.program_counter = 0x1000;
+    cpu.registers.x = 1;
+    assert_eq!(Ok(5), cpu.step());
+}
+
+#[test]
+fn decimal_mode() {
+    // sed; clc; lda #$19; adc #$28; sta $2000; sec; lda #$10; sbc #$01; sta $2001; rts
+    let mut cpu = cpu_with_program(&[
+        0x00, 0x10, 0xF8, 0x18, 0xA9, 0x19, 0x69, 0x28, 0x8D, 0x00, 0x20, 0x38, 0xA9, 0x10, 0xE9, 0x01, 0x8D, 0x01,
+        0x20, 0x60,
+    ]);
+    assert_eq!(Ok(StopReason::Return), cpu.call(0x1000));
+    assert_eq!(0x47, cpu.bus.read(0x2000));
+    assert_eq!(0x09, cpu.bus.read(0x2001));
+    assert!(cpu.registers.flag(FLAG_DECIMAL));
+    assert!(cpu.registers.flag(FLAG_CARRY));
+}
+
+#[test]
+fn binary_overflow() {
+    // clc; lda #$7f; adc #$01; rts
+    let mut cpu = cpu_with_program(&[0x00, 0x10, 0x18, 0xA9, 0x7F, 0x69, 0x01, 0x60]);
+    assert_eq!(Ok(StopReason::Return), cpu.call(0x1000));
+    assert_eq!(0x80, cpu.registers.a);
+    assert!(cpu.registers.flag(FLAG_OVERFLOW));
+    assert!(!cpu.registers.flag(FLAG_CARRY));
+}
+
+#[test]
+fn indirect_jump_page_wrap() {
+    // jmp ($10ff)
+    let mut cpu = cpu_with_program(&[0x00, 0x10, 0x6C, 0xFF, 0x10]);
+    cpu.bus.write_bytes(0x10FF, &[0x34, 0x56]);
+    cpu.registers.program_counter = 0x1000;
+    // The high byte of the pointer is read from the start of the same page ($1000).
+    assert_eq!(Ok(5), cpu.step());
+    assert_eq!(0x6C34, cpu.registers.program_counter);
+}
+
+#[test]
+fn invalid_opcode() {
+    let mut cpu = cpu_with_program(&[0x00, 0x10, 0x02]);
+    assert_eq!(
+        Err(Error::InvalidOpCode {
+            address: 0x1000,
+            opcode: 0x02
+        }),
+        cpu.call(0x1000)
+    );
+}
+
+#[test]
+fn missing_load_address() {
+    let mut cpu = Cpu::<Ram>::default();
+    assert_eq!(Err(Error::MissingLoadAddress), cpu.load_program(&[0x00]));
+    assert_eq!(Err(Error::MissingLoadAddress), cpu.run_program(&[]));
+    assert_eq!(Ok(0x1000), cpu.load_program(&[0x00, 0x10]));
+}
+
+/// Programs with a BASIC header are started at the address of the SYS command.
+#[test]
+fn run_program_basic_header() {
+    // 10 SYS 2061
+    // lda #$42; sta $2000; rts
+    let program = [
+        0x01, 0x08, 0x0B, 0x08, 0x0A, 0x00, 0x9E, 0x32, 0x30, 0x36, 0x31, 0x00, 0x00, 0x00, 0xA9, 0x42, 0x8D, 0x00,
+        0x20, 0x60,
+    ];
+    let mut cpu = Cpu::<Ram>::default();
+    cpu.set_cycle_limit(Some(10_000));
+    assert_eq!(Ok(StopReason::Return), cpu.run_program(&program));
+    assert_eq!(0x42, cpu.bus.read(0x2000));
+    assert_eq!(2 + 4 + 6, cpu.cycles());
+
+    // Loaded at $0800 the BASIC line is preceded by a zero byte.
+    let mut cpu = Cpu::<Ram>::default();
+    cpu.set_cycle_limit(Some(10_000));
+    assert_eq!(
+        Ok(StopReason::Return),
+        cpu.run_program(&[&[0x00, 0x08, 0x00], &program[2..]].concat())
+    );
+    assert_eq!(0x42, cpu.bus.read(0x2000));
+}
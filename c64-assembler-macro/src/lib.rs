@@ -1,4 +1,4 @@
-use proc_macro::{TokenStream, TokenTree};
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
 
 #[proc_macro]
 pub fn application(input: TokenStream) -> TokenStream {
@@ -35,6 +35,18 @@ pub fn application(input: TokenStream) -> TokenStream {
             if name == "include_sid_defines" {
                 lines.push("    .include_sid_defines()".to_string());
             }
+            if name == "fix_out_of_range_branches" {
+                lines.push("    .fix_out_of_range_branches()".to_string());
+            }
+            if name == "strip_unused_functions" {
+                lines.push("    .strip_unused_functions()".to_string());
+            }
+            if name == "variant_tag" {
+                let _eq = iter.next().unwrap();
+                if let Some(TokenTree::Literal(tag)) = iter.next() {
+                    lines.push(format!("    .variant_tag({tag})"));
+                }
+            }
             if name == "module" {
                 let _eq = iter.next().unwrap();
                 if let Some(TokenTree::Group(sub_tree)) = iter.next() {
@@ -144,27 +156,22 @@ fn build_address_mode(
     allow_indirect: bool,
 ) -> usize {
     match tokens.first().unwrap() {
-        TokenTree::Punct(punct) => {
-            if punct.to_string() == "#" {
-                assert!(allow_immediate);
-                return build_address_mode_imm(line, &tokens[1..]) + 1;
-            }
+        TokenTree::Punct(punct) if punct.as_char() == '#' => {
+            assert!(allow_immediate);
+            build_address_mode_imm(line, &tokens[1..]) + 1
         }
-        TokenTree::Ident(ident) => {
-            if ident.to_string() == *"a" {
-                assert!(allow_accumulator);
-                return build_address_mode_accumulator(line, tokens);
-            }
-            assert!(allow_absolute);
-            return build_address_mode_absolute(line, tokens);
+        TokenTree::Ident(ident) if ident.to_string() == *"a" && allow_accumulator => {
+            build_address_mode_accumulator(line, tokens)
         }
-        TokenTree::Group(_group) => {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
             assert!(allow_indirect);
-            return build_address_mode_indirect(line, &tokens[0..]);
+            build_address_mode_indirect(line, &tokens[0..])
+        }
+        _ => {
+            assert!(allow_absolute);
+            build_address_mode_absolute(line, tokens)
         }
-        _ => todo!("HUH!"),
     }
-    0
 }
 
 fn build_address_mode_accumulator(line: &mut Vec<String>, _tokens: &[TokenTree]) -> usize {
@@ -173,42 +180,17 @@ fn build_address_mode_accumulator(line: &mut Vec<String>, _tokens: &[TokenTree])
 }
 
 fn build_address_mode_absolute(line: &mut Vec<String>, tokens: &[TokenTree]) -> usize {
-    let mut num_tokens = 1;
-    let address = if let Some(TokenTree::Ident(ident)) = tokens.first() {
-        ident.to_string()
-    } else {
-        "".to_string()
-    };
+    let (expression, mut num_tokens) = parse_expression(tokens);
     line.push("_addr".to_string());
-    let mut offset = 0;
-    let mut index = "".to_string();
-    if let Some(TokenTree::Punct(p)) = tokens.get(1) {
-        if p.as_char() == '+' {
-            if let Some(TokenTree::Literal(l)) = tokens.get(2) {
-                offset = l.to_string().parse::<u16>().unwrap();
-            }
-            num_tokens = 3;
-        }
+    if let (Some(TokenTree::Punct(p)), Some(TokenTree::Ident(index))) =
+        (tokens.get(num_tokens), tokens.get(num_tokens + 1))
+    {
         if p.as_char() == ',' {
-            if let Some(TokenTree::Ident(identifier)) = tokens.get(2) {
-                index = identifier.to_string();
-            }
-            num_tokens = 3;
+            line.push(format!("_{}", index));
+            num_tokens += 2;
         }
     }
-    if (offset) > 0 {
-        line.push("_offs".to_string());
-    }
-    if !index.is_empty() {
-        line.push(format!("_{}", index));
-    }
-
-    line.push("(".to_string());
-    line.push(format!("\"{}\"", address));
-    if offset > 0 {
-        line.push(format!(", {}", offset));
-    }
-    line.push(")".to_string());
+    line.push(format!("({})", expression.address_code()));
     num_tokens
 }
 
@@ -217,15 +199,12 @@ fn build_address_mode_indirect(line: &mut Vec<String>, tokens: &[TokenTree]) ->
     let mut is_indexed_indirect = false;
     let mut address = String::new();
     if let TokenTree::Group(group) = &tokens[0] {
-        for token in group.stream() {
-            if let TokenTree::Ident(identifier) = &token {
-                address = identifier.to_string();
-            }
-            if let TokenTree::Punct(punct) = &token {
-                if punct.as_char() == ',' {
-                    is_indexed_indirect = true;
-                    break;
-                }
+        let group_tokens = group.stream().into_iter().collect::<Vec<TokenTree>>();
+        let (expression, num_tokens) = parse_expression(&group_tokens);
+        address = expression.address_code();
+        if let Some(TokenTree::Punct(punct)) = group_tokens.get(num_tokens) {
+            if punct.as_char() == ',' {
+                is_indexed_indirect = true;
             }
         }
     }
@@ -236,61 +215,177 @@ fn build_address_mode_indirect(line: &mut Vec<String>, tokens: &[TokenTree]) ->
     }
 
     if is_indexed_indirect {
-        line.push(format!("_ind_x(\"{address}\")"));
+        line.push(format!("_ind_x({address})"));
         1
     } else if is_indirect_indexed {
-        line.push(format!("_ind_y(\"{address}\")"));
+        line.push(format!("_ind_y({address})"));
         3
     } else {
-        line.push(format!("_ind(\"{address}\")"));
+        line.push(format!("_ind({address})"));
         1
     }
 }
 
 fn build_address_mode_imm(line: &mut Vec<String>, tokens: &[TokenTree]) -> usize {
-    let mut num_tokens = 0;
+    let (expression, num_tokens) = parse_expression(tokens);
     line.push("_imm".to_string());
-    let mut is_hex = false;
-    let mut is_low = false;
-    let mut is_high = false;
-    for token in tokens {
-        num_tokens += 1;
-        match token {
-            TokenTree::Punct(punct) => {
-                if punct.to_string() == "$" {
-                    is_hex = true;
-                }
-                if punct.to_string() == "<" {
-                    is_low = true;
-                }
-                if punct.to_string() == ">" {
-                    is_high = true;
-                }
-            }
-            TokenTree::Literal(value) => {
-                if is_hex {
-                    line.push(format!("(0x{})", value));
-                } else {
-                    line.push(format!("({})", value));
-                }
-                break;
+    match expression {
+        Expression::Number(value) => line.push(format!("({value})")),
+        Expression::Low(expression) => line.push(format!("_low({})", expression.address_code())),
+        Expression::High(expression) => line.push(format!("_high({})", expression.address_code())),
+        expression => line.push(format!("_expr({})", expression.code())),
+    }
+    num_tokens
+}
+
+/// Expression in the instructions of a macro.
+///
+/// Uses the operators and precedence of dasm; `[` and `]` are used for grouping as `(` and `)`
+/// are used by the indirect address modes.
+enum Expression {
+    Number(String),
+    Name(String),
+    Low(Box<Expression>),
+    High(Box<Expression>),
+    Negate(Box<Expression>),
+    Binary(&'static str, Box<Expression>, Box<Expression>),
+}
+
+const EXPRESSION: &str = "c64_assembler::memory::expression::Expression";
+
+impl Expression {
+    /// Rust code that builds the expression.
+    fn code(&self) -> String {
+        match self {
+            Expression::Number(value) => format!("{EXPRESSION}::number({value})"),
+            Expression::Name(name) => format!("{EXPRESSION}::name(\"{name}\")"),
+            Expression::Low(expression) => format!("{}.low()", expression.code()),
+            Expression::High(expression) => format!("{}.high()", expression.code()),
+            Expression::Negate(expression) => format!("(-{})", expression.code()),
+            Expression::Binary(operator, left, right) => format!("({} {operator} {})", left.code(), right.code()),
+        }
+    }
+
+    /// Rust code for an address; a single name is passed as a string.
+    fn address_code(&self) -> String {
+        match self {
+            Expression::Name(name) => format!("\"{name}\""),
+            expression => expression.code(),
+        }
+    }
+}
+
+/// Parse an expression at the start of the tokens, returns the expression and the number of tokens used.
+fn parse_expression(tokens: &[TokenTree]) -> (Expression, usize) {
+    let mut position = 0;
+    let expression = parse_binary_expression(tokens, &mut position, 0);
+    (expression, position)
+}
+
+fn parse_binary_expression(tokens: &[TokenTree], position: &mut usize, min_precedence: u8) -> Expression {
+    let mut left = parse_unary_expression(tokens, position);
+    // An expression ends at the end of the line, the next line can start with an anonymous label.
+    while let Some((operator, precedence, length)) =
+        binary_operator(&tokens[*position..]).filter(|_| same_line(&tokens[*position - 1], &tokens[*position]))
+    {
+        if precedence < min_precedence {
+            break;
+        }
+        *position += length;
+        let right = parse_binary_expression(tokens, position, precedence + 1);
+        left = Expression::Binary(operator, Box::new(left), Box::new(right));
+    }
+    left
+}
+
+fn parse_unary_expression(tokens: &[TokenTree], position: &mut usize) -> Expression {
+    let token = tokens.get(*position).expect("expected an expression");
+    if let Some((name, length)) = scoped_label(&tokens[*position..]) {
+        *position += length;
+        return Expression::Name(name);
+    }
+    *position += 1;
+    match token {
+        TokenTree::Punct(punct) => match punct.as_char() {
+            '<' => Expression::Low(Box::new(parse_unary_expression(tokens, position))),
+            '>' => Expression::High(Box::new(parse_unary_expression(tokens, position))),
+            '-' => Expression::Negate(Box::new(parse_unary_expression(tokens, position))),
+            '$' | '%' => {
+                let digits = tokens.get(*position).expect("expected a number").to_string();
+                *position += 1;
+                let prefix = if punct.as_char() == '$' { "0x" } else { "0b" };
+                Expression::Number(format!("{prefix}{digits}"))
             }
-            TokenTree::Ident(value) => {
-                if is_low {
-                    line.push(format!("_low(\"{}\")", value));
-                } else if is_high {
-                    line.push(format!("_high(\"{}\")", value));
-                } else if is_hex {
-                    line.push(format!("(0x{})", value));
-                } else {
-                    line.push(format!("(\"{}\")", value));
-                }
-                break;
+            _ => panic!("unexpected '{punct}' in expression"),
+        },
+        TokenTree::Literal(literal) => Expression::Number(literal.to_string()),
+        TokenTree::Ident(ident) => Expression::Name(ident.to_string()),
+        TokenTree::Group(group) => {
+            assert!(
+                group.delimiter() == Delimiter::Bracket,
+                "use '[' and ']' to group expressions"
+            );
+            let group_tokens = group.stream().into_iter().collect::<Vec<TokenTree>>();
+            parse_expression(&group_tokens).0
+        }
+    }
+}
+
+fn same_line(token: &TokenTree, other: &TokenTree) -> bool {
+    token.span().line() == other.span().line()
+}
+
+/// Local label (`.loop`) or anonymous label reference (`-`, `--`, `+`, `++`) at the start of the
+/// tokens: the name and the number of tokens.
+///
+/// A sign is only an anonymous label reference when nothing follows it on the same line,
+/// otherwise it is an operator.
+fn scoped_label(tokens: &[TokenTree]) -> Option<(String, usize)> {
+    let TokenTree::Punct(punct) = tokens.first()? else {
+        return None;
+    };
+    match punct.as_char() {
+        '.' => match tokens.get(1) {
+            Some(TokenTree::Ident(ident)) => Some((format!(".{ident}"), 2)),
+            _ => None,
+        },
+        sign @ ('-' | '+') => {
+            let length = tokens
+                .iter()
+                .take_while(|token| {
+                    matches!(token, TokenTree::Punct(other) if other.as_char() == sign) && same_line(token, &tokens[0])
+                })
+                .count();
+            match tokens.get(length) {
+                Some(next) if same_line(next, &tokens[0]) => None,
+                _ => Some((sign.to_string().repeat(length), length)),
             }
-            _ => todo!(),
         }
+        _ => None,
+    }
+}
+
+/// Binary operator at the start of the tokens: the Rust operator, its precedence and number of tokens.
+fn binary_operator(tokens: &[TokenTree]) -> Option<(&'static str, u8, usize)> {
+    let Some(TokenTree::Punct(punct)) = tokens.first() else {
+        return None;
+    };
+    let next = match tokens.get(1) {
+        Some(TokenTree::Punct(next)) if punct.spacing() == Spacing::Joint => Some(next.as_char()),
+        _ => None,
+    };
+    match (punct.as_char(), next) {
+        ('<', Some('<')) => Some(("<<", 3, 2)),
+        ('>', Some('>')) => Some((">>", 3, 2)),
+        ('*', _) => Some(("*", 5, 1)),
+        ('/', _) => Some(("/", 5, 1)),
+        ('+', _) => Some(("+", 4, 1)),
+        ('-', _) => Some(("-", 4, 1)),
+        ('&', _) => Some(("&", 2, 1)),
+        ('^', _) => Some(("^", 1, 1)),
+        ('|', _) => Some(("|", 0, 1)),
+        _ => None,
     }
-    num_tokens
 }
 
 fn build_instructions(input: TokenStream) -> String {
@@ -303,6 +398,23 @@ fn build_instructions(input: TokenStream) -> String {
             continue;
         }
         let token = &tokens[i];
+        // Local label (`.loop:`) or anonymous label (`-:` or `+:`).
+        if let TokenTree::Punct(punct) = token {
+            let label = match punct.as_char() {
+                '.' => scoped_label(&tokens[i..]),
+                '-' | '+' => Some((punct.as_char().to_string(), 1)),
+                _ => None,
+            };
+            if let Some((label, length)) = label {
+                if matches!(tokens.get(i + length), Some(TokenTree::Punct(colon)) if colon.as_char() == ':') {
+                    lines.push(format!("    .label(\"{label}\")"));
+                    let span = punct.span();
+                    lines.push(format!("    .source_location({}, {})", span.line(), span.column()));
+                    sub_start = i + length + 1;
+                }
+            }
+            continue;
+        }
         if let TokenTree::Ident(identifier) = token {
             let name = identifier.to_string();
             match name.as_str() {
@@ -334,6 +446,28 @@ fn build_instructions(input: TokenStream) -> String {
                     sub_start = i + 1 + add_tokens_parsed;
                 }
 
+                "slo" | "rla" | "sre" | "rra" | "sax" | "lax" | "dcp" | "isc" => {
+                    let mut line = Vec::default();
+                    line.push(format!("    .{name}"));
+                    let add_tokens_parsed = build_address_mode(&mut line, &tokens[i + 1..], false, false, true, true);
+                    lines.push(line.join(""));
+                    sub_start = i + 1 + add_tokens_parsed;
+                }
+                "anc" | "alr" | "arr" | "sbx" => {
+                    let mut line = Vec::default();
+                    line.push(format!("    .{name}"));
+                    let add_tokens_parsed = build_address_mode(&mut line, &tokens[i + 1..], true, false, false, false);
+                    lines.push(line.join(""));
+                    sub_start = i + 1 + add_tokens_parsed;
+                }
+                "las" => {
+                    let mut line = Vec::default();
+                    line.push(format!("    .{name}"));
+                    let add_tokens_parsed = build_address_mode(&mut line, &tokens[i + 1..], false, false, true, false);
+                    lines.push(line.join(""));
+                    sub_start = i + 1 + add_tokens_parsed;
+                }
+
                 "sta" | "stx" | "sty" | "jmp" => {
                     let mut line = Vec::default();
                     line.push(format!("    .{name}"));
@@ -354,6 +488,11 @@ fn build_instructions(input: TokenStream) -> String {
                     sub_start = i + 2;
                 }
             }
+            // The basic header consists of multiple instructions and isn't located.
+            if name != "include_basic_header" {
+                let span = identifier.span();
+                lines.push(format!("    .source_location({}, {})", span.line(), span.column()));
+            }
         }
     }
 
@@ -1,11 +1,9 @@
+use c64_assembler::disk::d64::D64;
+use c64_assembler::disk::{DiskFile, DiskImage};
 use c64_assembler::generator::Generator;
 use c64_assembler::generator::ProgramGenerator;
 use c64_assembler::validator::AssemblerResult;
 use c64_assembler_macro::application;
-use cbm::disk::directory::FileType;
-use cbm::disk::file::{FileOps, Scheme};
-use cbm::disk::{Disk, Id, D64};
-use cbm::Petscii;
 
 fn main() -> AssemblerResult<()> {
     let application = application!(
@@ -28,20 +26,12 @@ fn main() -> AssemblerResult<()> {
     let program = ProgramGenerator::default().generate(application)?;
 
     // Create a disk in memory.
-    let geometry = D64::geometry(false);
-    let mut disk = D64::open_memory(geometry).unwrap();
-    disk.write_format(&Petscii::from_str("DISK 1"), &Id::from_bytes(b"FYR"))
-        .unwrap();
+    let mut disk = D64::new("disk 1", "fy")?;
 
     // Write program to disk.
-    let d64_file = disk
-        .create_file(&Petscii::from_str("set black border"), FileType::PRG, Scheme::Linear)
-        .unwrap();
-    d64_file.writer().unwrap().write(&program).unwrap();
+    disk.write_file(&DiskFile::prg("set black border", &program))?;
 
     // List file entries on disk.
-    disk.iter().flatten().for_each(|entry| {
-        println!("{:<4} {:<16}", entry.file_size, entry.filename);
-    });
+    print!("{}", disk.listing()?);
     Ok(())
 }
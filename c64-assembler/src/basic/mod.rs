@@ -0,0 +1,331 @@
+//! BASIC V2 programs that are stored in the instruction stream.
+//!
+//! A C64 program is usually started from BASIC using a `SYS` line, see
+//! [crate::builder::InstructionBuilder::basic_stub]. Other BASIC programs can be tokenized
+//! using [BasicProgram] and stored using [crate::builder::InstructionBuilder::basic_program].
+//! Existing programs can be read from a `.PRG` file and listed as text.
+//!
+//! ```
+//! use c64_assembler::basic::BasicProgram;
+//!
+//! let program = BasicProgram::parse("10 print \"hello\"\n20 goto 10").unwrap();
+//! assert_eq!(
+//!     vec![
+//!         0x0F, 0x08, 0x0A, 0x00, 0x99, 0x20, 0x22, 0x48, 0x45, 0x4C, 0x4C, 0x4F, 0x22, 0x00,
+//!         0x18, 0x08, 0x14, 0x00, 0x89, 0x20, 0x31, 0x30, 0x00,
+//!         0x00, 0x00,
+//!     ],
+//!     program.bytes(0x0801)
+//! );
+//!
+//! let program = BasicProgram::from_prg(&program.prg(0x1C01)).unwrap();
+//! assert_eq!("10 print \"hello\"\n20 goto 10\n", program.to_string());
+//! ```
+use std::fmt::Display;
+
+use crate::{
+    instruction::data::{TextEncoding, DECIMAL_DIGITS},
+    memory::{label::AddressReference, Address, ZeroPage},
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+pub(crate) mod petscii;
+mod tokens;
+
+pub use petscii::CONTROL_CODES;
+pub use tokens::{detokenize_line, tokenize_line, KEYWORDS};
+pub(crate) use tokens::{TOKEN_REM, TOKEN_SYS};
+
+/// Address where BASIC programs start.
+pub const BASIC_START: Address = 0x0801;
+
+/// Highest line number that BASIC accepts.
+pub const MAX_LINE_NUMBER: u16 = 63999;
+
+/// Tokenized BASIC line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicLine {
+    pub number: u16,
+    /// Tokenized statements, without the line number and the terminating zero.
+    pub tokens: Vec<u8>,
+}
+
+impl BasicLine {
+    /// Tokenize the statements of a line, see [tokenize_line].
+    pub fn new(number: u16, statements: &str) -> AssemblerResult<BasicLine> {
+        Ok(BasicLine {
+            number,
+            tokens: tokenize_line(statements)?,
+        })
+    }
+
+    /// Number of bytes the line takes, including the link to the next line and the
+    /// terminating zero.
+    pub fn byte_size(&self) -> Address {
+        self.tokens.len() as Address + 5
+    }
+
+    /// Bytes of the line, starting with the address of the next line.
+    pub fn bytes(&self, next_line: Address) -> Vec<u8> {
+        let mut result = vec![next_line.low(), next_line.high(), self.number.low(), self.number.high()];
+        result.extend(&self.tokens);
+        result.push(0x00);
+        result
+    }
+}
+
+impl Display for BasicLine {
+    /// Line as shown by `LIST`, see [detokenize_line].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.number, detokenize_line(&self.tokens))
+    }
+}
+
+/// Tokenized BASIC program with its lines ordered by line number.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BasicProgram {
+    pub lines: Vec<BasicLine>,
+}
+
+impl BasicProgram {
+    /// Tokenize a program; each line starts with its line number.
+    ///
+    /// Like typing in a program, lines are ordered by their number and a line replaces an
+    /// earlier line with the same number. Empty lines are skipped.
+    pub fn parse(text: &str) -> AssemblerResult<BasicProgram> {
+        let mut program = BasicProgram::default();
+        for (line_index, line) in text.lines().enumerate() {
+            let statements = line.trim_start();
+            if statements.is_empty() {
+                continue;
+            }
+            let column = line.len() - statements.len() + 1;
+            let error = |message: &str| Error::Parse {
+                line: line_index + 1,
+                column,
+                message: message.to_string(),
+            };
+            let digits = statements.chars().take_while(|c| c.is_ascii_digit()).count();
+            if digits == 0 {
+                return Err(error("line number expected"));
+            }
+            let number = statements[..digits]
+                .parse::<u32>()
+                .ok()
+                .filter(|number| *number <= MAX_LINE_NUMBER as u32)
+                .ok_or_else(|| error("line number out of range"))?;
+            program.add_line(BasicLine::new(number as u16, statements[digits..].trim_start())?);
+        }
+        Ok(program)
+    }
+
+    /// Add a line, replacing the line with the same number.
+    pub fn add_line(&mut self, line: BasicLine) {
+        match self.lines.binary_search_by_key(&line.number, |other| other.number) {
+            Ok(index) => self.lines[index] = line,
+            Err(index) => self.lines.insert(index, line),
+        }
+    }
+
+    /// Bytes of the program when its first line is stored at the given address.
+    ///
+    /// Each line starts with the address of the next line; the program ends with two zeros.
+    pub fn bytes(&self, address: Address) -> Vec<u8> {
+        let mut result = vec![];
+        let mut next_line = address;
+        for line in &self.lines {
+            next_line = next_line.wrapping_add(line.byte_size());
+            result.extend(line.bytes(next_line));
+        }
+        result.extend([0x00, 0x00]);
+        result
+    }
+
+    /// `.PRG` file of the program that is loaded at the given address.
+    ///
+    /// The lines are linked for the load address, so the program can also be used at other
+    /// addresses than `$0801`, for example `$1C01` on a C128.
+    pub fn prg(&self, load_address: Address) -> Vec<u8> {
+        let mut result = vec![load_address.low(), load_address.high()];
+        result.extend(self.bytes(load_address));
+        result
+    }
+
+    /// Read the program from a `.PRG` file; the first two bytes are the load address.
+    ///
+    /// When the program is loaded at `$0800` it may start with a zero byte.
+    pub fn from_prg(prg: &[u8]) -> AssemblerResult<BasicProgram> {
+        if prg.len() < 2 {
+            return Err(Error::InvalidProgram("load address is missing".to_string()));
+        }
+        let load_address = Address::from_le_bytes([prg[0], prg[1]]);
+        let start = basic_program_start(load_address);
+        let skip = if start != load_address && prg.get(2) == Some(&0x00) {
+            1
+        } else {
+            0
+        };
+        BasicProgram::from_bytes(load_address + skip, &prg[2 + skip as usize..])
+    }
+
+    /// Read the program that is stored at the given address by following the links between
+    /// the lines.
+    pub fn from_bytes(address: Address, bytes: &[u8]) -> AssemblerResult<BasicProgram> {
+        let truncated = || Error::InvalidProgram("BASIC program is truncated".to_string());
+        let mut program = BasicProgram::default();
+        let mut offset = 0;
+        loop {
+            let header = bytes.get(offset..offset + 2).ok_or_else(truncated)?;
+            let next_line = Address::from_le_bytes([header[0], header[1]]);
+            if next_line == 0x0000 {
+                return Ok(program);
+            }
+            let header = bytes.get(offset + 2..offset + 4).ok_or_else(truncated)?;
+            let number = u16::from_le_bytes([header[0], header[1]]);
+            let length = bytes[offset + 4..]
+                .iter()
+                .position(|byte| *byte == 0x00)
+                .ok_or_else(truncated)?;
+            let tokens = bytes[offset + 4..offset + 4 + length].to_vec();
+            let next_offset = next_line.wrapping_sub(address) as usize;
+            if next_offset < offset + 5 + length {
+                return Err(Error::InvalidProgram(format!(
+                    "line {number} links to ${next_line:04X}, which isn't after the line"
+                )));
+            }
+            program.lines.push(BasicLine { number, tokens });
+            offset = next_offset;
+        }
+    }
+}
+
+impl Display for BasicProgram {
+    /// Listing of the program, one line per line number.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// BASIC program that starts the machine code with `SYS`.
+///
+/// The `SYS` address is taken from a label, so it follows the machine code when the size of
+/// the stub or the load address changes. The links between the lines are computed from the
+/// address the stub is placed at. See [crate::builder::InstructionBuilder::basic_stub].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicStub {
+    pub(crate) target: AddressReference,
+    pub(crate) line_number: u16,
+    pub(crate) rem: Option<(u16, String)>,
+}
+
+impl BasicStub {
+    /// Stub `10 SYS target`.
+    pub fn new(target: &str) -> BasicStub {
+        BasicStub {
+            target: AddressReference::new(target),
+            line_number: 10,
+            rem: None,
+        }
+    }
+
+    /// Line number of the `SYS` line. Defaults to 10.
+    pub fn line_number(mut self, line_number: u16) -> Self {
+        self.line_number = line_number;
+        self
+    }
+
+    /// Add a `REM` line with the given text, for example the name of the program.
+    ///
+    /// The text is encoded as PETSCII, lowercase letters are shown as uppercase.
+    pub fn rem(mut self, line_number: u16, text: &str) -> Self {
+        self.rem = Some((line_number, text.to_string()));
+        self
+    }
+
+    /// Line numbers of the stub in listing order, with the text of the `REM` line.
+    fn lines(&self) -> Vec<(u16, Option<&String>)> {
+        let rem_line = self.rem.as_ref().map(|(number, text)| (*number, Some(text)));
+        let mut lines = rem_line
+            .into_iter()
+            .chain([(self.line_number, None)])
+            .collect::<Vec<_>>();
+        lines.sort_by_key(|(number, _)| *number);
+        lines
+    }
+
+    /// Listing of the stub, one entry per line.
+    pub(crate) fn listing(&self) -> Vec<String> {
+        self.lines()
+            .into_iter()
+            .map(|(number, rem)| match rem {
+                Some(text) => format!("{number} REM {text}"),
+                None => format!("{number} SYS {}", self.target.expression),
+            })
+            .collect()
+    }
+
+    /// Number of bytes of the stub when it is placed at the given address.
+    pub(crate) fn byte_size(&self, address: Address) -> Address {
+        let lines = self
+            .lines()
+            .into_iter()
+            .map(|(_, rem)| match rem {
+                // Token, space and text.
+                Some(text) => text.chars().count() as Address + 2,
+                // Token and the right aligned address.
+                None => DECIMAL_DIGITS + 1,
+            })
+            .map(|statement_size| statement_size + 5)
+            .sum::<Address>();
+        basic_program_start(address) - address + lines + 2
+    }
+
+    /// Bytes of the stub when it is placed at the given address.
+    pub(crate) fn bytes(&self, application: &Application, address: Address) -> AssemblerResult<Vec<u8>> {
+        let mut line_address = basic_program_start(address);
+        let mut result = vec![0x00; (line_address - address) as usize];
+        for (number, rem) in self.lines() {
+            let statement = match rem {
+                Some(text) => {
+                    let mut statement = vec![TOKEN_REM];
+                    for character in format!(" {text}").chars() {
+                        statement.push(
+                            TextEncoding::Petscii
+                                .encode(character)
+                                .ok_or(Error::UnsupportedCharacter(character))?,
+                        );
+                    }
+                    statement
+                }
+                None => {
+                    let target = application.address(&self.target)?;
+                    let mut statement = vec![TOKEN_SYS];
+                    statement.extend(format!("{target:>width$}", width = DECIMAL_DIGITS as usize).into_bytes());
+                    statement
+                }
+            };
+            line_address = line_address.wrapping_add(statement.len() as Address + 5);
+            let line = BasicLine {
+                number,
+                tokens: statement,
+            };
+            result.extend(line.bytes(line_address));
+        }
+        result.extend([0x00, 0x00]);
+        Ok(result)
+    }
+}
+
+/// Address where the first line of a BASIC program is stored when it is loaded at the given
+/// address; when loaded at `$0800` a zero byte precedes the program.
+pub(crate) fn basic_program_start(load_address: Address) -> Address {
+    if load_address == BASIC_START - 1 {
+        BASIC_START
+    } else {
+        load_address
+    }
+}
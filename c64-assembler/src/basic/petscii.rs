@@ -0,0 +1,122 @@
+use crate::{
+    instruction::data::TextEncoding,
+    validator::{AssemblerResult, Error},
+};
+
+/// Names of PETSCII control codes that can be written between braces, like `{clr}`.
+///
+/// The first name of a code is used when detokenizing; the names after it are aliases.
+pub const CONTROL_CODES: [(&str, u8); 43] = [
+    ("stop", 0x03),
+    ("wht", 0x05),
+    ("dish", 0x08),
+    ("ensh", 0x09),
+    ("swlc", 0x0E),
+    ("down", 0x11),
+    ("rvon", 0x12),
+    ("home", 0x13),
+    ("del", 0x14),
+    ("red", 0x1C),
+    ("rght", 0x1D),
+    ("grn", 0x1E),
+    ("blu", 0x1F),
+    ("orng", 0x81),
+    ("f1", 0x85),
+    ("f3", 0x86),
+    ("f5", 0x87),
+    ("f7", 0x88),
+    ("f2", 0x89),
+    ("f4", 0x8A),
+    ("f6", 0x8B),
+    ("f8", 0x8C),
+    ("swuc", 0x8E),
+    ("blk", 0x90),
+    ("up", 0x91),
+    ("rvof", 0x92),
+    ("clr", 0x93),
+    ("inst", 0x94),
+    ("brn", 0x95),
+    ("lred", 0x96),
+    ("gry1", 0x97),
+    ("gry2", 0x98),
+    ("lgrn", 0x99),
+    ("lblu", 0x9A),
+    ("gry3", 0x9B),
+    ("pur", 0x9C),
+    ("left", 0x9D),
+    ("yel", 0x9E),
+    ("cyn", 0x9F),
+    ("clear", 0x93),
+    ("right", 0x1D),
+    ("rvs on", 0x12),
+    ("rvs off", 0x92),
+];
+
+/// PETSCII code of the pi character.
+const PI: u8 = 0xFF;
+
+/// Encode the character at the start of the text, together with the number of characters
+/// that are used. Control codes are written as `{name}` or as hexadecimal value `{$93}`.
+pub(crate) fn encode(text: &[char]) -> AssemblerResult<(u8, usize)> {
+    match text[0] {
+        '{' => {
+            let Some(length) = text.iter().position(|character| *character == '}') else {
+                return Err(Error::UnsupportedCharacter('{'));
+            };
+            let name = text[1..length].iter().collect::<String>();
+            Ok((control_code(&name)?, length + 1))
+        }
+        'π' => Ok((PI, 1)),
+        character => TextEncoding::Petscii
+            .encode(character)
+            .map(|petscii| (petscii, 1))
+            .ok_or(Error::UnsupportedCharacter(character)),
+    }
+}
+
+fn control_code(name: &str) -> AssemblerResult<u8> {
+    let unknown = || Error::UnknownControlCode(name.to_string());
+    if let Some(hex) = name.strip_prefix('$') {
+        return u8::from_str_radix(hex, 16).map_err(|_| unknown());
+    }
+    CONTROL_CODES
+        .iter()
+        .find(|(control_code, _)| control_code.eq_ignore_ascii_case(name))
+        .map(|(_, petscii)| *petscii)
+        .ok_or_else(unknown)
+}
+
+/// Text of a PETSCII code as it is written in a listing; the inverse of [encode].
+pub(crate) fn decode(petscii: u8) -> String {
+    match petscii {
+        0x20..=0x40 | 0x5B | 0x5D => (petscii as char).to_string(),
+        0x41..=0x5A => ((petscii - 0x41 + b'a') as char).to_string(),
+        0x5C => "£".to_string(),
+        0x5E => "^".to_string(),
+        0x5F => "←".to_string(),
+        0xC1..=0xDA => ((petscii - 0xC1 + b'A') as char).to_string(),
+        PI => "π".to_string(),
+        _ => match CONTROL_CODES.iter().find(|(_, control_code)| *control_code == petscii) {
+            Some((name, _)) => format!("{{{name}}}"),
+            None => format!("{{${petscii:02x}}}"),
+        },
+    }
+}
+
+/// Encode a text, see [encode].
+pub(crate) fn encode_text(text: &str) -> AssemblerResult<Vec<u8>> {
+    let characters = text.chars().collect::<Vec<char>>();
+    let mut result = vec![];
+    let mut index = 0;
+    while index < characters.len() {
+        let (petscii, length) = encode(&characters[index..])?;
+        result.push(petscii);
+        index += length;
+    }
+    Ok(result)
+}
+
+/// Decode a text, see [decode].
+pub(crate) fn decode_text(petscii: &[u8]) -> String {
+    petscii.iter().map(|petscii| decode(*petscii)).collect()
+}
@@ -0,0 +1,148 @@
+use super::petscii::{decode, encode, encode_text};
+use crate::validator::AssemblerResult;
+
+/// Keywords of BASIC V2, the token of a keyword is `0x80` plus its index.
+///
+/// The order matters: the tokenizer uses the first keyword that matches, like the C64 does.
+pub const KEYWORDS: [&str; 76] = [
+    "END", "FOR", "NEXT", "DATA", "INPUT#", "INPUT", "DIM", "READ", "LET", "GOTO", "RUN", "IF", "RESTORE", "GOSUB",
+    "RETURN", "REM", "STOP", "ON", "WAIT", "LOAD", "SAVE", "VERIFY", "DEF", "POKE", "PRINT#", "PRINT", "CONT", "LIST",
+    "CLR", "CMD", "SYS", "OPEN", "CLOSE", "GET", "NEW", "TAB(", "TO", "FN", "SPC(", "THEN", "NOT", "STEP", "+", "-",
+    "*", "/", "^", "AND", "OR", ">", "=", "<", "SGN", "INT", "ABS", "USR", "FRE", "POS", "SQR", "RND", "LOG", "EXP",
+    "COS", "SIN", "TAN", "ATN", "PEEK", "LEN", "STR$", "VAL", "ASC", "CHR$", "LEFT$", "RIGHT$", "MID$", "GO",
+];
+
+/// Token of the first keyword.
+const FIRST_TOKEN: u8 = 0x80;
+pub(crate) const TOKEN_DATA: u8 = 0x83;
+pub(crate) const TOKEN_REM: u8 = 0x8F;
+pub(crate) const TOKEN_PRINT: u8 = 0x99;
+pub(crate) const TOKEN_SYS: u8 = 0x9E;
+
+/// Token of the keyword at the start of the text, together with the number of characters of
+/// the keyword.
+///
+/// Keywords are matched case insensitive. Like on the C64 a keyword can be abbreviated by
+/// writing its first letters in lowercase followed by the next letter in uppercase, so `pO`
+/// is `POKE` and `gO` is `GOTO`.
+fn keyword(text: &[char]) -> Option<(u8, usize)> {
+    KEYWORDS.iter().enumerate().find_map(|(index, keyword)| {
+        let token = FIRST_TOKEN + index as u8;
+        for (position, expected) in keyword.chars().enumerate() {
+            let actual = *text.get(position)?;
+            if expected != actual.to_ascii_uppercase() && !(expected == '^' && actual == '↑') {
+                return None;
+            }
+            let abbreviated =
+                position > 0 && actual.is_ascii_uppercase() && text[..position].iter().all(char::is_ascii_lowercase);
+            if abbreviated {
+                return Some((token, position + 1));
+            }
+        }
+        Some((token, keyword.chars().count()))
+    })
+}
+
+/// Tokenize the statements of a BASIC line, without the line number.
+///
+/// Keywords are replaced by their token, except inside strings, after `REM` and in `DATA`
+/// statements. Keywords can be abbreviated, see [KEYWORDS]. Letters outside strings are
+/// stored unshifted. Inside strings the text is encoded using
+/// [crate::instruction::data::TextEncoding::Petscii], so
+/// lowercase letters are shown as uppercase in the default character set. Control codes
+/// are written between braces using their name from [super::CONTROL_CODES] or as a
+/// hexadecimal value, like `{clr}` or `{$93}`.
+///
+/// ```
+/// use c64_assembler::basic::tokenize_line;
+///
+/// assert_eq!(vec![0x9E, 0x32, 0x30, 0x36, 0x31], tokenize_line("sys2061").unwrap());
+/// assert_eq!(
+///     vec![0x99, 0x22, 0x48, 0x49, 0x22, 0x3A, 0x89, 0x31, 0x30],
+///     tokenize_line("print\"hi\":goto10").unwrap()
+/// );
+/// assert_eq!(vec![0x99, 0x22, 0x93, 0x05, 0x22], tokenize_line("?\"{clr}{wht}\"").unwrap());
+/// assert_eq!(
+///     tokenize_line("poke53280,0").unwrap(),
+///     tokenize_line("pO53280,0").unwrap()
+/// );
+/// ```
+pub fn tokenize_line(text: &str) -> AssemblerResult<Vec<u8>> {
+    let characters = text.chars().collect::<Vec<char>>();
+    let mut result = vec![];
+    let mut in_string = false;
+    let mut in_data = false;
+    let mut index = 0;
+    while index < characters.len() {
+        let character = characters[index];
+        if character == '"' {
+            in_string = !in_string;
+        }
+        let (petscii, length) = if in_string || character == '"' {
+            encode(&characters[index..])?
+        } else if in_data {
+            in_data = character != ':';
+            unshifted(&characters[index..])?
+        } else if character == '?' {
+            (TOKEN_PRINT, 1)
+        } else if let Some((token, length)) = keyword(&characters[index..]) {
+            if token == TOKEN_REM {
+                result.push(token);
+                let comment = characters[index + length..].iter().collect::<String>();
+                result.extend(encode_text(&comment)?);
+                break;
+            }
+            in_data = token == TOKEN_DATA;
+            (token, length)
+        } else {
+            unshifted(&characters[index..])?
+        };
+        result.push(petscii);
+        index += length;
+    }
+    Ok(result)
+}
+
+/// Encode the character at the start of the text, letters are always unshifted.
+fn unshifted(text: &[char]) -> AssemblerResult<(u8, usize)> {
+    if text[0].is_ascii_uppercase() {
+        encode(&[text[0].to_ascii_lowercase()])
+    } else {
+        encode(text)
+    }
+}
+
+/// Text of a tokenized BASIC line, without the line number; the inverse of [tokenize_line].
+///
+/// Keywords and unshifted letters are written in lowercase, shifted letters in uppercase.
+/// Control codes and other characters that cannot be typed are written between braces.
+///
+/// ```
+/// use c64_assembler::basic::detokenize_line;
+///
+/// assert_eq!(
+///     "print\"{clr}Hello\":goto10",
+///     detokenize_line(&[0x99, 0x22, 0x93, 0xC8, 0x45, 0x4C, 0x4C, 0x4F, 0x22, 0x3A, 0x89, 0x31, 0x30])
+/// );
+/// ```
+pub fn detokenize_line(tokens: &[u8]) -> String {
+    let mut result = String::new();
+    let mut in_string = false;
+    let mut in_rem = false;
+    for token in tokens {
+        if *token == b'"' {
+            in_string = !in_string;
+        }
+        let keyword = KEYWORDS
+            .get(token.wrapping_sub(FIRST_TOKEN) as usize)
+            .filter(|_| *token >= FIRST_TOKEN && !in_string && !in_rem);
+        match keyword {
+            Some(keyword) => {
+                result.push_str(&keyword.to_ascii_lowercase());
+                in_rem = *token == TOKEN_REM;
+            }
+            None => result.push_str(&decode(*token)),
+        }
+    }
+    result
+}
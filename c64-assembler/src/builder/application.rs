@@ -4,17 +4,24 @@ use crate::{
     memory::{
         define::{Define, Value},
         label::AddressReference,
+        memory_map::ForbiddenRegion,
+        segment::Segment,
+        zeropage::ZeropagePool,
         Address, ZeroPage,
     },
     validator::AssemblerResult,
     Application, Module,
 };
 
-use super::finalize::finalize;
+use super::{finalize::finalize, variant::VariantSelection};
 
 #[derive(Clone)]
 pub struct ApplicationBuilder {
     application: Application,
+    fix_out_of_range_branches: bool,
+    strip_unused_functions: bool,
+    variant_selection: VariantSelection,
+    zeropage_pool: ZeropagePool,
 }
 
 impl Default for ApplicationBuilder {
@@ -25,8 +32,16 @@ impl Default for ApplicationBuilder {
                 entry_point: 0x0800,
                 modules: vec![],
                 defines: vec![],
+                segments: vec![],
                 address_lookup: HashMap::default(),
+                removed_functions: vec![],
+                zeropage_map: vec![],
+                forbidden_regions: ForbiddenRegion::c64_defaults(),
             },
+            fix_out_of_range_branches: false,
+            strip_unused_functions: false,
+            variant_selection: VariantSelection::default(),
+            zeropage_pool: ZeropagePool::default(),
         }
     }
 }
@@ -80,16 +95,7 @@ impl ApplicationBuilder {
     ///     .build();
     /// ```
     pub fn define_address(&mut self, name: &str, address: Address) -> &mut Self {
-        self.application.address_lookup.insert(name.to_string(), address);
-        if address.is_zeropage() {
-            self.application
-                .defines
-                .push(Define::new(name, Value::Zeropage(address)));
-        } else {
-            self.application
-                .defines
-                .push(Define::new(name, Value::Address(address)));
-        }
+        self.application.define_address(name, address);
         self
     }
 
@@ -279,27 +285,271 @@ impl ApplicationBuilder {
             .define_address("SID_ENV3", 0xD41C)
     }
 
+    /// Add a segment to place modules and functions at their own start address.
+    ///
+    /// ```
+    /// use c64_assembler::builder::ApplicationBuilder;
+    /// use c64_assembler::memory::segment::Segment;
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .segment(Segment::new("music", 0x1000))
+    ///     .build();
+    /// ```
+    pub fn segment(&mut self, segment: Segment) -> &mut Self {
+        self.application.segments.push(segment);
+        self
+    }
+
+    /// Rewrite branches that cannot reach their target into an inverted branch over a `jmp`.
+    ///
+    /// Without this option these branches are reported by the [crate::validator::Validator]
+    /// and the [crate::generator::ProgramGenerator] fails to generate the program.
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::validator::Validator;
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .fix_out_of_range_branches()
+    ///     .module(
+    ///         ModuleBuilder::default()
+    ///             .instructions(
+    ///                 InstructionBuilder::default()
+    ///                     .label("loop")
+    ///                     .fill(200, 0xEA)
+    ///                     .bne_addr("loop")
+    ///                     .build(),
+    ///             )
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(application.validate().is_ok());
+    /// ```
+    pub fn fix_out_of_range_branches(&mut self) -> &mut Self {
+        self.fix_out_of_range_branches = true;
+        self
+    }
+
+    /// Remove the functions that are never used.
+    ///
+    /// The instructions of the modules are always kept. Functions that can't be reached from
+    /// them, directly or via other functions, are removed from the application, so they are
+    /// neither part of the generated program nor of the generated source. The removed
+    /// functions are listed in [Application::removed_functions].
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder};
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .strip_unused_functions()
+    ///     .module(
+    ///         ModuleBuilder::default()
+    ///             .name("main")
+    ///             .instructions(InstructionBuilder::default().jsr_addr("used").rts().build())
+    ///             .function(
+    ///                 FunctionBuilder::default()
+    ///                     .name("used")
+    ///                     .instructions(InstructionBuilder::default().rts().build())
+    ///                     .build(),
+    ///             )
+    ///             .function(
+    ///                 FunctionBuilder::default()
+    ///                     .name("unused")
+    ///                     .instructions(InstructionBuilder::default().lda_imm(0x00).rts().build())
+    ///                     .build(),
+    ///             )
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(1, application.modules[0].functions.len());
+    /// assert_eq!("unused", application.removed_functions[0].name);
+    /// assert_eq!(3, application.removed_bytes());
+    /// ```
+    pub fn strip_unused_functions(&mut self) -> &mut Self {
+        self.strip_unused_functions = true;
+        self
+    }
+
+    /// Select the variation of a function.
+    ///
+    /// Use [crate::builder::DEFAULT_VARIANT] to select the instructions that were given when
+    /// building the function. Takes precedence over [ApplicationBuilder::variant_tag].
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder};
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .function_variant("clear", "last_known_working")
+    ///     .module(
+    ///         ModuleBuilder::default()
+    ///             .function(
+    ///                 FunctionBuilder::default()
+    ///                     .name("clear")
+    ///                     .instructions(InstructionBuilder::default().lda_imm(0x00).rts().build())
+    ///                     .variant("last_known_working", &[], InstructionBuilder::default().rts().build())
+    ///                     .build(),
+    ///             )
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// let function = &application.modules[0].functions[0];
+    /// assert_eq!(Some("last_known_working".to_string()), function.variant);
+    /// assert_eq!(1, function.instructions.instructions.len());
+    /// ```
+    pub fn function_variant(&mut self, function_name: &str, variant_name: &str) -> &mut Self {
+        self.variant_selection
+            .functions
+            .insert(function_name.to_string(), variant_name.to_string());
+        self
+    }
+
+    /// Select the variations that have the given tag, for all functions.
+    ///
+    /// Can be called multiple times; earlier tags are preferred. Functions without a variation
+    /// with any of the tags use their default instructions.
+    ///
+    /// ```
+    /// use c64_assembler::builder::ApplicationBuilder;
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .variant_tag("size")
+    ///     .build();
+    /// ```
+    pub fn variant_tag(&mut self, tag: &str) -> &mut Self {
+        self.variant_selection.tags.push(tag.to_string());
+        self
+    }
+
+    /// Add a memory region that code and data may not be placed in.
+    ///
+    /// Defaults to [ForbiddenRegion::c64_defaults]. Placing code or data in a forbidden
+    /// region is reported by the [crate::validator::Validator].
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::memory::memory_map::ForbiddenRegion;
+    /// use c64_assembler::validator::Validator;
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .forbidden_region(ForbiddenRegion::new("screen", 0x0400, 0x07FF))
+    ///     .entry_point(0x0400)
+    ///     .module(ModuleBuilder::default().instructions(InstructionBuilder::default().rts().build()).build())
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(application.validate().is_err());
+    /// ```
+    pub fn forbidden_region(&mut self, region: ForbiddenRegion) -> &mut Self {
+        self.application.forbidden_regions.push(region);
+        self
+    }
+
+    /// Allow code and data in the forbidden region with the given name, for example when the
+    /// BASIC ROM is banked out.
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::validator::Validator;
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .allow_region("BASIC ROM")
+    ///     .entry_point(0xA000)
+    ///     .module(ModuleBuilder::default().instructions(InstructionBuilder::default().rts().build()).build())
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(application.validate().is_ok());
+    /// ```
+    pub fn allow_region(&mut self, name: &str) -> &mut Self {
+        self.application.forbidden_regions.retain(|region| region.name != name);
+        self
+    }
+
+    /// Zeropage addresses that can be assigned to the zeropage variables of the modules.
+    ///
+    /// Defaults to [ZeropagePool::default], the addresses that are free when BASIC and the
+    /// KERNAL are in use. Zeropage defines of the application are never assigned; two bytes
+    /// are reserved for each of them.
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, ModuleBuilder};
+    /// use c64_assembler::memory::zeropage::{ZeropageKind, ZeropagePool};
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .zeropage_pool(ZeropagePool::without_basic().reserve(0x02..=0x0F))
+    ///     .module(ModuleBuilder::default().zeropage("pointer", ZeropageKind::Pointer).build())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(0x10, application.lookup_address(&"pointer".to_string()).unwrap());
+    /// ```
+    pub fn zeropage_pool(&mut self, zeropage_pool: ZeropagePool) -> &mut Self {
+        self.zeropage_pool = zeropage_pool;
+        self
+    }
+
     pub fn module(&mut self, module: Module) -> &mut Self {
         self.application.modules.push(module);
         self
     }
 
+    /// Include a module under a namespace, binding values to its parameters.
+    ///
+    /// The labels, functions and parameters of the module are prefixed with the namespace, so the
+    /// same module can be included multiple times. Missing bindings are reported when building
+    /// the application.
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::ParameterKind;
+    ///
+    /// let module = ModuleBuilder::default()
+    ///     .name("fill")
+    ///     .parameter("SCREEN", ParameterKind::Address)
+    ///     .instructions(InstructionBuilder::default().label("fill").sta_addr("SCREEN").build())
+    ///     .build();
+    /// let application = ApplicationBuilder::default()
+    ///     .module_instance("top", &module, &[("SCREEN", 0x0400)])
+    ///     .module_instance("bottom", &module, &[("SCREEN", 0x07C0)])
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(0x07C0, application.lookup_address(&"bottom_SCREEN".to_string()).unwrap());
+    /// assert_eq!(0x0803, application.lookup_address(&"bottom_fill".to_string()).unwrap());
+    /// ```
+    pub fn module_instance(&mut self, namespace: &str, module: &Module, bindings: &[(&str, Address)]) -> &mut Self {
+        self.application.modules.push(module.instantiate(namespace, bindings));
+        self
+    }
+
     /// Build the application
     pub fn build(&mut self) -> AssemblerResult<Application> {
-        finalize(&mut self.application)?;
+        finalize(
+            &mut self.application,
+            &self.variant_selection,
+            &self.zeropage_pool,
+            self.fix_out_of_range_branches,
+            self.strip_unused_functions,
+        )?;
         Ok(self.application.clone())
     }
 }
 
 impl Application {
-    pub(crate) fn define_mut(&mut self, define_name: &String) -> &mut Define {
-        self.defines
-            .iter_mut()
-            .find(|define| &define.name == define_name)
-            .unwrap()
+    pub(crate) fn define_address(&mut self, name: &str, address: Address) {
+        self.address_lookup.insert(name.to_string(), address);
+        if address.is_zeropage() {
+            self.defines.push(Define::new(name, Value::Zeropage(address)));
+        } else {
+            self.defines.push(Define::new(name, Value::Address(address)));
+        }
+    }
+
+    pub(crate) fn define_mut(&mut self, define_name: &String) -> Option<&mut Define> {
+        self.defines.iter_mut().find(|define| &define.name == define_name)
     }
 
-    pub fn address(&self, address_reference: &AddressReference) -> Address {
-        self.address_lookup.get(&address_reference.name).unwrap() + address_reference.offset
+    /// Evaluate the address of the given reference.
+    pub fn address(&self, address_reference: &AddressReference) -> AssemblerResult<Address> {
+        Ok(address_reference.expression.evaluate(self)? as Address)
     }
 }
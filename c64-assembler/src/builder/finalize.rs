@@ -1,20 +1,139 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
 
 use crate::{
-    instruction::operation::Operation,
+    instruction::{operation::Operation, Instruction},
     memory::{
         address_mode::{AddressMode, Immediate},
+        expression::Expression,
+        label::AddressReference,
+        segment::Placement,
         user_count::UserCount,
+        zeropage::{allocate_zeropage, ZeropagePool},
         Address,
     },
-    validator::AssemblerResult,
-    Application, Instructions,
+    validator::{AssemblerResult, Error},
+    Application, Function, Instructions, RemovedFunction,
+};
+
+use super::{
+    instance::bind_module_parameters,
+    scope::resolve_scoped_labels,
+    variant::{select_variants, VariantSelection},
 };
 
-pub fn finalize(application: &mut Application) -> AssemblerResult<()> {
+/// Maximum number of passes to determine the addresses of labels.
+const MAX_PASSES: usize = 8;
+
+pub fn finalize(
+    application: &mut Application,
+    variant_selection: &VariantSelection,
+    zeropage_pool: &ZeropagePool,
+    fix_out_of_range_branches: bool,
+    strip_unused_functions: bool,
+) -> AssemblerResult<()> {
+    bind_module_parameters(application)?;
+    allocate_zeropage(application, zeropage_pool)?;
+    select_variants(application, variant_selection)?;
+    resolve_scoped_labels(application);
+    if strip_unused_functions {
+        remove_unused_functions(application)?;
+    }
     defines_update_user_count(application);
     functions_update_user_count(application);
-    update_label_addresses(application)
+    update_label_addresses(application)?;
+    if fix_out_of_range_branches {
+        fix_branches(application)?;
+    }
+    validate_expressions(application)
+}
+
+/// Remove the functions that can't be reached from the instructions of the modules.
+fn remove_unused_functions(application: &mut Application) -> AssemblerResult<()> {
+    let used_functions = used_functions(application);
+
+    // The addresses are needed to determine the size of the removed functions.
+    update_label_addresses(application)?;
+    let mut byte_sizes = HashMap::<String, Address>::default();
+    for layout in application.layout()? {
+        let mut current_address = layout.segment.run_start();
+        for placement in &layout.placements {
+            for instruction in &placement.instructions().instructions {
+                let byte_size = instruction.byte_size(application, current_address)?;
+                if let Some(function) = placement.function() {
+                    if !used_functions.contains(&function.name) {
+                        *byte_sizes.entry(function.name.clone()).or_default() += byte_size;
+                    }
+                }
+                current_address = current_address.wrapping_add(byte_size);
+            }
+        }
+    }
+
+    let mut removed_functions = vec![];
+    for module in &mut application.modules {
+        for function in module
+            .functions
+            .extract_if(.., |function| !used_functions.contains(&function.name))
+        {
+            for name in function_names(&function) {
+                application.address_lookup.remove(name);
+            }
+            removed_functions.push(RemovedFunction {
+                module: module.name.clone(),
+                byte_size: byte_sizes.get(&function.name).copied().unwrap_or_default(),
+                name: function.name,
+            });
+        }
+    }
+    application.removed_functions = removed_functions;
+    Ok(())
+}
+
+/// Names of the functions that are used by the instructions of the modules, directly or via
+/// other functions.
+fn used_functions(application: &Application) -> HashSet<String> {
+    // Function that each name belongs to; a function can be used via its name or its labels.
+    let mut functions = HashMap::<&String, &Function>::default();
+    for function in application.modules.iter().flat_map(|module| &module.functions) {
+        for name in function_names(function) {
+            functions.insert(name, function);
+        }
+    }
+
+    let mut used_functions = HashSet::default();
+    let mut pending = application
+        .modules
+        .iter()
+        .map(|module| &module.instructions)
+        .collect::<Vec<_>>();
+    while let Some(instructions) = pending.pop() {
+        for name in instructions
+            .instructions
+            .iter()
+            .flat_map(|instruction| instruction.address_names())
+        {
+            if let Some(function) = functions.get(name) {
+                if used_functions.insert(function.name.clone()) {
+                    pending.push(&function.instructions);
+                }
+            }
+        }
+    }
+    used_functions
+}
+
+/// Name of the function and the names of its labels.
+fn function_names(function: &Function) -> Vec<&String> {
+    let mut names = vec![&function.name];
+    for instruction in &function.instructions.instructions {
+        if let Operation::Label(label) = &instruction.operation {
+            names.push(label);
+        }
+    }
+    names
 }
 
 fn defines_update_user_count(application: &mut Application) {
@@ -27,9 +146,10 @@ fn defines_update_user_count(application: &mut Application) {
     }
 
     for (define_name, user_count) in define_users {
-        let define = application.define_mut(&define_name);
-        for _ in 0..user_count {
-            define.user_increase();
+        if let Some(define) = application.define_mut(&define_name) {
+            for _ in 0..user_count {
+                define.user_increase();
+            }
         }
     }
 }
@@ -68,32 +188,15 @@ fn count_users(application: &Application, name: &String) -> usize {
 }
 
 fn count_users_instructions(instructions: &Instructions, name: &String) -> usize {
-    let mut result = 0;
-    for instruction in &instructions.instructions {
-        match &instruction.address_mode {
-            AddressMode::Absolute(address_reference)
-            | AddressMode::AbsoluteX(address_reference)
-            | AddressMode::AbsoluteY(address_reference)
-            | AddressMode::Indirect(address_reference)
-            | AddressMode::IndexedIndirect(address_reference)
-            | AddressMode::IndirectIndexed(address_reference)
-            | AddressMode::Immediate(Immediate::Low(address_reference))
-            | AddressMode::Immediate(Immediate::High(address_reference))
-            | AddressMode::Relative(address_reference) => {
-                if &address_reference.name == name {
-                    result += 1;
-                }
-            }
-
-            AddressMode::Immediate(Immediate::Byte(_))
-            | crate::memory::address_mode::AddressMode::Implied
-            | AddressMode::Accumulator => {}
-        }
-    }
-    result
+    instructions
+        .instructions
+        .iter()
+        .flat_map(|instruction| instruction.address_names())
+        .filter(|address_name| *address_name == name)
+        .count()
 }
 
-fn update_label_addresses(application: &mut Application) -> AssemblerResult<()> {
+pub(crate) fn update_label_addresses(application: &mut Application) -> AssemblerResult<()> {
     // First go over all labels and add dummy addresses to the application address lookup.
     // This will ensure that the correct instruction byte size can be determined.
     let mut label_addresses = HashMap::<String, Address>::default();
@@ -116,32 +219,215 @@ fn update_label_addresses(application: &mut Application) -> AssemblerResult<()>
     application.address_lookup.extend(label_addresses);
     application.address_lookup.extend(function_addresses);
 
-    // Now go over all the labels again and determine the correct address.
-    let mut label_addresses = HashMap::<String, Address>::default();
-    let mut function_addresses = HashMap::<String, Address>::default();
-    let mut current_address = application.entry_point;
+    // Now go over all the labels again and determine the correct address. Instructions that
+    // refer to labels can shrink to their zeropage variant once the addresses are known, so
+    // repeat until the addresses are stable.
+    for _ in 0..MAX_PASSES {
+        let addresses = determine_label_addresses(application)?;
+        let stable = addresses
+            .iter()
+            .all(|(name, address)| application.address_lookup.get(name) == Some(address));
+        application.address_lookup.extend(addresses);
+        if stable {
+            return Ok(());
+        }
+    }
+    Err(Error::LabelAddressesUnstable { passes: MAX_PASSES })
+}
+
+fn determine_label_addresses(application: &Application) -> AssemblerResult<HashMap<String, Address>> {
+    let mut addresses = HashMap::<String, Address>::default();
 
     let mut update_label_addresses_instructions =
         |current_address: &mut Address, instructions: &Instructions| -> AssemblerResult<()> {
             for instruction in &instructions.instructions {
                 if let Operation::Label(label) = &instruction.operation {
-                    label_addresses.insert(label.clone(), *current_address);
+                    addresses.insert(label.clone(), *current_address);
                 }
-                let byte_size = instruction.byte_size(application)?;
-                *current_address += byte_size;
+                let byte_size = instruction.byte_size(application, *current_address)?;
+                *current_address = current_address.wrapping_add(byte_size);
             }
             Ok(())
         };
 
+    let mut function_addresses = HashMap::<String, Address>::default();
+    for layout in application.layout()? {
+        let mut current_address = layout.segment.run_start();
+        for placement in &layout.placements {
+            if let Placement::Function(_, function) = placement {
+                function_addresses.insert(function.name.clone(), current_address);
+            }
+            update_label_addresses_instructions(&mut current_address, placement.instructions())?;
+        }
+
+        let size = current_address.wrapping_sub(layout.segment.run_start()) as usize;
+        if let Some(max_size) = layout.segment.max_size {
+            if size > max_size as usize {
+                return Err(Error::SegmentOverflow {
+                    segment: layout.segment.name,
+                    size,
+                    max_size,
+                });
+            }
+        }
+    }
+
+    addresses.extend(function_addresses);
+    Ok(addresses)
+}
+
+/// Rewrite branches that cannot reach their target into an inverted branch over a `jmp`.
+///
+/// ```asm
+///   bne far_away        ; becomes:  beq branch_fixup_0
+///                       ;           jmp far_away
+///                       ; branch_fixup_0:
+/// ```
+///
+/// Rewriting a branch moves the instructions after it, which can push other branches out of
+/// range. Repeat until all branches can reach their target.
+fn fix_branches(application: &mut Application) -> AssemblerResult<()> {
+    let mut fixup_index = 0;
+    loop {
+        let branches = out_of_range_branches(application)?;
+        if branches.is_empty() {
+            return Ok(());
+        }
+        let mut labels = vec![];
+        while labels.len() < branches.len() {
+            let label = format!("branch_fixup_{fixup_index}");
+            fixup_index += 1;
+            if !application.address_lookup.contains_key(&label) {
+                labels.push(label);
+            }
+        }
+        // Rewrite from the back, so the indices of the other branches stay valid.
+        for ((module_index, function_index, instruction_index), label) in branches.into_iter().zip(labels).rev() {
+            let module = &mut application.modules[module_index];
+            let instructions = match function_index {
+                Some(function_index) => &mut module.functions[function_index].instructions,
+                None => &mut module.instructions,
+            };
+            fix_branch(instructions, instruction_index, label);
+        }
+        update_label_addresses(application)?;
+    }
+}
+
+/// Module, function and instruction index of the branches that cannot reach their target.
+///
+/// Sorted by the order of the instructions in the application.
+fn out_of_range_branches(application: &Application) -> AssemblerResult<Vec<(usize, Option<usize>, usize)>> {
+    let mut branches = vec![];
+    let mut result = Ok(());
+    application.visit_placed_instructions(|placement, instruction_index, instruction, address| {
+        match instruction.branch_distance(application, address) {
+            Ok(Some(distance)) if !(-128..=127).contains(&distance) => {
+                let module_index = application
+                    .modules
+                    .iter()
+                    .position(|module| std::ptr::eq(module, placement.module()));
+                let function_index = placement.function().and_then(|function| {
+                    placement
+                        .module()
+                        .functions
+                        .iter()
+                        .position(|other| std::ptr::eq(other, function))
+                });
+                if let Some(module_index) = module_index {
+                    branches.push((module_index, function_index, instruction_index));
+                }
+            }
+            Ok(_) => {}
+            Err(error) => result = Err(error),
+        }
+    })?;
+    result?;
+    branches.sort();
+    Ok(branches)
+}
+
+fn fix_branch(instructions: &mut Instructions, instruction_index: usize, label: String) {
+    let branch = &mut instructions.instructions[instruction_index];
+    let (Some(inverted), AddressMode::Relative(target)) = (branch.operation.inverted_branch(), &branch.address_mode)
+    else {
+        return;
+    };
+    let jump = Instruction {
+        operation: Operation::JMP,
+        address_mode: AddressMode::Absolute(target.clone()),
+        comments: vec![],
+        source: branch.source,
+    };
+    branch.operation = inverted;
+    branch.address_mode = AddressMode::Relative(AddressReference::new(&label));
+    instructions.instructions.splice(
+        instruction_index + 1..instruction_index + 1,
+        [
+            jump,
+            Instruction {
+                operation: Operation::Label(label),
+                ..Default::default()
+            },
+        ],
+    );
+}
+
+/// Evaluate all expressions and check that their values fit in the operands.
+fn validate_expressions(application: &Application) -> AssemblerResult<()> {
     for module in &application.modules {
-        update_label_addresses_instructions(&mut current_address, &module.instructions)?;
+        validate_expressions_instructions(application, &module.instructions)?;
         for function in &module.functions {
-            function_addresses.insert(function.name.clone(), current_address);
-            update_label_addresses_instructions(&mut current_address, &function.instructions)?;
+            validate_expressions_instructions(application, &function.instructions)?;
         }
     }
+    Ok(())
+}
 
-    application.address_lookup.extend(label_addresses);
-    application.address_lookup.extend(function_addresses);
+fn validate_expressions_instructions(application: &Application, instructions: &Instructions) -> AssemblerResult<()> {
+    const ADDRESS: RangeInclusive<i64> = 0..=0xFFFF;
+    const ZEROPAGE: RangeInclusive<i64> = 0..=0xFF;
+    const BYTE: RangeInclusive<i64> = -0x80..=0xFF;
+    const ANY: RangeInclusive<i64> = i64::MIN..=i64::MAX;
+
+    for instruction in &instructions.instructions {
+        for address_reference in instruction.operation.data_address_references() {
+            let range = match instruction.operation {
+                Operation::Words(_) | Operation::Decimal(_) | Operation::BasicStub(_) => ADDRESS,
+                _ => ANY,
+            };
+            validate_expression(application, &address_reference.expression, range)?;
+        }
+        let (expression, range) = match &instruction.address_mode {
+            AddressMode::Absolute(address_reference)
+            | AddressMode::AbsoluteX(address_reference)
+            | AddressMode::AbsoluteY(address_reference)
+            | AddressMode::Indirect(address_reference)
+            | AddressMode::Relative(address_reference) => (&address_reference.expression, ADDRESS),
+            AddressMode::IndexedIndirect(address_reference) | AddressMode::IndirectIndexed(address_reference) => {
+                (&address_reference.expression, ZEROPAGE)
+            }
+            AddressMode::Immediate(Immediate::Low(address_reference))
+            | AddressMode::Immediate(Immediate::High(address_reference)) => (&address_reference.expression, ANY),
+            AddressMode::Immediate(Immediate::Expression(expression)) => (expression, BYTE),
+            AddressMode::Immediate(Immediate::Byte(_)) | AddressMode::Implied | AddressMode::Accumulator => continue,
+        };
+        validate_expression(application, expression, range)?;
+    }
+    Ok(())
+}
+
+fn validate_expression(
+    application: &Application,
+    expression: &Expression,
+    range: RangeInclusive<i64>,
+) -> AssemblerResult<()> {
+    let value = expression.evaluate(application)?;
+    if !range.contains(&value) {
+        return Err(Error::ExpressionOutOfRange {
+            expression: expression.to_string(),
+            value,
+        });
+    }
     Ok(())
 }
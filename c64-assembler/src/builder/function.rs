@@ -1,4 +1,4 @@
-use crate::{Function, Instructions};
+use crate::{Function, FunctionVariant, Instructions};
 
 #[derive(Default, Clone)]
 pub struct FunctionBuilder {
@@ -23,6 +23,38 @@ impl FunctionBuilder {
         self
     }
 
+    /// Place the function in the segment with the given name instead of the segment of its module.
+    pub fn segment(&mut self, segment_name: &str) -> &mut Self {
+        self.function.segment = Some(segment_name.to_string());
+        self
+    }
+
+    /// Add a variation of the function.
+    ///
+    /// The instructions given by [FunctionBuilder::instructions] are used, unless another
+    /// variation is selected by [crate::builder::ApplicationBuilder::function_variant] or
+    /// [crate::builder::ApplicationBuilder::variant_tag].
+    ///
+    /// ```
+    /// use c64_assembler::builder::{FunctionBuilder, InstructionBuilder};
+    ///
+    /// let function = FunctionBuilder::default()
+    ///     .name("clear_screen")
+    ///     .instructions(InstructionBuilder::default().label("clear_screen_entry").rts().build())
+    ///     .variant(
+    ///         "unrolled",
+    ///         &["speed"],
+    ///         InstructionBuilder::default().label("clear_screen_entry").rts().build(),
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn variant(&mut self, name: &str, tags: &[&str], instructions: Instructions) -> &mut Self {
+        self.function
+            .variants
+            .push(FunctionVariant::new(name, tags, instructions));
+        self
+    }
+
     pub fn build(&self) -> Function {
         self.function.clone()
     }
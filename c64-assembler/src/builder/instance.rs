@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use crate::{
+    instruction::operation::Operation,
+    memory::{label::is_scoped_label, Address, ZeroPage},
+    validator::{AssemblerResult, Error},
+    Application, Instructions, Module, ParameterKind,
+};
+
+impl Module {
+    /// Copy of this module where the labels, functions, parameters and zeropage variables of the module are
+    /// prefixed with the namespace, so the module can be included multiple times.
+    pub(crate) fn instantiate(&self, namespace: &str, bindings: &[(&str, Address)]) -> Module {
+        let mut names = HashSet::<String>::default();
+        let mut add_labels = |instructions: &Instructions| {
+            for instruction in &instructions.instructions {
+                match &instruction.operation {
+                    // Scoped labels are made unique when the application is built.
+                    Operation::Label(label) if !is_scoped_label(label) => {
+                        names.insert(label.clone());
+                    }
+                    _ => {}
+                }
+            }
+        };
+        add_labels(&self.instructions);
+        for function in &self.functions {
+            add_labels(&function.instructions);
+            for variant in &function.variants {
+                add_labels(&variant.instructions);
+            }
+        }
+        names.extend(self.functions.iter().map(|function| function.name.clone()));
+        names.extend(self.parameters.iter().map(|parameter| parameter.name.clone()));
+        names.extend(self.zeropage.iter().map(|variable| variable.name.clone()));
+        let rename = |name: &str| names.contains(name).then(|| namespaced(namespace, name));
+
+        let mut module = self.clone();
+        module.name = if self.name.is_empty() {
+            namespace.to_string()
+        } else {
+            namespaced(namespace, &self.name)
+        };
+        module.namespace = Some(namespace.to_string());
+        module.bindings = bindings
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        module.instructions.rename(&rename);
+        for variable in &mut module.zeropage {
+            variable.name = namespaced(namespace, &variable.name);
+        }
+        for function in &mut module.functions {
+            function.name = namespaced(namespace, &function.name);
+            function.instructions.rename(&rename);
+            for variant in &mut function.variants {
+                variant.instructions.rename(&rename);
+            }
+        }
+        module
+    }
+}
+
+fn namespaced(namespace: &str, name: &str) -> String {
+    format!("{namespace}_{name}")
+}
+
+/// Define the bound parameters of the module instances and check that the parameters of all
+/// modules have a valid value.
+pub(crate) fn bind_module_parameters(application: &mut Application) -> AssemblerResult<()> {
+    let mut defines = vec![];
+    for module in &application.modules {
+        for (name, _) in &module.bindings {
+            if !module.parameters.iter().any(|parameter| &parameter.name == name) {
+                return Err(Error::ModuleParameterUnknown {
+                    module: module.name.clone(),
+                    parameter: name.clone(),
+                });
+            }
+        }
+
+        for parameter in &module.parameters {
+            let value = match &module.namespace {
+                Some(namespace) => {
+                    let value = module
+                        .bindings
+                        .iter()
+                        .find(|(name, _)| name == &parameter.name)
+                        .map(|(_, value)| *value);
+                    defines.extend(value.map(|value| (namespaced(namespace, &parameter.name), value)));
+                    value
+                }
+                None => application.address_lookup.get(&parameter.name).copied(),
+            };
+            let Some(value) = value else {
+                return Err(Error::ModuleParameterUnbound {
+                    module: module.name.clone(),
+                    parameter: parameter.name.clone(),
+                });
+            };
+            if parameter.kind == ParameterKind::Zeropage && !value.is_zeropage() {
+                return Err(Error::ModuleParameterNotZeropage {
+                    module: module.name.clone(),
+                    parameter: parameter.name.clone(),
+                    value,
+                });
+            }
+        }
+    }
+
+    for (name, value) in defines {
+        // Building an application twice binds the parameters again.
+        if application.define_mut(&name).is_none() {
+            application.define_address(&name, value);
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,13 @@
 use crate::{
-    instruction::{operation::Operation, Instruction},
+    basic::{basic_program_start, BasicProgram, BasicStub},
+    instruction::{
+        data::{TextEncoding, Word},
+        operation::Operation,
+        Instruction, SourceLocation,
+    },
     memory::{
         address_mode::{AddressMode, Immediate},
+        expression::Expression,
         label::AddressReference,
         Address,
     },
@@ -33,6 +39,7 @@ impl InstructionBuilder {
             operation,
             address_mode,
             comments: vec![],
+            source: None,
         });
     }
 
@@ -64,10 +71,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn adc_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.adc(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn adc_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.adc(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a adc instruction with higher byte of an address.
@@ -80,10 +85,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn adc_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.adc(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn adc_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.adc(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a adc instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .adc_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn adc_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.adc(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a adc instruction that use an absolute address.
@@ -96,8 +115,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn adc_addr(&mut self, address_name: &str) -> &mut Self {
-        self.adc(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn adc_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.adc(AddressMode::Absolute(address.into()))
     }
 
     /// Record a adc instruction that use an absolute address with an offset.
@@ -129,8 +148,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn adc_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.adc(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn adc_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.adc(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a adc instructon that use an absolute address with y-register as indexer.
@@ -144,8 +163,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn adc_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.adc(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn adc_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.adc(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a adc instruction that uses indexed indirect addressing mode.
@@ -159,8 +178,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn adc_ind_x(&mut self, address_name: &str) -> &mut Self {
-        self.adc(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
+    pub fn adc_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.adc(AddressMode::IndexedIndirect(address.into()))
     }
 
     /// Record a adc instruction that uses indirect indexed addressing mode.
@@ -174,8 +193,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn adc_ind_y(&mut self, address_name: &str) -> &mut Self {
-        self.adc(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
+    pub fn adc_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.adc(AddressMode::IndirectIndexed(address.into()))
     }
 
     /// Record a new and instruction with the given addressing mode.
@@ -206,10 +225,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn and_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.and(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn and_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.and(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a and instruction with higher byte of an address.
@@ -222,10 +239,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn and_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.and(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn and_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.and(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a and instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .and_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn and_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.and(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a and instruction that use an absolute address.
@@ -238,8 +269,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn and_addr(&mut self, address_name: &str) -> &mut Self {
-        self.and(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn and_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.and(AddressMode::Absolute(address.into()))
     }
 
     /// Record a and instruction that use an absolute address with an offset.
@@ -271,8 +302,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn and_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.and(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn and_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.and(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a and instructon that use an absolute address with y-register as indexer.
@@ -286,8 +317,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn and_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.and(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn and_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.and(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a and instruction that uses indexed indirect addressing mode.
@@ -301,8 +332,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn and_ind_x(&mut self, address_name: &str) -> &mut Self {
-        self.and(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
+    pub fn and_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.and(AddressMode::IndexedIndirect(address.into()))
     }
 
     /// Record a and instruction that uses indirect indexed addressing mode.
@@ -316,8 +347,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn and_ind_y(&mut self, address_name: &str) -> &mut Self {
-        self.and(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
+    pub fn and_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.and(AddressMode::IndirectIndexed(address.into()))
     }
 
     /// Record a new asl instruction with the given addressing mode.
@@ -349,8 +380,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn asl_addr(&mut self, address_name: &str) -> &mut Self {
-        self.asl(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn asl_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.asl(AddressMode::Absolute(address.into()))
     }
 
     /// Record a asl instruction that use an absolute address with an offset.
@@ -382,8 +413,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn asl_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.asl(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn asl_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.asl(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a new bcc instruction with the given addressing mode.
@@ -402,8 +433,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn bcc_addr(&mut self, address_name: &str) -> &mut Self {
-        self.bcc(AddressMode::Relative(AddressReference::new(address_name)))
+    pub fn bcc_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.bcc(AddressMode::Relative(address.into()))
     }
 
     /// Record a bcc instruction that use a relative address with an offset.
@@ -440,8 +471,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn bcs_addr(&mut self, address_name: &str) -> &mut Self {
-        self.bcs(AddressMode::Relative(AddressReference::new(address_name)))
+    pub fn bcs_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.bcs(AddressMode::Relative(address.into()))
     }
 
     /// Record a bcs instruction that use a relative address with an offset.
@@ -478,8 +509,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn beq_addr(&mut self, address_name: &str) -> &mut Self {
-        self.beq(AddressMode::Relative(AddressReference::new(address_name)))
+    pub fn beq_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.beq(AddressMode::Relative(address.into()))
     }
 
     /// Record a beq instruction that use a relative address with an offset.
@@ -516,8 +547,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn bit_addr(&mut self, address_name: &str) -> &mut Self {
-        self.bit(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn bit_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.bit(AddressMode::Absolute(address.into()))
     }
 
     /// Record a bit instruction that use an absolute address with an offset.
@@ -554,8 +585,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn bmi_addr(&mut self, address_name: &str) -> &mut Self {
-        self.bmi(AddressMode::Relative(AddressReference::new(address_name)))
+    pub fn bmi_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.bmi(AddressMode::Relative(address.into()))
     }
 
     /// Record a bmi instruction that use a relative address with an offset.
@@ -592,8 +623,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn bne_addr(&mut self, address_name: &str) -> &mut Self {
-        self.bne(AddressMode::Relative(AddressReference::new(address_name)))
+    pub fn bne_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.bne(AddressMode::Relative(address.into()))
     }
 
     /// Record a bne instruction that use a relative address with an offset.
@@ -630,8 +661,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn bpl_addr(&mut self, address_name: &str) -> &mut Self {
-        self.bpl(AddressMode::Relative(AddressReference::new(address_name)))
+    pub fn bpl_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.bpl(AddressMode::Relative(address.into()))
     }
 
     /// Record a bpl instruction that use a relative address with an offset.
@@ -682,8 +713,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn bvc_addr(&mut self, address_name: &str) -> &mut Self {
-        self.bvc(AddressMode::Relative(AddressReference::new(address_name)))
+    pub fn bvc_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.bvc(AddressMode::Relative(address.into()))
     }
 
     /// Record a bvc instruction that use a relative address with an offset.
@@ -720,8 +751,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn bvs_addr(&mut self, address_name: &str) -> &mut Self {
-        self.bvs(AddressMode::Relative(AddressReference::new(address_name)))
+    pub fn bvs_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.bvs(AddressMode::Relative(address.into()))
     }
 
     /// Record a bvs instruction that use a relative address with an offset.
@@ -826,10 +857,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn cmp_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.cmp(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn cmp_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cmp(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a cmp instruction with higher byte of an address.
@@ -842,10 +871,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn cmp_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.cmp(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn cmp_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cmp(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a cmp instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .cmp_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn cmp_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.cmp(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a cmp instruction that use an absolute address.
@@ -858,8 +901,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn cmp_addr(&mut self, address_name: &str) -> &mut Self {
-        self.cmp(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn cmp_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cmp(AddressMode::Absolute(address.into()))
     }
 
     /// Record a cmp instruction that use an absolute address with an offset.
@@ -891,8 +934,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn cmp_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.cmp(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn cmp_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cmp(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a cmp instructon that use an absolute address with y-register as indexer.
@@ -906,8 +949,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn cmp_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.cmp(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn cmp_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cmp(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a cmp instruction that uses indexed indirect addressing mode.
@@ -921,8 +964,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn cmp_ind_x(&mut self, address_name: &str) -> &mut Self {
-        self.cmp(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
+    pub fn cmp_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cmp(AddressMode::IndexedIndirect(address.into()))
     }
 
     /// Record a cmp instruction that uses indirect indexed addressing mode.
@@ -936,8 +979,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn cmp_ind_y(&mut self, address_name: &str) -> &mut Self {
-        self.cmp(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
+    pub fn cmp_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cmp(AddressMode::IndirectIndexed(address.into()))
     }
 
     /// Record a new cpx instruction with the given addressing mode.
@@ -968,10 +1011,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn cpx_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.cpx(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn cpx_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cpx(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a cpx instruction with higher byte of an address.
@@ -984,10 +1025,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn cpx_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.cpx(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn cpx_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cpx(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a cpx instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .cpx_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn cpx_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.cpx(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a cpx instruction that use an absolute address.
@@ -1000,8 +1055,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn cpx_addr(&mut self, address_name: &str) -> &mut Self {
-        self.cpx(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn cpx_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cpx(AddressMode::Absolute(address.into()))
     }
 
     /// Record a cpx instruction that use an absolute address with an offset.
@@ -1050,10 +1105,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn cpy_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.cpy(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn cpy_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cpy(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a cpy instruction with higher byte of an address.
@@ -1066,10 +1119,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn cpy_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.cpy(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn cpy_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cpy(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a cpy instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .cpy_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn cpy_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.cpy(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a cpy instruction that use an absolute address.
@@ -1082,8 +1149,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn cpy_addr(&mut self, address_name: &str) -> &mut Self {
-        self.cpy(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn cpy_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.cpy(AddressMode::Absolute(address.into()))
     }
 
     /// Record a cpy instruction that use an absolute address with an offset.
@@ -1120,8 +1187,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn dec_addr(&mut self, address_name: &str) -> &mut Self {
-        self.dec(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn dec_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.dec(AddressMode::Absolute(address.into()))
     }
 
     /// Record a dec instruction that use an absolute address with an offset.
@@ -1153,8 +1220,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn dec_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.dec(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn dec_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.dec(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a new dex instruction (addressing mode is implied).
@@ -1213,10 +1280,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn eor_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.eor(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn eor_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.eor(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a eor instruction with higher byte of an address.
@@ -1229,10 +1294,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn eor_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.eor(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn eor_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.eor(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a eor instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .eor_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn eor_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.eor(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a eor instruction that use an absolute address.
@@ -1245,8 +1324,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn eor_addr(&mut self, address_name: &str) -> &mut Self {
-        self.eor(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn eor_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.eor(AddressMode::Absolute(address.into()))
     }
 
     /// Record a eor instruction that use an absolute address with an offset.
@@ -1278,8 +1357,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn eor_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.eor(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn eor_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.eor(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a eor instructon that use an absolute address with y-register as indexer.
@@ -1293,8 +1372,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn eor_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.eor(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn eor_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.eor(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a eor instruction that uses indexed indirect addressing mode.
@@ -1308,8 +1387,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn eor_ind_x(&mut self, address_name: &str) -> &mut Self {
-        self.eor(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
+    pub fn eor_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.eor(AddressMode::IndexedIndirect(address.into()))
     }
 
     /// Record a eor instruction that uses indirect indexed addressing mode.
@@ -1323,8 +1402,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn eor_ind_y(&mut self, address_name: &str) -> &mut Self {
-        self.eor(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
+    pub fn eor_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.eor(AddressMode::IndirectIndexed(address.into()))
     }
 
     /// Record a new inc instruction with the given addressing mode.
@@ -1343,8 +1422,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn inc_addr(&mut self, address_name: &str) -> &mut Self {
-        self.inc(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn inc_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.inc(AddressMode::Absolute(address.into()))
     }
 
     /// Record a inc instruction that use an absolute address with an offset.
@@ -1376,8 +1455,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn inc_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.inc(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn inc_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.inc(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a new inx instruction (addressing mode is implied).
@@ -1424,8 +1503,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn jmp_addr(&mut self, address_name: &str) -> &mut Self {
-        self.jmp(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn jmp_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.jmp(AddressMode::Absolute(address.into()))
     }
 
     /// Record a jmp instruction that use an absolute address with an offset.
@@ -1456,8 +1535,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn jmp_ind(&mut self, address_name: &str) -> &mut Self {
-        self.jmp(AddressMode::Indirect(AddressReference::new(address_name)))
+    pub fn jmp_ind(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.jmp(AddressMode::Indirect(address.into()))
     }
 
     /// Record a new jsr instruction with the given addressing mode.
@@ -1476,8 +1555,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn jsr_addr(&mut self, address_name: &str) -> &mut Self {
-        self.jsr(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn jsr_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.jsr(AddressMode::Absolute(address.into()))
     }
 
     /// Record a jsr instruction that use an absolute address with an offset.
@@ -1526,10 +1605,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn lda_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.lda(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn lda_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lda(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a lda instruction with higher byte of an address.
@@ -1542,10 +1619,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn lda_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.lda(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn lda_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lda(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a lda instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .lda_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn lda_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.lda(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a lda instruction that use an absolute address.
@@ -1558,8 +1649,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn lda_addr(&mut self, address_name: &str) -> &mut Self {
-        self.lda(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn lda_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lda(AddressMode::Absolute(address.into()))
     }
 
     /// Record a lda instruction that use an absolute address with an offset.
@@ -1591,8 +1682,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn lda_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.lda(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn lda_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lda(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a lda instructon that use an absolute address with y-register as indexer.
@@ -1606,8 +1697,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn lda_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.lda(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn lda_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lda(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a lda instruction that uses indexed indirect addressing mode.
@@ -1621,8 +1712,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn lda_ind_x(&mut self, address_name: &str) -> &mut Self {
-        self.lda(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
+    pub fn lda_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lda(AddressMode::IndexedIndirect(address.into()))
     }
 
     /// Record a lda instruction that uses indirect indexed addressing mode.
@@ -1636,8 +1727,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn lda_ind_y(&mut self, address_name: &str) -> &mut Self {
-        self.lda(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
+    pub fn lda_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lda(AddressMode::IndirectIndexed(address.into()))
     }
 
     /// Record a new ldx instruction with the given addressing mode.
@@ -1668,10 +1759,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn ldx_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.ldx(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn ldx_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ldx(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a ldx instruction with higher byte of an address.
@@ -1684,10 +1773,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn ldx_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.ldx(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn ldx_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ldx(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a ldx instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn ldx_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.ldx(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a ldx instruction that use an absolute address.
@@ -1700,8 +1803,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ldx_addr(&mut self, address_name: &str) -> &mut Self {
-        self.ldx(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn ldx_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ldx(AddressMode::Absolute(address.into()))
     }
 
     /// Record a ldx instruction that use an absolute address with an offset.
@@ -1733,8 +1836,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ldx_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.ldx(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn ldx_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ldx(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a new ldy instruction with the given addressing mode.
@@ -1765,10 +1868,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn ldy_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.ldy(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn ldy_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ldy(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a ldy instruction with higher byte of an address.
@@ -1781,10 +1882,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn ldy_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.ldy(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn ldy_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ldy(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a ldy instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn ldy_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.ldy(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a ldy instruction that use an absolute address.
@@ -1797,8 +1912,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ldy_addr(&mut self, address_name: &str) -> &mut Self {
-        self.ldy(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn ldy_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ldy(AddressMode::Absolute(address.into()))
     }
 
     /// Record a ldy instruction that use an absolute address with an offset.
@@ -1830,8 +1945,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ldy_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.ldy(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn ldy_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ldy(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a new lsr instruction with the given addressing mode.
@@ -1863,8 +1978,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn lsr_addr(&mut self, address_name: &str) -> &mut Self {
-        self.lsr(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn lsr_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lsr(AddressMode::Absolute(address.into()))
     }
 
     /// Record a lsr instruction that use an absolute address with an offset.
@@ -1896,8 +2011,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn lsr_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.lsr(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn lsr_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lsr(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a new nop instruction (addressing mode is implied).
@@ -1942,10 +2057,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn ora_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.ora(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn ora_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ora(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a ora instruction with higher byte of an address.
@@ -1958,10 +2071,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn ora_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.ora(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn ora_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ora(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a ora instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ora_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn ora_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.ora(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a ora instruction that use an absolute address.
@@ -1974,8 +2101,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ora_addr(&mut self, address_name: &str) -> &mut Self {
-        self.ora(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn ora_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ora(AddressMode::Absolute(address.into()))
     }
 
     /// Record a ora instruction that use an absolute address with an offset.
@@ -2007,8 +2134,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ora_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.ora(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn ora_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ora(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a ora instructon that use an absolute address with y-register as indexer.
@@ -2022,8 +2149,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ora_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.ora(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn ora_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ora(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a ora instruction that uses indexed indirect addressing mode.
@@ -2037,8 +2164,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ora_ind_x(&mut self, address_name: &str) -> &mut Self {
-        self.ora(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
+    pub fn ora_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ora(AddressMode::IndexedIndirect(address.into()))
     }
 
     /// Record a ora instruction that uses indirect indexed addressing mode.
@@ -2052,8 +2179,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ora_ind_y(&mut self, address_name: &str) -> &mut Self {
-        self.ora(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
+    pub fn ora_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ora(AddressMode::IndirectIndexed(address.into()))
     }
 
     /// Record a new pha instruction (addressing mode is implied).
@@ -2141,8 +2268,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn rol_addr(&mut self, address_name: &str) -> &mut Self {
-        self.rol(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn rol_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rol(AddressMode::Absolute(address.into()))
     }
 
     /// Record a rol instruction that use an absolute address with an offset.
@@ -2174,8 +2301,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn rol_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.rol(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn rol_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rol(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a new ror instruction with the given addressing mode.
@@ -2207,8 +2334,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ror_addr(&mut self, address_name: &str) -> &mut Self {
-        self.ror(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn ror_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ror(AddressMode::Absolute(address.into()))
     }
 
     /// Record a ror instruction that use an absolute address with an offset.
@@ -2240,8 +2367,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn ror_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.ror(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn ror_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.ror(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a new rti instruction (addressing mode is implied).
@@ -2300,10 +2427,8 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn sbc_imm_low(&mut self, address_name: &str) -> &mut Self {
-        self.sbc(AddressMode::Immediate(Immediate::Low(AddressReference::new(
-            address_name,
-        ))))
+    pub fn sbc_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbc(AddressMode::Immediate(Immediate::Low(address.into())))
     }
 
     /// Record a sbc instruction with higher byte of an address.
@@ -2316,10 +2441,24 @@ impl InstructionBuilder {
     ///     .label("test_data")
     ///     .build();
     /// ```
-    pub fn sbc_imm_high(&mut self, address_name: &str) -> &mut Self {
-        self.sbc(AddressMode::Immediate(Immediate::High(AddressReference::new(
-            address_name,
-        ))))
+    pub fn sbc_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbc(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a sbc instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sbc_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn sbc_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.sbc(AddressMode::Immediate(Immediate::Expression(expression)))
     }
 
     /// Record a sbc instruction that use an absolute address.
@@ -2332,8 +2471,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sbc_addr(&mut self, address_name: &str) -> &mut Self {
-        self.sbc(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn sbc_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbc(AddressMode::Absolute(address.into()))
     }
 
     /// Record a sbc instruction that use an absolute address with an offset.
@@ -2365,8 +2504,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sbc_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.sbc(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn sbc_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbc(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a sbc instructon that use an absolute address with y-register as indexer.
@@ -2380,8 +2519,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sbc_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.sbc(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn sbc_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbc(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a sbc instruction that uses indexed indirect addressing mode.
@@ -2395,8 +2534,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sbc_ind_x(&mut self, address_name: &str) -> &mut Self {
-        self.sbc(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
+    pub fn sbc_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbc(AddressMode::IndexedIndirect(address.into()))
     }
 
     /// Record a sbc instruction that uses indirect indexed addressing mode.
@@ -2410,8 +2549,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sbc_ind_y(&mut self, address_name: &str) -> &mut Self {
-        self.sbc(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
+    pub fn sbc_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbc(AddressMode::IndirectIndexed(address.into()))
     }
 
     /// Record a new sec instruction (addressing mode is implied).
@@ -2472,8 +2611,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sta_addr(&mut self, address_name: &str) -> &mut Self {
-        self.sta(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn sta_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sta(AddressMode::Absolute(address.into()))
     }
 
     /// Record a sta instruction that use an absolute address with an offset.
@@ -2505,8 +2644,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sta_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.sta(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn sta_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sta(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a sta instructon that use an absolute address with y-register as indexer.
@@ -2520,8 +2659,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sta_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.sta(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn sta_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sta(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a sta instruction that uses indexed indirect addressing mode.
@@ -2535,8 +2674,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sta_ind_x(&mut self, address_name: &str) -> &mut Self {
-        self.sta(AddressMode::IndexedIndirect(AddressReference::new(address_name)))
+    pub fn sta_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sta(AddressMode::IndexedIndirect(address.into()))
     }
 
     /// Record a sta instruction that uses indirect indexed addressing mode.
@@ -2550,8 +2689,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sta_ind_y(&mut self, address_name: &str) -> &mut Self {
-        self.sta(AddressMode::IndirectIndexed(AddressReference::new(address_name)))
+    pub fn sta_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sta(AddressMode::IndirectIndexed(address.into()))
     }
 
     /// Record a new stx instruction with the given addressing mode.
@@ -2570,8 +2709,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn stx_addr(&mut self, address_name: &str) -> &mut Self {
-        self.stx(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn stx_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.stx(AddressMode::Absolute(address.into()))
     }
 
     /// Record a stx instruction that use an absolute address with an offset.
@@ -2603,8 +2742,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn stx_addr_y(&mut self, address_name: &str) -> &mut Self {
-        self.stx(AddressMode::AbsoluteY(AddressReference::new(address_name)))
+    pub fn stx_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.stx(AddressMode::AbsoluteY(address.into()))
     }
 
     /// Record a new sty instruction with the given addressing mode.
@@ -2623,8 +2762,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sty_addr(&mut self, address_name: &str) -> &mut Self {
-        self.sty(AddressMode::Absolute(AddressReference::new(address_name)))
+    pub fn sty_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sty(AddressMode::Absolute(address.into()))
     }
 
     /// Record a sty instruction that use an absolute address with an offset.
@@ -2656,8 +2795,8 @@ impl InstructionBuilder {
     ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn sty_addr_x(&mut self, address_name: &str) -> &mut Self {
-        self.sty(AddressMode::AbsoluteX(AddressReference::new(address_name)))
+    pub fn sty_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sty(AddressMode::AbsoluteX(address.into()))
     }
 
     /// Record a new tax instruction (addressing mode is implied).
@@ -2744,83 +2883,1420 @@ impl InstructionBuilder {
         self
     }
 
-    /// Record some raw data (bytes) in the instruction stream.
+    /// Record a new slo instruction with the given addressing mode.
+    fn slo(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::SLO, addressing_mode);
+        self
+    }
+
+    /// Record a slo instruction that use an absolute address.
     ///
     /// # Example
     /// ```
     /// use c64_assembler::builder::InstructionBuilder;
     /// let instructions = InstructionBuilder::default()
-    ///     .label("my_data")
-    ///     .raw(&[0x42, 0xDE, 0xAD])
+    ///     .slo_addr("test_label")
+    ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn raw(&mut self, data: &[u8]) -> &mut Self {
-        self.add_instruction(Operation::Raw(Vec::from(data)), AddressMode::Implied);
-        self
+    pub fn slo_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.slo(AddressMode::Absolute(address.into()))
     }
 
-    /// Record a label into the instruction stream.
+    /// Record a slo instruction that use an absolute address with an offset.
+    /// Offset is in bytes.
     ///
     /// # Example
     /// ```
     /// use c64_assembler::builder::InstructionBuilder;
     /// let instructions = InstructionBuilder::default()
-    ///     .label("my_data")
-    ///     .raw(&[0x42, 0xDE, 0xAD])
+    ///     .slo_addr_offs("test_label", 8)
+    ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn label(&mut self, label: &str) -> &mut Self {
-        self.add_instruction(Operation::Label(label.to_string()), AddressMode::Implied);
-        self
+    pub fn slo_addr_offs(&mut self, address_name: &str, offset: Address) -> &mut Self {
+        self.slo(AddressMode::Absolute(AddressReference::with_offset(
+            address_name,
+            offset,
+        )))
     }
 
-    /// Add a comment to the last instruction.
+    /// Record a slo instructon that use an absolute address with x-register as indexer.
     ///
     /// # Example
     /// ```
     /// use c64_assembler::builder::InstructionBuilder;
     /// let instructions = InstructionBuilder::default()
-    ///     .label("frame_number").comment("Current frame number")
-    ///     .raw(&[0x00, 0x00]).comment("Frames are counted from 0")
+    ///     .ldx_imm(0x08)
+    ///     .slo_addr_x("test_label")
+    ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn comment(&mut self, comment: &str) -> &mut Self {
-        self.instructions
-            .instructions
-            .last_mut()
-            .unwrap()
-            .comments
-            .push(comment.to_string());
+    pub fn slo_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.slo(AddressMode::AbsoluteX(address.into()))
+    }
+
+    /// Record a slo instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .slo_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn slo_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.slo(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record a slo instruction that uses indexed indirect addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .slo_ind_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn slo_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.slo(AddressMode::IndexedIndirect(address.into()))
+    }
+
+    /// Record a slo instruction that uses indirect indexed addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .slo_ind_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn slo_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.slo(AddressMode::IndirectIndexed(address.into()))
+    }
+
+    /// Record a new rla instruction with the given addressing mode.
+    fn rla(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::RLA, addressing_mode);
         self
     }
 
-    /// Add a basic program, when run will start the instructions recorded right after.
+    /// Record a rla instruction that use an absolute address.
     ///
-    /// ```basic
-    /// 10 SYS 2062
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .rla_addr("test_label")
+    ///     .label("test_label")
+    ///     .build();
     /// ```
+    pub fn rla_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rla(AddressMode::Absolute(address.into()))
+    }
+
+    /// Record a rla instruction that use an absolute address with an offset.
+    /// Offset is in bytes.
     ///
-    /// NOTE: Application entry point should be 0x0800 and add_basic_header must be
-    /// called as first instruction in the first module.
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .rla_addr_offs("test_label", 8)
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rla_addr_offs(&mut self, address_name: &str, offset: Address) -> &mut Self {
+        self.rla(AddressMode::Absolute(AddressReference::with_offset(
+            address_name,
+            offset,
+        )))
+    }
+
+    /// Record a rla instructon that use an absolute address with x-register as indexer.
     ///
     /// # Example
     /// ```
     /// use c64_assembler::builder::InstructionBuilder;
     /// let instructions = InstructionBuilder::default()
-    ///     .add_basic_header()
-    ///     .rts()
+    ///     .ldx_imm(0x08)
+    ///     .rla_addr_x("test_label")
+    ///     .label("test_label")
     ///     .build();
     /// ```
-    pub fn add_basic_header(&mut self) -> &mut Self {
-        /* Basic line header */
-        self.raw(&[0x00, 0x0c, 0x08])
-            .comment("New basic line")
-            /* 10 SYS 2062 */
-            .raw(&[0x0a, 0x00, 0x9e, 0x20, 0x32, 0x30, 0x36, 0x32])
-            .comment("10 SYS 2062")
-            /* Basic line heaer */
-            .raw(&[0x00, 0x00, 0x00])
-            .comment("End basic program")
+    pub fn rla_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rla(AddressMode::AbsoluteX(address.into()))
+    }
+
+    /// Record a rla instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .rla_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rla_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rla(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record a rla instruction that uses indexed indirect addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .rla_ind_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rla_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rla(AddressMode::IndexedIndirect(address.into()))
+    }
+
+    /// Record a rla instruction that uses indirect indexed addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .rla_ind_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rla_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rla(AddressMode::IndirectIndexed(address.into()))
+    }
+
+    /// Record a new sre instruction with the given addressing mode.
+    fn sre(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::SRE, addressing_mode);
+        self
+    }
+
+    /// Record a sre instruction that use an absolute address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sre_addr("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sre_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sre(AddressMode::Absolute(address.into()))
+    }
+
+    /// Record a sre instruction that use an absolute address with an offset.
+    /// Offset is in bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sre_addr_offs("test_label", 8)
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sre_addr_offs(&mut self, address_name: &str, offset: Address) -> &mut Self {
+        self.sre(AddressMode::Absolute(AddressReference::with_offset(
+            address_name,
+            offset,
+        )))
+    }
+
+    /// Record a sre instructon that use an absolute address with x-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .sre_addr_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sre_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sre(AddressMode::AbsoluteX(address.into()))
+    }
+
+    /// Record a sre instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .sre_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sre_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sre(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record a sre instruction that uses indexed indirect addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .sre_ind_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sre_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sre(AddressMode::IndexedIndirect(address.into()))
+    }
+
+    /// Record a sre instruction that uses indirect indexed addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .sre_ind_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sre_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sre(AddressMode::IndirectIndexed(address.into()))
+    }
+
+    /// Record a new rra instruction with the given addressing mode.
+    fn rra(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::RRA, addressing_mode);
+        self
+    }
+
+    /// Record a rra instruction that use an absolute address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .rra_addr("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rra_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rra(AddressMode::Absolute(address.into()))
+    }
+
+    /// Record a rra instruction that use an absolute address with an offset.
+    /// Offset is in bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .rra_addr_offs("test_label", 8)
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rra_addr_offs(&mut self, address_name: &str, offset: Address) -> &mut Self {
+        self.rra(AddressMode::Absolute(AddressReference::with_offset(
+            address_name,
+            offset,
+        )))
+    }
+
+    /// Record a rra instructon that use an absolute address with x-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .rra_addr_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rra_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rra(AddressMode::AbsoluteX(address.into()))
+    }
+
+    /// Record a rra instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .rra_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rra_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rra(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record a rra instruction that uses indexed indirect addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .rra_ind_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rra_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rra(AddressMode::IndexedIndirect(address.into()))
+    }
+
+    /// Record a rra instruction that uses indirect indexed addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .rra_ind_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn rra_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.rra(AddressMode::IndirectIndexed(address.into()))
+    }
+
+    /// Record a new sax instruction with the given addressing mode.
+    fn sax(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::SAX, addressing_mode);
+        self
+    }
+
+    /// Record a sax instruction that use an absolute address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sax_addr("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sax_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sax(AddressMode::Absolute(address.into()))
+    }
+
+    /// Record a sax instruction that use an absolute address with an offset.
+    /// Offset is in bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sax_addr_offs("test_label", 8)
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sax_addr_offs(&mut self, address_name: &str, offset: Address) -> &mut Self {
+        self.sax(AddressMode::Absolute(AddressReference::with_offset(
+            address_name,
+            offset,
+        )))
+    }
+
+    /// Record a sax instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .sax_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sax_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sax(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record a sax instruction that uses indexed indirect addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .sax_ind_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn sax_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sax(AddressMode::IndexedIndirect(address.into()))
+    }
+
+    /// Record a new lax instruction with the given addressing mode.
+    fn lax(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::LAX, addressing_mode);
+        self
+    }
+
+    /// Record a lax instruction that use an absolute address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .lax_addr("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn lax_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lax(AddressMode::Absolute(address.into()))
+    }
+
+    /// Record a lax instruction that use an absolute address with an offset.
+    /// Offset is in bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .lax_addr_offs("test_label", 8)
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn lax_addr_offs(&mut self, address_name: &str, offset: Address) -> &mut Self {
+        self.lax(AddressMode::Absolute(AddressReference::with_offset(
+            address_name,
+            offset,
+        )))
+    }
+
+    /// Record a lax instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .lax_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn lax_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lax(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record a lax instruction that uses indexed indirect addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .lax_ind_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn lax_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lax(AddressMode::IndexedIndirect(address.into()))
+    }
+
+    /// Record a lax instruction that uses indirect indexed addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .lax_ind_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn lax_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.lax(AddressMode::IndirectIndexed(address.into()))
+    }
+
+    /// Record a new dcp instruction with the given addressing mode.
+    fn dcp(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::DCP, addressing_mode);
+        self
+    }
+
+    /// Record a dcp instruction that use an absolute address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .dcp_addr("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn dcp_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.dcp(AddressMode::Absolute(address.into()))
+    }
+
+    /// Record a dcp instruction that use an absolute address with an offset.
+    /// Offset is in bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .dcp_addr_offs("test_label", 8)
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn dcp_addr_offs(&mut self, address_name: &str, offset: Address) -> &mut Self {
+        self.dcp(AddressMode::Absolute(AddressReference::with_offset(
+            address_name,
+            offset,
+        )))
+    }
+
+    /// Record a dcp instructon that use an absolute address with x-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .dcp_addr_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn dcp_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.dcp(AddressMode::AbsoluteX(address.into()))
+    }
+
+    /// Record a dcp instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .dcp_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn dcp_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.dcp(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record a dcp instruction that uses indexed indirect addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .dcp_ind_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn dcp_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.dcp(AddressMode::IndexedIndirect(address.into()))
+    }
+
+    /// Record a dcp instruction that uses indirect indexed addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .dcp_ind_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn dcp_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.dcp(AddressMode::IndirectIndexed(address.into()))
+    }
+
+    /// Record a new isc instruction with the given addressing mode.
+    fn isc(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::ISC, addressing_mode);
+        self
+    }
+
+    /// Record a isc instruction that use an absolute address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .isc_addr("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn isc_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.isc(AddressMode::Absolute(address.into()))
+    }
+
+    /// Record a isc instruction that use an absolute address with an offset.
+    /// Offset is in bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .isc_addr_offs("test_label", 8)
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn isc_addr_offs(&mut self, address_name: &str, offset: Address) -> &mut Self {
+        self.isc(AddressMode::Absolute(AddressReference::with_offset(
+            address_name,
+            offset,
+        )))
+    }
+
+    /// Record a isc instructon that use an absolute address with x-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .isc_addr_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn isc_addr_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.isc(AddressMode::AbsoluteX(address.into()))
+    }
+
+    /// Record a isc instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .isc_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn isc_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.isc(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record a isc instruction that uses indexed indirect addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .isc_ind_x("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn isc_ind_x(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.isc(AddressMode::IndexedIndirect(address.into()))
+    }
+
+    /// Record a isc instruction that uses indirect indexed addressing mode.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .isc_ind_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn isc_ind_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.isc(AddressMode::IndirectIndexed(address.into()))
+    }
+
+    /// Record a new anc instruction with the given addressing mode.
+    fn anc(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::ANC, addressing_mode);
+        self
+    }
+
+    /// Record a anc instruction with data (byte).
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .anc_imm(0xC0)
+    ///     .build();
+    /// ```
+    pub fn anc_imm(&mut self, byte: u8) -> &mut Self {
+        self.anc(AddressMode::Immediate(Immediate::Byte(byte)))
+    }
+
+    /// Record a anc instruction with lower byte of an address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .anc_imm_low("test_data")
+    ///     .label("test_data")
+    ///     .build();
+    /// ```
+    pub fn anc_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.anc(AddressMode::Immediate(Immediate::Low(address.into())))
+    }
+
+    /// Record a anc instruction with higher byte of an address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .anc_imm_high("test_data")
+    ///     .label("test_data")
+    ///     .build();
+    /// ```
+    pub fn anc_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.anc(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a anc instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .anc_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn anc_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.anc(AddressMode::Immediate(Immediate::Expression(expression)))
+    }
+
+    /// Record a new alr instruction with the given addressing mode.
+    fn alr(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::ALR, addressing_mode);
+        self
+    }
+
+    /// Record a alr instruction with data (byte).
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .alr_imm(0xC0)
+    ///     .build();
+    /// ```
+    pub fn alr_imm(&mut self, byte: u8) -> &mut Self {
+        self.alr(AddressMode::Immediate(Immediate::Byte(byte)))
+    }
+
+    /// Record a alr instruction with lower byte of an address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .alr_imm_low("test_data")
+    ///     .label("test_data")
+    ///     .build();
+    /// ```
+    pub fn alr_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.alr(AddressMode::Immediate(Immediate::Low(address.into())))
+    }
+
+    /// Record a alr instruction with higher byte of an address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .alr_imm_high("test_data")
+    ///     .label("test_data")
+    ///     .build();
+    /// ```
+    pub fn alr_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.alr(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a alr instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .alr_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn alr_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.alr(AddressMode::Immediate(Immediate::Expression(expression)))
+    }
+
+    /// Record a new arr instruction with the given addressing mode.
+    fn arr(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::ARR, addressing_mode);
+        self
+    }
+
+    /// Record a arr instruction with data (byte).
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .arr_imm(0xC0)
+    ///     .build();
+    /// ```
+    pub fn arr_imm(&mut self, byte: u8) -> &mut Self {
+        self.arr(AddressMode::Immediate(Immediate::Byte(byte)))
+    }
+
+    /// Record a arr instruction with lower byte of an address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .arr_imm_low("test_data")
+    ///     .label("test_data")
+    ///     .build();
+    /// ```
+    pub fn arr_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.arr(AddressMode::Immediate(Immediate::Low(address.into())))
+    }
+
+    /// Record a arr instruction with higher byte of an address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .arr_imm_high("test_data")
+    ///     .label("test_data")
+    ///     .build();
+    /// ```
+    pub fn arr_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.arr(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a arr instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .arr_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn arr_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.arr(AddressMode::Immediate(Immediate::Expression(expression)))
+    }
+
+    /// Record a new sbx instruction with the given addressing mode.
+    fn sbx(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::SBX, addressing_mode);
+        self
+    }
+
+    /// Record a sbx instruction with data (byte).
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sbx_imm(0xC0)
+    ///     .build();
+    /// ```
+    pub fn sbx_imm(&mut self, byte: u8) -> &mut Self {
+        self.sbx(AddressMode::Immediate(Immediate::Byte(byte)))
+    }
+
+    /// Record a sbx instruction with lower byte of an address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sbx_imm_low("test_data")
+    ///     .label("test_data")
+    ///     .build();
+    /// ```
+    pub fn sbx_imm_low(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbx(AddressMode::Immediate(Immediate::Low(address.into())))
+    }
+
+    /// Record a sbx instruction with higher byte of an address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sbx_imm_high("test_data")
+    ///     .label("test_data")
+    ///     .build();
+    /// ```
+    pub fn sbx_imm_high(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.sbx(AddressMode::Immediate(Immediate::High(address.into())))
+    }
+
+    /// Record a sbx instruction with an expression that evaluates to a byte.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    /// let instructions = InstructionBuilder::default()
+    ///     .sbx_imm_expr(Expression::name("test_end") - Expression::name("test_start"))
+    ///     .label("test_start")
+    ///     .label("test_end")
+    ///     .build();
+    /// ```
+    pub fn sbx_imm_expr(&mut self, expression: Expression) -> &mut Self {
+        self.sbx(AddressMode::Immediate(Immediate::Expression(expression)))
+    }
+
+    /// Record a new las instruction with the given addressing mode.
+    fn las(&mut self, addressing_mode: AddressMode) -> &mut Self {
+        self.add_instruction(Operation::LAS, addressing_mode);
+        self
+    }
+
+    /// Record a las instructon that use an absolute address with y-register as indexer.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldy_imm(0x08)
+    ///     .las_addr_y("test_label")
+    ///     .label("test_label")
+    ///     .build();
+    /// ```
+    pub fn las_addr_y(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.las(AddressMode::AbsoluteY(address.into()))
+    }
+
+    /// Record some raw data (bytes) in the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("my_data")
+    ///     .raw(&[0x42, 0xDE, 0xAD])
+    ///     .build();
+    /// ```
+    pub fn raw(&mut self, data: &[u8]) -> &mut Self {
+        self.add_instruction(Operation::Raw(Vec::from(data)), AddressMode::Implied);
+        self
+    }
+
+    /// Record little-endian words in the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("my_data")
+    ///     .words(&[0x1234, 0xDEAD])
+    ///     .build();
+    /// ```
+    pub fn words(&mut self, words: &[u16]) -> &mut Self {
+        let words = words.iter().map(|word| Word::Value(*word)).collect();
+        self.add_instruction(Operation::Words(words), AddressMode::Implied);
+        self
+    }
+
+    /// Record the addresses of labels or defines as little-endian words in the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("vectors")
+    ///     .words_addr(&["irq", "nmi"])
+    ///     .label("irq")
+    ///     .rti()
+    ///     .label("nmi")
+    ///     .rti()
+    ///     .build();
+    /// ```
+    pub fn words_addr(&mut self, address_names: &[&str]) -> &mut Self {
+        let words = address_names
+            .iter()
+            .map(|address_name| Word::Address(AddressReference::new(address_name)))
+            .collect();
+        self.add_instruction(Operation::Words(words), AddressMode::Implied);
+        self
+    }
+
+    /// Record the low bytes of the addresses of labels or defines in the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("handlers_low")
+    ///     .low_bytes_addr(&["handler_a", "handler_b"])
+    ///     .label("handler_a")
+    ///     .rts()
+    ///     .label("handler_b")
+    ///     .rts()
+    ///     .build();
+    /// ```
+    pub fn low_bytes_addr(&mut self, address_names: &[&str]) -> &mut Self {
+        let address_references = address_names.iter().map(|name| AddressReference::new(name)).collect();
+        self.add_instruction(Operation::LowBytes(address_references), AddressMode::Implied);
+        self
+    }
+
+    /// Record the high bytes of the addresses of labels or defines in the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("handlers_high")
+    ///     .high_bytes_addr(&["handler_a", "handler_b"])
+    ///     .label("handler_a")
+    ///     .rts()
+    ///     .label("handler_b")
+    ///     .rts()
+    ///     .build();
+    /// ```
+    pub fn high_bytes_addr(&mut self, address_names: &[&str]) -> &mut Self {
+        let address_references = address_names.iter().map(|name| AddressReference::new(name)).collect();
+        self.add_instruction(Operation::HighBytes(address_references), AddressMode::Implied);
+        self
+    }
+
+    /// Record a split address table; the low bytes are labeled `{name}_low` and the high
+    /// bytes `{name}_high`.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x01)
+    ///     .lda_addr_x("handlers_low")
+    ///     .sta_addr("jump")
+    ///     .lda_addr_x("handlers_high")
+    ///     .sta_addr_offs("jump", 1)
+    ///     .jmp_ind("jump")
+    ///     .split_address_table("handlers", &["handler_a", "handler_b"])
+    ///     .label("jump")
+    ///     .words(&[0x0000])
+    ///     .label("handler_a")
+    ///     .rts()
+    ///     .label("handler_b")
+    ///     .rts()
+    ///     .build();
+    /// ```
+    pub fn split_address_table(&mut self, name: &str, address_names: &[&str]) -> &mut Self {
+        self.label(&format!("{name}_low"))
+            .low_bytes_addr(address_names)
+            .label(&format!("{name}_high"))
+            .high_bytes_addr(address_names)
+    }
+
+    /// Record a text encoded as PETSCII in the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("message")
+    ///     .text_petscii("hello world\n")
+    ///     .raw(&[0x00])
+    ///     .build();
+    /// ```
+    pub fn text_petscii(&mut self, text: &str) -> &mut Self {
+        self.add_instruction(
+            Operation::Text(text.to_string(), TextEncoding::Petscii),
+            AddressMode::Implied,
+        );
+        self
+    }
+
+    /// Record a text encoded as screen codes in the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("title")
+    ///     .text_screen_codes("game over")
+    ///     .build();
+    /// ```
+    pub fn text_screen_codes(&mut self, text: &str) -> &mut Self {
+        self.add_instruction(
+            Operation::Text(text.to_string(), TextEncoding::ScreenCode),
+            AddressMode::Implied,
+        );
+        self
+    }
+
+    /// Record the address as PETSCII decimal digits in the instruction stream.
+    ///
+    /// The digits are right aligned with spaces to 5 characters, so the size doesn't depend on
+    /// the address. Used by BASIC lines that refer to a label.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .raw(&[0x9E])
+    ///     .comment("SYS")
+    ///     .decimal_addr("main")
+    ///     .label("main")
+    ///     .rts()
+    ///     .build();
+    /// ```
+    pub fn decimal_addr(&mut self, address: impl Into<AddressReference>) -> &mut Self {
+        self.add_instruction(Operation::Decimal(address.into()), AddressMode::Implied);
+        self
+    }
+
+    /// Record the given byte `count` times in the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("buffer")
+    ///     .fill(40, 0x20)
+    ///     .build();
+    /// ```
+    pub fn fill(&mut self, count: Address, byte: u8) -> &mut Self {
+        self.add_instruction(Operation::Fill(count, byte), AddressMode::Implied);
+        self
+    }
+
+    /// Record zeros until the address is a multiple of the given alignment.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .align(0x0100)
+    ///     .label("page_aligned_table")
+    ///     .fill(0x0100, 0x00)
+    ///     .build();
+    /// ```
+    pub fn align(&mut self, alignment: Address) -> &mut Self {
+        self.add_instruction(Operation::Align(alignment), AddressMode::Implied);
+        self
+    }
+
+    /// Record a label into the instruction stream.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("my_data")
+    ///     .raw(&[0x42, 0xDE, 0xAD])
+    ///     .build();
+    /// ```
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.add_instruction(Operation::Label(label.to_string()), AddressMode::Implied);
+        self
+    }
+
+    /// Record a local label into the instruction stream.
+    ///
+    /// Local labels are only visible inside the function, or inside the module when recorded
+    /// in the instructions of the module. Refer to them by their name prefixed with a dot.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .ldx_imm(0x08)
+    ///     .local_label("loop")
+    ///     .dex()
+    ///     .bne_addr(".loop")
+    ///     .build();
+    /// ```
+    pub fn local_label(&mut self, label: &str) -> &mut Self {
+        self.label(&format!(".{label}"))
+    }
+
+    /// Record an anonymous label into the instruction stream.
+    ///
+    /// Refer to the previous anonymous label using `-` and to the next using `+`. Repeat the
+    /// sign to skip anonymous labels (`--`, `++`).
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .anonymous_label()
+    ///     .lda_addr("VIC2_RASTER")
+    ///     .bne_addr("-")
+    ///     .beq_addr("+")
+    ///     .nop()
+    ///     .anonymous_label()
+    ///     .rts()
+    ///     .build();
+    /// ```
+    pub fn anonymous_label(&mut self) -> &mut Self {
+        self.label("-")
+    }
+
+    /// Add a comment to the last instruction.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .label("frame_number").comment("Current frame number")
+    ///     .raw(&[0x00, 0x00]).comment("Frames are counted from 0")
+    ///     .build();
+    /// ```
+    pub fn comment(&mut self, comment: &str) -> &mut Self {
+        self.instructions
+            .instructions
+            .last_mut()
+            .unwrap()
+            .comments
+            .push(comment.to_string());
+        self
+    }
+
+    /// Record the location in the source code of the last instruction.
+    ///
+    /// Is used by the macros to report errors at the location of the instruction.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .rts().source_location(12, 5)
+    ///     .build();
+    /// ```
+    pub fn source_location(&mut self, line: usize, column: usize) -> &mut Self {
+        self.instructions.instructions.last_mut().unwrap().source = Some(SourceLocation { line, column });
+        self
+    }
+
+    /// Add a basic program, when run will start the instructions recorded right after.
+    ///
+    /// ```basic
+    /// 10 SYS 2062
+    /// ```
+    ///
+    /// NOTE: Application entry point should be 0x0800 and add_basic_header must be
+    /// called as first instruction in the first module. Use [Self::basic_stub] to start a
+    /// label at any load address.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::builder::InstructionBuilder;
+    /// let instructions = InstructionBuilder::default()
+    ///     .add_basic_header()
+    ///     .rts()
+    ///     .build();
+    /// ```
+    pub fn add_basic_header(&mut self) -> &mut Self {
+        /* Basic line header */
+        self.raw(&[0x00, 0x0c, 0x08])
+            .comment("New basic line")
+            /* 10 SYS 2062 */
+            .raw(&[0x0a, 0x00, 0x9e, 0x20, 0x32, 0x30, 0x36, 0x32])
+            .comment("10 SYS 2062")
+            /* Basic line heaer */
+            .raw(&[0x00, 0x00, 0x00])
+            .comment("End basic program")
+    }
+
+    /// Add a BASIC program that starts the machine code at the target label of the stub.
+    ///
+    /// ```basic
+    /// 10 SYS 2062
+    /// ```
+    ///
+    /// The `SYS` address is updated when the address of the label changes. Must be the first
+    /// instruction of the application; the links between the lines are computed from the
+    /// address the stub is placed at.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::basic::BasicStub;
+    /// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::generator::{Generator, ProgramGenerator};
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .entry_point(0x0801)
+    ///     .module(
+    ///         ModuleBuilder::default()
+    ///             .instructions(
+    ///                 InstructionBuilder::default()
+    ///                     .basic_stub(&BasicStub::new("main").line_number(2025))
+    ///                     .label("main")
+    ///                     .rts()
+    ///                     .build(),
+    ///             )
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// let program = ProgramGenerator::default().generate(application).unwrap();
+    /// // 2025 SYS 2062
+    /// assert_eq!(
+    ///     vec![0x01, 0x08, 0x0C, 0x08, 0xE9, 0x07, 0x9E, 0x20, 0x32, 0x30, 0x36, 0x32, 0x00, 0x00, 0x00, 0x60],
+    ///     program
+    /// );
+    /// ```
+    pub fn basic_stub(&mut self, stub: &BasicStub) -> &mut Self {
+        self.add_instruction(Operation::BasicStub(stub.clone()), AddressMode::Implied);
+        for line in stub.listing() {
+            self.comment(&line);
+        }
+        self
+    }
+
+    /// Add a tokenized BASIC program. The instructions should be placed at the load address.
+    ///
+    /// BASIC programs start at `$0801`, when the load address is `$0800` the program is
+    /// preceded by a zero byte. Each line is commented with its listing.
+    ///
+    /// # Example
+    /// ```
+    /// use c64_assembler::basic::BasicProgram;
+    /// use c64_assembler::builder::InstructionBuilder;
+    ///
+    /// let program = BasicProgram::parse("10 poke 53280,0\n20 sys 2064").unwrap();
+    /// let instructions = InstructionBuilder::default()
+    ///     .basic_program(&program, 0x0801)
+    ///     .build();
+    /// ```
+    pub fn basic_program(&mut self, program: &BasicProgram, load_address: Address) -> &mut Self {
+        let mut address = basic_program_start(load_address);
+        if address != load_address {
+            self.raw(&[0x00])
+                .comment(&format!("BASIC program starts at ${address:04X}"));
+        }
+        for line in &program.lines {
+            address = address.wrapping_add(line.byte_size());
+            self.raw(&line.bytes(address)).comment(&line.to_string());
+        }
+        self.raw(&[0x00, 0x00]).comment("End basic program")
     }
 
     /// Create [crate::Instructions] from this instance.
@@ -3,10 +3,14 @@
 mod application;
 mod finalize;
 mod function;
+mod instance;
 mod instruction;
 mod module;
+mod scope;
+mod variant;
 
 pub use application::*;
 pub use function::*;
 pub use instruction::*;
 pub use module::*;
+pub use variant::DEFAULT_VARIANT;
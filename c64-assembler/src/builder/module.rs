@@ -1,4 +1,7 @@
-use crate::{Function, Instructions, Module};
+use crate::{
+    memory::zeropage::{ZeropageKind, ZeropageVariable},
+    Function, Instructions, Module, ModuleParameter, ParameterKind,
+};
 
 #[derive(Default, Clone)]
 pub struct ModuleBuilder {
@@ -20,6 +23,65 @@ impl ModuleBuilder {
         self
     }
 
+    /// Place the module in the segment with the given name.
+    pub fn segment(&mut self, segment_name: &str) -> &mut Self {
+        self.module.segment = Some(segment_name.to_string());
+        self
+    }
+
+    /// Add a parameter that the application must bind when including the module.
+    ///
+    /// ```
+    /// use c64_assembler::builder::{InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::ParameterKind;
+    ///
+    /// let module = ModuleBuilder::default()
+    ///     .name("clear_screen")
+    ///     .parameter("SCREEN", ParameterKind::Address)
+    ///     .parameter("POINTER", ParameterKind::Zeropage)
+    ///     .instructions(InstructionBuilder::default().lda_imm_low("SCREEN").sta_addr("POINTER").build())
+    ///     .build();
+    /// ```
+    pub fn parameter(&mut self, name: &str, kind: ParameterKind) -> &mut Self {
+        self.module.parameters.push(ModuleParameter {
+            name: name.to_string(),
+            kind,
+        });
+        self
+    }
+
+    /// Request a zeropage variable.
+    ///
+    /// When the application is built the variable gets a free address from the
+    /// [crate::memory::zeropage::ZeropagePool] and is added as a define, so instructions can
+    /// refer to it by its name and use the zeropage addressing modes.
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::memory::zeropage::ZeropageKind;
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .module(
+    ///         ModuleBuilder::default()
+    ///             .name("clear_screen")
+    ///             .zeropage("counter", ZeropageKind::Byte)
+    ///             .zeropage("pointer", ZeropageKind::Pointer)
+    ///             .instructions(InstructionBuilder::default().lda_imm(0x00).sta_ind_y("pointer").build())
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(0x02, application.lookup_address(&"counter".to_string()).unwrap());
+    /// assert_eq!(0xFB, application.lookup_address(&"pointer".to_string()).unwrap());
+    /// ```
+    pub fn zeropage(&mut self, name: &str, kind: ZeropageKind) -> &mut Self {
+        self.module.zeropage.push(ZeropageVariable {
+            name: name.to_string(),
+            kind,
+        });
+        self
+    }
+
     pub fn build(&self) -> Module {
         self.module.clone()
     }
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    instruction::operation::Operation,
+    memory::label::{is_anonymous_label, is_local_label, is_scoped_label},
+    Application, Instructions,
+};
+
+/// Rename the local and anonymous labels to names that are unique in the application.
+///
+/// Local labels of a function are named `function__label`, local labels of a module
+/// `module__label` and anonymous labels `scope__anon_0`. A reference to a local label resolves
+/// to the function before the module. The function-local labels are remembered in
+/// [crate::Function::local_labels], so generators can write them as local labels again.
+pub(crate) fn resolve_scoped_labels(application: &mut Application) {
+    let mut names = application.address_lookup.keys().cloned().collect::<HashSet<_>>();
+    for module in &application.modules {
+        names.extend(label_names(&module.instructions));
+        for function in &module.functions {
+            names.insert(function.name.clone());
+            names.extend(label_names(&function.instructions));
+            for variant in &function.variants {
+                names.extend(label_names(&variant.instructions));
+            }
+        }
+    }
+
+    for (module_index, module) in application.modules.iter_mut().enumerate() {
+        let module_scope = scope_name(&module.name, module_index);
+        let module_labels = local_labels(&mut names, &module_scope, [&module.instructions]);
+        resolve(&mut module.instructions, &mut names, &module_scope, &[&module_labels]);
+
+        for function in &mut module.functions {
+            let function_scope = scope_name(&function.name, module_index);
+            let streams = std::iter::once(&function.instructions)
+                .chain(function.variants.iter().map(|variant| &variant.instructions));
+            let function_labels = local_labels(&mut names, &function_scope, streams);
+            let labels = [&function_labels, &module_labels];
+            resolve(&mut function.instructions, &mut names, &function_scope, &labels);
+            for variant in &mut function.variants {
+                resolve(&mut variant.instructions, &mut names, &function_scope, &labels);
+            }
+            function.local_labels.extend(
+                function_labels
+                    .into_iter()
+                    .map(|(local_label, unique_label)| (unique_label, local_label)),
+            );
+        }
+    }
+}
+
+/// Names of the labels that aren't scoped.
+fn label_names(instructions: &Instructions) -> impl Iterator<Item = String> + '_ {
+    instructions
+        .instructions
+        .iter()
+        .filter_map(|instruction| match &instruction.operation {
+            Operation::Label(label) if !is_scoped_label(label) => Some(label.clone()),
+            _ => None,
+        })
+}
+
+/// Name of a scope that can be used as a prefix of a label.
+fn scope_name(name: &str, module_index: usize) -> String {
+    if name.is_empty() {
+        return format!("module_{module_index}");
+    }
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+/// Name based on the given name that isn't used yet.
+fn unique_name(names: &mut HashSet<String>, name: String) -> String {
+    let mut result = name.clone();
+    let mut index = 1;
+    while names.contains(&result) {
+        result = format!("{name}_{index}");
+        index += 1;
+    }
+    names.insert(result.clone());
+    result
+}
+
+/// Unique name of each local label that is defined in the instructions.
+fn local_labels<'a>(
+    names: &mut HashSet<String>,
+    scope: &str,
+    streams: impl IntoIterator<Item = &'a Instructions>,
+) -> HashMap<String, String> {
+    let mut result = HashMap::<String, String>::default();
+    for instructions in streams {
+        for instruction in &instructions.instructions {
+            if let Operation::Label(label) = &instruction.operation {
+                if is_local_label(label) && !result.contains_key(label) {
+                    let unique_label = unique_name(names, format!("{scope}__{}", &label[1..]));
+                    result.insert(label.clone(), unique_label);
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Rename the scoped labels of the instructions and the references to them.
+///
+/// `labels` contains the local labels of each scope, the innermost scope first.
+fn resolve(
+    instructions: &mut Instructions,
+    names: &mut HashSet<String>,
+    scope: &str,
+    labels: &[&HashMap<String, String>],
+) {
+    let anonymous_labels = instructions
+        .instructions
+        .iter()
+        .enumerate()
+        .filter(
+            |(_, instruction)| matches!(&instruction.operation, Operation::Label(label) if is_anonymous_label(label)),
+        )
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let anonymous_names = (0..anonymous_labels.len())
+        .map(|anonymous_index| unique_name(names, format!("{scope}__anon_{anonymous_index}")))
+        .collect::<Vec<_>>();
+
+    for (index, instruction) in instructions.instructions.iter_mut().enumerate() {
+        if let Operation::Label(label) = &mut instruction.operation {
+            if is_local_label(label) {
+                if let Some(unique_label) = labels[0].get(label) {
+                    *label = unique_label.clone();
+                }
+            } else if is_anonymous_label(label) {
+                let anonymous_index = anonymous_labels.binary_search(&index).unwrap();
+                *label = anonymous_names[anonymous_index].clone();
+            }
+            continue;
+        }
+
+        instruction.rename(&|name| {
+            if is_local_label(name) {
+                return labels.iter().find_map(|labels| labels.get(name)).cloned();
+            }
+            if !is_anonymous_label(name) {
+                return None;
+            }
+            // Number of anonymous labels before the instruction.
+            let previous = anonymous_labels.partition_point(|label_index| *label_index < index);
+            let anonymous_index = if name.starts_with('-') {
+                previous.checked_sub(name.len())?
+            } else {
+                previous + name.len() - 1
+            };
+            anonymous_names.get(anonymous_index).cloned()
+        });
+    }
+}
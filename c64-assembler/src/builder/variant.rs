@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::{
+    memory::Address,
+    validator::{AssemblerResult, Error},
+    Application, Function, FunctionVariant, Instructions, VariantReport,
+};
+
+use super::finalize::update_label_addresses;
+
+/// Name of the variation that contains the instructions that were given when building the function.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Variations to select when building an application.
+#[derive(Default, Clone)]
+pub(crate) struct VariantSelection {
+    /// Variation to select per function name.
+    pub functions: HashMap<String, String>,
+    /// Tags to select variations for all functions, in order of preference.
+    pub tags: Vec<String>,
+}
+
+/// Swap the selected variations into the functions of the application.
+pub(crate) fn select_variants(application: &mut Application, selection: &VariantSelection) -> AssemblerResult<()> {
+    for function_name in selection.functions.keys() {
+        let exists = application
+            .modules
+            .iter()
+            .flat_map(|module| &module.functions)
+            .any(|function| &function.name == function_name);
+        if !exists {
+            return Err(Error::AddressNameUnknown(function_name.clone()));
+        }
+    }
+
+    for function in application.modules.iter_mut().flat_map(|module| &mut module.functions) {
+        let variant_name = selection.functions.get(&function.name).cloned().or_else(|| {
+            selection.tags.iter().find_map(|tag| {
+                function
+                    .variants
+                    .iter()
+                    .find(|variant| variant.tags.contains(tag))
+                    .map(|variant| variant.name.clone())
+            })
+        });
+        if let Some(variant_name) = variant_name {
+            select_variant(function, &variant_name)?;
+        }
+    }
+    Ok(())
+}
+
+fn select_variant(function: &mut Function, variant_name: &str) -> AssemblerResult<()> {
+    let selected_name = function.variant.as_deref().unwrap_or(DEFAULT_VARIANT);
+    if selected_name == variant_name {
+        return Ok(());
+    }
+    let Some(variant) = function
+        .variants
+        .iter_mut()
+        .find(|variant| variant.name == variant_name)
+    else {
+        return Err(Error::VariantUnknown {
+            function: function.name.clone(),
+            variant: variant_name.to_string(),
+        });
+    };
+    std::mem::swap(&mut function.instructions, &mut variant.instructions);
+    variant.name = selected_name.to_string();
+    variant.tags.clear();
+    function.variant = (variant_name != DEFAULT_VARIANT).then(|| variant_name.to_string());
+    Ok(())
+}
+
+impl Application {
+    /// Size and cycles of each variation of the functions that have variations.
+    ///
+    /// Each variation is measured at the address of the function, as if it was selected.
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder};
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .module(
+    ///         ModuleBuilder::default()
+    ///             .function(
+    ///                 FunctionBuilder::default()
+    ///                     .name("clear")
+    ///                     .instructions(InstructionBuilder::default().lda_imm(0x00).tax().rts().build())
+    ///                     .variant("small", &["size"], InstructionBuilder::default().rts().build())
+    ///                     .build(),
+    ///             )
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// let report = application.variant_report().unwrap();
+    /// assert_eq!(("default", true, 4), (report[0].variant.as_str(), report[0].selected, report[0].byte_size));
+    /// assert_eq!(("small", false, 1), (report[1].variant.as_str(), report[1].selected, report[1].byte_size));
+    /// ```
+    pub fn variant_report(&self) -> AssemblerResult<Vec<VariantReport>> {
+        let mut result = vec![];
+        for (module_index, module) in self.modules.iter().enumerate() {
+            for (function_index, function) in module.functions.iter().enumerate() {
+                if function.variants.is_empty() {
+                    continue;
+                }
+                let (byte_size, min_cycles, max_cycles) = measure(self, function)?;
+                result.push(VariantReport {
+                    module: module.name.clone(),
+                    function: function.name.clone(),
+                    variant: function.variant.as_deref().unwrap_or(DEFAULT_VARIANT).to_string(),
+                    selected: true,
+                    byte_size,
+                    min_cycles,
+                    max_cycles,
+                });
+
+                for variant in &function.variants {
+                    let mut application = self.clone();
+                    let other = &mut application.modules[module_index].functions[function_index];
+                    other.instructions = variant.instructions.clone();
+                    update_label_addresses(&mut application)?;
+                    let other = &application.modules[module_index].functions[function_index];
+                    let (byte_size, min_cycles, max_cycles) = measure(&application, other)?;
+                    result.push(VariantReport {
+                        module: module.name.clone(),
+                        function: function.name.clone(),
+                        variant: variant.name.clone(),
+                        selected: false,
+                        byte_size,
+                        min_cycles,
+                        max_cycles,
+                    });
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Number of bytes and the sum of the minimum and maximum cycles of the instructions of a function.
+fn measure(application: &Application, function: &Function) -> AssemblerResult<(Address, u32, u32)> {
+    let address = application.lookup_address(&function.name)?;
+    let timing = function.timing(application)?;
+    let (min_cycles, max_cycles) =
+        timing
+            .instructions
+            .iter()
+            .flatten()
+            .fold((0, 0), |(min_cycles, max_cycles), timing| {
+                (
+                    min_cycles + timing.min_cycles as u32,
+                    max_cycles + timing.max_cycles as u32,
+                )
+            });
+    Ok((
+        byte_size(application, &function.instructions, address)?,
+        min_cycles,
+        max_cycles,
+    ))
+}
+
+fn byte_size(application: &Application, instructions: &Instructions, address: Address) -> AssemblerResult<Address> {
+    let mut current_address = address;
+    for instruction in &instructions.instructions {
+        current_address = current_address.wrapping_add(instruction.byte_size(application, current_address)?);
+    }
+    Ok(current_address.wrapping_sub(address))
+}
+
+impl FunctionVariant {
+    pub fn new(name: &str, tags: &[&str], instructions: Instructions) -> FunctionVariant {
+        FunctionVariant {
+            name: name.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            instructions,
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! D64 disk images of the 1541 disk drive.
+use super::{
+    encode_name, is_free_in_bitmap, padded_name, set_free_in_bitmap, unpadded_name, DiskImage, DiskResult, Error,
+    SectorImage, NAME_LENGTH,
+};
+
+/// Track that contains the BAM and the directory.
+pub(crate) const DIRECTORY_TRACK: u8 = 18;
+/// Sector of the directory track that contains the BAM and the disk name.
+pub(crate) const BAM_SECTOR: u8 = 0;
+/// Offset of the BAM entries of tracks 1-35 in the BAM sector.
+const BAM_OFFSET: usize = 0x04;
+/// Offset of the BAM entries of tracks 36-40 in the BAM sector, as used by SpeedDOS.
+const BAM_OFFSET_EXTENDED: usize = 0xC0;
+/// Offset of the disk name in the BAM sector.
+const DISK_NAME_OFFSET: usize = 0x90;
+/// Offset of the disk id in the BAM sector.
+const DISK_ID_OFFSET: usize = 0xA2;
+/// Offset of the DOS type in the BAM sector.
+const DOS_TYPE_OFFSET: usize = 0xA5;
+
+/// Number of sectors of a disk with 35 tracks.
+const SECTORS_35_TRACKS: usize = 683;
+/// Number of sectors of a disk with 40 tracks.
+const SECTORS_40_TRACKS: usize = 768;
+
+/// D64 disk image with 35 or 40 tracks.
+///
+/// Tracks 36-40 are stored in the BAM like SpeedDOS does. The image can contain an error
+/// byte per sector, which copy protections use to mark sectors that cannot be read.
+///
+/// ```
+/// use c64_assembler::disk::{d64::D64, DiskFile, DiskImage};
+///
+/// let mut disk = D64::new("demo disk", "01").unwrap().error_bytes();
+/// disk.write_file(&DiskFile::prg("demo", &[0x01, 0x08, 0x00, 0x00])).unwrap();
+/// disk.set_error(18, 2, 0x05);
+///
+/// let bytes = disk.to_bytes();
+/// assert_eq!(175531, bytes.len());
+/// let disk = D64::from_bytes(&bytes).unwrap();
+/// assert_eq!(Some(0x05), disk.error(18, 2));
+/// assert_eq!(vec![0x01, 0x08, 0x00, 0x00], disk.read_file("demo").unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct D64 {
+    tracks: u8,
+    image: SectorImage,
+}
+
+impl D64 {
+    /// Formatted disk with 35 tracks.
+    pub fn new(name: &str, id: &str) -> DiskResult<D64> {
+        D64::with_tracks(35, name, id)
+    }
+
+    /// Formatted disk with 35 or 40 tracks.
+    ///
+    /// The id has at most 2 characters and is shown after the disk name.
+    pub fn with_tracks(tracks: u8, name: &str, id: &str) -> DiskResult<D64> {
+        let sector_count = match tracks {
+            35 => SECTORS_35_TRACKS,
+            40 => SECTORS_40_TRACKS,
+            _ => {
+                return Err(Error::InvalidDiskImage(format!(
+                    "D64 has 35 or 40 tracks, not {tracks}"
+                )))
+            }
+        };
+        let mut disk = D64 {
+            tracks,
+            image: SectorImage::new(sector_count),
+        };
+        for track in 1..=tracks {
+            for sector in 0..disk.sectors(track) {
+                disk.set_sector_free(track, sector, true);
+            }
+        }
+        disk.set_sector_free(DIRECTORY_TRACK, BAM_SECTOR, false);
+        disk.set_sector_free(DIRECTORY_TRACK, 1, false);
+        write_header(disk.sector_mut(DIRECTORY_TRACK, BAM_SECTOR)?, name, id)?;
+        disk.sector_mut(DIRECTORY_TRACK, 1)?[0..2].copy_from_slice(&[0x00, 0xFF]);
+        Ok(disk)
+    }
+
+    /// Read a disk image; the number of tracks and the error bytes are detected from the
+    /// size of the image.
+    pub fn from_bytes(bytes: &[u8]) -> DiskResult<D64> {
+        [(35, SECTORS_35_TRACKS), (40, SECTORS_40_TRACKS)]
+            .into_iter()
+            .find_map(|(tracks, sector_count)| {
+                SectorImage::from_bytes(bytes, sector_count).map(|image| D64 { tracks, image })
+            })
+            .ok_or_else(|| Error::InvalidDiskImage(format!("{} bytes isn't the size of a D64", bytes.len())))
+    }
+
+    /// Store an error byte per sector; all sectors start without errors.
+    pub fn error_bytes(mut self) -> Self {
+        self.image.enable_errors();
+        self
+    }
+
+    /// Error code of a sector, `None` when the image doesn't contain error bytes.
+    pub fn error(&self, track: u8, sector: u8) -> Option<u8> {
+        self.image.error(self.sector_offset(track, sector).ok()?)
+    }
+
+    /// Set the error code of a sector; adds error bytes to the image when needed.
+    pub fn set_error(&mut self, track: u8, sector: u8, error: u8) {
+        if let Ok(offset) = self.sector_offset(track, sector) {
+            self.image.set_error(offset, error);
+        }
+    }
+
+    /// Bytes of the disk image, followed by the error bytes when present.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.image.to_bytes()
+    }
+
+    /// Offset of the BAM entry of the track in the BAM sector.
+    fn bam_offset(track: u8) -> usize {
+        if track <= 35 {
+            BAM_OFFSET + (track as usize - 1) * 4
+        } else {
+            BAM_OFFSET_EXTENDED + (track as usize - 36) * 4
+        }
+    }
+}
+
+/// Number of sectors of a track of the 1541, the outer tracks contain more sectors.
+pub(crate) fn sectors_per_track(track: u8) -> u8 {
+    match track {
+        1..=17 => 21,
+        18..=24 => 19,
+        25..=30 => 18,
+        _ => 17,
+    }
+}
+
+/// Write the disk name, id and DOS type to the BAM sector.
+pub(crate) fn write_header(bam: &mut [u8], name: &str, id: &str) -> DiskResult<()> {
+    let name = encode_name(name, NAME_LENGTH)?;
+    let id = encode_name(id, 2)?;
+    bam[0..4].copy_from_slice(&[DIRECTORY_TRACK, 1, b'A', 0x00]);
+    bam[DISK_NAME_OFFSET..DISK_NAME_OFFSET + 0x1B].fill(0xA0);
+    bam[DISK_NAME_OFFSET..DISK_NAME_OFFSET + NAME_LENGTH].copy_from_slice(&padded_name(&name, NAME_LENGTH));
+    bam[DISK_ID_OFFSET..DISK_ID_OFFSET + 2].copy_from_slice(&padded_name(&id, 2));
+    bam[DOS_TYPE_OFFSET..DOS_TYPE_OFFSET + 2].copy_from_slice(b"2A");
+    Ok(())
+}
+
+/// Disk name stored in the BAM sector.
+pub(crate) fn header_name(bam: &[u8]) -> String {
+    unpadded_name(&bam[DISK_NAME_OFFSET..DISK_NAME_OFFSET + NAME_LENGTH])
+}
+
+/// Disk id and DOS type stored in the BAM sector.
+pub(crate) fn header_id(bam: &[u8]) -> String {
+    format!(
+        "{} {}",
+        unpadded_name(&bam[DISK_ID_OFFSET..DISK_ID_OFFSET + 2]),
+        unpadded_name(&bam[DOS_TYPE_OFFSET..DOS_TYPE_OFFSET + 2])
+    )
+}
+
+impl DiskImage for D64 {
+    fn tracks(&self) -> u8 {
+        self.tracks
+    }
+
+    fn sectors(&self, track: u8) -> u8 {
+        sectors_per_track(track)
+    }
+
+    fn directory_track(&self) -> u8 {
+        DIRECTORY_TRACK
+    }
+
+    fn first_directory_sector(&self) -> u8 {
+        1
+    }
+
+    fn default_interleave(&self) -> u8 {
+        10
+    }
+
+    fn directory_interleave(&self) -> u8 {
+        3
+    }
+
+    fn image(&self) -> &[u8] {
+        &self.image.sectors
+    }
+
+    fn image_mut(&mut self) -> &mut [u8] {
+        &mut self.image.sectors
+    }
+
+    fn is_sector_free(&self, track: u8, sector: u8) -> bool {
+        let offset = D64::bam_offset(track);
+        let bam = self.sector(DIRECTORY_TRACK, BAM_SECTOR).unwrap();
+        is_free_in_bitmap(&bam[offset + 1..offset + 4], sector)
+    }
+
+    fn set_sector_free(&mut self, track: u8, sector: u8, free: bool) {
+        let offset = D64::bam_offset(track);
+        let bam = self.sector_mut(DIRECTORY_TRACK, BAM_SECTOR).unwrap();
+        bam[offset] = set_free_in_bitmap(&mut bam[offset + 1..offset + 4], sector, free);
+    }
+
+    fn disk_name(&self) -> String {
+        header_name(self.sector(DIRECTORY_TRACK, BAM_SECTOR).unwrap())
+    }
+
+    fn disk_id(&self) -> String {
+        header_id(self.sector(DIRECTORY_TRACK, BAM_SECTOR).unwrap())
+    }
+}
@@ -0,0 +1,162 @@
+//! D71 disk images of the double sided 1571 disk drive.
+use super::{
+    d64::{header_id, header_name, sectors_per_track, write_header, BAM_SECTOR, DIRECTORY_TRACK},
+    is_free_in_bitmap, set_free_in_bitmap, DiskImage, DiskResult, Error, SectorImage,
+};
+
+/// Number of tracks, side 2 contains tracks 36-70.
+const TRACKS: u8 = 70;
+/// Track on side 2 that contains the bitmaps of the BAM of side 2.
+const BAM_TRACK_SIDE_2: u8 = 53;
+/// Offset of the BAM entries of tracks 1-35 in the BAM sector.
+const BAM_OFFSET: usize = 0x04;
+/// Offset of the number of free sectors of tracks 36-70 in the BAM sector.
+const FREE_COUNT_OFFSET_SIDE_2: usize = 0xDD;
+/// Offset of the double sided flag in the BAM sector.
+const DOUBLE_SIDED_OFFSET: usize = 0x03;
+/// Number of sectors of a disk.
+const SECTOR_COUNT: usize = 1366;
+
+/// D71 disk image with 70 tracks on two sides.
+///
+/// The second side has the same layout as the first side. Its BAM is stored on track 53,
+/// which isn't used for files.
+///
+/// ```
+/// use c64_assembler::disk::{d71::D71, DiskFile, DiskImage};
+///
+/// let mut disk = D71::new("demo disk", "01").unwrap();
+/// disk.write_file(&DiskFile::prg("demo", &[0x01, 0x08, 0x00, 0x00])).unwrap();
+/// assert_eq!(1327, disk.blocks_free());
+///
+/// let disk = D71::from_bytes(&disk.to_bytes()).unwrap();
+/// assert_eq!(vec![0x01, 0x08, 0x00, 0x00], disk.read_file("demo").unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct D71 {
+    image: SectorImage,
+}
+
+impl D71 {
+    /// Formatted disk, the id has at most 2 characters.
+    pub fn new(name: &str, id: &str) -> DiskResult<D71> {
+        let mut disk = D71 {
+            image: SectorImage::new(SECTOR_COUNT),
+        };
+        for track in (1..=TRACKS).filter(|track| *track != BAM_TRACK_SIDE_2) {
+            for sector in 0..disk.sectors(track) {
+                disk.set_sector_free(track, sector, true);
+            }
+        }
+        disk.set_sector_free(DIRECTORY_TRACK, BAM_SECTOR, false);
+        disk.set_sector_free(DIRECTORY_TRACK, 1, false);
+        let bam = disk.sector_mut(DIRECTORY_TRACK, BAM_SECTOR)?;
+        write_header(bam, name, id)?;
+        bam[DOUBLE_SIDED_OFFSET] = 0x80;
+        disk.sector_mut(DIRECTORY_TRACK, 1)?[0..2].copy_from_slice(&[0x00, 0xFF]);
+        Ok(disk)
+    }
+
+    /// Read a disk image, with or without error bytes.
+    pub fn from_bytes(bytes: &[u8]) -> DiskResult<D71> {
+        SectorImage::from_bytes(bytes, SECTOR_COUNT)
+            .map(|image| D71 { image })
+            .ok_or_else(|| Error::InvalidDiskImage(format!("{} bytes isn't the size of a D71", bytes.len())))
+    }
+
+    /// Store an error byte per sector; all sectors start without errors.
+    pub fn error_bytes(mut self) -> Self {
+        self.image.enable_errors();
+        self
+    }
+
+    /// Error code of a sector, `None` when the image doesn't contain error bytes.
+    pub fn error(&self, track: u8, sector: u8) -> Option<u8> {
+        self.image.error(self.sector_offset(track, sector).ok()?)
+    }
+
+    /// Set the error code of a sector; adds error bytes to the image when needed.
+    pub fn set_error(&mut self, track: u8, sector: u8, error: u8) {
+        if let Ok(offset) = self.sector_offset(track, sector) {
+            self.image.set_error(offset, error);
+        }
+    }
+
+    /// Bytes of the disk image, followed by the error bytes when present.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.image.to_bytes()
+    }
+
+    /// Sector and offset of the free count and of the bitmap of the track.
+    fn bam_location(track: u8) -> ((u8, usize), (u8, usize)) {
+        if track <= 35 {
+            let offset = BAM_OFFSET + (track as usize - 1) * 4;
+            ((DIRECTORY_TRACK, offset), (DIRECTORY_TRACK, offset + 1))
+        } else {
+            let index = track as usize - 36;
+            (
+                (DIRECTORY_TRACK, FREE_COUNT_OFFSET_SIDE_2 + index),
+                (BAM_TRACK_SIDE_2, index * 3),
+            )
+        }
+    }
+}
+
+impl DiskImage for D71 {
+    fn tracks(&self) -> u8 {
+        TRACKS
+    }
+
+    fn sectors(&self, track: u8) -> u8 {
+        sectors_per_track((track - 1) % 35 + 1)
+    }
+
+    fn directory_track(&self) -> u8 {
+        DIRECTORY_TRACK
+    }
+
+    fn first_directory_sector(&self) -> u8 {
+        1
+    }
+
+    fn reserved_tracks(&self) -> Vec<u8> {
+        vec![DIRECTORY_TRACK, BAM_TRACK_SIDE_2]
+    }
+
+    fn default_interleave(&self) -> u8 {
+        6
+    }
+
+    fn directory_interleave(&self) -> u8 {
+        3
+    }
+
+    fn image(&self) -> &[u8] {
+        &self.image.sectors
+    }
+
+    fn image_mut(&mut self) -> &mut [u8] {
+        &mut self.image.sectors
+    }
+
+    fn is_sector_free(&self, track: u8, sector: u8) -> bool {
+        let (_, (bitmap_track, offset)) = D71::bam_location(track);
+        let bam = self.sector(bitmap_track, BAM_SECTOR).unwrap();
+        is_free_in_bitmap(&bam[offset..offset + 3], sector)
+    }
+
+    fn set_sector_free(&mut self, track: u8, sector: u8, free: bool) {
+        let ((count_track, count_offset), (bitmap_track, offset)) = D71::bam_location(track);
+        let bam = self.sector_mut(bitmap_track, BAM_SECTOR).unwrap();
+        let free_sectors = set_free_in_bitmap(&mut bam[offset..offset + 3], sector, free);
+        self.sector_mut(count_track, BAM_SECTOR).unwrap()[count_offset] = free_sectors;
+    }
+
+    fn disk_name(&self) -> String {
+        header_name(self.sector(DIRECTORY_TRACK, BAM_SECTOR).unwrap())
+    }
+
+    fn disk_id(&self) -> String {
+        header_id(self.sector(DIRECTORY_TRACK, BAM_SECTOR).unwrap())
+    }
+}
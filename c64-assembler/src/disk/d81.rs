@@ -0,0 +1,380 @@
+//! D81 disk images of the 1581 disk drive, including partitions and sub-directories.
+use std::ops::RangeInclusive;
+
+use super::{
+    encode_name, is_free_in_bitmap, padded_name, set_free_in_bitmap, unpadded_name, DirectoryEntry, DiskImage,
+    DiskResult, Error, FileType, SectorImage, ENTRY_SIZE, NAME_LENGTH, SECTOR_SIZE,
+};
+
+/// Number of tracks.
+const TRACKS: u8 = 80;
+/// Number of sectors of each track.
+const SECTORS: u8 = 40;
+/// Track that contains the header, the BAM and the directory of the disk.
+const DIRECTORY_TRACK: u8 = 40;
+/// Sector of the header track that contains the first directory entries. Sector 0 contains
+/// the disk name, sectors 1 and 2 the BAM of tracks 1-40 and 41-80.
+const FIRST_DIRECTORY_SECTOR: u8 = 3;
+/// Offset of the disk name in the header sector.
+const DISK_NAME_OFFSET: usize = 0x04;
+/// Offset of the disk id in the header sector.
+const DISK_ID_OFFSET: usize = 0x16;
+/// Offset of the DOS type in the header sector.
+const DOS_TYPE_OFFSET: usize = 0x19;
+/// Offset of the BAM entries in the BAM sectors; each entry has 6 bytes.
+const BAM_OFFSET: usize = 0x10;
+/// Minimum number of tracks of a sub-directory.
+const MIN_SUB_DIRECTORY_TRACKS: u8 = 3;
+
+/// D81 disk image with 80 tracks of 40 sectors.
+///
+/// Tracks can be reserved as partition, which can be formatted as sub-directory with its
+/// own BAM and directory.
+///
+/// ```
+/// use c64_assembler::disk::{d81::D81, DiskFile, DiskImage};
+///
+/// let mut disk = D81::new("demo disk", "01").unwrap();
+/// disk.write_file(&DiskFile::prg("demo", &[0x01, 0x08, 0x00, 0x00])).unwrap();
+/// disk.create_sub_directory("tools", 60, 5).unwrap();
+/// disk.sub_directory("tools")
+///     .unwrap()
+///     .write_file(&DiskFile::prg("monitor", &[0x00, 0xC0]))
+///     .unwrap();
+///
+/// let mut disk = D81::from_bytes(&disk.to_bytes()).unwrap();
+/// assert_eq!(vec![0x01, 0x08, 0x00, 0x00], disk.read_file("demo").unwrap());
+/// assert_eq!(vec![0x00, 0xC0], disk.sub_directory("tools").unwrap().read_file("monitor").unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct D81 {
+    image: SectorImage,
+}
+
+impl D81 {
+    /// Formatted disk, the id has at most 2 characters.
+    pub fn new(name: &str, id: &str) -> DiskResult<D81> {
+        let mut disk = D81 {
+            image: SectorImage::new(TRACKS as usize * SECTORS as usize),
+        };
+        format(&mut disk.image.sectors, DIRECTORY_TRACK, 1..=TRACKS, name, id)?;
+        Ok(disk)
+    }
+
+    /// Read a disk image, with or without error bytes.
+    pub fn from_bytes(bytes: &[u8]) -> DiskResult<D81> {
+        SectorImage::from_bytes(bytes, TRACKS as usize * SECTORS as usize)
+            .map(|image| D81 { image })
+            .ok_or_else(|| Error::InvalidDiskImage(format!("{} bytes isn't the size of a D81", bytes.len())))
+    }
+
+    /// Store an error byte per sector; all sectors start without errors.
+    pub fn error_bytes(mut self) -> Self {
+        self.image.enable_errors();
+        self
+    }
+
+    /// Error code of a sector, `None` when the image doesn't contain error bytes.
+    pub fn error(&self, track: u8, sector: u8) -> Option<u8> {
+        self.image.error(self.sector_offset(track, sector).ok()?)
+    }
+
+    /// Set the error code of a sector; adds error bytes to the image when needed.
+    pub fn set_error(&mut self, track: u8, sector: u8, error: u8) {
+        if let Ok(offset) = self.sector_offset(track, sector) {
+            self.image.set_error(offset, error);
+        }
+    }
+
+    /// Bytes of the disk image, followed by the error bytes when present.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.image.to_bytes()
+    }
+
+    /// Reserve tracks as a partition. The tracks are marked as used and a directory entry
+    /// of type [FileType::Cbm] is added.
+    pub fn create_partition(&mut self, name: &str, first_track: u8, tracks: u8) -> DiskResult<DirectoryEntry> {
+        create_partition(self, name, first_track, tracks)
+    }
+
+    /// Reserve tracks as a partition and format it as sub-directory. A sub-directory has at
+    /// least 3 tracks; the first track contains its header, BAM and directory.
+    pub fn create_sub_directory(&mut self, name: &str, first_track: u8, tracks: u8) -> DiskResult<()> {
+        create_sub_directory(self, name, first_track, tracks)
+    }
+
+    /// Sub-directory to read and write the files in.
+    pub fn sub_directory(&mut self, name: &str) -> DiskResult<D81SubDirectory<'_>> {
+        let header_track = sub_directory_track(self, name)?;
+        Ok(D81SubDirectory {
+            image: &mut self.image.sectors,
+            header_track,
+        })
+    }
+}
+
+/// Sub-directory of a [D81] disk image.
+///
+/// Files in the sub-directory are stored inside its partition. Sub-directories can contain
+/// sub-directories themselves.
+#[derive(Debug, PartialEq)]
+pub struct D81SubDirectory<'a> {
+    image: &'a mut [u8],
+    header_track: u8,
+}
+
+impl D81SubDirectory<'_> {
+    /// Reserve tracks of this sub-directory as a partition, see [D81::create_partition].
+    pub fn create_partition(&mut self, name: &str, first_track: u8, tracks: u8) -> DiskResult<DirectoryEntry> {
+        create_partition(self, name, first_track, tracks)
+    }
+
+    /// Format tracks of this sub-directory as sub-directory, see [D81::create_sub_directory].
+    pub fn create_sub_directory(&mut self, name: &str, first_track: u8, tracks: u8) -> DiskResult<()> {
+        create_sub_directory(self, name, first_track, tracks)
+    }
+
+    /// Sub-directory of this sub-directory.
+    pub fn sub_directory(&mut self, name: &str) -> DiskResult<D81SubDirectory<'_>> {
+        let header_track = sub_directory_track(self, name)?;
+        Ok(D81SubDirectory {
+            image: self.image,
+            header_track,
+        })
+    }
+}
+
+fn create_partition(disk: &mut impl DiskImage, name: &str, first_track: u8, tracks: u8) -> DiskResult<DirectoryEntry> {
+    let encoded_name = encode_name(name, NAME_LENGTH)?;
+    if disk.directory()?.iter().any(|entry| entry.name == encoded_name) {
+        return Err(Error::FileExists(name.to_string()));
+    }
+    let last_track = first_track as u16 + tracks as u16 - 1;
+    if first_track == 0 || tracks == 0 || last_track > TRACKS as u16 {
+        return Err(Error::PartitionUnavailable {
+            name: name.to_string(),
+            track: first_track,
+        });
+    }
+    for track in first_track..=last_track as u8 {
+        if disk.free_sectors(track) != SECTORS || disk.reserved_tracks().contains(&track) {
+            return Err(Error::PartitionUnavailable {
+                name: name.to_string(),
+                track,
+            });
+        }
+    }
+
+    let (directory_track, directory_sector, entry_offset) = disk.directory_slot(name)?;
+    for track in first_track..=last_track as u8 {
+        for sector in 0..SECTORS {
+            disk.set_sector_free(track, sector, false);
+        }
+    }
+    let entry = DirectoryEntry {
+        name: encoded_name,
+        file_type: FileType::Cbm,
+        closed: true,
+        locked: false,
+        track: first_track,
+        sector: 0,
+        blocks: tracks as u16 * SECTORS as u16,
+    };
+    let bytes = disk.sector_mut(directory_track, directory_sector)?;
+    entry.write(&mut bytes[entry_offset..entry_offset + ENTRY_SIZE]);
+    Ok(entry)
+}
+
+fn create_sub_directory(disk: &mut impl DiskImage, name: &str, first_track: u8, tracks: u8) -> DiskResult<()> {
+    if tracks < MIN_SUB_DIRECTORY_TRACKS {
+        return Err(Error::PartitionUnavailable {
+            name: name.to_string(),
+            track: first_track,
+        });
+    }
+    let entry = create_partition(disk, name, first_track, tracks)?;
+    let id = disk.disk_id();
+    let id = id.split(' ').next().unwrap_or_default().to_string();
+    let last_track = entry.track + tracks - 1;
+    format(disk.image_mut(), entry.track, entry.track..=last_track, name, &id)
+}
+
+/// Header track of the sub-directory with the given name.
+fn sub_directory_track(disk: &impl DiskImage, name: &str) -> DiskResult<u8> {
+    let entry = disk.find_entry(name)?;
+    let is_sub_directory = entry.file_type == FileType::Cbm
+        && entry.sector == 0
+        && entry.blocks >= MIN_SUB_DIRECTORY_TRACKS as u16 * SECTORS as u16
+        && entry.blocks % SECTORS as u16 == 0
+        && disk.sector(entry.track, 0)?[2] == b'D';
+    if !is_sub_directory {
+        return Err(Error::InvalidDiskImage(format!("'{name}' isn't a sub-directory")));
+    }
+    Ok(entry.track)
+}
+
+/// Offset of a sector in the image.
+fn offset(track: u8, sector: u8) -> usize {
+    ((track as usize - 1) * SECTORS as usize + sector as usize) * SECTOR_SIZE
+}
+
+/// Offset of the BAM entry of a track in the image.
+fn bam_offset(header_track: u8, track: u8) -> usize {
+    let (bam_sector, index) = if track <= 40 { (1, track - 1) } else { (2, track - 41) };
+    offset(header_track, bam_sector) + BAM_OFFSET + index as usize * 6
+}
+
+fn is_sector_free(image: &[u8], header_track: u8, track: u8, sector: u8) -> bool {
+    let offset = bam_offset(header_track, track);
+    is_free_in_bitmap(&image[offset + 1..offset + 6], sector)
+}
+
+fn set_sector_free(image: &mut [u8], header_track: u8, track: u8, sector: u8, free: bool) {
+    let offset = bam_offset(header_track, track);
+    image[offset] = set_free_in_bitmap(&mut image[offset + 1..offset + 6], sector, free);
+}
+
+/// Write the header, BAM and directory of a disk or sub-directory. Only the sectors of the
+/// given tracks are marked as free.
+fn format(image: &mut [u8], header_track: u8, tracks: RangeInclusive<u8>, name: &str, id: &str) -> DiskResult<()> {
+    let name = padded_name(&encode_name(name, NAME_LENGTH)?, NAME_LENGTH);
+    let id = padded_name(&encode_name(id, 2)?, 2);
+
+    let header = offset(header_track, 0);
+    image[header..header + 4 * SECTOR_SIZE].fill(0x00);
+    image[header..header + 4].copy_from_slice(&[header_track, FIRST_DIRECTORY_SECTOR, b'D', 0x00]);
+    image[header + DISK_NAME_OFFSET..header + DOS_TYPE_OFFSET + 4].fill(0xA0);
+    image[header + DISK_NAME_OFFSET..header + DISK_NAME_OFFSET + NAME_LENGTH].copy_from_slice(&name);
+    image[header + DISK_ID_OFFSET..header + DISK_ID_OFFSET + 2].copy_from_slice(&id);
+    image[header + DOS_TYPE_OFFSET..header + DOS_TYPE_OFFSET + 2].copy_from_slice(b"3D");
+
+    for (bam_sector, link) in [(1, [header_track, 2]), (2, [0x00, 0xFF])] {
+        let bam = offset(header_track, bam_sector);
+        image[bam..bam + 8].copy_from_slice(&[link[0], link[1], b'D', !b'D', id[0], id[1], 0xC0, 0x00]);
+    }
+    for track in tracks {
+        for sector in 0..SECTORS {
+            set_sector_free(image, header_track, track, sector, true);
+        }
+    }
+    for sector in 0..=FIRST_DIRECTORY_SECTOR {
+        set_sector_free(image, header_track, header_track, sector, false);
+    }
+    let directory = offset(header_track, FIRST_DIRECTORY_SECTOR);
+    image[directory..directory + 2].copy_from_slice(&[0x00, 0xFF]);
+    Ok(())
+}
+
+fn header_name(image: &[u8], header_track: u8) -> String {
+    let header = offset(header_track, 0);
+    unpadded_name(&image[header + DISK_NAME_OFFSET..header + DISK_NAME_OFFSET + NAME_LENGTH])
+}
+
+fn header_id(image: &[u8], header_track: u8) -> String {
+    let header = offset(header_track, 0);
+    format!(
+        "{} {}",
+        unpadded_name(&image[header + DISK_ID_OFFSET..header + DISK_ID_OFFSET + 2]),
+        unpadded_name(&image[header + DOS_TYPE_OFFSET..header + DOS_TYPE_OFFSET + 2])
+    )
+}
+
+impl DiskImage for D81 {
+    fn tracks(&self) -> u8 {
+        TRACKS
+    }
+
+    fn sectors(&self, _track: u8) -> u8 {
+        SECTORS
+    }
+
+    fn directory_track(&self) -> u8 {
+        DIRECTORY_TRACK
+    }
+
+    fn first_directory_sector(&self) -> u8 {
+        FIRST_DIRECTORY_SECTOR
+    }
+
+    fn default_interleave(&self) -> u8 {
+        1
+    }
+
+    fn directory_interleave(&self) -> u8 {
+        1
+    }
+
+    fn image(&self) -> &[u8] {
+        &self.image.sectors
+    }
+
+    fn image_mut(&mut self) -> &mut [u8] {
+        &mut self.image.sectors
+    }
+
+    fn is_sector_free(&self, track: u8, sector: u8) -> bool {
+        is_sector_free(&self.image.sectors, DIRECTORY_TRACK, track, sector)
+    }
+
+    fn set_sector_free(&mut self, track: u8, sector: u8, free: bool) {
+        set_sector_free(&mut self.image.sectors, DIRECTORY_TRACK, track, sector, free)
+    }
+
+    fn disk_name(&self) -> String {
+        header_name(&self.image.sectors, DIRECTORY_TRACK)
+    }
+
+    fn disk_id(&self) -> String {
+        header_id(&self.image.sectors, DIRECTORY_TRACK)
+    }
+}
+
+impl DiskImage for D81SubDirectory<'_> {
+    fn tracks(&self) -> u8 {
+        TRACKS
+    }
+
+    fn sectors(&self, _track: u8) -> u8 {
+        SECTORS
+    }
+
+    fn directory_track(&self) -> u8 {
+        self.header_track
+    }
+
+    fn first_directory_sector(&self) -> u8 {
+        FIRST_DIRECTORY_SECTOR
+    }
+
+    fn default_interleave(&self) -> u8 {
+        1
+    }
+
+    fn directory_interleave(&self) -> u8 {
+        1
+    }
+
+    fn image(&self) -> &[u8] {
+        self.image
+    }
+
+    fn image_mut(&mut self) -> &mut [u8] {
+        self.image
+    }
+
+    fn is_sector_free(&self, track: u8, sector: u8) -> bool {
+        is_sector_free(self.image, self.header_track, track, sector)
+    }
+
+    fn set_sector_free(&mut self, track: u8, sector: u8, free: bool) {
+        set_sector_free(self.image, self.header_track, track, sector, free)
+    }
+
+    fn disk_name(&self) -> String {
+        header_name(self.image, self.header_track)
+    }
+
+    fn disk_id(&self) -> String {
+        header_id(self.image, self.header_track)
+    }
+}
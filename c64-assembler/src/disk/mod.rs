@@ -0,0 +1,637 @@
+//! Disk images to store the generated programs on, without the need of other tools.
+//!
+//! All disk image formats ([d64::D64], [d71::D71] and [d81::D81]) implement [DiskImage],
+//! which writes and reads files, lists the directory and inspects the block availability map
+//! (BAM).
+//!
+//! ```
+//! use c64_assembler::disk::{d64::D64, DiskFile, DiskImage};
+//!
+//! let mut disk = D64::new("demo disk", "01").unwrap();
+//! disk.write_file(&DiskFile::prg("demo", &[0x01, 0x08, 0x00, 0x00])).unwrap();
+//! assert_eq!(vec![0x01, 0x08, 0x00, 0x00], disk.read_file("demo").unwrap());
+//! assert_eq!(663, disk.blocks_free());
+//! ```
+use std::fmt::Display;
+
+use crate::basic::petscii::{decode_text, encode_text};
+
+pub mod d64;
+pub mod d71;
+pub mod d81;
+
+/// Number of bytes in a sector.
+pub const SECTOR_SIZE: usize = 256;
+/// Number of file bytes in a sector; the first two bytes link to the next sector.
+const SECTOR_DATA_SIZE: usize = SECTOR_SIZE - 2;
+/// Size of a directory entry; each directory sector contains 8 entries.
+const ENTRY_SIZE: usize = 32;
+/// Maximum length of file and disk names.
+pub const NAME_LENGTH: usize = 16;
+/// Names are padded with shifted spaces.
+const NAME_PADDING: u8 = 0xA0;
+
+pub type DiskResult<T> = Result<T, Error>;
+
+/// Errors of reading and writing disk images. They convert into [crate::validator::Error], so
+/// they can be returned from functions that also generate the program.
+#[derive(Debug)]
+pub enum Error {
+    /// A file or disk name contains text that cannot be encoded as PETSCII.
+    InvalidName { name: String, reason: String },
+    /// A file or disk name doesn't fit in the directory of a disk image.
+    NameTooLong { name: String, max_length: usize },
+    /// A file with the same name is already stored on the disk image.
+    FileExists(String),
+    /// The file isn't stored on the disk image.
+    FileNotFound(String),
+    /// The disk image has not enough free blocks to store the file.
+    DiskFull(String),
+    /// The directory of the disk image has no room left for the file.
+    DirectoryFull(String),
+    /// The tracks of a partition are outside the disk or already in use. Track is the first
+    /// track that cannot be used.
+    PartitionUnavailable { name: String, track: u8 },
+    /// Byte stream could not be read as a disk image.
+    InvalidDiskImage(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidName { name, reason } => write!(f, "name '{name}' cannot be encoded: {reason}"),
+            Error::NameTooLong { name, max_length } => {
+                write!(f, "name '{name}' is longer than {max_length} characters")
+            }
+            Error::FileExists(name) => write!(f, "file '{name}' already exists"),
+            Error::FileNotFound(name) => write!(f, "file '{name}' not found"),
+            Error::DiskFull(name) => write!(f, "disk full, file '{name}' doesn't fit"),
+            Error::DirectoryFull(name) => write!(f, "directory full, no entry left for file '{name}'"),
+            Error::PartitionUnavailable { name, track } => {
+                write!(f, "track {track} cannot be used for partition '{name}'")
+            }
+            Error::InvalidDiskImage(message) => write!(f, "invalid disk image: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Type of a file as shown in the directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    /// Deleted file. Without data these entries are used for directory art.
+    Del,
+    /// Sequential data file.
+    Seq,
+    /// Program, the first two bytes contain the load address.
+    Prg,
+    /// User file, stored like a sequential file.
+    Usr,
+    /// Relative file. Can be read, but not written.
+    Rel,
+    /// Partition of a 1581 disk, see [d81::D81::create_partition].
+    Cbm,
+}
+
+impl FileType {
+    fn code(&self) -> u8 {
+        match self {
+            FileType::Del => 0,
+            FileType::Seq => 1,
+            FileType::Prg => 2,
+            FileType::Usr => 3,
+            FileType::Rel => 4,
+            FileType::Cbm => 5,
+        }
+    }
+
+    fn from_code(code: u8) -> DiskResult<FileType> {
+        match code & 0x07 {
+            0 => Ok(FileType::Del),
+            1 => Ok(FileType::Seq),
+            2 => Ok(FileType::Prg),
+            3 => Ok(FileType::Usr),
+            4 => Ok(FileType::Rel),
+            5 => Ok(FileType::Cbm),
+            code => Err(Error::InvalidDiskImage(format!("unknown file type {code}"))),
+        }
+    }
+}
+
+impl Display for FileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            FileType::Del => "del",
+            FileType::Seq => "seq",
+            FileType::Prg => "prg",
+            FileType::Usr => "usr",
+            FileType::Rel => "rel",
+            FileType::Cbm => "cbm",
+        })
+    }
+}
+
+/// File to write to a disk image, see [DiskImage::write_file].
+///
+/// Names are encoded as PETSCII like [crate::basic::tokenize_line] encodes strings, so
+/// lowercase letters are shown as uppercase and control codes or graphic characters can be
+/// written as `{$a6}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskFile {
+    pub(crate) name: String,
+    pub(crate) file_type: FileType,
+    pub(crate) data: Vec<u8>,
+    pub(crate) interleave: Option<u8>,
+    pub(crate) locked: bool,
+}
+
+impl DiskFile {
+    pub fn new(name: &str, file_type: FileType, data: &[u8]) -> DiskFile {
+        DiskFile {
+            name: name.to_string(),
+            file_type,
+            data: data.to_vec(),
+            interleave: None,
+            locked: false,
+        }
+    }
+
+    /// Program file, for example the output of [crate::generator::ProgramGenerator].
+    pub fn prg(name: &str, data: &[u8]) -> DiskFile {
+        DiskFile::new(name, FileType::Prg, data)
+    }
+
+    /// Sequential data file.
+    pub fn seq(name: &str, data: &[u8]) -> DiskFile {
+        DiskFile::new(name, FileType::Seq, data)
+    }
+
+    /// User file.
+    pub fn usr(name: &str, data: &[u8]) -> DiskFile {
+        DiskFile::new(name, FileType::Usr, data)
+    }
+
+    /// Directory entry without data, used to draw directory art.
+    pub fn directory_art(name: &str) -> DiskFile {
+        DiskFile::new(name, FileType::Del, &[])
+    }
+
+    /// Number of sectors to skip between the sectors of the file. Defaults to the interleave
+    /// of the disk image.
+    pub fn interleave(mut self, interleave: u8) -> Self {
+        self.interleave = Some(interleave);
+        self
+    }
+
+    /// Protect the file from being scratched; shown as `<` in the directory.
+    pub fn locked(mut self) -> Self {
+        self.locked = true;
+        self
+    }
+}
+
+/// Entry in the directory of a disk image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryEntry {
+    /// PETSCII name without padding.
+    pub name: Vec<u8>,
+    pub file_type: FileType,
+    /// Files that weren't written completely are not closed; shown as `*` in the directory.
+    pub closed: bool,
+    pub locked: bool,
+    /// Track of the first sector; 0 when the entry has no data.
+    pub track: u8,
+    pub sector: u8,
+    /// Size in sectors.
+    pub blocks: u16,
+}
+
+impl DirectoryEntry {
+    /// Name as text, using the same notation as [DiskFile] names.
+    pub fn name(&self) -> String {
+        decode_text(&self.name)
+    }
+
+    fn read(bytes: &[u8]) -> DiskResult<DirectoryEntry> {
+        let name_length = bytes[5..5 + NAME_LENGTH]
+            .iter()
+            .position(|byte| *byte == NAME_PADDING)
+            .unwrap_or(NAME_LENGTH);
+        Ok(DirectoryEntry {
+            name: bytes[5..5 + name_length].to_vec(),
+            file_type: FileType::from_code(bytes[2])?,
+            closed: bytes[2] & 0x80 != 0,
+            locked: bytes[2] & 0x40 != 0,
+            track: bytes[3],
+            sector: bytes[4],
+            blocks: u16::from_le_bytes([bytes[30], bytes[31]]),
+        })
+    }
+
+    fn write(&self, bytes: &mut [u8]) {
+        bytes[2] =
+            self.file_type.code() | if self.closed { 0x80 } else { 0x00 } | if self.locked { 0x40 } else { 0x00 };
+        bytes[3] = self.track;
+        bytes[4] = self.sector;
+        bytes[5..5 + NAME_LENGTH].copy_from_slice(&padded_name(&self.name, NAME_LENGTH));
+        bytes[30..32].copy_from_slice(&self.blocks.to_le_bytes());
+    }
+}
+
+impl Display for DirectoryEntry {
+    /// Entry as shown when listing the directory, like `1    "demo"             prg`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = format!("\"{}\"", self.name());
+        let closed = if self.closed { " " } else { "*" };
+        let locked = if self.locked { "<" } else { "" };
+        write!(f, "{:<5}{name:<18}{closed}{}{locked}", self.blocks, self.file_type)
+    }
+}
+
+/// Encode a file or disk name as PETSCII.
+pub(crate) fn encode_name(name: &str, max_length: usize) -> DiskResult<Vec<u8>> {
+    let encoded = encode_text(name).map_err(|error| Error::InvalidName {
+        name: name.to_string(),
+        reason: error.to_string(),
+    })?;
+    if encoded.len() > max_length {
+        return Err(Error::NameTooLong {
+            name: name.to_string(),
+            max_length,
+        });
+    }
+    Ok(encoded)
+}
+
+/// Name padded with shifted spaces.
+pub(crate) fn padded_name(name: &[u8], length: usize) -> Vec<u8> {
+    let mut result = name.to_vec();
+    result.resize(length, NAME_PADDING);
+    result
+}
+
+/// Name without the padding.
+pub(crate) fn unpadded_name(name: &[u8]) -> String {
+    let length = name.iter().position(|byte| *byte == NAME_PADDING).unwrap_or(name.len());
+    decode_text(&name[..length])
+}
+
+/// Is the sector marked as free in the bitmap of a track; bit 0 of the first byte is sector 0.
+pub(crate) fn is_free_in_bitmap(bitmap: &[u8], sector: u8) -> bool {
+    bitmap[sector as usize / 8] & (1 << (sector % 8)) != 0
+}
+
+/// Mark the sector as free or used in the bitmap of a track. Returns the number of free
+/// sectors of the track.
+pub(crate) fn set_free_in_bitmap(bitmap: &mut [u8], sector: u8, free: bool) -> u8 {
+    let bit = 1 << (sector % 8);
+    let byte = &mut bitmap[sector as usize / 8];
+    *byte = if free { *byte | bit } else { *byte & !bit };
+    bitmap.iter().map(|byte| byte.count_ones() as u8).sum()
+}
+
+/// Error code of a sector that can be read without errors.
+pub const NO_ERROR: u8 = 0x01;
+
+/// Sectors of a disk image with an optional error byte per sector.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SectorImage {
+    pub(crate) sectors: Vec<u8>,
+    errors: Option<Vec<u8>>,
+}
+
+impl SectorImage {
+    pub(crate) fn new(sector_count: usize) -> SectorImage {
+        SectorImage {
+            sectors: vec![0x00; sector_count * SECTOR_SIZE],
+            errors: None,
+        }
+    }
+
+    /// Read the sectors, followed by the error bytes when the size includes them. Returns
+    /// `None` when the size doesn't match the number of sectors.
+    pub(crate) fn from_bytes(bytes: &[u8], sector_count: usize) -> Option<SectorImage> {
+        let sectors_size = sector_count * SECTOR_SIZE;
+        if bytes.len() != sectors_size && bytes.len() != sectors_size + sector_count {
+            return None;
+        }
+        Some(SectorImage {
+            sectors: bytes[..sectors_size].to_vec(),
+            errors: (bytes.len() > sectors_size).then(|| bytes[sectors_size..].to_vec()),
+        })
+    }
+
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut result = self.sectors.clone();
+        if let Some(errors) = &self.errors {
+            result.extend(errors);
+        }
+        result
+    }
+
+    pub(crate) fn enable_errors(&mut self) {
+        let sector_count = self.sectors.len() / SECTOR_SIZE;
+        self.errors.get_or_insert_with(|| vec![NO_ERROR; sector_count]);
+    }
+
+    /// Error code of the sector at the given offset in the image.
+    pub(crate) fn error(&self, offset: usize) -> Option<u8> {
+        self.errors.as_ref().map(|errors| errors[offset / SECTOR_SIZE])
+    }
+
+    pub(crate) fn set_error(&mut self, offset: usize, error: u8) {
+        self.enable_errors();
+        if let Some(errors) = &mut self.errors {
+            errors[offset / SECTOR_SIZE] = error;
+        }
+    }
+}
+
+/// Disk image that stores files in sectors of 256 bytes.
+///
+/// The required methods describe the geometry and the block availability map (BAM) of the
+/// format; the provided methods read and write files and the directory.
+pub trait DiskImage {
+    /// Number of tracks, tracks are numbered from 1.
+    fn tracks(&self) -> u8;
+
+    /// Number of sectors of the track, sectors are numbered from 0.
+    fn sectors(&self, track: u8) -> u8;
+
+    /// Track that contains the BAM and the directory; files are stored around this track.
+    fn directory_track(&self) -> u8;
+
+    /// Sector of the directory track that contains the first directory entries.
+    fn first_directory_sector(&self) -> u8;
+
+    /// Tracks that aren't used to store files.
+    fn reserved_tracks(&self) -> Vec<u8> {
+        vec![self.directory_track()]
+    }
+
+    /// Number of sectors to skip between the sectors of a file.
+    fn default_interleave(&self) -> u8;
+
+    /// Number of sectors to skip between the sectors of the directory.
+    fn directory_interleave(&self) -> u8;
+
+    /// Sectors of all tracks, in order.
+    fn image(&self) -> &[u8];
+
+    /// Mutable sectors of all tracks, in order.
+    fn image_mut(&mut self) -> &mut [u8];
+
+    /// Is the sector marked as free in the BAM.
+    fn is_sector_free(&self, track: u8, sector: u8) -> bool;
+
+    /// Mark the sector as free or used in the BAM, updating the free count of the track.
+    fn set_sector_free(&mut self, track: u8, sector: u8, free: bool);
+
+    /// Name of the disk, as shown in the header of the directory.
+    fn disk_name(&self) -> String;
+
+    /// Id of the disk, as shown in the header of the directory.
+    fn disk_id(&self) -> String;
+
+    /// Offset of the sector in [DiskImage::image].
+    fn sector_offset(&self, track: u8, sector: u8) -> DiskResult<usize> {
+        if track == 0 || track > self.tracks() || sector >= self.sectors(track) {
+            return Err(Error::InvalidDiskImage(format!(
+                "track {track} sector {sector} doesn't exist"
+            )));
+        }
+        let previous_sectors = (1..track).map(|track| self.sectors(track) as usize).sum::<usize>();
+        Ok((previous_sectors + sector as usize) * SECTOR_SIZE)
+    }
+
+    /// Bytes of a sector.
+    fn sector(&self, track: u8, sector: u8) -> DiskResult<&[u8]> {
+        let offset = self.sector_offset(track, sector)?;
+        Ok(&self.image()[offset..offset + SECTOR_SIZE])
+    }
+
+    /// Mutable bytes of a sector.
+    fn sector_mut(&mut self, track: u8, sector: u8) -> DiskResult<&mut [u8]> {
+        let offset = self.sector_offset(track, sector)?;
+        Ok(&mut self.image_mut()[offset..offset + SECTOR_SIZE])
+    }
+
+    /// Number of free sectors of a track.
+    fn free_sectors(&self, track: u8) -> u8 {
+        (0..self.sectors(track))
+            .filter(|sector| self.is_sector_free(track, *sector))
+            .count() as u8
+    }
+
+    /// Number of free blocks that can be used to store files.
+    fn blocks_free(&self) -> u16 {
+        let reserved_tracks = self.reserved_tracks();
+        (1..=self.tracks())
+            .filter(|track| !reserved_tracks.contains(track))
+            .map(|track| self.free_sectors(track) as u16)
+            .sum()
+    }
+
+    /// Entries of the directory, in order.
+    fn directory(&self) -> DiskResult<Vec<DirectoryEntry>> {
+        let mut result = vec![];
+        for (track, sector) in self.sector_chain(self.directory_track(), self.first_directory_sector())? {
+            let bytes = self.sector(track, sector)?;
+            for entry in bytes.chunks(ENTRY_SIZE) {
+                if entry[2] != 0x00 {
+                    result.push(DirectoryEntry::read(entry)?);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Listing of the directory as shown by `LOAD"$",8` and `LIST`.
+    fn listing(&self) -> DiskResult<String> {
+        let mut result = format!("0 \"{:<16}\" {}\n", self.disk_name(), self.disk_id());
+        for entry in self.directory()? {
+            result.push_str(&format!("{entry}\n"));
+        }
+        result.push_str(&format!("{} blocks free.\n", self.blocks_free()));
+        Ok(result)
+    }
+
+    /// Find the directory entry of a file.
+    fn find_entry(&self, name: &str) -> DiskResult<DirectoryEntry> {
+        let encoded_name = encode_name(name, NAME_LENGTH)?;
+        self.directory()?
+            .into_iter()
+            .find(|entry| entry.name == encoded_name)
+            .ok_or_else(|| Error::FileNotFound(name.to_string()))
+    }
+
+    /// Read the data of a file.
+    fn read_file(&self, name: &str) -> DiskResult<Vec<u8>> {
+        let entry = self.find_entry(name)?;
+        self.read_entry(&entry)
+    }
+
+    /// Read the data of a directory entry. The data of a partition contains all its sectors.
+    fn read_entry(&self, entry: &DirectoryEntry) -> DiskResult<Vec<u8>> {
+        let mut result = vec![];
+        if entry.track == 0 {
+            return Ok(result);
+        }
+        if entry.file_type == FileType::Cbm {
+            let start = self.sector_offset(entry.track, entry.sector)?;
+            let end = start + entry.blocks as usize * SECTOR_SIZE;
+            return match self.image().get(start..end) {
+                Some(bytes) => Ok(bytes.to_vec()),
+                None => Err(Error::InvalidDiskImage(format!(
+                    "partition '{}' exceeds the disk",
+                    entry.name()
+                ))),
+            };
+        }
+        for (track, sector) in self.sector_chain(entry.track, entry.sector)? {
+            let bytes = self.sector(track, sector)?;
+            let end = if bytes[0] == 0x00 {
+                (bytes[1] as usize + 1).clamp(2, SECTOR_SIZE)
+            } else {
+                SECTOR_SIZE
+            };
+            result.extend(&bytes[2..end]);
+        }
+        Ok(result)
+    }
+
+    /// Sectors that are linked together, starting at the given sector.
+    fn sector_chain(&self, track: u8, sector: u8) -> DiskResult<Vec<(u8, u8)>> {
+        let sector_count = (1..=self.tracks()).map(|track| self.sectors(track) as usize).sum();
+        let mut result = vec![(track, sector)];
+        loop {
+            let (track, sector) = result[result.len() - 1];
+            let bytes = self.sector(track, sector)?;
+            if bytes[0] == 0x00 {
+                return Ok(result);
+            }
+            if result.len() == sector_count {
+                return Err(Error::InvalidDiskImage(format!(
+                    "sectors starting at track {} sector {} are linked in a loop",
+                    result[0].0, result[0].1
+                )));
+            }
+            result.push((bytes[0], bytes[1]));
+        }
+    }
+
+    /// Write a file and add it to the directory.
+    ///
+    /// Like the 1541 the first sector is placed on the free track closest to the directory
+    /// track. The next sectors are placed [DiskFile::interleave] sectors further on the same
+    /// track, moving away from the directory track when the track is full.
+    fn write_file(&mut self, file: &DiskFile) -> DiskResult<DirectoryEntry> {
+        let name = encode_name(&file.name, NAME_LENGTH)?;
+        if self.directory()?.iter().any(|entry| entry.name == name) {
+            return Err(Error::FileExists(file.name.clone()));
+        }
+        let sector_count = if file.file_type == FileType::Del && file.data.is_empty() {
+            0
+        } else {
+            file.data.len().div_ceil(SECTOR_DATA_SIZE).max(1)
+        };
+        if sector_count > self.blocks_free() as usize {
+            return Err(Error::DiskFull(file.name.clone()));
+        }
+        let slot = self.directory_slot(&file.name)?;
+
+        let interleave = file.interleave.unwrap_or(self.default_interleave());
+        let mut sectors: Vec<(u8, u8)> = vec![];
+        for _ in 0..sector_count {
+            let next = self
+                .next_free_sector(sectors.last().copied(), interleave)
+                .ok_or_else(|| Error::DiskFull(file.name.clone()))?;
+            self.set_sector_free(next.0, next.1, false);
+            sectors.push(next);
+        }
+        for (index, (track, sector)) in sectors.iter().enumerate() {
+            let chunk = file.data.chunks(SECTOR_DATA_SIZE).nth(index).unwrap_or(&[]);
+            let link = match sectors.get(index + 1) {
+                Some(next) => [next.0, next.1],
+                None => [0x00, chunk.len() as u8 + 1],
+            };
+            let bytes = self.sector_mut(*track, *sector)?;
+            bytes.fill(0x00);
+            bytes[0..2].copy_from_slice(&link);
+            bytes[2..2 + chunk.len()].copy_from_slice(chunk);
+        }
+
+        let (track, sector) = sectors.first().copied().unwrap_or((0, 0));
+        let entry = DirectoryEntry {
+            name,
+            file_type: file.file_type,
+            closed: true,
+            locked: file.locked,
+            track,
+            sector,
+            blocks: sector_count as u16,
+        };
+        let bytes = self.sector_mut(slot.0, slot.1)?;
+        entry.write(&mut bytes[slot.2..slot.2 + ENTRY_SIZE]);
+        Ok(entry)
+    }
+
+    /// Find an unused directory entry, adding a sector to the directory when all entries are
+    /// used. Returns the sector and the offset of the entry in the sector.
+    fn directory_slot(&mut self, name: &str) -> DiskResult<(u8, u8, usize)> {
+        let chain = self.sector_chain(self.directory_track(), self.first_directory_sector())?;
+        for (track, sector) in &chain {
+            let bytes = self.sector(*track, *sector)?;
+            if let Some(entry) = bytes.chunks(ENTRY_SIZE).position(|entry| entry[2] == 0x00) {
+                return Ok((*track, *sector, entry * ENTRY_SIZE));
+            }
+        }
+
+        let (track, last_sector) = chain[chain.len() - 1];
+        let sectors = self.sectors(track);
+        let start = (last_sector + self.directory_interleave()) % sectors;
+        let sector = (0..sectors)
+            .map(|offset| (start + offset) % sectors)
+            .find(|sector| self.is_sector_free(track, *sector))
+            .ok_or_else(|| Error::DirectoryFull(name.to_string()))?;
+        self.set_sector_free(track, sector, false);
+        self.sector_mut(track, last_sector)?[0..2].copy_from_slice(&[track, sector]);
+        let bytes = self.sector_mut(track, sector)?;
+        bytes.fill(0x00);
+        bytes[1] = 0xFF;
+        Ok((track, sector, 0))
+    }
+
+    /// Next free sector of a file; `None` when the disk is full.
+    fn next_free_sector(&self, previous: Option<(u8, u8)>, interleave: u8) -> Option<(u8, u8)> {
+        let free_sector_from = |track: u8, start: u8| {
+            let sectors = self.sectors(track);
+            (0..sectors)
+                .map(|offset| ((start as u16 + offset as u16) % sectors as u16) as u8)
+                .find(|sector| self.is_sector_free(track, *sector))
+        };
+        let reserved_tracks = self.reserved_tracks();
+        let directory_track = self.directory_track();
+
+        if let Some((previous_track, previous_sector)) = previous {
+            let mut track = previous_track;
+            let start = previous_sector as u16 + interleave as u16;
+            while track >= 1 && track <= self.tracks() && !reserved_tracks.contains(&track) {
+                let start = (start % self.sectors(track) as u16) as u8;
+                if let Some(sector) = free_sector_from(track, start) {
+                    return Some((track, sector));
+                }
+                track = if track < directory_track { track - 1 } else { track + 1 };
+            }
+        }
+
+        let mut tracks = (1..=self.tracks())
+            .filter(|track| !reserved_tracks.contains(track))
+            .collect::<Vec<u8>>();
+        tracks.sort_by_key(|track| (track.abs_diff(directory_track), *track));
+        tracks
+            .into_iter()
+            .find_map(|track| free_sector_from(track, 0).map(|sector| (track, sector)))
+    }
+}
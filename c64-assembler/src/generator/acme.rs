@@ -0,0 +1,129 @@
+use crate::{
+    memory::{expression::BinaryOperator, segment::Segment, Address},
+    validator::AssemblerResult,
+    Application,
+};
+
+use super::{
+    source::{Dialect, SourceEmitter},
+    Generator,
+};
+
+/// Illegal instructions where ACME uses a different mnemonic than the instruction name.
+const ACME_MNEMONICS: [(&str, &str); 1] = [("alr", "asr")];
+
+/// Syntax of the ACME cross-assembler.
+struct Acme;
+
+impl Dialect for Acme {
+    fn header(&self, illegal_opcodes: bool) -> Vec<String> {
+        let cpu = if illegal_opcodes { "6510" } else { "6502" };
+        vec![format!("  !cpu {cpu}")]
+    }
+
+    fn segment(&self, segment: &Segment, named: bool) -> Vec<String> {
+        let mut lines = vec![];
+        if named {
+            lines.push(format!("; --- Segment: {} ---", segment.name.to_uppercase()));
+        }
+        lines.push(format!("  * = ${:04X}", segment.start));
+        if let Some(run_address) = segment.run_address {
+            lines.push(format!("  !pseudopc ${run_address:04X} {{"));
+        }
+        lines
+    }
+
+    fn segment_end(&self, segment: &Segment) -> Vec<String> {
+        match segment.run_address {
+            Some(_) => vec!["  }".to_string()],
+            None => vec![],
+        }
+    }
+
+    /// Labels are written without a colon.
+    fn label(&self, label: &str) -> String {
+        label.to_string()
+    }
+
+    fn mnemonic<'a>(&self, instruction: &'a str) -> &'a str {
+        ACME_MNEMONICS
+            .iter()
+            .find(|(name, _)| *name == instruction)
+            .map_or(instruction, |(_, mnemonic)| mnemonic)
+    }
+
+    fn bytes(&self) -> &'static str {
+        "!byte"
+    }
+
+    fn words(&self) -> &'static str {
+        "!word"
+    }
+
+    fn fill(&self, count: Address, byte: u8) -> String {
+        format!("!fill {count}, ${byte:02X}")
+    }
+
+    /// `!align` only supports powers of two, other alignments are filled.
+    fn align(&self, alignment: Address, padding: usize) -> String {
+        if alignment.is_power_of_two() {
+            format!("!align {}, 0", alignment - 1)
+        } else {
+            format!("!fill {padding}, $00")
+        }
+    }
+
+    /// `^` is the power operator in ACME.
+    fn operator(&self, operator: BinaryOperator) -> &'static str {
+        match operator {
+            BinaryOperator::Xor => " XOR ",
+            operator => operator.symbol(),
+        }
+    }
+}
+
+/// ACME source code generator
+///
+/// Each segment is emitted as `* =` with its start address. Relocated segments are wrapped in
+/// a `!pseudopc` block.
+///
+/// ```
+/// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+/// use c64_assembler::generator::{AcmeGenerator, Generator};
+///
+/// let application = ApplicationBuilder::default()
+///     .module(
+///         ModuleBuilder::default()
+///             .instructions(InstructionBuilder::default().label("loop").raw(&[0x01, 0x02]).rts().build())
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// let source = AcmeGenerator::default().generate(application).unwrap();
+/// assert!(source.contains("  * = $0800\n"));
+/// assert!(source.contains("\nloop\n  !byte $01, $02\n  rts"));
+/// ```
+#[derive(Default, Debug)]
+pub struct AcmeGenerator {
+    annotate_cycles: bool,
+}
+
+impl AcmeGenerator {
+    /// Annotate each instruction with the number of cycles it takes.
+    ///
+    /// See [super::DasmGenerator::annotate_cycles].
+    pub fn annotate_cycles(mut self, annotate_cycles: bool) -> Self {
+        self.annotate_cycles = annotate_cycles;
+        self
+    }
+}
+
+impl Generator for AcmeGenerator {
+    type Output = String;
+
+    fn generate(self, application: Application) -> AssemblerResult<Self::Output> {
+        SourceEmitter::new(Acme, &application)
+            .annotate_cycles(self.annotate_cycles)
+            .emit()
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    memory::{
+        segment::{Segment, DEFAULT_SEGMENT},
+        Address,
+    },
+    validator::AssemblerResult,
+    Application, Function,
+};
+
+use super::{
+    source::{Dialect, SourceEmitter},
+    Generator,
+};
+
+/// Illegal instructions where ca65 uses a different mnemonic than the instruction name.
+const CA65_MNEMONICS: [(&str, &str); 1] = [("sbx", "axs")];
+
+/// Name of the ca65 segment that the default segment is placed in.
+const CA65_DEFAULT_SEGMENT: &str = "CODE";
+
+/// Syntax of ca65, the assembler of the cc65 suite.
+struct Ca65;
+
+impl Dialect for Ca65 {
+    fn header(&self, illegal_opcodes: bool) -> Vec<String> {
+        let cpu = if illegal_opcodes { "6502X" } else { "6502" };
+        vec![format!("  .setcpu \"{cpu}\"")]
+    }
+
+    fn segment(&self, segment: &Segment, _named: bool) -> Vec<String> {
+        let name = if segment.name == DEFAULT_SEGMENT {
+            CA65_DEFAULT_SEGMENT
+        } else {
+            &segment.name
+        };
+        vec![
+            format!("  .segment \"{name}\""),
+            format!("  .org ${:04X}", segment.run_start()),
+        ]
+    }
+
+    fn function_begin(&self, function: &Function) -> Vec<String> {
+        vec![format!(".proc {}", function.name)]
+    }
+
+    fn function_end(&self, _function: &Function) -> Vec<String> {
+        vec![".endproc".to_string()]
+    }
+
+    fn scoped_functions(&self) -> bool {
+        true
+    }
+
+    fn mnemonic<'a>(&self, instruction: &'a str) -> &'a str {
+        CA65_MNEMONICS
+            .iter()
+            .find(|(name, _)| *name == instruction)
+            .map_or(instruction, |(_, mnemonic)| mnemonic)
+    }
+
+    fn bytes(&self) -> &'static str {
+        ".byte"
+    }
+
+    fn words(&self) -> &'static str {
+        ".word"
+    }
+
+    fn fill(&self, count: Address, byte: u8) -> String {
+        format!(".res {count}, ${byte:02X}")
+    }
+
+    /// `.align` requires the alignment of the segment in the linker configuration, so the
+    /// padding is reserved instead.
+    fn align(&self, _alignment: Address, padding: usize) -> String {
+        format!(".res {padding}, $00")
+    }
+
+    fn accumulator(&self) -> &'static str {
+        " a"
+    }
+}
+
+/// ca65 source code generator
+///
+/// Each segment is emitted as `.segment` with an `.org` of the address it runs at; where it
+/// is loaded is up to the linker configuration. The default segment is named `CODE`. Functions are emitted as `.proc`, labels inside a function are
+/// referenced from other scopes as `function::label`.
+///
+/// ```
+/// use c64_assembler::builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder};
+/// use c64_assembler::generator::{Ca65Generator, Generator};
+///
+/// let application = ApplicationBuilder::default()
+///     .module(
+///         ModuleBuilder::default()
+///             .name("main")
+///             .instructions(InstructionBuilder::default().jsr_addr("clear").rts().build())
+///             .function(
+///                 FunctionBuilder::default()
+///                     .name("clear")
+///                     .instructions(InstructionBuilder::default().lda_imm(0x00).rts().build())
+///                     .build(),
+///             )
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// let source = Ca65Generator::default().generate(application).unwrap();
+/// assert!(source.contains("  .segment \"CODE\"\n  .org $0800\n"));
+/// assert!(source.contains(".proc clear\n  lda #$00\n  rts\n.endproc\n"));
+/// ```
+#[derive(Default, Debug)]
+pub struct Ca65Generator {
+    annotate_cycles: bool,
+}
+
+impl Ca65Generator {
+    /// Annotate each instruction with the number of cycles it takes.
+    ///
+    /// See [super::DasmGenerator::annotate_cycles].
+    pub fn annotate_cycles(mut self, annotate_cycles: bool) -> Self {
+        self.annotate_cycles = annotate_cycles;
+        self
+    }
+}
+
+impl Generator for Ca65Generator {
+    type Output = String;
+
+    fn generate(self, application: Application) -> AssemblerResult<Self::Output> {
+        SourceEmitter::new(Ca65, &application)
+            .annotate_cycles(self.annotate_cycles)
+            .emit()
+    }
+}
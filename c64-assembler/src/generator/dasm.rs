@@ -1,200 +1,177 @@
 use crate::{
-    instruction::operation::Operation,
-    memory::{
-        address_mode::{AddressMode, Immediate},
-        define::{Define, Value},
-        user_count::UserCount,
-        ZeroPage,
-    },
+    instruction::Instruction,
+    memory::{expression::BinaryOperator, segment::Segment, Address},
     validator::AssemblerResult,
-    Application, Function, Instructions, Module,
+    Application, Function,
 };
 
-use super::Generator;
+use super::{
+    source::{Dialect, SourceEmitter},
+    Generator,
+};
 
-/// Dasm source code generator
-pub struct DasmGenerator {
-    output: Vec<String>,
-    comment_column: usize,
+/// Illegal instructions where dasm uses a different mnemonic than the instruction name.
+const DASM_MNEMONICS: [(&str, &str); 2] = [("isc", "isb"), ("alr", "asr")];
+
+/// Mnemonic that dasm uses for the given instruction.
+pub(crate) fn dasm_mnemonic(instruction: &str) -> &str {
+    DASM_MNEMONICS
+        .iter()
+        .find(|(name, _)| *name == instruction)
+        .map_or(instruction, |(_, mnemonic)| mnemonic)
 }
 
-impl Default for DasmGenerator {
-    fn default() -> Self {
-        DasmGenerator {
-            output: Vec::<String>::default(),
-            comment_column: 25,
-        }
-    }
+/// Instruction name of the given dasm mnemonic.
+pub(crate) fn from_dasm_mnemonic(mnemonic: &str) -> String {
+    let mnemonic = mnemonic.to_lowercase();
+    DASM_MNEMONICS
+        .iter()
+        .find(|(_, dasm_mnemonic)| *dasm_mnemonic == mnemonic)
+        .map_or(mnemonic.clone(), |(name, _)| name.to_string())
 }
 
-impl Generator for DasmGenerator {
-    type Output = String;
+/// Syntax of dasm.
+#[derive(Default)]
+struct Dasm {
+    fill_byte: u8,
+}
 
-    fn generate(mut self, application: Application) -> AssemblerResult<Self::Output> {
-        self.line(format!("; --- Application: {} ---", application.name.to_uppercase()));
-        self.line("; NOTE: This file is generated, do not modify".to_string());
-        self.line_new();
-        self.line("  processor 6502".to_string());
-        self.line_new();
-
-        for define in &application.defines {
-            if !define.user_empty() {
-                self.add_define(define);
-            }
-        }
-        self.line_new();
-        self.line(format!("  org ${:04X}", application.entry_point));
+impl Dialect for Dasm {
+    fn header(&self, _illegal_opcodes: bool) -> Vec<String> {
+        vec!["  processor 6502".to_string()]
+    }
 
-        for module in &application.modules {
-            self.module(&application, module);
+    fn segment(&self, segment: &Segment, named: bool) -> Vec<String> {
+        let mut lines = vec![];
+        if named {
+            lines.push(format!("  seg {}", segment.name));
         }
-
-        Ok(self.output.join("\n"))
+        if self.fill_byte == 0 {
+            lines.push(format!("  org ${:04X}", segment.start));
+        } else {
+            lines.push(format!("  org ${:04X},${:02X}", segment.start, self.fill_byte));
+        }
+        if let Some(run_address) = segment.run_address {
+            lines.push(format!("  rorg ${run_address:04X}"));
+        }
+        lines
     }
-}
 
-impl DasmGenerator {
-    fn module(&mut self, application: &Application, module: &Module) {
-        self.line_new();
-        self.line(format!("; --- Module begin: {} ---", module.name.to_uppercase()));
-        self.instructions(application, &module.instructions);
-        for function in &module.functions {
-            self.function(application, function);
+    fn segment_end(&self, segment: &Segment) -> Vec<String> {
+        match segment.run_address {
+            Some(_) => vec!["  rend".to_string()],
+            None => vec![],
         }
-        self.line(format!("; --- Module end: {} ---", module.name.to_uppercase()));
     }
 
-    fn function(&mut self, application: &Application, function: &Function) {
-        self.line_new();
-        self.line(format!("; --- Function begin: {} ---", function.name.to_uppercase()));
-        self.line_new();
-        for d in &function.documentation {
-            self.line(format!("; {}", d));
-        }
-        self.line(format!("{}:", function.name));
-        self.instructions(application, &function.instructions);
-
-        self.line(format!("; --- Function end: {} ---", function.name.to_uppercase()));
-    }
-
-    fn instructions(&mut self, _application: &Application, instructions: &Instructions) {
-        for instruction in &instructions.instructions {
-            let mut line: Vec<String> = vec![];
-            if let Operation::Label(_) = &instruction.operation {
-                self.line_new();
-            } else {
-                line.push("  ".to_string());
-            }
-
-            // Add operation
-            match &instruction.operation {
-                Operation::Raw(bytes) => {
-                    line.push(format!("byte ${:02X}", bytes[0]));
-                    for byte in bytes.iter().skip(1) {
-                        line.push(format!(", ${:02X}", byte));
-                    }
-                }
-                Operation::Label(label) => line.push(format!("{}:", label)),
-                _ => {
-                    line.push(instruction.operation.definition().unwrap().instruction.to_string());
-                }
-            }
-
-            // Add address mode
-            match &instruction.address_mode {
-                AddressMode::Implied => {}
-                AddressMode::Immediate(immediate) => match immediate {
-                    Immediate::Byte(byte) => line.push(format!(" #${byte:02X}")),
-                    Immediate::Low(address_reference) => line.push(format!(" #<{}", address_reference.name)),
-                    Immediate::High(address_reference) => line.push(format!(" #>{}", address_reference.name)),
-                },
-                AddressMode::Absolute(address_reference) | AddressMode::Relative(address_reference) => {
-                    line.push(format!(" {}", address_reference.name));
-                    if address_reference.offset != 0 {
-                        line.push(format!("+{}", address_reference.offset));
-                    }
-                }
-                AddressMode::AbsoluteX(address_reference) => {
-                    line.push(format!(" {}", address_reference.name));
-                    if address_reference.offset != 0 {
-                        line.push(format!("+{}", address_reference.offset));
-                    }
-                    line.push(",x".to_string());
-                }
-                AddressMode::AbsoluteY(address_reference) => {
-                    line.push(format!(" {}", address_reference.name));
-                    if address_reference.offset != 0 {
-                        line.push(format!("+{}", address_reference.offset));
-                    }
-                    line.push(",y".to_string());
-                }
-                AddressMode::IndexedIndirect(address_reference) => {
-                    line.push(format!(" ({}", address_reference.name));
-                    if address_reference.offset != 0 {
-                        line.push(format!("+{}", address_reference.offset));
-                    }
-                    line.push("),x".to_string());
-                }
-                AddressMode::IndirectIndexed(address_reference) => {
-                    line.push(format!(" ({}", address_reference.name));
-                    if address_reference.offset != 0 {
-                        line.push(format!("+{}", address_reference.offset));
-                    }
-                    line.push("),y".to_string());
-                }
-                AddressMode::Accumulator => line.push(" A".to_string()),
-                AddressMode::Indirect(address_reference) => {
-                    line.push(format!(" ({}", address_reference.name));
-                    if address_reference.offset != 0 {
-                        line.push(format!("+{}", address_reference.offset));
-                    }
-                    line.push(")".to_string());
-                }
-            }
-
-            if instruction.comments.is_empty() {
-                self.line(line.join(""));
-            } else {
-                let mut line_len = line.join("").len();
-                let add_comments_before =
-                    line_len > self.comment_column || matches!(&instruction.operation, Operation::Label(_label));
-
-                if add_comments_before {
-                    for comment in &instruction.comments {
-                        self.line(format!("  ; {}", comment));
-                    }
-                    self.line(line.join(""))
-                } else {
-                    for comment in &instruction.comments {
-                        let inset = vec![" "; self.comment_column - line_len].join("");
-                        line.push(format!("{}; {}", inset, comment));
-                        self.line(line.join(""));
-                        line.clear();
-                        line_len = 0;
-                    }
-                }
-            }
+    /// Functions with local labels start a new scope for them.
+    fn function_begin(&self, function: &Function) -> Vec<String> {
+        let mut lines = vec![self.label(&function.name)];
+        if !function.local_labels.is_empty() {
+            lines.push("  SUBROUTINE".to_string());
         }
+        lines
     }
 
-    fn add_define(&mut self, define: &Define) {
-        let mut line = vec![];
-        line.push(define.name.clone());
-        line.push("=".to_string());
-        match &define.value {
-            Value::Address(address) => line.push(format!("${:04X}", address)),
-            Value::Zeropage(address) => line.push(format!("${:02X}", address.low())),
-        }
+    fn local_labels(&self) -> bool {
+        true
+    }
+
+    fn mnemonic<'a>(&self, instruction: &'a str) -> &'a str {
+        dasm_mnemonic(instruction)
+    }
+
+    fn bytes(&self) -> &'static str {
+        "byte"
+    }
 
-        self.line(line.join(" "));
+    fn words(&self) -> &'static str {
+        "word"
+    }
+
+    fn fill(&self, count: Address, byte: u8) -> String {
+        format!("ds {count}, ${byte:02X}")
+    }
+
+    fn align(&self, alignment: Address, _padding: usize) -> String {
+        format!("align {alignment}")
+    }
+
+    fn accumulator(&self) -> &'static str {
+        " A"
+    }
+
+    /// Parentheses are reserved for indirect addressing.
+    fn group(&self) -> (&'static str, &'static str) {
+        ("[", "]")
+    }
+
+    fn same_precedence(&self, _operator: BinaryOperator) -> bool {
+        true
     }
 }
 
+/// Dasm source code generator
+#[derive(Default, Debug)]
+pub struct DasmGenerator {
+    annotate_cycles: bool,
+    fill_byte: u8,
+}
+
 impl DasmGenerator {
-    fn line(&mut self, line: String) {
-        self.output.push(line);
+    /// Annotate each instruction with the number of cycles it takes.
+    ///
+    /// The cycles are added in front of the comment of the instruction. When the number of
+    /// cycles depends on page crossing or a branch being taken the range is added (`[4-5]`).
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::generator::{DasmGenerator, Generator};
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .module(
+    ///         ModuleBuilder::default()
+    ///             .instructions(InstructionBuilder::default().lda_imm(0x00).comment("Black").rts().build())
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// let source = DasmGenerator::default().annotate_cycles(true).generate(application).unwrap();
+    /// assert!(source.contains("  lda #$00               ; [2] Black"));
+    /// assert!(source.contains("  rts                    ; [6]"));
+    /// ```
+    pub fn annotate_cycles(mut self, annotate_cycles: bool) -> Self {
+        self.annotate_cycles = annotate_cycles;
+        self
     }
-    fn line_new(&mut self) {
-        self.output.push(String::default());
+
+    /// Byte used by dasm to pad the gaps between segments. Defaults to `0x00`.
+    pub fn fill_byte(mut self, fill_byte: u8) -> Self {
+        self.fill_byte = fill_byte;
+        self
     }
 }
+
+impl Generator for DasmGenerator {
+    type Output = String;
+
+    fn generate(self, application: Application) -> AssemblerResult<Self::Output> {
+        let dialect = Dasm {
+            fill_byte: self.fill_byte,
+        };
+        SourceEmitter::new(dialect, &application)
+            .annotate_cycles(self.annotate_cycles)
+            .emit()
+    }
+}
+
+/// Operation and operand of the instruction in dasm syntax, or the label including its colon.
+///
+/// Address is where the instruction is placed.
+pub(crate) fn dasm_instruction(
+    application: &Application,
+    instruction: &Instruction,
+    address: Address,
+) -> AssemblerResult<String> {
+    SourceEmitter::new(Dasm::default(), application).instruction(instruction, address)
+}
@@ -0,0 +1,126 @@
+use crate::{
+    memory::{define::Define, segment::Segment, Address},
+    validator::AssemblerResult,
+    Application,
+};
+
+use super::{
+    source::{define_value, Dialect, SourceEmitter},
+    Generator,
+};
+
+/// Illegal instructions where KickAssembler uses a different mnemonic than the instruction name.
+const KICK_ASSEMBLER_MNEMONICS: [(&str, &str); 1] = [("sbx", "axs")];
+
+/// Syntax of KickAssembler.
+struct KickAssembler;
+
+impl Dialect for KickAssembler {
+    fn header(&self, illegal_opcodes: bool) -> Vec<String> {
+        let cpu = if illegal_opcodes { "_6502" } else { "_6502NoIllegals" };
+        vec![format!("  .cpu {cpu}")]
+    }
+
+    fn comment(&self) -> &'static str {
+        "//"
+    }
+
+    fn define(&self, define: &Define) -> String {
+        format!(".label {} = {}", define.name, define_value(define))
+    }
+
+    fn segment(&self, segment: &Segment, named: bool) -> Vec<String> {
+        let mut lines = if named {
+            vec![format!("  * = ${:04X} \"{}\"", segment.start, segment.name)]
+        } else {
+            vec![format!("  * = ${:04X}", segment.start)]
+        };
+        if let Some(run_address) = segment.run_address {
+            lines.push(format!("  .pseudopc ${run_address:04X} {{"));
+        }
+        lines
+    }
+
+    fn segment_end(&self, segment: &Segment) -> Vec<String> {
+        match segment.run_address {
+            Some(_) => vec!["  }".to_string()],
+            None => vec![],
+        }
+    }
+
+    fn mnemonic<'a>(&self, instruction: &'a str) -> &'a str {
+        KICK_ASSEMBLER_MNEMONICS
+            .iter()
+            .find(|(name, _)| *name == instruction)
+            .map_or(instruction, |(_, mnemonic)| mnemonic)
+    }
+
+    fn bytes(&self) -> &'static str {
+        ".byte"
+    }
+
+    fn words(&self) -> &'static str {
+        ".word"
+    }
+
+    fn fill(&self, count: Address, byte: u8) -> String {
+        format!(".fill {count}, ${byte:02X}")
+    }
+
+    fn align(&self, alignment: Address, _padding: usize) -> String {
+        format!(".align {alignment}")
+    }
+}
+
+/// KickAssembler source code generator
+///
+/// Each segment is emitted as a memory block (`* = $1000 "music"`), relocated segments are
+/// wrapped in a `.pseudopc` block. Defines are emitted as `.label`.
+///
+/// ```
+/// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+/// use c64_assembler::generator::{Generator, KickAssemblerGenerator};
+///
+/// let application = ApplicationBuilder::default()
+///     .include_vic2_defines()
+///     .module(
+///         ModuleBuilder::default()
+///             .instructions(
+///                 InstructionBuilder::default()
+///                     .lda_imm(0x00)
+///                     .comment("Load black color")
+///                     .sta_addr("VIC2_BORDER_COLOR")
+///                     .build(),
+///             )
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// let source = KickAssemblerGenerator::default().generate(application).unwrap();
+/// assert!(source.contains(".label VIC2_BORDER_COLOR = $D020\n"));
+/// assert!(source.contains("  lda #$00               // Load black color\n"));
+/// ```
+#[derive(Default, Debug)]
+pub struct KickAssemblerGenerator {
+    annotate_cycles: bool,
+}
+
+impl KickAssemblerGenerator {
+    /// Annotate each instruction with the number of cycles it takes.
+    ///
+    /// See [super::DasmGenerator::annotate_cycles].
+    pub fn annotate_cycles(mut self, annotate_cycles: bool) -> Self {
+        self.annotate_cycles = annotate_cycles;
+        self
+    }
+}
+
+impl Generator for KickAssemblerGenerator {
+    type Output = String;
+
+    fn generate(self, application: Application) -> AssemblerResult<Self::Output> {
+        SourceEmitter::new(KickAssembler, &application)
+            .annotate_cycles(self.annotate_cycles)
+            .emit()
+    }
+}
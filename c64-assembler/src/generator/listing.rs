@@ -0,0 +1,248 @@
+use crate::{
+    instruction::{operation::Operation, Instruction},
+    memory::{
+        segment::{Placement, SegmentLayout},
+        Address,
+    },
+    validator::AssemblerResult,
+    Application, Module,
+};
+
+use super::{dasm::dasm_instruction, program::encode_instruction, Generator};
+
+/// Number of bytes that are listed on a single line.
+const BYTES_PER_LINE: usize = 3;
+
+/// Column where the comments of an instruction start.
+const COMMENT_COLUMN: usize = 45;
+
+/// Size of the address space; the free space of the last segment ends here.
+const MEMORY_END: u32 = 0x10000;
+
+/// Listing generator
+///
+/// Lists the address, bytes, cycles and source of every instruction, followed by a summary of
+/// the segments and the addresses of the zeropage variables. The bytes are encoded by the same
+/// code as the [super::ProgramGenerator], so the listing matches the generated program.
+///
+/// ```
+/// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+/// use c64_assembler::generator::{Generator, ListingGenerator};
+///
+/// let application = ApplicationBuilder::default()
+///     .name("Set black border")
+///     .include_vic2_defines()
+///     .module(
+///         ModuleBuilder::default()
+///             .name("main")
+///             .instructions(
+///                 InstructionBuilder::default()
+///                     .label("main_entry_point")
+///                     .lda_imm(0x00)
+///                     .comment("Load black color")
+///                     .sta_addr("VIC2_BORDER_COLOR")
+///                     .rts()
+///                     .build(),
+///             )
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// let listing = ListingGenerator::default().generate(application).unwrap();
+/// assert!(listing.contains("0800                   main_entry_point:\n"));
+/// assert!(listing.contains("0800  A9 00     [2]      lda #$00            ; Load black color\n"));
+/// assert!(listing.contains("0802  8D 20 D0  [4]      sta VIC2_BORDER_COLOR\n"));
+/// assert!(listing.contains("; default      $0800-$0805      6 bytes used, 63482 bytes free"));
+/// ```
+#[derive(Default, Debug)]
+pub struct ListingGenerator {
+    output: Vec<String>,
+}
+
+/// Size of a segment, used in the summary.
+struct SegmentSize {
+    name: String,
+    start: Address,
+    size: u32,
+    max_size: Option<Address>,
+}
+
+impl Generator for ListingGenerator {
+    type Output = String;
+
+    fn generate(mut self, application: Application) -> AssemblerResult<Self::Output> {
+        self.line(format!("; --- Application: {} ---", application.name.to_uppercase()));
+
+        let mut sizes = vec![];
+        for layout in application.layout()? {
+            let size = self.segment(&application, &layout)?;
+            sizes.push(SegmentSize {
+                name: layout.segment.name.clone(),
+                start: layout.segment.start,
+                size,
+                max_size: layout.segment.max_size,
+            });
+        }
+        self.summary(&sizes);
+        self.zeropage_map(&application);
+
+        Ok(self.output.join("\n"))
+    }
+}
+
+impl ListingGenerator {
+    /// List the instructions of a segment, returns the number of bytes of the segment.
+    fn segment(&mut self, application: &Application, layout: &SegmentLayout) -> AssemblerResult<u32> {
+        self.line_new();
+        match layout.segment.run_address {
+            Some(run_address) => self.line(format!(
+                "; --- Segment: {} ${:04X}, runs at ${run_address:04X} ---",
+                layout.segment.name, layout.segment.start
+            )),
+            None => self.line(format!(
+                "; --- Segment: {} ${:04X} ---",
+                layout.segment.name, layout.segment.start
+            )),
+        }
+
+        let mut address = layout.segment.run_start();
+        let mut size = 0;
+        let mut current_module: Option<&Module> = None;
+        for placement in &layout.placements {
+            let module = placement.module();
+            if current_module.is_none_or(|current_module| !std::ptr::eq(current_module, module)) {
+                self.line_new();
+                self.line(format!("; --- Module: {} ---", module.name.to_uppercase()));
+                current_module = Some(module);
+            }
+            if let Placement::Function(_, function) = placement {
+                self.line_new();
+                self.line(format!("; --- Function: {} ---", function.name.to_uppercase()));
+                self.line(listing_line(address, &[], "", &format!("{}:", function.name)));
+            }
+            for instruction in &placement.instructions().instructions {
+                let bytes = encode_instruction(application, instruction, address)?;
+                self.instruction(application, instruction, address, &bytes)?;
+                address = address.wrapping_add(bytes.len() as Address);
+                size += bytes.len() as u32;
+            }
+        }
+        Ok(size)
+    }
+
+    fn instruction(
+        &mut self,
+        application: &Application,
+        instruction: &Instruction,
+        address: Address,
+        bytes: &[u8],
+    ) -> AssemblerResult<()> {
+        let cycles = match instruction.timing(application, address)? {
+            Some(timing) if timing.min_cycles == timing.max_cycles => format!("[{}]", timing.min_cycles),
+            Some(timing) => format!("[{}-{}]", timing.min_cycles, timing.max_cycles),
+            None => String::new(),
+        };
+        let mut source = dasm_instruction(application, instruction, address)?;
+        if !matches!(instruction.operation, Operation::Label(_)) {
+            source = format!("  {source}");
+        }
+
+        let mut chunks = bytes.chunks(BYTES_PER_LINE);
+        let line = listing_line(address, chunks.next().unwrap_or_default(), &cycles, &source);
+        let mut comments = instruction.comments.iter();
+        match comments.next() {
+            Some(comment) => self.line(with_comment(line, comment)),
+            None => self.line(line),
+        }
+        for comment in comments {
+            self.line(with_comment(String::new(), comment));
+        }
+
+        let mut continuation_address = address;
+        for chunk in chunks {
+            continuation_address = continuation_address.wrapping_add(BYTES_PER_LINE as Address);
+            self.line(format!("{continuation_address:04X}  {}", hex_bytes(chunk)));
+        }
+        Ok(())
+    }
+
+    fn summary(&mut self, sizes: &[SegmentSize]) {
+        self.line_new();
+        self.line("; --- Segments ---".to_string());
+        for (index, size) in sizes.iter().enumerate() {
+            let end = size.start as u32 + size.size;
+            let free = match size.max_size {
+                Some(max_size) => (max_size as u32).saturating_sub(size.size),
+                None => sizes
+                    .get(index + 1)
+                    .map_or(MEMORY_END, |next| next.start as u32)
+                    .saturating_sub(end),
+            };
+            let range = if size.size == 0 {
+                format!("${:04X}", size.start)
+            } else {
+                format!("${:04X}-${:04X}", size.start, end - 1)
+            };
+            self.line(format!(
+                "; {:<12} {:<12} {:>5} bytes used, {:>5} bytes free",
+                size.name, range, size.size, free
+            ));
+        }
+    }
+
+    /// List the addresses that were assigned to the zeropage variables.
+    fn zeropage_map(&mut self, application: &Application) {
+        if application.zeropage_map.is_empty() {
+            return;
+        }
+        self.line_new();
+        self.line("; --- Zeropage ---".to_string());
+        for allocation in &application.zeropage_map {
+            let range = match allocation.kind.byte_size() {
+                1 => format!("${:02X}", allocation.address),
+                byte_size => format!(
+                    "${:02X}-${:02X}",
+                    allocation.address,
+                    allocation.address + byte_size - 1
+                ),
+            };
+            self.line(format!(
+                "; {:<8} {:<8} {} ({})",
+                range, allocation.kind, allocation.name, allocation.module
+            ));
+        }
+    }
+}
+
+/// Line with the address, the first bytes, cycles and source of an instruction.
+fn listing_line(address: Address, bytes: &[u8], cycles: &str, source: &str) -> String {
+    format!("{address:04X}  {:<8}  {cycles:<5}  {source}", hex_bytes(bytes))
+        .trim_end()
+        .to_string()
+}
+
+/// Append a comment at the comment column, or directly after the line when it is too long.
+fn with_comment(line: String, comment: &str) -> String {
+    if line.len() < COMMENT_COLUMN {
+        format!("{line:<COMMENT_COLUMN$}; {comment}")
+    } else {
+        format!("{line} ; {comment}")
+    }
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+impl ListingGenerator {
+    fn line(&mut self, line: String) {
+        self.output.push(line);
+    }
+    fn line_new(&mut self) {
+        self.output.push(String::default());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    memory::memory_map::{MemoryMap, MemoryRange},
+    validator::AssemblerResult,
+    Application,
+};
+
+use super::{symbols::json_string, Generator};
+
+/// Format of the report generated by the [MemoryMapGenerator].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryMapFormat {
+    /// Line per range, followed by the overlaps and forbidden regions.
+    #[default]
+    Text,
+    /// JSON object with the ranges, overlaps and forbidden regions.
+    Json,
+}
+
+/// Memory map generator
+///
+/// Reports the memory range of each module and function, the ranges that overlap and the
+/// ranges that are placed in a forbidden region. See [Application::memory_map].
+///
+/// ```
+/// use c64_assembler::builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder};
+/// use c64_assembler::generator::{Generator, MemoryMapGenerator};
+///
+/// let application = ApplicationBuilder::default()
+///     .module(
+///         ModuleBuilder::default()
+///             .name("main")
+///             .instructions(InstructionBuilder::default().jsr_addr("clear").rts().build())
+///             .function(
+///                 FunctionBuilder::default()
+///                     .name("clear")
+///                     .instructions(InstructionBuilder::default().lda_imm(0x00).rts().build())
+///                     .build(),
+///             )
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// let report = MemoryMapGenerator::default().generate(application).unwrap();
+/// assert_eq!(
+///     "; --- Memory map ---\n\
+///      $0800-$0803      4  default      main\n\
+///      $0804-$0806      3  default      main.clear\n",
+///     report
+/// );
+/// ```
+#[derive(Default, Debug)]
+pub struct MemoryMapGenerator {
+    format: MemoryMapFormat,
+}
+
+impl MemoryMapGenerator {
+    /// Format of the report. Defaults to [MemoryMapFormat::Text].
+    pub fn format(mut self, format: MemoryMapFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+impl Generator for MemoryMapGenerator {
+    type Output = String;
+
+    fn generate(self, application: Application) -> AssemblerResult<Self::Output> {
+        let memory_map = application.memory_map()?;
+        let lines = match self.format {
+            MemoryMapFormat::Text => text(&memory_map),
+            MemoryMapFormat::Json => json(&memory_map),
+        };
+        Ok(lines.iter().map(|line| format!("{line}\n")).collect())
+    }
+}
+
+fn text(memory_map: &MemoryMap) -> Vec<String> {
+    let mut lines = vec!["; --- Memory map ---".to_string()];
+    for range in &memory_map.ranges {
+        lines.push(format!(
+            "${:04X}-${:04X} {:>6}  {:<12} {}",
+            range.start,
+            range.last(),
+            range.byte_size,
+            range.segment,
+            range.scoped_name()
+        ));
+    }
+
+    let errors = memory_map.errors();
+    if !errors.is_empty() {
+        lines.push(String::new());
+        lines.push("; --- Problems ---".to_string());
+        lines.extend(errors.iter().map(|(_, error)| format!("; {error}")));
+    }
+    lines
+}
+
+fn json(memory_map: &MemoryMap) -> Vec<String> {
+    let ranges = memory_map
+        .ranges
+        .iter()
+        .map(|range| {
+            format!(
+                "{{\"module\": {}, \"function\": {}, \"segment\": {}, \"start\": {}, \"end\": {}, \"byte_size\": {}}}",
+                json_string(Some(&range.module)),
+                json_string(range.function.as_ref()),
+                json_string(Some(&range.segment)),
+                range.start,
+                range.last(),
+                range.byte_size
+            )
+        })
+        .collect::<Vec<String>>();
+    let overlaps = memory_map
+        .overlaps()
+        .iter()
+        .map(|(first, second)| {
+            format!(
+                "{{\"first\": {}, \"second\": {}, \"address\": {}}}",
+                json_name(first),
+                json_name(second),
+                second.start
+            )
+        })
+        .collect::<Vec<String>>();
+    let forbidden_regions = memory_map
+        .forbidden_writes()
+        .iter()
+        .map(|(range, region)| {
+            format!(
+                "{{\"name\": {}, \"region\": {}, \"address\": {}}}",
+                json_name(range),
+                json_string(Some(&region.name)),
+                range.start.max(region.start)
+            )
+        })
+        .collect::<Vec<String>>();
+
+    let sections = [
+        ("ranges", ranges),
+        ("overlaps", overlaps),
+        ("forbidden_regions", forbidden_regions),
+    ]
+    .into_iter()
+    .map(|(key, objects)| {
+        if objects.is_empty() {
+            format!("  \"{key}\": []")
+        } else {
+            let objects = objects
+                .iter()
+                .map(|object| format!("    {object}"))
+                .collect::<Vec<String>>();
+            format!("  \"{key}\": [\n{}\n  ]", objects.join(",\n"))
+        }
+    })
+    .collect::<Vec<String>>();
+    vec!["{".to_string(), sections.join(",\n"), "}".to_string()]
+}
+
+fn json_name(range: &MemoryRange) -> String {
+    json_string(Some(&range.scoped_name()))
+}
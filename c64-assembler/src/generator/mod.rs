@@ -1,8 +1,15 @@
-//! Generators to export to a .PRG or source code.
+//! Generators to export to a .PRG, source code, listing, symbol table or memory map.
 use crate::{validator::AssemblerResult, Application};
 
+mod acme;
+mod ca65;
 mod dasm;
+mod kick_assembler;
+mod listing;
+mod memory_map;
 mod program;
+mod source;
+mod symbols;
 
 /// Generate an output for a given application.
 pub trait Generator {
@@ -12,5 +19,11 @@ pub trait Generator {
     fn generate(self, application: Application) -> AssemblerResult<Self::Output>;
 }
 
+pub use acme::*;
+pub use ca65::*;
 pub use dasm::*;
+pub use kick_assembler::*;
+pub use listing::*;
+pub use memory_map::*;
 pub use program::*;
+pub use symbols::*;
@@ -1,45 +1,94 @@
 use c64_assembler_6502::{
     instruction::InstructionDef,
-    opcodes::{NO_ZEROPAGE, NO_ZEROPAGE_X, NO_ZEROPAGE_Y},
+    opcodes::{OpCode, NO_ABSOLUTE, NO_ABSOLUTE_X, NO_ABSOLUTE_Y, NO_ZEROPAGE, NO_ZEROPAGE_X, NO_ZEROPAGE_Y},
 };
 
 use crate::{
     instruction::{operation::Operation, Instruction},
     memory::{
         address_mode::{AddressMode, Immediate},
+        label::AddressReference,
+        segment::{Segment, SegmentLayout},
         Address, ZeroPage,
     },
     validator::{AssemblerResult, Error},
-    Application, Instructions, Module,
+    Application, Instructions,
 };
 
 use super::Generator;
 
 const PROGRAM_HEADER_BYTE_SIZE: Address = 2;
+/// Address after the last byte of memory.
+const MEMORY_END: u32 = 0x10000;
 
 /// .PRG byte code generator
+///
+/// Segments are combined into a single program. Gaps between segments are padded with the
+/// fill byte. Use [SegmentProgramGenerator] to generate a program per segment.
 #[derive(Default, Debug)]
 pub struct ProgramGenerator {
     output: Vec<u8>,
+    load_address: Address,
+    fill_byte: u8,
+    /// Distance between the address a segment runs at and the address it is loaded at.
+    relocation: Address,
 }
 
 impl Generator for ProgramGenerator {
     type Output = Vec<u8>;
 
     fn generate(mut self, application: Application) -> AssemblerResult<Self::Output> {
-        self.add_u16(application.entry_point);
-        for module in &application.modules {
-            self.generate_module(&application, module)?;
+        let layouts = application.layout()?;
+        self.load_address = layouts
+            .first()
+            .map_or(application.entry_point, |layout| layout.segment.start);
+        self.add_u16(self.load_address);
+
+        let mut previous_segment: Option<&Segment> = None;
+        for layout in &layouts {
+            let end_address = self.end_address();
+            if (layout.segment.start as u32) < end_address {
+                let previous_segment = previous_segment.map_or(String::new(), |segment| segment.name.clone());
+                return Err(Error::SegmentOverlap(previous_segment, layout.segment.name.clone()));
+            }
+            for _ in end_address..layout.segment.start as u32 {
+                self.add_u8(self.fill_byte);
+            }
+            self.generate_layout(&application, layout)?;
+            previous_segment = Some(&layout.segment);
         }
         Ok(self.output)
     }
 }
 
 impl ProgramGenerator {
-    fn generate_module(&mut self, application: &Application, module: &Module) -> AssemblerResult<()> {
-        self.generate_instructions(application, &module.instructions)?;
-        for function in &module.functions {
-            self.generate_instructions(application, &function.instructions)?;
+    /// Byte used to pad the gaps between segments. Defaults to `0x00`.
+    pub fn fill_byte(mut self, fill_byte: u8) -> Self {
+        self.fill_byte = fill_byte;
+        self
+    }
+
+    fn current_address(&self) -> Address {
+        self.end_address() as Address
+    }
+
+    /// Address after the last byte that is generated, can be past the end of memory.
+    fn end_address(&self) -> u32 {
+        self.load_address as u32 + (self.output.len() as u32 - PROGRAM_HEADER_BYTE_SIZE as u32)
+    }
+
+    /// Address of the next instruction when the segment runs.
+    fn instruction_address(&self) -> Address {
+        self.current_address().wrapping_add(self.relocation)
+    }
+
+    fn generate_layout(&mut self, application: &Application, layout: &SegmentLayout) -> AssemblerResult<()> {
+        self.relocation = layout.segment.run_start().wrapping_sub(layout.segment.start);
+        for placement in &layout.placements {
+            self.generate_instructions(application, placement.instructions())?;
+        }
+        if self.end_address() > MEMORY_END {
+            return Err(Error::SegmentOutOfMemory(layout.segment.name.clone()));
         }
         Ok(())
     }
@@ -58,8 +107,11 @@ impl ProgramGenerator {
                 // Labels don't have bytes in the byte stream, they are only markers
                 Ok(())
             }
-            (None, Operation::Raw(bytes)) => {
-                self.add_bytes(bytes);
+            (None, operation) if operation.is_data() => {
+                let bytes = operation
+                    .data_bytes(application, self.instruction_address())?
+                    .ok_or(Error::InternalCompilerError)?;
+                self.add_bytes(&bytes);
                 Ok(())
             }
 
@@ -73,79 +125,98 @@ impl ProgramGenerator {
         address_mode: &AddressMode,
         instruction: &InstructionDef,
     ) -> AssemblerResult<()> {
+        // Report unsupported addressing modes before emitting any bytes.
+        if !address_mode.is_supported_by(instruction) {
+            return Err(Error::AddressModeNotSupported {
+                instruction: instruction.instruction.to_string(),
+                address_mode: address_mode.name().to_string(),
+            });
+        }
         match address_mode {
             AddressMode::Implied => {
-                self.add_u8(instruction.implied);
+                self.add_opcode(instruction.implied)?;
             }
             AddressMode::Immediate(Immediate::Byte(byte)) => {
-                self.add_u8(instruction.immediate);
+                self.add_opcode(instruction.immediate)?;
                 self.add_u8(*byte);
             }
             AddressMode::Immediate(Immediate::Low(address_reference)) => {
-                self.add_u8(instruction.immediate);
-                self.add_u8(application.address(address_reference).low());
+                self.add_opcode(instruction.immediate)?;
+                self.add_u8(application.address(address_reference)?.low());
             }
             AddressMode::Immediate(Immediate::High(address_reference)) => {
-                self.add_u8(instruction.immediate);
-                self.add_u8(application.address(address_reference).high());
+                self.add_opcode(instruction.immediate)?;
+                self.add_u8(application.address(address_reference)?.high());
+            }
+            AddressMode::Immediate(Immediate::Expression(expression)) => {
+                self.add_opcode(instruction.immediate)?;
+                self.add_u8(expression.evaluate(application)? as u8);
             }
             AddressMode::Accumulator => {
-                self.add_u8(instruction.accumulator);
+                self.add_opcode(instruction.accumulator)?;
             }
             AddressMode::Absolute(address_reference) => {
-                let address = application.address(address_reference);
+                let address = application.address(address_reference)?;
                 if instruction.zeropage != NO_ZEROPAGE && address.is_zeropage() {
-                    self.add_u8(instruction.zeropage);
-                    self.add_u8(application.address(address_reference).low());
+                    self.add_opcode(instruction.zeropage)?;
+                    self.add_u8(address.low());
+                } else if instruction.absolute == NO_ABSOLUTE {
+                    return Err(zeropage_required(instruction, address_reference, address));
                 } else {
-                    self.add_u8(instruction.absolute);
+                    self.add_opcode(instruction.absolute)?;
                     self.add_u16(address);
                 }
             }
             AddressMode::AbsoluteX(address_reference) => {
-                let address = application.address(address_reference);
+                let address = application.address(address_reference)?;
                 if instruction.zeropage_x != NO_ZEROPAGE_X && address.is_zeropage() {
-                    self.add_u8(instruction.zeropage_x);
+                    self.add_opcode(instruction.zeropage_x)?;
                     self.add_u8(address.low());
+                } else if instruction.absolute_x == NO_ABSOLUTE_X {
+                    return Err(zeropage_required(instruction, address_reference, address));
                 } else {
-                    self.add_u8(instruction.absolute_x);
+                    self.add_opcode(instruction.absolute_x)?;
                     self.add_u16(address);
                 }
             }
             AddressMode::AbsoluteY(address_reference) => {
-                let address = application.address(address_reference);
+                let address = application.address(address_reference)?;
                 if instruction.zeropage_y != NO_ZEROPAGE_Y && address.is_zeropage() {
-                    self.add_u8(instruction.zeropage_y);
+                    self.add_opcode(instruction.zeropage_y)?;
                     self.add_u8(address.low());
+                } else if instruction.absolute_y == NO_ABSOLUTE_Y {
+                    return Err(zeropage_required(instruction, address_reference, address));
                 } else {
-                    self.add_u8(instruction.absolute_y);
+                    self.add_opcode(instruction.absolute_y)?;
                     self.add_u16(address);
                 }
             }
             AddressMode::Relative(address_reference) => {
-                let current_instruction =
-                    application.entry_point + self.output.len() as Address - PROGRAM_HEADER_BYTE_SIZE;
-                let address = application.address(address_reference);
+                let current_instruction = self.instruction_address();
+                let address = application.address(address_reference)?;
                 let next_instruction = current_instruction + address_mode.byte_size(application)?;
-                let relative_address = (address as i32 - next_instruction as i32) as i8;
+                let distance = address as i32 - next_instruction as i32;
+                let relative_address = i8::try_from(distance).map_err(|_| Error::BranchOutOfRange {
+                    target: address_reference.expression.to_string(),
+                    distance,
+                })?;
 
-                self.add_u8(instruction.relative);
+                self.add_opcode(instruction.relative)?;
                 self.add_u8(relative_address as u8);
             }
             AddressMode::Indirect(address_reference) => {
-                let address = application.address(address_reference);
-                self.add_u8(instruction.indirect);
+                let address = application.address(address_reference)?;
+                self.add_opcode(instruction.indirect)?;
                 self.add_u16(address);
             }
             AddressMode::IndexedIndirect(address_reference) => {
-                let address = application.address(address_reference);
-                assert!(address.is_zeropage());
-                self.add_u8(instruction.indexed_indirect);
+                let address = zeropage_address(application, instruction, address_reference)?;
+                self.add_opcode(instruction.indexed_indirect)?;
                 self.add_u8(address.low());
             }
             AddressMode::IndirectIndexed(address_reference) => {
-                let address = application.address(address_reference);
-                self.add_u8(instruction.indirect_indexed);
+                let address = zeropage_address(application, instruction, address_reference)?;
+                self.add_opcode(instruction.indirect_indexed)?;
                 self.add_u8(address.low());
             }
         };
@@ -153,11 +224,56 @@ impl ProgramGenerator {
     }
 }
 
+/// Bytes of a single instruction when it is placed at the given address.
+pub(crate) fn encode_instruction(
+    application: &Application,
+    instruction: &Instruction,
+    address: Address,
+) -> AssemblerResult<Vec<u8>> {
+    let mut generator = ProgramGenerator {
+        load_address: address,
+        ..Default::default()
+    };
+    generator.add_u16(address);
+    generator.generate_instruction(application, instruction)?;
+    Ok(generator.output.split_off(PROGRAM_HEADER_BYTE_SIZE as usize))
+}
+
+/// Evaluate an address that must be in the zeropage.
+fn zeropage_address(
+    application: &Application,
+    instruction: &InstructionDef,
+    address_reference: &AddressReference,
+) -> AssemblerResult<Address> {
+    let value = address_reference.expression.evaluate(application)?;
+    if !(0..=0xFF).contains(&value) {
+        return Err(zeropage_required(instruction, address_reference, value));
+    }
+    Ok(value as Address)
+}
+
+fn zeropage_required(
+    instruction: &InstructionDef,
+    address_reference: &AddressReference,
+    value: impl Into<i64>,
+) -> Error {
+    Error::ZeropageAddressRequired {
+        instruction: instruction.instruction.to_string(),
+        expression: address_reference.expression.to_string(),
+        value: value.into(),
+    }
+}
+
 impl ProgramGenerator {
     fn add_u8(&mut self, byte: u8) {
         self.output.push(byte);
     }
 
+    fn add_opcode(&mut self, opcode: Option<OpCode>) -> AssemblerResult<()> {
+        self.add_u8(opcode.ok_or(Error::InternalCompilerError)?);
+        Ok(())
+    }
+
     fn add_u16(&mut self, address: Address) {
         self.add_u8(address.low());
         self.add_u8(address.high());
@@ -170,6 +286,62 @@ impl ProgramGenerator {
     }
 }
 
+/// Program generated for a single segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentProgram {
+    /// Name of the segment.
+    pub segment: String,
+    /// .PRG bytes of the segment, starting with the load address of the segment.
+    pub bytes: Vec<u8>,
+}
+
+/// .PRG byte code generator that generates a separate program for each segment.
+///
+/// ```
+/// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+/// use c64_assembler::generator::{Generator, SegmentProgramGenerator};
+/// use c64_assembler::memory::segment::Segment;
+///
+/// let application = ApplicationBuilder::default()
+///     .segment(Segment::new("music", 0x1000))
+///     .module(ModuleBuilder::default().instructions(InstructionBuilder::default().rts().build()).build())
+///     .module(
+///         ModuleBuilder::default()
+///             .name("music")
+///             .segment("music")
+///             .instructions(InstructionBuilder::default().raw(&[0x01, 0x02]).build())
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// let programs = SegmentProgramGenerator::default().generate(application).unwrap();
+/// assert_eq!(vec![0x00, 0x08, 0x60], programs[0].bytes);
+/// assert_eq!(vec![0x00, 0x10, 0x01, 0x02], programs[1].bytes);
+/// ```
+#[derive(Default, Debug)]
+pub struct SegmentProgramGenerator {}
+
+impl Generator for SegmentProgramGenerator {
+    type Output = Vec<SegmentProgram>;
+
+    fn generate(self, application: Application) -> AssemblerResult<Self::Output> {
+        let mut result = vec![];
+        for layout in application.layout()? {
+            let mut generator = ProgramGenerator {
+                load_address: layout.segment.start,
+                ..Default::default()
+            };
+            generator.add_u16(layout.segment.start);
+            generator.generate_layout(&application, &layout)?;
+            result.push(SegmentProgram {
+                segment: layout.segment.name,
+                bytes: generator.output,
+            });
+        }
+        Ok(result)
+    }
+}
+
 /// Utility function to print the set of bytes into a hexdump kind of format to the console.
 pub fn print_hexdump(bytes: &[u8]) {
     let mut address = 0;
@@ -0,0 +1,530 @@
+use std::collections::HashMap;
+
+use crate::{
+    instruction::{data::Word, operation::Operation, Instruction},
+    memory::{
+        address_mode::{AddressMode, Immediate},
+        define::{Define, Value},
+        expression::{BinaryOperator, Expression},
+        segment::{Placement, Segment, SegmentLayout, DEFAULT_SEGMENT},
+        user_count::UserCount,
+        Address, ZeroPage,
+    },
+    validator::{AssemblerResult, Error},
+    Application, Function, Instructions, Module,
+};
+
+/// Column where the comments of an instruction start.
+const COMMENT_COLUMN: usize = 25;
+
+/// Syntax of the assembler that a source generator emits.
+///
+/// The [SourceEmitter] walks the application and asks the dialect how to write each part.
+pub(crate) trait Dialect {
+    /// Directives at the start of the source, like selecting the processor.
+    fn header(&self, illegal_opcodes: bool) -> Vec<String>;
+
+    /// Start of a single line comment.
+    fn comment(&self) -> &'static str {
+        ";"
+    }
+
+    fn define(&self, define: &Define) -> String {
+        format!("{} = {}", define.name, define_value(define))
+    }
+
+    /// Directives that place the next instructions in the segment. `named` is false when the
+    /// application only uses the default segment.
+    fn segment(&self, segment: &Segment, named: bool) -> Vec<String>;
+
+    /// Lines that end a segment, for example to end the relocation of a segment that runs at
+    /// another address than it is loaded at.
+    fn segment_end(&self, _segment: &Segment) -> Vec<String> {
+        vec![]
+    }
+
+    /// Lines that start a function, after its documentation.
+    fn function_begin(&self, function: &Function) -> Vec<String> {
+        vec![self.label(&function.name)]
+    }
+
+    /// Lines that end a function.
+    fn function_end(&self, _function: &Function) -> Vec<String> {
+        vec![]
+    }
+
+    /// Labels inside a function are local to that function; other scopes refer to them as
+    /// `function::label`.
+    fn scoped_functions(&self) -> bool {
+        false
+    }
+
+    /// Function-local labels are written as local labels (`.loop`) instead of their unique name.
+    fn local_labels(&self) -> bool {
+        false
+    }
+
+    fn label(&self, label: &str) -> String {
+        format!("{label}:")
+    }
+
+    /// Mnemonic of the given instruction.
+    fn mnemonic<'a>(&self, instruction: &'a str) -> &'a str {
+        instruction
+    }
+
+    /// Directive for a list of bytes.
+    fn bytes(&self) -> &'static str;
+
+    /// Directive for a list of words.
+    fn words(&self) -> &'static str;
+
+    fn fill(&self, count: Address, byte: u8) -> String;
+
+    /// `padding` is the number of bytes between the current address and the alignment.
+    fn align(&self, alignment: Address, padding: usize) -> String;
+
+    /// Operand of instructions using the accumulator addressing mode.
+    fn accumulator(&self) -> &'static str {
+        ""
+    }
+
+    /// Characters that group a sub-expression.
+    fn group(&self) -> (&'static str, &'static str) {
+        ("(", ")")
+    }
+
+    fn operator(&self, operator: BinaryOperator) -> &'static str {
+        operator.symbol()
+    }
+
+    /// Does the assembler use the same precedence for this operator as [BinaryOperator::precedence].
+    ///
+    /// Sub-expressions using other operators are always grouped. Assemblers agree on the
+    /// precedence of the arithmetic operators, but not on the precedence of the bitwise ones.
+    fn same_precedence(&self, operator: BinaryOperator) -> bool {
+        matches!(
+            operator,
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply | BinaryOperator::Divide
+        )
+    }
+}
+
+/// Value of a define as hexadecimal number.
+pub(crate) fn define_value(define: &Define) -> String {
+    match &define.value {
+        Value::Address(address) => format!("${:04X}", address),
+        Value::Zeropage(address) => format!("${:02X}", address.low()),
+    }
+}
+
+/// Does the application use illegal instructions.
+fn uses_illegal_opcodes(application: &Application) -> bool {
+    application.modules.iter().any(|module| {
+        std::iter::once(&module.instructions)
+            .chain(module.functions.iter().map(|function| &function.instructions))
+            .flat_map(|instructions| &instructions.instructions)
+            .any(|instruction| instruction.operation.is_illegal())
+    })
+}
+
+/// Emits the source of an application in the syntax of a [Dialect].
+pub(crate) struct SourceEmitter<'a, D: Dialect> {
+    dialect: D,
+    application: &'a Application,
+    output: Vec<String>,
+    annotate_cycles: bool,
+    /// Address of the instruction that is being generated.
+    address: Address,
+    /// Function that each label of a function belongs to; only filled for scoped dialects.
+    label_functions: HashMap<&'a str, &'a str>,
+    /// Function that is being generated.
+    function: Option<&'a Function>,
+}
+
+impl<'a, D: Dialect> SourceEmitter<'a, D> {
+    pub(crate) fn new(dialect: D, application: &'a Application) -> Self {
+        let mut label_functions = HashMap::new();
+        if dialect.scoped_functions() {
+            for function in application.modules.iter().flat_map(|module| &module.functions) {
+                for instruction in &function.instructions.instructions {
+                    if let Operation::Label(label) = &instruction.operation {
+                        label_functions.insert(label.as_str(), function.name.as_str());
+                    }
+                }
+            }
+        }
+        SourceEmitter {
+            dialect,
+            application,
+            output: vec![],
+            annotate_cycles: false,
+            address: 0,
+            label_functions,
+            function: None,
+        }
+    }
+
+    /// Annotate each instruction with the number of cycles it takes.
+    pub(crate) fn annotate_cycles(mut self, annotate_cycles: bool) -> Self {
+        self.annotate_cycles = annotate_cycles;
+        self
+    }
+
+    pub(crate) fn emit(mut self) -> AssemblerResult<String> {
+        let application = self.application;
+        let comment = self.dialect.comment();
+        self.line(format!(
+            "{comment} --- Application: {} ---",
+            application.name.to_uppercase()
+        ));
+        self.line(format!("{comment} NOTE: This file is generated, do not modify"));
+        self.line_new();
+        for line in self.dialect.header(uses_illegal_opcodes(application)) {
+            self.line(line);
+        }
+        self.line_new();
+
+        for define in &application.defines {
+            if !define.user_empty() {
+                self.line(self.dialect.define(define));
+            }
+        }
+
+        let layouts = application.layout()?;
+        if layouts.is_empty() {
+            self.segment_begin(&Segment::new(DEFAULT_SEGMENT, application.entry_point), false);
+        }
+        for layout in &layouts {
+            let named = layouts.len() > 1 || layout.segment.name != DEFAULT_SEGMENT;
+            self.segment(layout, named)?;
+        }
+
+        Ok(self.output.join("\n"))
+    }
+
+    fn segment_begin(&mut self, segment: &Segment, named: bool) {
+        self.line_new();
+        for line in self.dialect.segment(segment, named) {
+            self.line(line);
+        }
+    }
+
+    fn segment(&mut self, layout: &SegmentLayout<'a>, named: bool) -> AssemblerResult<()> {
+        self.segment_begin(&layout.segment, named);
+
+        self.address = layout.segment.run_start();
+        let mut current_module: Option<&Module> = None;
+        for placement in &layout.placements {
+            let module = placement.module();
+            if current_module.is_none_or(|current_module| !std::ptr::eq(current_module, module)) {
+                if let Some(current_module) = current_module {
+                    self.module_end(current_module);
+                }
+                self.module_begin(module);
+                current_module = Some(module);
+            }
+            match placement {
+                Placement::Module(module) => self.instructions(&module.instructions)?,
+                Placement::Function(_, function) => self.function(function)?,
+            }
+        }
+        if let Some(current_module) = current_module {
+            self.module_end(current_module);
+        }
+        for line in self.dialect.segment_end(&layout.segment) {
+            self.line(line);
+        }
+        Ok(())
+    }
+
+    fn module_begin(&mut self, module: &Module) {
+        self.line_new();
+        self.line(format!(
+            "{} --- Module begin: {} ---",
+            self.dialect.comment(),
+            module.name.to_uppercase()
+        ));
+    }
+
+    fn module_end(&mut self, module: &Module) {
+        self.line(format!(
+            "{} --- Module end: {} ---",
+            self.dialect.comment(),
+            module.name.to_uppercase()
+        ));
+    }
+
+    fn function(&mut self, function: &'a Function) -> AssemblerResult<()> {
+        let comment = self.dialect.comment();
+        self.line_new();
+        self.line(format!(
+            "{comment} --- Function begin: {} ---",
+            function.name.to_uppercase()
+        ));
+        self.line_new();
+        for d in &function.documentation {
+            self.line(format!("{comment} {}", d));
+        }
+        for line in self.dialect.function_begin(function) {
+            self.line(line);
+        }
+        self.function = Some(function);
+        self.instructions(&function.instructions)?;
+        self.function = None;
+        for line in self.dialect.function_end(function) {
+            self.line(line);
+        }
+
+        self.line(format!(
+            "{comment} --- Function end: {} ---",
+            function.name.to_uppercase()
+        ));
+        Ok(())
+    }
+
+    fn instructions(&mut self, instructions: &Instructions) -> AssemblerResult<()> {
+        let comment = self.dialect.comment();
+        for instruction in &instructions.instructions {
+            let comments = self.comments(instruction)?;
+            let address = self.address;
+            self.address += instruction.byte_size(self.application, address)?;
+
+            let mut line: Vec<String> = vec![];
+            if let Operation::Label(_) = &instruction.operation {
+                self.line_new();
+            } else {
+                line.push("  ".to_string());
+            }
+
+            line.push(self.instruction(instruction, address)?);
+
+            if comments.is_empty() {
+                self.line(line.join(""));
+            } else {
+                let mut line_len = line.join("").len();
+                let add_comments_before =
+                    line_len > COMMENT_COLUMN || matches!(&instruction.operation, Operation::Label(_label));
+
+                if add_comments_before {
+                    for c in &comments {
+                        self.line(format!("  {comment} {}", c));
+                    }
+                    self.line(line.join(""))
+                } else {
+                    for c in &comments {
+                        let inset = vec![" "; COMMENT_COLUMN - line_len].join("");
+                        line.push(format!("{}{comment} {}", inset, c));
+                        self.line(line.join(""));
+                        line.clear();
+                        line_len = 0;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Comments of the instruction, including the cycle annotation when enabled.
+    fn comments(&self, instruction: &Instruction) -> AssemblerResult<Vec<String>> {
+        let mut comments = instruction.comments.clone();
+        if !self.annotate_cycles {
+            return Ok(comments);
+        }
+        if let Some(timing) = instruction.timing(self.application, self.address)? {
+            let cycles = if timing.min_cycles == timing.max_cycles {
+                format!("[{}]", timing.min_cycles)
+            } else {
+                format!("[{}-{}]", timing.min_cycles, timing.max_cycles)
+            };
+            match comments.first_mut() {
+                Some(comment) => *comment = format!("{cycles} {comment}"),
+                None => comments.push(cycles),
+            }
+        }
+        Ok(comments)
+    }
+
+    /// Operation and operand of the instruction, or the label.
+    ///
+    /// Address is where the instruction is placed.
+    pub(crate) fn instruction(&self, instruction: &Instruction, address: Address) -> AssemblerResult<String> {
+        let mut line: Vec<String> = vec![];
+
+        // Add operation
+        match &instruction.operation {
+            Operation::Raw(bytes) => {
+                let bytes = bytes.iter().map(|byte| format!("${byte:02X}")).collect::<Vec<String>>();
+                line.push(format!("{} {}", self.dialect.bytes(), bytes.join(", ")));
+            }
+            Operation::Words(words) => {
+                let words = words
+                    .iter()
+                    .map(|word| match word {
+                        Word::Value(value) => format!("${value:04X}"),
+                        Word::Address(address_reference) => self.expression(&address_reference.expression),
+                    })
+                    .collect::<Vec<String>>();
+                line.push(format!("{} {}", self.dialect.words(), words.join(", ")));
+            }
+            Operation::LowBytes(address_references) | Operation::HighBytes(address_references) => {
+                let operator = if let Operation::LowBytes(_) = &instruction.operation {
+                    "<"
+                } else {
+                    ">"
+                };
+                let bytes = address_references
+                    .iter()
+                    .map(|address_reference| self.unary_expression(operator, &address_reference.expression))
+                    .collect::<Vec<String>>();
+                line.push(format!("{} {}", self.dialect.bytes(), bytes.join(", ")));
+            }
+            Operation::Text(_, _) | Operation::Decimal(_) | Operation::BasicStub(_) => {
+                let bytes = instruction
+                    .operation
+                    .data_bytes(self.application, address)?
+                    .ok_or(Error::InternalCompilerError)?;
+                let bytes = bytes.iter().map(|byte| format!("${byte:02X}")).collect::<Vec<String>>();
+                line.push(format!("{} {}", self.dialect.bytes(), bytes.join(", ")));
+            }
+            Operation::Fill(count, byte) => line.push(self.dialect.fill(*count, *byte)),
+            Operation::Align(alignment) => {
+                let padding = instruction.byte_size(self.application, address)? as usize;
+                line.push(self.dialect.align(*alignment, padding));
+            }
+            Operation::Label(label) => line.push(self.dialect.label(&self.name(label))),
+            _ => {
+                let instruction = instruction
+                    .operation
+                    .definition()
+                    .ok_or(Error::InternalCompilerError)?
+                    .instruction;
+                line.push(self.dialect.mnemonic(instruction).to_string());
+            }
+        }
+
+        // Add address mode
+        match &instruction.address_mode {
+            AddressMode::Implied => {}
+            AddressMode::Immediate(immediate) => match immediate {
+                Immediate::Byte(byte) => line.push(format!(" #${byte:02X}")),
+                Immediate::Low(address_reference) => line.push(format!(
+                    " #{}",
+                    self.unary_expression("<", &address_reference.expression)
+                )),
+                Immediate::High(address_reference) => line.push(format!(
+                    " #{}",
+                    self.unary_expression(">", &address_reference.expression)
+                )),
+                Immediate::Expression(expression) => line.push(format!(" #{}", self.expression(expression))),
+            },
+            AddressMode::Absolute(address_reference) | AddressMode::Relative(address_reference) => {
+                line.push(format!(" {}", self.operand(&address_reference.expression)));
+            }
+            AddressMode::AbsoluteX(address_reference) => {
+                line.push(format!(" {},x", self.operand(&address_reference.expression)));
+            }
+            AddressMode::AbsoluteY(address_reference) => {
+                line.push(format!(" {},y", self.operand(&address_reference.expression)));
+            }
+            AddressMode::IndexedIndirect(address_reference) => {
+                line.push(format!(" ({},x)", self.expression(&address_reference.expression)));
+            }
+            AddressMode::IndirectIndexed(address_reference) => {
+                line.push(format!(" ({}),y", self.expression(&address_reference.expression)));
+            }
+            AddressMode::Accumulator => line.push(self.dialect.accumulator().to_string()),
+            AddressMode::Indirect(address_reference) => {
+                line.push(format!(" ({})", self.expression(&address_reference.expression)));
+            }
+        }
+        Ok(line.join(""))
+    }
+
+    /// Operand of a non-indirect addressing mode. When the dialect groups using parentheses
+    /// an operand starting with a group would be read as an indirect address.
+    fn operand(&self, expression: &Expression) -> String {
+        let operand = self.expression(expression);
+        if operand.starts_with('(') {
+            format!("0+{operand}")
+        } else {
+            operand
+        }
+    }
+
+    fn expression(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Number(value) if (0..0x100).contains(value) => format!("{value}"),
+            Expression::Number(value) if (0..0x10000).contains(value) => format!("${value:04X}"),
+            Expression::Number(value) => format!("{value}"),
+            Expression::Name(name) => self.name(name),
+            Expression::Low(expression) => self.unary_expression("<", expression),
+            Expression::High(expression) => self.unary_expression(">", expression),
+            Expression::Negate(expression) => self.unary_expression("-", expression),
+            Expression::Binary(operator, left, right) => {
+                let binary_operator = |expression: &Expression| match expression {
+                    Expression::Binary(operator, _, _) => Some(*operator),
+                    _ => None,
+                };
+                let needs_group = |child: Option<BinaryOperator>, strict: bool| {
+                    let Some(child) = child else {
+                        return false;
+                    };
+                    if !self.dialect.same_precedence(child) || !self.dialect.same_precedence(*operator) {
+                        return true;
+                    }
+                    if strict {
+                        child.precedence() <= operator.precedence()
+                    } else {
+                        child.precedence() < operator.precedence()
+                    }
+                };
+                let mut left_text = self.expression(left);
+                if needs_group(binary_operator(left), false) {
+                    left_text = self.group(&left_text);
+                }
+                let mut right_text = self.expression(right);
+                if needs_group(binary_operator(right), true) {
+                    right_text = self.group(&right_text);
+                }
+                format!("{left_text}{}{right_text}", self.dialect.operator(*operator))
+            }
+        }
+    }
+
+    /// Unary operator applied to an expression; the expression is grouped when it isn't atomic.
+    fn unary_expression(&self, operator: &str, expression: &Expression) -> String {
+        if expression.is_atomic() {
+            format!("{operator}{}", self.expression(expression))
+        } else {
+            format!("{operator}{}", self.group(&self.expression(expression)))
+        }
+    }
+
+    fn group(&self, expression: &str) -> String {
+        let (open, close) = self.dialect.group();
+        format!("{open}{expression}{close}")
+    }
+
+    /// Name of a label or define, prefixed with its function when it is local to another function.
+    fn name(&self, name: &str) -> String {
+        if let (true, Some(function)) = (self.dialect.local_labels(), self.function) {
+            if let Some(local_label) = function.local_labels.get(name) {
+                return local_label.clone();
+            }
+        }
+        let current_function = self.function.map(|function| function.name.as_str());
+        match self.label_functions.get(name) {
+            Some(function) if current_function != Some(*function) => format!("{function}::{name}"),
+            _ => name.to_string(),
+        }
+    }
+
+    fn line(&mut self, line: String) {
+        self.output.push(line);
+    }
+
+    fn line_new(&mut self) {
+        self.output.push(String::default());
+    }
+}
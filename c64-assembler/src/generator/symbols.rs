@@ -0,0 +1,236 @@
+use crate::{
+    instruction::operation::Operation,
+    memory::{define::Value, Address},
+    validator::AssemblerResult,
+    Application,
+};
+
+use super::Generator;
+
+/// Kind of named address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Define,
+    Function,
+    Label,
+}
+
+impl SymbolKind {
+    fn name(&self) -> &'static str {
+        match self {
+            SymbolKind::Define => "define",
+            SymbolKind::Function => "function",
+            SymbolKind::Label => "label",
+        }
+    }
+}
+
+/// Named address of an application.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub address: Address,
+    pub kind: SymbolKind,
+    /// Module the label or function is part of; `None` for defines.
+    pub module: Option<String>,
+    /// Function the label is part of; `None` for labels in the module instructions.
+    pub function: Option<String>,
+}
+
+impl Symbol {
+    /// Name prefixed with the module and function it is part of, separated by dots.
+    ///
+    /// Modules without a name are left out.
+    pub fn scoped_name(&self) -> String {
+        [self.module.as_ref(), self.function.as_ref(), Some(&self.name)]
+            .into_iter()
+            .flatten()
+            .filter(|name| !name.is_empty())
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+}
+
+impl Application {
+    /// All defines, functions and labels of the application, ordered by address.
+    ///
+    /// The addresses of functions and labels are known after the application is built.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        let mut symbols = vec![];
+        for define in &self.defines {
+            let address = match define.value {
+                Value::Address(address) | Value::Zeropage(address) => address,
+            };
+            symbols.push(Symbol {
+                name: define.name.clone(),
+                address,
+                kind: SymbolKind::Define,
+                module: None,
+                function: None,
+            });
+        }
+
+        let mut add_symbol = |name: &String, kind: SymbolKind, module: &String, function: Option<&String>| {
+            if let Some(address) = self.address_lookup.get(name) {
+                symbols.push(Symbol {
+                    name: name.clone(),
+                    address: *address,
+                    kind,
+                    module: Some(module.clone()),
+                    function: function.cloned(),
+                });
+            }
+        };
+        for module in &self.modules {
+            for instruction in &module.instructions.instructions {
+                if let Operation::Label(label) = &instruction.operation {
+                    add_symbol(label, SymbolKind::Label, &module.name, None);
+                }
+            }
+            for function in &module.functions {
+                add_symbol(&function.name, SymbolKind::Function, &module.name, None);
+                for instruction in &function.instructions.instructions {
+                    if let Operation::Label(label) = &instruction.operation {
+                        add_symbol(label, SymbolKind::Label, &module.name, Some(&function.name));
+                    }
+                }
+            }
+        }
+
+        symbols.sort_by(|a, b| (a.address, &a.name).cmp(&(b.address, &b.name)));
+        symbols
+    }
+}
+
+/// Format of the symbol table generated by the [SymbolGenerator].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolFormat {
+    /// VICE monitor commands (`al C:0810 .main_entry_point`). Load them using `-moncommands`.
+    #[default]
+    Vice,
+    /// Plain assignments (`main_entry_point = $0810`).
+    Plain,
+    /// JSON array with an object per symbol.
+    Json,
+}
+
+/// Symbol table generator
+///
+/// Exports the addresses of the defines, functions and labels for use in debuggers like the
+/// VICE monitor.
+///
+/// ```
+/// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+/// use c64_assembler::generator::{Generator, SymbolFormat, SymbolGenerator};
+///
+/// let application = ApplicationBuilder::default()
+///     .module(
+///         ModuleBuilder::default()
+///             .name("main")
+///             .instructions(
+///                 InstructionBuilder::default()
+///                     .add_basic_header()
+///                     .label("main_entry_point")
+///                     .rts()
+///                     .build(),
+///             )
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+///
+/// let symbols = SymbolGenerator::default().generate(application.clone()).unwrap();
+/// assert_eq!("al C:080E .main_entry_point\n", symbols);
+///
+/// let symbols = SymbolGenerator::default()
+///     .format(SymbolFormat::Plain)
+///     .scoped(true)
+///     .generate(application)
+///     .unwrap();
+/// assert_eq!("main.main_entry_point = $080E\n", symbols);
+/// ```
+#[derive(Default, Debug)]
+pub struct SymbolGenerator {
+    format: SymbolFormat,
+    scoped: bool,
+}
+
+impl SymbolGenerator {
+    /// Format of the symbol table. Defaults to [SymbolFormat::Vice].
+    pub fn format(mut self, format: SymbolFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Prefix labels and functions with the module and function they are part of.
+    pub fn scoped(mut self, scoped: bool) -> Self {
+        self.scoped = scoped;
+        self
+    }
+
+    fn name(&self, symbol: &Symbol) -> String {
+        if self.scoped {
+            symbol.scoped_name()
+        } else {
+            symbol.name.clone()
+        }
+    }
+}
+
+impl Generator for SymbolGenerator {
+    type Output = String;
+
+    fn generate(self, application: Application) -> AssemblerResult<Self::Output> {
+        let symbols = application.symbols();
+        let lines = match self.format {
+            SymbolFormat::Vice => symbols
+                .iter()
+                .map(|symbol| format!("al C:{:04X} .{}", symbol.address, self.name(symbol)))
+                .collect::<Vec<String>>(),
+            SymbolFormat::Plain => symbols
+                .iter()
+                .map(|symbol| format!("{} = ${:04X}", self.name(symbol), symbol.address))
+                .collect::<Vec<String>>(),
+            SymbolFormat::Json => {
+                let objects = symbols
+                    .iter()
+                    .map(|symbol| {
+                        format!(
+                            "  {{\"name\": {}, \"address\": {}, \"kind\": \"{}\", \"module\": {}, \"function\": {}}}",
+                            json_string(Some(&self.name(symbol))),
+                            symbol.address,
+                            symbol.kind.name(),
+                            json_string(symbol.module.as_ref()),
+                            json_string(symbol.function.as_ref()),
+                        )
+                    })
+                    .collect::<Vec<String>>();
+                if objects.is_empty() {
+                    vec!["[]".to_string()]
+                } else {
+                    vec!["[".to_string(), objects.join(",\n"), "]".to_string()]
+                }
+            }
+        };
+        Ok(lines.iter().map(|line| format!("{line}\n")).collect())
+    }
+}
+
+/// Quoted and escaped JSON string, or `null`.
+pub(crate) fn json_string(value: Option<&String>) -> String {
+    let Some(value) = value else {
+        return "null".to_string();
+    };
+    let mut result = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            character if character.is_control() => result.push_str(&format!("\\u{:04x}", character as u32)),
+            character => result.push(character),
+        }
+    }
+    result.push('"');
+    result
+}
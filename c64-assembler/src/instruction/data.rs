@@ -0,0 +1,200 @@
+//! Data that is stored in the instruction stream.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+//! use c64_assembler::generator::{Generator, ProgramGenerator};
+//!
+//! let application = ApplicationBuilder::default()
+//!     .entry_point(0x1000)
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .instructions(
+//!                 InstructionBuilder::default()
+//!                     .rts()
+//!                     .words_addr(&["handler"])
+//!                     .text_screen_codes("ok")
+//!                     .align(4)
+//!                     .label("handler")
+//!                     .fill(2, 0xEA)
+//!                     .build(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! let bytes = ProgramGenerator::default().generate(application).unwrap();
+//! assert_eq!(vec![0x00, 0x10, 0x60, 0x08, 0x10, 0x0F, 0x0B, 0x00, 0x00, 0x00, 0xEA, 0xEA], bytes);
+//! ```
+use crate::{
+    memory::{label::AddressReference, Address, ZeroPage},
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+use super::operation::Operation;
+
+/// Number of characters of [Operation::Decimal]; the highest address has 5 digits.
+pub const DECIMAL_DIGITS: Address = 5;
+
+/// Word (16 bits) that is stored little-endian in the instruction stream.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Word {
+    /// Fixed value.
+    Value(u16),
+    /// Address of a label or define.
+    Address(AddressReference),
+}
+
+/// Encoding used to store text in the instruction stream.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextEncoding {
+    /// PETSCII as used by the kernal routines (CHROUT).
+    ///
+    /// Lowercase letters are stored unshifted (`$41-$5A`) and uppercase letters shifted
+    /// (`$C1-$DA`). In the default character set unshifted letters are shown in uppercase.
+    Petscii,
+    /// Screen codes that can be stored directly in screen memory.
+    ///
+    /// Lowercase letters are stored as `$01-$1A` and uppercase letters as `$41-$5A`.
+    ScreenCode,
+}
+
+impl TextEncoding {
+    /// Encode a single character.
+    ///
+    /// ```
+    /// use c64_assembler::instruction::data::TextEncoding;
+    ///
+    /// assert_eq!(Some(0x41), TextEncoding::Petscii.encode('a'));
+    /// assert_eq!(Some(0x01), TextEncoding::ScreenCode.encode('a'));
+    /// assert_eq!(None, TextEncoding::ScreenCode.encode('{'));
+    /// ```
+    pub fn encode(&self, character: char) -> Option<u8> {
+        let petscii = match character {
+            ' '..='@' => character as u8,
+            'a'..='z' => character as u8 - b'a' + 0x41,
+            '[' => 0x5B,
+            '£' => 0x5C,
+            ']' => 0x5D,
+            '^' | '↑' => 0x5E,
+            '←' => 0x5F,
+            'A'..='Z' => character as u8 - b'A' + 0xC1,
+            '\n' => 0x0D,
+            _ => return None,
+        };
+        match self {
+            TextEncoding::Petscii => Some(petscii),
+            TextEncoding::ScreenCode => match petscii {
+                0x20..=0x3F => Some(petscii),
+                0x40..=0x5F => Some(petscii - 0x40),
+                0xC1..=0xDA => Some(petscii - 0x80),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl Operation {
+    /// Is this operation data that is stored in the instruction stream.
+    pub fn is_data(&self) -> bool {
+        matches!(
+            self,
+            Operation::Raw(_)
+                | Operation::Words(_)
+                | Operation::LowBytes(_)
+                | Operation::HighBytes(_)
+                | Operation::Text(_, _)
+                | Operation::Decimal(_)
+                | Operation::Fill(_, _)
+                | Operation::Align(_)
+                | Operation::BasicStub(_)
+        )
+    }
+
+    /// Number of bytes the data occupies when it is placed at the given address.
+    ///
+    /// Returns `None` when the operation isn't data.
+    pub fn data_byte_size(&self, address: Address) -> Option<Address> {
+        match self {
+            Operation::Raw(bytes) => Some(bytes.len() as Address),
+            Operation::Words(words) => Some(words.len() as Address * 2),
+            Operation::LowBytes(address_references) | Operation::HighBytes(address_references) => {
+                Some(address_references.len() as Address)
+            }
+            Operation::Text(text, _) => Some(text.chars().count() as Address),
+            Operation::Decimal(_) => Some(DECIMAL_DIGITS),
+            Operation::Fill(count, _) => Some(*count),
+            Operation::Align(alignment) => Some(align_padding(address, *alignment)),
+            Operation::BasicStub(stub) => Some(stub.byte_size(address)),
+            _ => None,
+        }
+    }
+
+    /// Bytes of the data when it is placed at the given address.
+    ///
+    /// Returns `None` when the operation isn't data.
+    pub fn data_bytes(&self, application: &Application, address: Address) -> AssemblerResult<Option<Vec<u8>>> {
+        let bytes = match self {
+            Operation::Raw(bytes) => bytes.clone(),
+            Operation::Words(words) => {
+                let mut bytes = vec![];
+                for word in words {
+                    let value = match word {
+                        Word::Value(value) => *value,
+                        Word::Address(address_reference) => application.address(address_reference)?,
+                    };
+                    bytes.extend([value.low(), value.high()]);
+                }
+                bytes
+            }
+            Operation::LowBytes(address_references) => address_references
+                .iter()
+                .map(|address_reference| Ok(application.address(address_reference)?.low()))
+                .collect::<AssemblerResult<Vec<u8>>>()?,
+            Operation::HighBytes(address_references) => address_references
+                .iter()
+                .map(|address_reference| Ok(application.address(address_reference)?.high()))
+                .collect::<AssemblerResult<Vec<u8>>>()?,
+            Operation::Text(text, encoding) => text
+                .chars()
+                .map(|character| encoding.encode(character).ok_or(Error::UnsupportedCharacter(character)))
+                .collect::<AssemblerResult<Vec<u8>>>()?,
+            Operation::Decimal(address_reference) => {
+                let value = application.address(address_reference)?;
+                format!("{value:>width$}", width = DECIMAL_DIGITS as usize).into_bytes()
+            }
+            Operation::Fill(count, byte) => vec![*byte; *count as usize],
+            Operation::Align(alignment) => vec![0x00; align_padding(address, *alignment) as usize],
+            Operation::BasicStub(stub) => stub.bytes(application, address)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(bytes))
+    }
+
+    /// Addresses that are referenced by the data.
+    pub fn data_address_references(&self) -> Vec<&AddressReference> {
+        match self {
+            Operation::Words(words) => words
+                .iter()
+                .filter_map(|word| match word {
+                    Word::Value(_) => None,
+                    Word::Address(address_reference) => Some(address_reference),
+                })
+                .collect(),
+            Operation::LowBytes(address_references) | Operation::HighBytes(address_references) => {
+                address_references.iter().collect()
+            }
+            Operation::Decimal(address_reference) => vec![address_reference],
+            Operation::BasicStub(stub) => vec![&stub.target],
+            _ => vec![],
+        }
+    }
+}
+
+/// Number of bytes needed to move the address to the next multiple of alignment.
+fn align_padding(address: Address, alignment: Address) -> Address {
+    if alignment <= 1 {
+        return 0;
+    }
+    (alignment - address % alignment) % alignment
+}
@@ -1,16 +1,20 @@
+use c64_assembler_6502::opcodes::{NO_ZEROPAGE, NO_ZEROPAGE_X, NO_ZEROPAGE_Y};
+use data::Word;
 use operation::Operation;
 
-use crate::memory::address_mode::AddressMode;
+use crate::memory::address_mode::{AddressMode, Immediate};
 use crate::memory::Address;
 use crate::validator::AssemblerResult;
-use crate::Application;
+use crate::{Application, Instructions};
+pub mod data;
 pub mod operation;
+pub mod timing;
 
 /// Assembly instruction
 ///
 /// An instruction is the combination of the operation and the address mode that the
 /// operation should be using.
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone)]
 pub struct Instruction {
     /// Operation of the instruction.
     pub operation: Operation,
@@ -18,19 +22,136 @@ pub struct Instruction {
     pub address_mode: AddressMode,
     /// Comments for when generating source code.
     pub comments: Vec<String>,
+    /// Location in the dasm source or macro the instruction originates from.
+    pub source: Option<SourceLocation>,
+}
+
+/// Instructions are equal when they encode the same; the source location isn't compared.
+impl PartialEq for Instruction {
+    fn eq(&self, other: &Instruction) -> bool {
+        self.operation == other.operation && self.address_mode == other.address_mode && self.comments == other.comments
+    }
+}
+
+/// Location in source code. Line and column are 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
 }
 
 impl Instruction {
     /// Total number of bytes the instruction occupies on a 6502.
     ///
     /// Application parameter is used to identify if an instruction should use its zeropage variant.
-    pub fn byte_size(&self, application: &Application) -> AssemblerResult<Address> {
-        if let Operation::Raw(bytes) = &self.operation {
-            Ok(bytes.len() as u16)
+    /// Address is where the instruction is placed, the size of [Operation::Align] depends on it.
+    pub fn byte_size(&self, application: &Application, address: Address) -> AssemblerResult<Address> {
+        if let Some(byte_size) = self.operation.data_byte_size(address) {
+            Ok(byte_size)
         } else if let Operation::Label(_) = &self.operation {
             Ok(0)
+        } else if !self.has_zeropage_opcode() {
+            // Zeropage addresses are accessed using the absolute op-code.
+            Ok(3)
         } else {
             self.address_mode.byte_size(application)
         }
     }
+
+    /// Distance in bytes from the next instruction to the target of a relative branch.
+    ///
+    /// Address is where the instruction is placed. Returns `None` when the instruction doesn't
+    /// use relative addressing. A 6502 can only branch when the distance is in -128..=127.
+    pub fn branch_distance(&self, application: &Application, address: Address) -> AssemblerResult<Option<i32>> {
+        let AddressMode::Relative(address_reference) = &self.address_mode else {
+            return Ok(None);
+        };
+        let target = application.address(address_reference)?;
+        let next_instruction = address.wrapping_add(self.address_mode.byte_size(application)?);
+        Ok(Some(target as i32 - next_instruction as i32))
+    }
+
+    /// Names of the labels and defines that are referenced by this instruction.
+    pub fn address_names(&self) -> Vec<&String> {
+        let mut result = vec![];
+        for address_reference in self.operation.data_address_references() {
+            result.extend(address_reference.names());
+        }
+        match &self.address_mode {
+            AddressMode::Absolute(address_reference)
+            | AddressMode::AbsoluteX(address_reference)
+            | AddressMode::AbsoluteY(address_reference)
+            | AddressMode::Indirect(address_reference)
+            | AddressMode::IndexedIndirect(address_reference)
+            | AddressMode::IndirectIndexed(address_reference)
+            | AddressMode::Immediate(Immediate::Low(address_reference))
+            | AddressMode::Immediate(Immediate::High(address_reference))
+            | AddressMode::Relative(address_reference) => result.extend(address_reference.names()),
+            AddressMode::Immediate(Immediate::Expression(expression)) => result.extend(expression.names()),
+            AddressMode::Immediate(Immediate::Byte(_)) | AddressMode::Implied | AddressMode::Accumulator => {}
+        }
+        result
+    }
+
+    /// Replace the names of labels and the referenced names; names for which `rename` returns
+    /// `None` are kept.
+    pub fn rename(&mut self, rename: &impl Fn(&str) -> Option<String>) {
+        match &mut self.operation {
+            Operation::Label(label) => {
+                if let Some(new_label) = rename(label) {
+                    *label = new_label;
+                }
+            }
+            Operation::Words(words) => {
+                for word in words {
+                    if let Word::Address(address_reference) = word {
+                        address_reference.expression.rename(rename);
+                    }
+                }
+            }
+            Operation::LowBytes(address_references) | Operation::HighBytes(address_references) => {
+                for address_reference in address_references {
+                    address_reference.expression.rename(rename);
+                }
+            }
+            Operation::Decimal(address_reference) => address_reference.expression.rename(rename),
+            Operation::BasicStub(stub) => stub.target.expression.rename(rename),
+            _ => {}
+        }
+        match &mut self.address_mode {
+            AddressMode::Absolute(address_reference)
+            | AddressMode::AbsoluteX(address_reference)
+            | AddressMode::AbsoluteY(address_reference)
+            | AddressMode::Indirect(address_reference)
+            | AddressMode::IndexedIndirect(address_reference)
+            | AddressMode::IndirectIndexed(address_reference)
+            | AddressMode::Immediate(Immediate::Low(address_reference))
+            | AddressMode::Immediate(Immediate::High(address_reference))
+            | AddressMode::Relative(address_reference) => address_reference.expression.rename(rename),
+            AddressMode::Immediate(Immediate::Expression(expression)) => expression.rename(rename),
+            AddressMode::Immediate(Immediate::Byte(_)) | AddressMode::Implied | AddressMode::Accumulator => {}
+        }
+    }
+
+    /// Does the operation have a zeropage op-code for the (absolute) address mode of this instruction.
+    fn has_zeropage_opcode(&self) -> bool {
+        let Some(definition) = self.operation.definition() else {
+            return true;
+        };
+        match &self.address_mode {
+            AddressMode::Absolute(_) => definition.zeropage != NO_ZEROPAGE,
+            AddressMode::AbsoluteX(_) => definition.zeropage_x != NO_ZEROPAGE_X,
+            AddressMode::AbsoluteY(_) => definition.zeropage_y != NO_ZEROPAGE_Y,
+            _ => true,
+        }
+    }
+}
+
+impl Instructions {
+    /// Replace the names of labels and the referenced names of all instructions.
+    pub fn rename(&mut self, rename: &impl Fn(&str) -> Option<String>) {
+        for instruction in &mut self.instructions {
+            instruction.rename(rename);
+        }
+    }
 }
@@ -1,5 +1,12 @@
 use c64_assembler_6502::instruction::*;
 
+use crate::{
+    basic::BasicStub,
+    memory::{label::AddressReference, Address},
+};
+
+use super::data::{TextEncoding, Word};
+
 /// Enumeration containing all operations.
 #[derive(Clone, Default, Debug, PartialEq)]
 pub enum Operation {
@@ -75,8 +82,56 @@ pub enum Operation {
     /// Return (from stack)
     RTS,
 
+    // Illegal (undocumented) instructions. Only accepted by the validator when the
+    // `illegal-opcodes` feature is enabled.
+    /// ASL memory, then ORA with accumulator
+    SLO,
+    /// ROL memory, then AND with accumulator
+    RLA,
+    /// LSR memory, then EOR with accumulator
+    SRE,
+    /// ROR memory, then ADC to accumulator
+    RRA,
+    /// Store accumulator AND x-register
+    SAX,
+    /// Load accumulator and x-register
+    LAX,
+    /// DEC memory, then CMP with accumulator
+    DCP,
+    /// INC memory, then SBC from accumulator
+    ISC,
+    /// AND immediate, then copy bit 7 into carry
+    ANC,
+    /// AND immediate, then LSR accumulator
+    ALR,
+    /// AND immediate, then ROR accumulator
+    ARR,
+    /// x-register = (accumulator AND x-register) - immediate
+    SBX,
+    /// Load memory AND stack pointer into accumulator, x-register and stack pointer
+    LAS,
+
     /// Store a byte in the instruction stream. Only immediate addressing mode can be used.
     Raw(Vec<u8>),
+    /// Store little-endian words in the instruction stream.
+    Words(Vec<Word>),
+    /// Store the low bytes of addresses (low table of a split address table).
+    LowBytes(Vec<AddressReference>),
+    /// Store the high bytes of addresses (high table of a split address table).
+    HighBytes(Vec<AddressReference>),
+    /// Store a text using the given encoding.
+    Text(String, TextEncoding),
+    /// Store an address as PETSCII decimal digits, right aligned with spaces to
+    /// [super::data::DECIMAL_DIGITS] characters. Used by BASIC lines that refer to a label, like
+    /// `SYS`.
+    Decimal(AddressReference),
+    /// Store a byte the given number of times.
+    Fill(Address, u8),
+    /// Store zeros until the address is a multiple of the given alignment.
+    Align(Address),
+    /// Store a BASIC program that starts the machine code with `SYS`; the links between the
+    /// lines depend on the address it is placed at.
+    BasicStub(BasicStub),
     /// Label
     Label(String),
 }
@@ -141,8 +196,167 @@ impl Operation {
             Operation::SEC => Some(&OPCODES_SEC),
             Operation::CLC => Some(&OPCODES_CLC),
             Operation::RTS => Some(&OPCODES_RTS),
+            Operation::SLO => Some(&OPCODES_SLO),
+            Operation::RLA => Some(&OPCODES_RLA),
+            Operation::SRE => Some(&OPCODES_SRE),
+            Operation::RRA => Some(&OPCODES_RRA),
+            Operation::SAX => Some(&OPCODES_SAX),
+            Operation::LAX => Some(&OPCODES_LAX),
+            Operation::DCP => Some(&OPCODES_DCP),
+            Operation::ISC => Some(&OPCODES_ISC),
+            Operation::ANC => Some(&OPCODES_ANC),
+            Operation::ALR => Some(&OPCODES_ALR),
+            Operation::ARR => Some(&OPCODES_ARR),
+            Operation::SBX => Some(&OPCODES_SBX),
+            Operation::LAS => Some(&OPCODES_LAS),
             Operation::Raw(_vec) => None,
+            Operation::Words(_)
+            | Operation::LowBytes(_)
+            | Operation::HighBytes(_)
+            | Operation::Text(_, _)
+            | Operation::Decimal(_)
+            | Operation::Fill(_, _)
+            | Operation::Align(_)
+            | Operation::BasicStub(_) => None,
             Operation::Label(_) => None,
         }
     }
+
+    /// Is this operation an illegal (undocumented) instruction.
+    ///
+    /// ```
+    /// use c64_assembler::instruction::operation::Operation;
+    ///
+    /// assert!(Operation::LAX.is_illegal());
+    /// assert!(!Operation::LDA.is_illegal());
+    /// ```
+    pub fn is_illegal(&self) -> bool {
+        matches!(
+            self,
+            Operation::SLO
+                | Operation::RLA
+                | Operation::SRE
+                | Operation::RRA
+                | Operation::SAX
+                | Operation::LAX
+                | Operation::DCP
+                | Operation::ISC
+                | Operation::ANC
+                | Operation::ALR
+                | Operation::ARR
+                | Operation::SBX
+                | Operation::LAS
+        )
+    }
+
+    /// Branch operation that branches on the opposite condition.
+    ///
+    /// Returns `None` when the operation isn't a branch.
+    ///
+    /// ```
+    /// use c64_assembler::instruction::operation::Operation;
+    ///
+    /// assert_eq!(Some(Operation::BEQ), Operation::BNE.inverted_branch());
+    /// assert_eq!(None, Operation::JMP.inverted_branch());
+    /// ```
+    pub fn inverted_branch(&self) -> Option<Operation> {
+        match self {
+            Operation::BCC => Some(Operation::BCS),
+            Operation::BCS => Some(Operation::BCC),
+            Operation::BEQ => Some(Operation::BNE),
+            Operation::BNE => Some(Operation::BEQ),
+            Operation::BMI => Some(Operation::BPL),
+            Operation::BPL => Some(Operation::BMI),
+            Operation::BVC => Some(Operation::BVS),
+            Operation::BVS => Some(Operation::BVC),
+            _ => None,
+        }
+    }
+
+    /// Get the operation for the given instruction (lda, sta, ...).
+    ///
+    /// Instruction is matched case insensitive. Data operations and [Operation::Label] don't
+    /// have an instruction and will never be returned.
+    ///
+    /// ```
+    /// use c64_assembler::instruction::operation::Operation;
+    ///
+    /// assert_eq!(Some(Operation::LDA), Operation::from_instruction("lda"));
+    /// assert_eq!(Some(Operation::STA), Operation::from_instruction("STA"));
+    /// assert_eq!(None, Operation::from_instruction("byte"));
+    /// ```
+    pub fn from_instruction(instruction: &str) -> Option<Operation> {
+        match instruction.to_lowercase().as_str() {
+            "adc" => Some(Operation::ADC),
+            "and" => Some(Operation::AND),
+            "asl" => Some(Operation::ASL),
+            "bcc" => Some(Operation::BCC),
+            "bcs" => Some(Operation::BCS),
+            "beq" => Some(Operation::BEQ),
+            "bit" => Some(Operation::BIT),
+            "bmi" => Some(Operation::BMI),
+            "bne" => Some(Operation::BNE),
+            "bpl" => Some(Operation::BPL),
+            "brk" => Some(Operation::BRK),
+            "bvc" => Some(Operation::BVC),
+            "bvs" => Some(Operation::BVS),
+            "cld" => Some(Operation::CLD),
+            "cli" => Some(Operation::CLI),
+            "clv" => Some(Operation::CLV),
+            "cmp" => Some(Operation::CMP),
+            "cpx" => Some(Operation::CPX),
+            "cpy" => Some(Operation::CPY),
+            "dec" => Some(Operation::DEC),
+            "dex" => Some(Operation::DEX),
+            "dey" => Some(Operation::DEY),
+            "eor" => Some(Operation::EOR),
+            "inc" => Some(Operation::INC),
+            "inx" => Some(Operation::INX),
+            "iny" => Some(Operation::INY),
+            "ldx" => Some(Operation::LDX),
+            "lsr" => Some(Operation::LSR),
+            "nop" => Some(Operation::NOP),
+            "ora" => Some(Operation::ORA),
+            "pha" => Some(Operation::PHA),
+            "php" => Some(Operation::PHP),
+            "pla" => Some(Operation::PLA),
+            "plp" => Some(Operation::PLP),
+            "rol" => Some(Operation::ROL),
+            "ror" => Some(Operation::ROR),
+            "rti" => Some(Operation::RTI),
+            "sbc" => Some(Operation::SBC),
+            "sed" => Some(Operation::SED),
+            "sei" => Some(Operation::SEI),
+            "stx" => Some(Operation::STX),
+            "sty" => Some(Operation::STY),
+            "tax" => Some(Operation::TAX),
+            "tay" => Some(Operation::TAY),
+            "tsx" => Some(Operation::TSX),
+            "txa" => Some(Operation::TXA),
+            "txs" => Some(Operation::TXS),
+            "tya" => Some(Operation::TYA),
+            "lda" => Some(Operation::LDA),
+            "ldy" => Some(Operation::LDY),
+            "sta" => Some(Operation::STA),
+            "jmp" => Some(Operation::JMP),
+            "jsr" => Some(Operation::JSR),
+            "sec" => Some(Operation::SEC),
+            "clc" => Some(Operation::CLC),
+            "rts" => Some(Operation::RTS),
+            "slo" => Some(Operation::SLO),
+            "rla" => Some(Operation::RLA),
+            "sre" => Some(Operation::SRE),
+            "rra" => Some(Operation::RRA),
+            "sax" => Some(Operation::SAX),
+            "lax" => Some(Operation::LAX),
+            "dcp" => Some(Operation::DCP),
+            "isc" => Some(Operation::ISC),
+            "anc" => Some(Operation::ANC),
+            "alr" => Some(Operation::ALR),
+            "arr" => Some(Operation::ARR),
+            "sbx" => Some(Operation::SBX),
+            "las" => Some(Operation::LAS),
+            _ => None,
+        }
+    }
 }
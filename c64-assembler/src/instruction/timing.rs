@@ -0,0 +1,234 @@
+//! Cycle timing of instructions.
+//!
+//! Timing is determined after the addresses have been resolved, so zeropage variants, page
+//! crossing of indexed addresses and branches can be taken into account.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder};
+//!
+//! let application = ApplicationBuilder::default()
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .function(
+//!                 FunctionBuilder::default()
+//!                     .name("wait")
+//!                     .instructions(
+//!                         InstructionBuilder::default()
+//!                             .ldx_imm(0x10)
+//!                             .label("wait_loop")
+//!                             .dex()
+//!                             .bne_addr("wait_loop")
+//!                             .rts()
+//!                             .build(),
+//!                     )
+//!                     .build(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//!
+//! let function = &application.modules[0].functions[0];
+//! let timing = function.timing(&application).unwrap();
+//! // ldx #$10
+//! assert_eq!((2, 2), (timing.blocks[0].min_cycles, timing.blocks[0].max_cycles));
+//! // dex, bne wait_loop
+//! assert_eq!((4, 5), (timing.blocks[1].min_cycles, timing.blocks[1].max_cycles));
+//! // rts
+//! assert_eq!((6, 6), (timing.blocks[2].min_cycles, timing.blocks[2].max_cycles));
+//! ```
+use std::ops::Range;
+
+use c64_assembler_6502::{
+    instruction::{AddressingMode, PagePenalty},
+    opcodes::{NO_ZEROPAGE, NO_ZEROPAGE_X, NO_ZEROPAGE_Y},
+};
+
+use crate::{
+    memory::{address_mode::AddressMode, label::AddressReference, Address, ZeroPage},
+    validator::{AssemblerResult, Error},
+    Application, Function, Instructions,
+};
+
+use super::{operation::Operation, Instruction};
+
+/// Timing of a single instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InstructionTiming {
+    /// Address of the instruction.
+    pub address: Address,
+    /// Number of cycles when no page is crossed and branches aren't taken.
+    pub min_cycles: u8,
+    /// Number of cycles when indexing crosses a page and branches are taken.
+    pub max_cycles: u8,
+    /// The instruction is a branch with its target on another page than the next instruction.
+    pub branch_crosses_page: bool,
+}
+
+/// Timing of a straight-line block of instructions.
+///
+/// A block starts at a label and ends after a branch, jump or return. Cycles spent in called
+/// subroutines are not included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTiming {
+    /// Address of the first instruction of the block.
+    pub address: Address,
+    /// Indices of the instructions that are part of the block.
+    pub instructions: Range<usize>,
+    pub min_cycles: u32,
+    pub max_cycles: u32,
+}
+
+/// Timing of a stream of instructions.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Timing {
+    /// Timing of each instruction; `None` for labels and raw data.
+    pub instructions: Vec<Option<InstructionTiming>>,
+    /// Straight-line blocks of instructions.
+    pub blocks: Vec<BlockTiming>,
+}
+
+impl Timing {
+    /// Branches that take an additional cycle as their target is on another page.
+    pub fn page_crossing_branches(&self) -> impl Iterator<Item = &InstructionTiming> {
+        self.instructions
+            .iter()
+            .flatten()
+            .filter(|timing| timing.branch_crosses_page)
+    }
+}
+
+impl Instruction {
+    /// Determine the cycles this instruction takes when located at the given address.
+    ///
+    /// Returns `None` for labels and raw data.
+    pub fn timing(&self, application: &Application, address: Address) -> AssemblerResult<Option<InstructionTiming>> {
+        let Some(definition) = self.operation.definition() else {
+            return Ok(None);
+        };
+        let zeropage_or_absolute =
+            |address_reference: &AddressReference, zeropage, absolute, has_zeropage| -> AssemblerResult<_> {
+                let operand = resolve(application, address_reference)?;
+                let addressing_mode = if has_zeropage && operand.is_zeropage() {
+                    zeropage
+                } else {
+                    absolute
+                };
+                Ok((addressing_mode, Some(operand)))
+            };
+        let (addressing_mode, operand) = match &self.address_mode {
+            AddressMode::Implied => (AddressingMode::Implied, None),
+            AddressMode::Accumulator => (AddressingMode::Accumulator, None),
+            AddressMode::Immediate(_) => (AddressingMode::Immediate, None),
+            AddressMode::Absolute(address_reference) => zeropage_or_absolute(
+                address_reference,
+                AddressingMode::Zeropage,
+                AddressingMode::Absolute,
+                definition.zeropage != NO_ZEROPAGE,
+            )?,
+            AddressMode::AbsoluteX(address_reference) => zeropage_or_absolute(
+                address_reference,
+                AddressingMode::ZeropageX,
+                AddressingMode::AbsoluteX,
+                definition.zeropage_x != NO_ZEROPAGE_X,
+            )?,
+            AddressMode::AbsoluteY(address_reference) => zeropage_or_absolute(
+                address_reference,
+                AddressingMode::ZeropageY,
+                AddressingMode::AbsoluteY,
+                definition.zeropage_y != NO_ZEROPAGE_Y,
+            )?,
+            AddressMode::Relative(address_reference) => {
+                (AddressingMode::Relative, Some(resolve(application, address_reference)?))
+            }
+            AddressMode::Indirect(_) => (AddressingMode::Indirect, None),
+            AddressMode::IndexedIndirect(_) => (AddressingMode::IndexedIndirect, None),
+            AddressMode::IndirectIndexed(_) => (AddressingMode::IndirectIndexed, None),
+        };
+        let min_cycles = definition.cycles(addressing_mode).ok_or(Error::InternalCompilerError)?;
+
+        let mut timing = InstructionTiming {
+            address,
+            min_cycles,
+            max_cycles: min_cycles,
+            branch_crosses_page: false,
+        };
+        match (definition.page_penalty, addressing_mode, operand) {
+            // Indexing never leaves the page when the base address is at the start of a page.
+            (PagePenalty::IndexedRead, AddressingMode::AbsoluteX | AddressingMode::AbsoluteY, Some(operand))
+                if operand.low() != 0 =>
+            {
+                timing.max_cycles += 1
+            }
+            (PagePenalty::IndexedRead, AddressingMode::IndirectIndexed, _) => timing.max_cycles += 1,
+            (PagePenalty::Branch, _, Some(target)) => {
+                let next_instruction = address.wrapping_add(2);
+                timing.branch_crosses_page = next_instruction.high() != target.high();
+                timing.max_cycles += 1 + timing.branch_crosses_page as u8;
+            }
+            _ => {}
+        }
+        Ok(Some(timing))
+    }
+
+    /// Does this instruction end a straight-line block of instructions.
+    fn ends_block(&self) -> bool {
+        match &self.operation {
+            Operation::JMP | Operation::RTS | Operation::RTI | Operation::BRK => true,
+            operation => operation
+                .definition()
+                .is_some_and(|definition| definition.page_penalty == PagePenalty::Branch),
+        }
+    }
+}
+
+impl Instructions {
+    /// Determine the timing of these instructions when they start at the given address.
+    ///
+    /// Addresses must have been resolved, which is done when building the application.
+    pub fn timing(&self, application: &Application, address: Address) -> AssemblerResult<Timing> {
+        let mut result = Timing::default();
+        let mut current_address = address;
+        let mut block: Option<BlockTiming> = None;
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let timing = instruction.timing(application, current_address)?;
+            result.instructions.push(timing);
+            current_address = current_address.wrapping_add(instruction.byte_size(application, current_address)?);
+
+            let Some(timing) = timing else {
+                // Labels start and raw data ends a block.
+                result.blocks.extend(block.take());
+                continue;
+            };
+            let current = block.get_or_insert(BlockTiming {
+                address: timing.address,
+                instructions: index..index,
+                min_cycles: 0,
+                max_cycles: 0,
+            });
+            current.instructions.end = index + 1;
+            current.min_cycles += timing.min_cycles as u32;
+            current.max_cycles += timing.max_cycles as u32;
+            if instruction.ends_block() {
+                result.blocks.extend(block.take());
+            }
+        }
+        result.blocks.extend(block);
+        Ok(result)
+    }
+}
+
+impl Function {
+    /// Determine the timing of the instructions of this function.
+    ///
+    /// Addresses must have been resolved, which is done when building the application.
+    pub fn timing(&self, application: &Application) -> AssemblerResult<Timing> {
+        let address = application.lookup_address(&self.name)?;
+        self.instructions.timing(application, address)
+    }
+}
+
+fn resolve(application: &Application, address_reference: &AddressReference) -> AssemblerResult<Address> {
+    Ok(address_reference.expression.evaluate(application)? as Address)
+}
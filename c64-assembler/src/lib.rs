@@ -3,8 +3,8 @@
 //! The goal of this crate is to being able to compile C64 assembly directly from Rust.
 //!
 //! The reasoning behind it is that in a typical C64 development workflow the programs
-//! are generated in a separate step and then being saved to a disk image. For generating
-//! disk images there are already crates out there like cmb.
+//! are generated in a separate step and then being saved to a disk image. The [crate::disk]
+//! and [crate::tape] modules store the generated programs on disk and tape images directly.
 //!
 //! However some projects require more control over the compilation stage and disk
 //! construction stage. Having a C64 assembler written in rust can build a bridge and
@@ -103,6 +103,21 @@
 //! ; --- Module end: MAIN ---
 //! ```
 //!
+//! ### Generating source for other assemblers
+//!
+//! The [crate::generator::Ca65Generator], [crate::generator::AcmeGenerator] and
+//! [crate::generator::KickAssemblerGenerator] generate the same source in the syntax of ca65,
+//! ACME and KickAssembler. With ca65 each function is emitted as `.proc`.
+//!
+//! ```
+//! use c64_assembler::generator::{Ca65Generator, Generator};
+//! # use c64_assembler::builder::ApplicationBuilder;
+//! # let application = ApplicationBuilder::default().build().unwrap();
+//!
+//! let source = Ca65Generator::default().generate(application).unwrap();
+//! println!("{}", source);
+//! ```
+//!
 //! ### Generating .PRG byte stream
 //!
 //! Using the [crate::generator::ProgramGenerator] to generate the byte stream.
@@ -122,6 +137,313 @@
 //! 0010:  A9 00 8D 20  D0 60
 //! ```
 //!
+//! ### Generating a listing
+//!
+//! Using the [crate::generator::ListingGenerator] to list the address, bytes, cycles and source
+//! of each instruction, followed by the used and free bytes of each segment.
+//!
+//! ```
+//! use c64_assembler::generator::{Generator, ListingGenerator};
+//! # use c64_assembler::builder::ApplicationBuilder;
+//! # let application = ApplicationBuilder::default().build().unwrap();
+//!
+//! let listing = ListingGenerator::default().generate(application).unwrap();
+//! println!("{}", listing);
+//! ```
+//!
+//! ```txt
+//! 0810  A9 00     [2]      lda #$00
+//! 0812  8D 20 D0  [4]      sta VIC2_BORDER_COLOR
+//! 0815  60        [6]      rts
+//! ```
+//!
+//! ### Exporting symbols
+//!
+//! Using the [crate::generator::SymbolGenerator] the addresses of the defines, functions and
+//! labels can be exported as VICE monitor commands, plain assignments or JSON.
+//!
+//! ```
+//! use c64_assembler::generator::{Generator, SymbolGenerator};
+//! # use c64_assembler::builder::ApplicationBuilder;
+//! # let application = ApplicationBuilder::default().build().unwrap();
+//!
+//! let labels = SymbolGenerator::default().scoped(true).generate(application).unwrap();
+//! println!("{}", labels);
+//! ```
+//!
+//! Save the output to a file and load it in VICE using `x64sc -moncommands labels.vs program.prg`.
+//!
+//! ### Disk images
+//!
+//! The generated program can be written to a [crate::disk::d64::D64], [crate::disk::d71::D71]
+//! or [crate::disk::d81::D81] disk image. Files are placed using a configurable interleave,
+//! entries without data can be used for directory art and the block availability map can be
+//! inspected. Disk images can also be read back. D81 images can contain sub-directories.
+//!
+//! ```
+//! use c64_assembler::disk::{d64::D64, DiskFile, DiskImage};
+//! # let program = vec![0x01, 0x08];
+//!
+//! let mut disk = D64::new("my disk", "01").unwrap();
+//! disk.write_file(&DiskFile::prg("my program", &program)).unwrap();
+//! let bytes = disk.to_bytes();
+//! ```
+//!
+//! ### Tape images
+//!
+//! The generated program can also be stored on tape. A [crate::tape::t64::T64] archive
+//! contains a directory of programs, a [crate::tape::tap::Tap] image contains the pulses
+//! that the KERNAL writes to the tape. Both can be read back into [crate::tape::TapeFile]s.
+//!
+//! ```
+//! use c64_assembler::tape::{tap::Tap, TapeFile};
+//! # let program = vec![0x01, 0x08];
+//!
+//! let mut tape = Tap::default();
+//! tape.add_file(&TapeFile::from_prg("my program", &program).unwrap());
+//! let bytes = tape.to_bytes();
+//! ```
+//!
+//! ### Memory map
+//!
+//! [crate::Application::memory_map] lists the memory range of each module and function. The
+//! [crate::validator::Validator] reports ranges that overlap or that are placed in a forbidden
+//! region, like the I/O area at `$D000`. The [crate::generator::MemoryMapGenerator] writes the
+//! memory map as text or JSON.
+//!
+//! ```
+//! use c64_assembler::generator::{Generator, MemoryMapFormat, MemoryMapGenerator};
+//! # use c64_assembler::builder::ApplicationBuilder;
+//! # let application = ApplicationBuilder::default().build().unwrap();
+//!
+//! let report = MemoryMapGenerator::default().format(MemoryMapFormat::Json).generate(application).unwrap();
+//! println!("{}", report);
+//! ```
+//!
+//! ### BASIC stub
+//!
+//! [crate::builder::InstructionBuilder::basic_stub] adds a BASIC program that starts the
+//! machine code with `SYS`. The address is taken from a label, so the stub follows the code
+//! when it moves. The lines are linked for the address the stub is placed at. Optional `REM`
+//! lines are configured using [crate::basic::BasicStub]; other BASIC programs are tokenized with
+//! [crate::basic::BasicProgram], which also reads `.PRG` files back into a listing. The
+//! [crate::generator::DasmGenerator] comments each BASIC line with its listing.
+//!
+//! ```
+//! use c64_assembler::basic::BasicStub;
+//! use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+//!
+//! let application = ApplicationBuilder::default()
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .instructions(
+//!                 InstructionBuilder::default()
+//!                     .basic_stub(&BasicStub::new("main").rem(1, "my demo"))
+//!                     .label("main")
+//!                     .rts()
+//!                     .build(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! ### Segments
+//!
+//! Modules and functions can be placed in named [crate::memory::segment::Segment]s, each
+//! with its own start address and optional maximum size. Labels resolve across segments.
+//! The [crate::generator::ProgramGenerator] combines the segments into a single program and
+//! pads the gaps with a fill byte; the [crate::generator::SegmentProgramGenerator] generates
+//! a program per segment. A relocated segment ([crate::memory::segment::Segment::relocated])
+//! is loaded at its start address, but its labels resolve to the address it runs at.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, ModuleBuilder};
+//! use c64_assembler::memory::segment::Segment;
+//!
+//! let application = ApplicationBuilder::default()
+//!     .segment(Segment::with_max_size("sprites", 0x2000, 0x0400))
+//!     .module(ModuleBuilder::default().name("sprites").segment("sprites").build())
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! ### Module parameters
+//!
+//! A [crate::Module] can declare [crate::ModuleParameter]s, like the address of a table or a
+//! zeropage pointer. [crate::builder::ApplicationBuilder::module_instance] binds them and
+//! prefixes the names of the module with a namespace, so a module can be included multiple
+//! times.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+//! use c64_assembler::ParameterKind;
+//!
+//! let module = ModuleBuilder::default()
+//!     .name("clear")
+//!     .parameter("POINTER", ParameterKind::Zeropage)
+//!     .instructions(InstructionBuilder::default().label("clear").sta_addr("POINTER").rts().build())
+//!     .build();
+//! let application = ApplicationBuilder::default()
+//!     .module_instance("first", &module, &[("POINTER", 0xFB)])
+//!     .module_instance("second", &module, &[("POINTER", 0xFD)])
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! ### Zeropage variables
+//!
+//! Modules request named zeropage variables using
+//! [crate::builder::ModuleBuilder::zeropage]. When the application is built each variable gets
+//! free addresses from a [crate::memory::zeropage::ZeropagePool] and is added as a define, so
+//! modules don't collide on zeropage addresses. [crate::Application::zeropage_map] lists the
+//! assigned addresses.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, ModuleBuilder};
+//! use c64_assembler::memory::zeropage::ZeropageKind;
+//!
+//! let application = ApplicationBuilder::default()
+//!     .module(ModuleBuilder::default().name("copy").zeropage("source", ZeropageKind::Pointer).build())
+//!     .module(ModuleBuilder::default().name("music").zeropage("tempo", ZeropageKind::Byte).build())
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(0xFB, application.zeropage_map[0].address);
+//! assert_eq!(0x02, application.zeropage_map[1].address);
+//! ```
+//!
+//! ### Function variations
+//!
+//! A [crate::Function] can have multiple [crate::FunctionVariant]s. The
+//! [crate::builder::ApplicationBuilder] selects a variation per function or for all functions
+//! by tag. [crate::Application::variant_report] lists the size and cycles of each variation.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder};
+//!
+//! let application = ApplicationBuilder::default()
+//!     .variant_tag("size")
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .function(
+//!                 FunctionBuilder::default()
+//!                     .name("wait")
+//!                     .instructions(InstructionBuilder::default().nop().nop().rts().build())
+//!                     .variant("small", &["size"], InstructionBuilder::default().rts().build())
+//!                     .build(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(Some("small".to_string()), application.modules[0].functions[0].variant);
+//! ```
+//!
+//! ### Local and anonymous labels
+//!
+//! Labels starting with a dot are local to the function or module they are defined in, so
+//! functions can reuse names like `.loop`. Anonymous labels (`-` and `+`) are referenced by
+//! the nearest previous (`-`, `--`, ...) or next (`+`, `++`, ...) anonymous label.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+//!
+//! let application = ApplicationBuilder::default()
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .name("main")
+//!             .instructions(
+//!                 InstructionBuilder::default()
+//!                     .local_label("loop")
+//!                     .dex()
+//!                     .bne_addr(".loop")
+//!                     .anonymous_label()
+//!                     .dey()
+//!                     .bne_addr("-")
+//!                     .rts()
+//!                     .build(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(0x0800, application.lookup_address(&"main__loop".to_string()).unwrap());
+//! ```
+//!
+//! ### Expressions
+//!
+//! Operands can be [crate::memory::expression::Expression]s that combine numbers, labels and
+//! defines with arithmetic and bitwise operators. Expressions are evaluated when the application
+//! is built and checked against the range of the addressing mode.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+//! use c64_assembler::memory::expression::Expression;
+//!
+//! let application = ApplicationBuilder::default()
+//!     .define_address("screen", 0x0400)
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .instructions(
+//!                 InstructionBuilder::default()
+//!                     .lda_imm_high(Expression::name("screen") + 40 * 10)
+//!                     .sta_addr(Expression::name("screen") + 40)
+//!                     .build(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! ### Parsing dasm source
+//!
+//! Using the [crate::parser::DasmParser] existing dasm sources (including the sources
+//! generated by the [crate::generator::DasmGenerator]) can be parsed into an application.
+//!
+//! ```
+//! use c64_assembler::parser::{DasmParser, Parser};
+//!
+//! let application = DasmParser::default()
+//!     .parse(
+//!         "
+//!   processor 6502
+//! VIC2_BORDER_COLOR = $D020
+//!   org $0800
+//! main_entry_point:
+//!   lda #$00
+//!   sta VIC2_BORDER_COLOR
+//!   rts
+//! ",
+//!     )
+//!     .unwrap();
+//! ```
+//!
+//! ### Disassembling .PRG byte stream
+//!
+//! Using the [crate::parser::ProgramParser] a .PRG byte stream can be disassembled into
+//! an application. Generating the application again results in the same byte stream.
+//!
+//! ```
+//! use c64_assembler::parser::{Parser, ProgramParser};
+//!
+//! let bytes = [0x00, 0x10, 0xA9, 0x00, 0x8D, 0x20, 0xD0, 0x60];
+//! let application = ProgramParser::default().parse(&bytes).unwrap();
+//! ```
+//!
+//! ### Illegal instructions
+//!
+//! The stable illegal (undocumented) NMOS 6502 instructions like `lax`, `sax`, `dcp` and `isc`
+//! can be recorded using the builders and macros. The [crate::validator::Validator] rejects
+//! them unless the `illegal-opcodes` feature is enabled. With the feature enabled the
+//! [crate::parser::ProgramParser] also decodes them. The [crate::generator::DasmGenerator]
+//! uses the dasm mnemonics (`isb` for `isc` and `asr` for `alr`).
+//!
+//! ```toml
+//! c64-assembler = { version = "0.2.0", features = ["illegal-opcodes"] }
+//! ```
+//!
 //! ### Using macros (work in progress)
 //!
 //! To reduce the boilerplating macros can be used. This is still under development.
@@ -150,13 +472,24 @@
 use std::collections::HashMap;
 
 use instruction::Instruction;
-use memory::{define::Define, user_count::UserCount, Address};
+use memory::{
+    define::Define,
+    memory_map::ForbiddenRegion,
+    segment::Segment,
+    user_count::UserCount,
+    zeropage::{ZeropageAllocation, ZeropageVariable},
+    Address,
+};
 use validator::{AssemblerResult, Error};
 
+pub mod basic;
 pub mod builder;
+pub mod disk;
 pub mod generator;
 pub mod instruction;
 pub mod memory;
+pub mod parser;
+pub mod tape;
 pub mod validator;
 
 #[cfg(test)]
@@ -173,8 +506,22 @@ pub struct Application {
     pub modules: Vec<Module>,
     /// Defines of the application
     pub defines: Vec<Define>,
+    /// Segments in addition to the default segment that starts at the entry point.
+    pub segments: Vec<Segment>,
     /// Lookup for addresses.
     pub address_lookup: HashMap<String, Address>,
+    /// Functions that were removed because they are never used.
+    ///
+    /// See [builder::ApplicationBuilder::strip_unused_functions].
+    pub removed_functions: Vec<RemovedFunction>,
+    /// Addresses assigned to the zeropage variables of the modules.
+    ///
+    /// See [builder::ModuleBuilder::zeropage].
+    pub zeropage_map: Vec<ZeropageAllocation>,
+    /// Memory regions that code and data may not be placed in.
+    ///
+    /// See [validator::Error::ForbiddenRegion].
+    pub forbidden_regions: Vec<ForbiddenRegion>,
 }
 impl Application {
     pub fn lookup_address(&self, address_name: &String) -> AssemblerResult<Address> {
@@ -184,6 +531,25 @@ impl Application {
             Err(Error::AddressNameUnknown(address_name.to_string()))
         }
     }
+
+    /// Number of bytes saved by removing the unused functions.
+    pub fn removed_bytes(&self) -> usize {
+        self.removed_functions
+            .iter()
+            .map(|function| function.byte_size as usize)
+            .sum()
+    }
+}
+
+/// Function that was removed from the application because it is never used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemovedFunction {
+    /// Name of the module the function was part of.
+    pub module: String,
+    /// Name of the function.
+    pub name: String,
+    /// Number of bytes the function took.
+    pub byte_size: Address,
 }
 
 /// Module
@@ -204,6 +570,45 @@ pub struct Module {
 
     /// Functions of this module.
     pub functions: Vec<Function>,
+
+    /// Segment the module is placed in; `None` places it in the default segment.
+    pub segment: Option<String>,
+
+    /// Parameters that must be bound by the application that includes the module.
+    pub parameters: Vec<ModuleParameter>,
+
+    /// Namespace of the module when it is included using
+    /// [builder::ApplicationBuilder::module_instance].
+    pub namespace: Option<String>,
+
+    /// Values bound to the parameters by [builder::ApplicationBuilder::module_instance].
+    pub bindings: Vec<(String, Address)>,
+
+    /// Zeropage variables the module needs; an address is assigned when the application is
+    /// built.
+    pub zeropage: Vec<ZeropageVariable>,
+}
+
+/// Parameter of a module.
+///
+/// The module refers to the parameter by its name. When the module is included using
+/// [builder::ApplicationBuilder::module] the parameter must be defined by the application, when
+/// included using [builder::ApplicationBuilder::module_instance] it must be bound.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleParameter {
+    pub name: String,
+    pub kind: ParameterKind,
+}
+
+/// Kind of value that can be bound to a module parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParameterKind {
+    /// Any address.
+    Address,
+    /// Address in the zeropage, for example a pointer.
+    Zeropage,
+    /// Constant value, for example the number of sprites.
+    Constant,
 }
 
 /// Function is a replaceble public part of a module.
@@ -230,9 +635,62 @@ pub struct Function {
     /// Instructions belonging to this function.
     pub instructions: Instructions,
 
+    /// Segment the function is placed in; `None` places it in the segment of its module.
+    pub segment: Option<String>,
+
+    /// Variations of the function that aren't selected.
+    ///
+    /// When a variation is selected its instructions are swapped with [Function::instructions],
+    /// so the instructions that were given when building the function become the variation
+    /// named [builder::DEFAULT_VARIANT]. See [builder::ApplicationBuilder::function_variant].
+    pub variants: Vec<FunctionVariant>,
+
+    /// Name of the selected variation; `None` when the default instructions are used.
+    pub variant: Option<String>,
+
+    /// Local labels of the function, by their unique name.
+    ///
+    /// Local labels (`.loop`) are renamed to a unique name when the application is built.
+    pub local_labels: HashMap<String, String>,
+
     user_count: usize,
 }
 
+/// Variation of a function.
+///
+/// All variations of a function must start with the same entry label, so callers don't need
+/// to know which variation is selected.
+#[derive(Default, Clone)]
+pub struct FunctionVariant {
+    /// Name of the variation, for example `size_optimized`.
+    pub name: String,
+    /// Tags to select the variation for all functions at once, for example `size` or `speed`.
+    pub tags: Vec<String>,
+    /// Instructions of the variation.
+    pub instructions: Instructions,
+}
+
+/// Size and cycles of a variation of a function.
+///
+/// See [Application::variant_report].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantReport {
+    /// Name of the module the function is part of.
+    pub module: String,
+    /// Name of the function.
+    pub function: String,
+    /// Name of the variation.
+    pub variant: String,
+    /// Is this the variation that is part of the application.
+    pub selected: bool,
+    /// Number of bytes the variation takes.
+    pub byte_size: Address,
+    /// Sum of the minimum cycles of all instructions of the variation.
+    pub min_cycles: u32,
+    /// Sum of the maximum cycles of all instructions of the variation.
+    pub max_cycles: u32,
+}
+
 impl UserCount for Function {
     fn user_increase(&mut self) {
         self.user_count += 1;
@@ -1,6 +1,11 @@
-use crate::{validator::AssemblerResult, Application};
+use c64_assembler_6502::{instruction::InstructionDef, opcodes::*};
 
-use super::{label::AddressReference, Address, ZeroPage};
+use crate::{
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+use super::{expression::Expression, label::AddressReference, Address};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum AddressMode {
@@ -17,8 +22,16 @@ pub enum AddressMode {
     IndirectIndexed(AddressReference),
 }
 
+/// Can the address be accessed using a zeropage op-code.
+///
+/// While label addresses are being determined an expression can fail to evaluate (for example
+/// a division by zero). These expressions use the absolute op-code until they can be evaluated.
 fn is_zeropage(application: &Application, address_reference: &AddressReference) -> AssemblerResult<bool> {
-    Ok(application.lookup_address(&address_reference.name)?.is_zeropage())
+    match address_reference.expression.evaluate(application) {
+        Ok(address) => Ok((0..=0xFF).contains(&address)),
+        Err(Error::AddressNameUnknown(name)) => Err(Error::AddressNameUnknown(name)),
+        Err(_) => Ok(false),
+    }
 }
 
 impl AddressMode {
@@ -44,6 +57,86 @@ impl AddressMode {
             }
         }
     }
+
+    /// Can an instruction with the given definition be encoded using this address mode.
+    ///
+    /// Absolute address modes are supported when the instruction has an absolute or a zeropage
+    /// op-code. Which one can be used depends on the address.
+    ///
+    /// ```
+    /// use c64_assembler::memory::address_mode::{AddressMode, Immediate};
+    /// use c64_assembler_6502::instruction::{OPCODES_LDA, OPCODES_STA};
+    ///
+    /// let address_mode = AddressMode::Immediate(Immediate::Byte(0x00));
+    /// assert!(address_mode.is_supported_by(&OPCODES_LDA));
+    /// assert!(!address_mode.is_supported_by(&OPCODES_STA));
+    /// ```
+    pub fn is_supported_by(&self, definition: &InstructionDef) -> bool {
+        match self {
+            AddressMode::Implied => definition.implied != NO_IMPLIED,
+            AddressMode::Accumulator => definition.accumulator != NO_ACCUMULATOR,
+            AddressMode::Immediate(_) => definition.immediate != NO_IMMEDIATE,
+            AddressMode::Absolute(_) => definition.absolute != NO_ABSOLUTE || definition.zeropage != NO_ZEROPAGE,
+            AddressMode::AbsoluteX(_) => {
+                definition.absolute_x != NO_ABSOLUTE_X || definition.zeropage_x != NO_ZEROPAGE_X
+            }
+            AddressMode::AbsoluteY(_) => {
+                definition.absolute_y != NO_ABSOLUTE_Y || definition.zeropage_y != NO_ZEROPAGE_Y
+            }
+            AddressMode::Relative(_) => definition.relative != NO_RELATIVE,
+            AddressMode::Indirect(_) => definition.indirect != NO_INDIRECT,
+            AddressMode::IndexedIndirect(_) => definition.indexed_indirect != NO_INDEXED_INDIRECT,
+            AddressMode::IndirectIndexed(_) => definition.indirect_indexed != NO_INDIRECT_INDEXED,
+        }
+    }
+
+    /// Does an instruction with the given definition only have a zeropage op-code for this
+    /// address mode. The address must then be a zeropage address.
+    ///
+    /// The indexed indirect and indirect indexed address modes always use a zeropage address.
+    pub fn requires_zeropage(&self, definition: &InstructionDef) -> bool {
+        match self {
+            AddressMode::Absolute(_) => definition.absolute == NO_ABSOLUTE && definition.zeropage != NO_ZEROPAGE,
+            AddressMode::AbsoluteX(_) => {
+                definition.absolute_x == NO_ABSOLUTE_X && definition.zeropage_x != NO_ZEROPAGE_X
+            }
+            AddressMode::AbsoluteY(_) => {
+                definition.absolute_y == NO_ABSOLUTE_Y && definition.zeropage_y != NO_ZEROPAGE_Y
+            }
+            AddressMode::IndexedIndirect(_) | AddressMode::IndirectIndexed(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Name of the address mode, used in error messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AddressMode::Implied => "implied",
+            AddressMode::Accumulator => "accumulator",
+            AddressMode::Immediate(_) => "immediate",
+            AddressMode::Absolute(_) => "absolute",
+            AddressMode::AbsoluteX(_) => "absolute,x",
+            AddressMode::AbsoluteY(_) => "absolute,y",
+            AddressMode::Relative(_) => "relative",
+            AddressMode::Indirect(_) => "indirect",
+            AddressMode::IndexedIndirect(_) => "indexed indirect",
+            AddressMode::IndirectIndexed(_) => "indirect indexed",
+        }
+    }
+
+    /// Reference to the address used by this address mode.
+    pub fn address_reference(&self) -> Option<&AddressReference> {
+        match self {
+            AddressMode::Absolute(address_reference)
+            | AddressMode::AbsoluteX(address_reference)
+            | AddressMode::AbsoluteY(address_reference)
+            | AddressMode::Relative(address_reference)
+            | AddressMode::Indirect(address_reference)
+            | AddressMode::IndexedIndirect(address_reference)
+            | AddressMode::IndirectIndexed(address_reference) => Some(address_reference),
+            AddressMode::Implied | AddressMode::Accumulator | AddressMode::Immediate(_) => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -51,4 +144,6 @@ pub enum Immediate {
     Byte(u8),
     Low(AddressReference),
     High(AddressReference),
+    /// Expression that evaluates to a byte; negative values are stored as two's complement.
+    Expression(Expression),
 }
@@ -0,0 +1,269 @@
+//! Expressions that are evaluated when the application is built.
+//!
+//! Expressions combine constants and the addresses of labels and defines using arithmetic
+//! and bitwise operators. They can be constructed using the Rust operators.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+//! use c64_assembler::memory::expression::Expression;
+//!
+//! let application = ApplicationBuilder::default()
+//!     .define_address("screen", 0x0400)
+//!     .define_address("row", 0x0002)
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .instructions(
+//!                 InstructionBuilder::default()
+//!                     .sta_addr(Expression::name("screen") + Expression::number(40) * Expression::name("row"))
+//!                     .lda_imm_expr(Expression::name("end") - Expression::name("start"))
+//!                     .label("start")
+//!                     .label("end")
+//!                     .build(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! ```
+use std::fmt::Display;
+
+use crate::{
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+use super::Address;
+
+/// Operator that combines two expressions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    And,
+    Or,
+    Xor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl BinaryOperator {
+    /// Symbol of the operator.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::And => "&",
+            BinaryOperator::Or => "|",
+            BinaryOperator::Xor => "^",
+            BinaryOperator::ShiftLeft => "<<",
+            BinaryOperator::ShiftRight => ">>",
+        }
+    }
+
+    /// Precedence of the operator; operators with a higher precedence bind stronger.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Multiply | BinaryOperator::Divide => 5,
+            BinaryOperator::Add | BinaryOperator::Subtract => 4,
+            BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => 3,
+            BinaryOperator::And => 2,
+            BinaryOperator::Xor => 1,
+            BinaryOperator::Or => 0,
+        }
+    }
+}
+
+/// Expression tree.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    /// Constant value.
+    Number(i64),
+    /// Address of a label or define.
+    Name(String),
+    /// Low byte of the expression (`<`).
+    Low(Box<Expression>),
+    /// High byte of the expression (`>`).
+    High(Box<Expression>),
+    /// Unary minus.
+    Negate(Box<Expression>),
+    /// Two expressions combined with an operator.
+    Binary(BinaryOperator, Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    pub fn number(value: i64) -> Expression {
+        Expression::Number(value)
+    }
+
+    pub fn name(name: &str) -> Expression {
+        Expression::Name(name.to_string())
+    }
+
+    /// Low byte of this expression.
+    pub fn low(self) -> Expression {
+        Expression::Low(Box::new(self))
+    }
+
+    /// High byte of this expression.
+    pub fn high(self) -> Expression {
+        Expression::High(Box::new(self))
+    }
+
+    pub fn binary(operator: BinaryOperator, left: Expression, right: Expression) -> Expression {
+        Expression::Binary(operator, Box::new(left), Box::new(right))
+    }
+
+    /// Names of the labels and defines that are used in this expression.
+    pub fn names(&self) -> Vec<&String> {
+        let mut result = vec![];
+        self.collect_names(&mut result);
+        result
+    }
+
+    fn collect_names<'a>(&'a self, names: &mut Vec<&'a String>) {
+        match self {
+            Expression::Number(_) => {}
+            Expression::Name(name) => names.push(name),
+            Expression::Low(expression) | Expression::High(expression) | Expression::Negate(expression) => {
+                expression.collect_names(names)
+            }
+            Expression::Binary(_, left, right) => {
+                left.collect_names(names);
+                right.collect_names(names);
+            }
+        }
+    }
+
+    /// Replace the names used in this expression; names for which `rename` returns `None` are kept.
+    pub fn rename(&mut self, rename: &impl Fn(&str) -> Option<String>) {
+        match self {
+            Expression::Number(_) => {}
+            Expression::Name(name) => {
+                if let Some(new_name) = rename(name) {
+                    *name = new_name;
+                }
+            }
+            Expression::Low(expression) | Expression::High(expression) | Expression::Negate(expression) => {
+                expression.rename(rename)
+            }
+            Expression::Binary(_, left, right) => {
+                left.rename(rename);
+                right.rename(rename);
+            }
+        }
+    }
+
+    /// Is this expression a single number or name.
+    pub fn is_atomic(&self) -> bool {
+        matches!(self, Expression::Number(_) | Expression::Name(_))
+    }
+
+    /// Evaluate the expression using the addresses of the application.
+    ///
+    /// ```
+    /// use c64_assembler::builder::ApplicationBuilder;
+    /// use c64_assembler::memory::expression::Expression;
+    ///
+    /// let application = ApplicationBuilder::default().define_address("table", 0x1234).build().unwrap();
+    /// let expression = (Expression::name("table") + 0x0100 * 2).high();
+    /// assert_eq!(0x14, expression.evaluate(&application).unwrap());
+    /// ```
+    pub fn evaluate(&self, application: &Application) -> AssemblerResult<i64> {
+        Ok(match self {
+            Expression::Number(value) => *value,
+            Expression::Name(name) => application.lookup_address(name)? as i64,
+            Expression::Low(expression) => expression.evaluate(application)? & 0xFF,
+            Expression::High(expression) => (expression.evaluate(application)? >> 8) & 0xFF,
+            Expression::Negate(expression) => expression.evaluate(application)?.wrapping_neg(),
+            Expression::Binary(operator, left, right) => {
+                let left = left.evaluate(application)?;
+                let right = right.evaluate(application)?;
+                match operator {
+                    BinaryOperator::Add => left.wrapping_add(right),
+                    BinaryOperator::Subtract => left.wrapping_sub(right),
+                    BinaryOperator::Multiply => left.wrapping_mul(right),
+                    BinaryOperator::Divide => left
+                        .checked_div(right)
+                        .ok_or_else(|| Error::InvalidExpression(format!("division by zero in {self}")))?,
+                    BinaryOperator::And => left & right,
+                    BinaryOperator::Or => left | right,
+                    BinaryOperator::Xor => left ^ right,
+                    BinaryOperator::ShiftLeft => shift(left, right, i64::checked_shl),
+                    BinaryOperator::ShiftRight => shift(left, right, i64::checked_shr),
+                }
+            }
+        })
+    }
+}
+
+fn shift(value: i64, amount: i64, operation: fn(i64, u32) -> Option<i64>) -> i64 {
+    u32::try_from(amount)
+        .ok()
+        .and_then(|amount| operation(value, amount))
+        .unwrap_or(0)
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expression::Number(value) => write!(f, "{value}"),
+            Expression::Name(name) => write!(f, "{name}"),
+            Expression::Low(expression) => write!(f, "<({expression})"),
+            Expression::High(expression) => write!(f, ">({expression})"),
+            Expression::Negate(expression) => write!(f, "-({expression})"),
+            Expression::Binary(operator, left, right) => write!(f, "({left}{}{right})", operator.symbol()),
+        }
+    }
+}
+
+impl From<&str> for Expression {
+    fn from(name: &str) -> Expression {
+        Expression::name(name)
+    }
+}
+
+impl From<i32> for Expression {
+    fn from(value: i32) -> Expression {
+        Expression::Number(value as i64)
+    }
+}
+
+impl From<Address> for Expression {
+    fn from(value: Address) -> Expression {
+        Expression::Number(value as i64)
+    }
+}
+
+macro_rules! binary_operator {
+    ($trait: ident, $function: ident, $operator: expr) => {
+        impl<T: Into<Expression>> std::ops::$trait<T> for Expression {
+            type Output = Expression;
+
+            fn $function(self, right: T) -> Expression {
+                Expression::binary($operator, self, right.into())
+            }
+        }
+    };
+}
+
+binary_operator!(Add, add, BinaryOperator::Add);
+binary_operator!(Sub, sub, BinaryOperator::Subtract);
+binary_operator!(Mul, mul, BinaryOperator::Multiply);
+binary_operator!(Div, div, BinaryOperator::Divide);
+binary_operator!(BitAnd, bitand, BinaryOperator::And);
+binary_operator!(BitOr, bitor, BinaryOperator::Or);
+binary_operator!(BitXor, bitxor, BinaryOperator::Xor);
+binary_operator!(Shl, shl, BinaryOperator::ShiftLeft);
+binary_operator!(Shr, shr, BinaryOperator::ShiftRight);
+
+impl std::ops::Neg for Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        Expression::Negate(Box::new(self))
+    }
+}
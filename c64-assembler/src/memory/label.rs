@@ -1,26 +1,65 @@
-use super::Address;
+use super::{expression::Expression, Address};
 
 pub struct Label {
     pub name: String,
     pub address: Address,
 }
 
+/// Reference to an address, given by an expression that is evaluated when the application is built.
 #[derive(Clone, Debug, PartialEq)]
 pub struct AddressReference {
-    pub name: String,
-    pub offset: Address,
+    pub expression: Expression,
 }
 impl AddressReference {
     pub fn new(name: &str) -> AddressReference {
         AddressReference {
-            name: name.to_string(),
-            offset: 0,
+            expression: Expression::name(name),
         }
     }
     pub fn with_offset(name: &str, offset: Address) -> AddressReference {
+        if offset == 0 {
+            return AddressReference::new(name);
+        }
         AddressReference {
-            name: name.to_string(),
-            offset,
+            expression: Expression::name(name) + offset,
         }
     }
+
+    /// Names of the labels and defines that are referenced.
+    pub fn names(&self) -> Vec<&String> {
+        self.expression.names()
+    }
+}
+
+impl From<&str> for AddressReference {
+    fn from(name: &str) -> AddressReference {
+        AddressReference::new(name)
+    }
+}
+
+impl From<Expression> for AddressReference {
+    fn from(expression: Expression) -> AddressReference {
+        AddressReference { expression }
+    }
+}
+
+/// Is the name a local label (`.loop`).
+///
+/// Local labels are scoped to the function they are defined in, or to the module when they are
+/// defined in the instructions of the module.
+pub fn is_local_label(name: &str) -> bool {
+    name.len() > 1 && name.starts_with('.')
+}
+
+/// Is the name an anonymous label (`-` or `+`) or a reference to one (`--`, `+`, `++`).
+///
+/// `-` refers to the previous anonymous label, `--` to the one before. `+` refers to the next
+/// anonymous label, `++` to the one after.
+pub fn is_anonymous_label(name: &str) -> bool {
+    !name.is_empty() && (name.chars().all(|c| c == '-') || name.chars().all(|c| c == '+'))
+}
+
+/// Is the name scoped to a function or module; see [is_local_label] and [is_anonymous_label].
+pub fn is_scoped_label(name: &str) -> bool {
+    is_local_label(name) || is_anonymous_label(name)
 }
@@ -0,0 +1,200 @@
+//! Memory ranges that are used by the modules and functions of an application.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+//! use c64_assembler::memory::segment::Segment;
+//!
+//! let application = ApplicationBuilder::default()
+//!     .segment(Segment::new("io", 0xD000))
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .name("main")
+//!             .instructions(InstructionBuilder::default().lda_imm(0x00).rts().build())
+//!             .build(),
+//!     )
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .name("data")
+//!             .segment("io")
+//!             .instructions(InstructionBuilder::default().raw(&[0x00; 4]).build())
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! let memory_map = application.memory_map().unwrap();
+//! assert_eq!(2, memory_map.ranges.len());
+//! assert_eq!("I/O", memory_map.forbidden_writes()[0].1.name);
+//! ```
+use crate::{
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+use super::Address;
+
+/// Memory region that code and data may not be placed in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForbiddenRegion {
+    pub name: String,
+    /// Address of the first byte of the region.
+    pub start: Address,
+    /// Address of the last byte of the region.
+    pub end: Address,
+}
+
+impl ForbiddenRegion {
+    pub fn new(name: &str, start: Address, end: Address) -> ForbiddenRegion {
+        ForbiddenRegion {
+            name: name.to_string(),
+            start,
+            end,
+        }
+    }
+
+    /// Regions of the C64 that are not usable without banking: the processor port, the stack,
+    /// the BASIC ROM, the I/O area and the KERNAL ROM.
+    pub fn c64_defaults() -> Vec<ForbiddenRegion> {
+        vec![
+            ForbiddenRegion::new("processor port", 0x0000, 0x0001),
+            ForbiddenRegion::new("stack", 0x0100, 0x01FF),
+            ForbiddenRegion::new("BASIC ROM", 0xA000, 0xBFFF),
+            ForbiddenRegion::new("I/O", 0xD000, 0xDFFF),
+            ForbiddenRegion::new("KERNAL ROM", 0xE000, 0xFFFF),
+        ]
+    }
+
+    /// Does the region contain any of the bytes of the range.
+    pub fn intersects(&self, range: &MemoryRange) -> bool {
+        range.byte_size > 0 && range.start as u32 <= self.end as u32 && self.start as u32 <= range.last()
+    }
+}
+
+/// Bytes used by the instructions of a module or by a function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryRange {
+    /// Name of the module.
+    pub module: String,
+    /// Name of the function; `None` for the instructions of the module.
+    pub function: Option<String>,
+    /// Name of the segment the range is placed in.
+    pub segment: String,
+    /// Address of the first byte.
+    pub start: Address,
+    /// Number of bytes; code that runs past `$FFFF` doesn't wrap around.
+    pub byte_size: u32,
+}
+
+impl MemoryRange {
+    /// Address of the last byte; can be beyond `$FFFF`.
+    pub fn last(&self) -> u32 {
+        self.start as u32 + self.byte_size.max(1) - 1
+    }
+
+    /// Name of the function prefixed with the module, separated by a dot.
+    pub fn scoped_name(&self) -> String {
+        [Some(&self.module), self.function.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter(|name| !name.is_empty())
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+
+    /// Do the ranges share any bytes.
+    pub fn overlaps(&self, other: &MemoryRange) -> bool {
+        self.byte_size > 0
+            && other.byte_size > 0
+            && self.start as u32 <= other.last()
+            && other.start as u32 <= self.last()
+    }
+}
+
+/// Memory ranges of an application together with the regions they may not use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryMap {
+    /// Ranges of the modules and functions that contain at least one byte, ordered by address.
+    pub ranges: Vec<MemoryRange>,
+    pub forbidden_regions: Vec<ForbiddenRegion>,
+}
+
+impl MemoryMap {
+    /// Pairs of ranges that share bytes; the first range starts before or at the second.
+    pub fn overlaps(&self) -> Vec<(&MemoryRange, &MemoryRange)> {
+        let mut result = vec![];
+        for (index, range) in self.ranges.iter().enumerate() {
+            for other in &self.ranges[index + 1..] {
+                if range.overlaps(other) {
+                    result.push((range, other));
+                }
+            }
+        }
+        result
+    }
+
+    /// Ranges that use bytes of a forbidden region.
+    pub fn forbidden_writes(&self) -> Vec<(&MemoryRange, &ForbiddenRegion)> {
+        let mut result = vec![];
+        for range in &self.ranges {
+            for region in &self.forbidden_regions {
+                if region.intersects(range) {
+                    result.push((range, region));
+                }
+            }
+        }
+        result
+    }
+
+    /// Overlaps and forbidden writes as errors, together with the range they are reported at.
+    pub fn errors(&self) -> Vec<(&MemoryRange, Error)> {
+        let mut result = vec![];
+        for (first, second) in self.overlaps() {
+            let error = Error::MemoryOverlap {
+                first: first.scoped_name(),
+                second: second.scoped_name(),
+                address: second.start,
+            };
+            result.push((second, error));
+        }
+        for (range, region) in self.forbidden_writes() {
+            let error = Error::ForbiddenRegion {
+                region: region.name.clone(),
+                address: range.start.max(region.start),
+            };
+            result.push((range, error));
+        }
+        result
+    }
+}
+
+impl Application {
+    /// Determine the memory ranges of the modules and functions.
+    ///
+    /// The addresses are known after the application is built.
+    pub fn memory_map(&self) -> AssemblerResult<MemoryMap> {
+        let mut ranges = vec![];
+        for layout in self.layout()? {
+            let mut address = layout.segment.run_start() as u32;
+            for placement in &layout.placements {
+                let start = address;
+                for instruction in &placement.instructions().instructions {
+                    address += instruction.byte_size(self, address as Address)? as u32;
+                }
+                if address > start {
+                    ranges.push(MemoryRange {
+                        module: placement.module().name.clone(),
+                        function: placement.function().map(|function| function.name.clone()),
+                        segment: layout.segment.name.clone(),
+                        start: start as Address,
+                        byte_size: address - start,
+                    });
+                }
+            }
+        }
+        ranges.sort_by_key(|range| range.start);
+        Ok(MemoryMap {
+            ranges,
+            forbidden_regions: self.forbidden_regions.clone(),
+        })
+    }
+}
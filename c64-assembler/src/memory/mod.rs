@@ -1,7 +1,11 @@
 pub mod address_mode;
 pub mod define;
+pub mod expression;
 pub mod label;
+pub mod memory_map;
+pub mod segment;
 pub mod user_count;
+pub mod zeropage;
 
 /// Memory address
 pub type Address = u16;
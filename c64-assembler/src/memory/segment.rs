@@ -0,0 +1,206 @@
+//! Segments place modules and functions at their own start address.
+//!
+//! ```
+//! use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+//! use c64_assembler::memory::segment::Segment;
+//!
+//! let application = ApplicationBuilder::default()
+//!     .segment(Segment::with_max_size("charset", 0x3800, 0x0800))
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .name("main")
+//!             .instructions(InstructionBuilder::default().lda_imm_high("charset").rts().build())
+//!             .build(),
+//!     )
+//!     .module(
+//!         ModuleBuilder::default()
+//!             .name("charset")
+//!             .segment("charset")
+//!             .instructions(InstructionBuilder::default().label("charset").raw(&[0x00; 8]).build())
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(0x3800, application.lookup_address(&"charset".to_string()).unwrap());
+//! ```
+use crate::{
+    instruction::Instruction,
+    validator::{AssemblerResult, Error},
+    Application, Function, Instructions, Module,
+};
+
+use super::Address;
+
+/// Name of the segment that starts at the entry point of the application.
+///
+/// Modules and functions that aren't assigned to a segment are placed in this segment.
+pub const DEFAULT_SEGMENT: &str = "default";
+
+/// Named memory region where modules and functions are placed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub name: String,
+    /// Address of the first byte of the segment.
+    pub start: Address,
+    /// Maximum number of bytes the segment may contain.
+    pub max_size: Option<Address>,
+    /// Address the instructions are assembled for when the segment is copied to another
+    /// address before it runs. `None` when the segment runs where it is loaded.
+    pub run_address: Option<Address>,
+}
+
+impl Segment {
+    pub fn new(name: &str, start: Address) -> Segment {
+        Segment {
+            name: name.to_string(),
+            start,
+            max_size: None,
+            run_address: None,
+        }
+    }
+
+    pub fn with_max_size(name: &str, start: Address, max_size: Address) -> Segment {
+        Segment {
+            name: name.to_string(),
+            start,
+            max_size: Some(max_size),
+            run_address: None,
+        }
+    }
+
+    /// Segment that is loaded at `start`, but assembled to run at `run_address`.
+    ///
+    /// Labels inside the segment resolve to addresses relative to `run_address`. The program
+    /// has to copy the segment to `run_address` before it is used.
+    ///
+    /// ```
+    /// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+    /// use c64_assembler::generator::{Generator, ProgramGenerator};
+    /// use c64_assembler::memory::segment::Segment;
+    ///
+    /// let application = ApplicationBuilder::default()
+    ///     .entry_point(0x0810)
+    ///     .segment(Segment::relocated("irq", 0x0820, 0xC000))
+    ///     .module(ModuleBuilder::default().instructions(InstructionBuilder::default().rts().build()).build())
+    ///     .module(
+    ///         ModuleBuilder::default()
+    ///             .segment("irq")
+    ///             .instructions(InstructionBuilder::default().label("irq").jmp_addr("irq").build())
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(0xC000, application.lookup_address(&"irq".to_string()).unwrap());
+    ///
+    /// let program = ProgramGenerator::default().generate(application).unwrap();
+    /// assert_eq!(&[0x4C, 0x00, 0xC0], &program[0x0820 - 0x0810 + 2..]);
+    /// ```
+    pub fn relocated(name: &str, start: Address, run_address: Address) -> Segment {
+        Segment {
+            name: name.to_string(),
+            start,
+            max_size: None,
+            run_address: Some(run_address),
+        }
+    }
+
+    /// Address of the first instruction when the segment runs.
+    pub fn run_start(&self) -> Address {
+        self.run_address.unwrap_or(self.start)
+    }
+}
+
+/// Part of an application that is placed inside a segment.
+#[derive(Clone, Copy)]
+pub enum Placement<'a> {
+    /// Instructions of the module, without its functions.
+    Module(&'a Module),
+    /// Function together with the module it belongs to.
+    Function(&'a Module, &'a Function),
+}
+
+/// Segment together with the parts of the application that are placed inside it.
+pub struct SegmentLayout<'a> {
+    pub segment: Segment,
+    pub placements: Vec<Placement<'a>>,
+}
+
+impl Application {
+    /// Determine where the modules and functions are placed in memory.
+    ///
+    /// Segments are ordered by their start address. The default segment starts at the entry
+    /// point. Segments without any modules or functions are left out.
+    pub fn layout(&self) -> AssemblerResult<Vec<SegmentLayout<'_>>> {
+        let mut layouts = vec![SegmentLayout {
+            segment: Segment::new(DEFAULT_SEGMENT, self.entry_point),
+            placements: vec![],
+        }];
+        layouts.extend(self.segments.iter().map(|segment| SegmentLayout {
+            segment: segment.clone(),
+            placements: vec![],
+        }));
+
+        let mut placements = vec![];
+        for module in &self.modules {
+            placements.push((module.segment.as_ref(), Placement::Module(module)));
+            for function in &module.functions {
+                let segment_name = function.segment.as_ref().or(module.segment.as_ref());
+                placements.push((segment_name, Placement::Function(module, function)));
+            }
+        }
+        for (segment_name, placement) in placements {
+            let segment_name = segment_name.map_or(DEFAULT_SEGMENT, String::as_str);
+            let layout = layouts
+                .iter_mut()
+                .find(|layout| layout.segment.name == segment_name)
+                .ok_or_else(|| Error::SegmentUnknown(segment_name.to_string()))?;
+            layout.placements.push(placement);
+        }
+
+        layouts.retain(|layout| !layout.placements.is_empty());
+        layouts.sort_by_key(|layout| layout.segment.start);
+        Ok(layouts)
+    }
+
+    /// Call the visitor for each instruction with its placement, index and address.
+    pub(crate) fn visit_placed_instructions(
+        &self,
+        mut visitor: impl FnMut(&Placement, usize, &Instruction, Address),
+    ) -> AssemblerResult<()> {
+        for layout in self.layout()? {
+            let mut address = layout.segment.run_start();
+            for placement in &layout.placements {
+                for (instruction_index, instruction) in placement.instructions().instructions.iter().enumerate() {
+                    visitor(placement, instruction_index, instruction, address);
+                    address = address.wrapping_add(instruction.byte_size(self, address)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Placement<'a> {
+    /// Module this placement belongs to.
+    pub fn module(&self) -> &'a Module {
+        match self {
+            Placement::Module(module) | Placement::Function(module, _) => module,
+        }
+    }
+
+    /// Function this placement belongs to; `None` for the instructions of a module.
+    pub fn function(&self) -> Option<&'a Function> {
+        match self {
+            Placement::Module(_) => None,
+            Placement::Function(_, function) => Some(function),
+        }
+    }
+
+    /// Instructions that are placed.
+    pub fn instructions(&self) -> &'a Instructions {
+        match self {
+            Placement::Module(module) => &module.instructions,
+            Placement::Function(_, function) => &function.instructions,
+        }
+    }
+}
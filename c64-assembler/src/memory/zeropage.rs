@@ -0,0 +1,217 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+use super::{define::Value, Address, ZeroPage};
+
+/// Kind of zeropage variable a module can request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZeropageKind {
+    /// Single byte.
+    Byte,
+    /// Two consecutive bytes, low byte first.
+    Word,
+    /// Two consecutive bytes that can be used for indirect addressing (`lda (pointer),y`).
+    Pointer,
+}
+
+impl ZeropageKind {
+    /// Number of bytes the variable takes.
+    pub fn byte_size(&self) -> Address {
+        match self {
+            ZeropageKind::Byte => 1,
+            ZeropageKind::Word | ZeropageKind::Pointer => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for ZeropageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            ZeropageKind::Byte => "byte",
+            ZeropageKind::Word => "word",
+            ZeropageKind::Pointer => "pointer",
+        })
+    }
+}
+
+/// Zeropage variable requested by a module.
+///
+/// See [crate::builder::ModuleBuilder::zeropage].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZeropageVariable {
+    pub name: String,
+    pub kind: ZeropageKind,
+}
+
+/// Address that was assigned to a zeropage variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZeropageAllocation {
+    /// Name of the module that requested the variable.
+    pub module: String,
+    /// Name of the variable.
+    pub name: String,
+    /// Kind of the variable.
+    pub kind: ZeropageKind,
+    /// Address of the first byte of the variable.
+    pub address: Address,
+}
+
+/// Zeropage addresses that are available for zeropage variables.
+///
+/// The default pool contains the addresses that are free when BASIC and the KERNAL are in use
+/// (`$02` and `$FB-$FE`). The processor port at `$00-$01` is never part of a pool.
+///
+/// ```
+/// use c64_assembler::memory::zeropage::{ZeropageKind, ZeropagePool};
+///
+/// let mut pool = ZeropagePool::default();
+/// assert_eq!(Some(0x02), pool.allocate(ZeropageKind::Byte));
+/// assert_eq!(Some(0xFB), pool.allocate(ZeropageKind::Pointer));
+/// assert_eq!(Some(0xFD), pool.allocate(ZeropageKind::Word));
+/// assert_eq!(None, pool.allocate(ZeropageKind::Byte));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZeropagePool {
+    free: [bool; 0x100],
+}
+
+/// Processor port; can never be allocated.
+const PROCESSOR_PORT: RangeInclusive<Address> = 0x00..=0x01;
+/// Unused by BASIC and the KERNAL.
+const UNUSED: RangeInclusive<Address> = 0x02..=0x02;
+/// Used by BASIC.
+const BASIC: RangeInclusive<Address> = 0x03..=0x8F;
+/// Unused by BASIC and the KERNAL.
+const FREE: RangeInclusive<Address> = 0xFB..=0xFE;
+
+impl Default for ZeropagePool {
+    fn default() -> Self {
+        ZeropagePool::empty().free(UNUSED).free(FREE)
+    }
+}
+
+impl ZeropagePool {
+    /// Pool without any free addresses.
+    pub fn empty() -> Self {
+        ZeropagePool { free: [false; 0x100] }
+    }
+
+    /// Pool for applications that don't return to BASIC, the addresses used by BASIC are
+    /// free as well (`$02-$8F` and `$FB-$FE`).
+    ///
+    /// ```
+    /// use c64_assembler::memory::zeropage::ZeropagePool;
+    ///
+    /// let pool = ZeropagePool::without_basic();
+    /// assert!(pool.is_free(0x10));
+    /// assert!(!pool.is_free(0x90));
+    /// ```
+    pub fn without_basic() -> Self {
+        ZeropagePool::default().free(BASIC)
+    }
+
+    /// Add the addresses to the pool.
+    ///
+    /// ```
+    /// use c64_assembler::memory::zeropage::ZeropagePool;
+    ///
+    /// let pool = ZeropagePool::empty().free(0x00..=0x03);
+    /// assert!(!pool.is_free(0x01));
+    /// assert!(pool.is_free(0x03));
+    /// ```
+    pub fn free(mut self, addresses: RangeInclusive<Address>) -> Self {
+        for address in addresses.filter(|address| address.is_zeropage() && !PROCESSOR_PORT.contains(address)) {
+            self.free[address as usize] = true;
+        }
+        self
+    }
+
+    /// Remove the addresses from the pool, for example addresses that the application uses
+    /// directly.
+    ///
+    /// ```
+    /// use c64_assembler::memory::zeropage::ZeropagePool;
+    ///
+    /// let pool = ZeropagePool::default().reserve(0xFB..=0xFC);
+    /// assert!(!pool.is_free(0xFB));
+    /// assert!(pool.is_free(0xFD));
+    /// ```
+    pub fn reserve(mut self, addresses: RangeInclusive<Address>) -> Self {
+        self.reserve_addresses(addresses);
+        self
+    }
+
+    /// Is the address free to be allocated.
+    pub fn is_free(&self, address: Address) -> bool {
+        address.is_zeropage() && self.free[address as usize]
+    }
+
+    /// Allocate the first free addresses that fit a variable of the given kind.
+    ///
+    /// Returns `None` when the pool has no room for the variable.
+    pub fn allocate(&mut self, kind: ZeropageKind) -> Option<Address> {
+        let byte_size = kind.byte_size();
+        let address =
+            (0..=0x100 - byte_size).find(|address| (*address..*address + byte_size).all(|a| self.is_free(a)))?;
+        self.reserve_addresses(address..=address + byte_size - 1);
+        Some(address)
+    }
+
+    fn reserve_addresses(&mut self, addresses: RangeInclusive<Address>) {
+        for address in addresses.filter(|address| address.is_zeropage()) {
+            self.free[address as usize] = false;
+        }
+    }
+}
+
+/// Number of bytes that are reserved for a zeropage define. The size of a define isn't known,
+/// it is reserved as a pointer.
+const DEFINE_BYTE_SIZE: Address = 2;
+
+/// Assign an address from the pool to the zeropage variables of all modules and register them
+/// as defines.
+///
+/// Zeropage defines, including zeropage parameters that are bound to an address, are removed
+/// from the pool first.
+pub(crate) fn allocate_zeropage(application: &mut Application, pool: &ZeropagePool) -> AssemblerResult<()> {
+    // Building an application twice allocates the variables again.
+    for allocation in std::mem::take(&mut application.zeropage_map) {
+        application.address_lookup.remove(&allocation.name);
+        application.defines.retain(|define| define.name != allocation.name);
+    }
+
+    let mut pool = pool.clone();
+    for define in &application.defines {
+        if let Value::Zeropage(address) = define.value {
+            pool.reserve_addresses(address..=address + DEFINE_BYTE_SIZE - 1);
+        }
+    }
+
+    let mut allocations = vec![];
+    for module in &application.modules {
+        for variable in &module.zeropage {
+            let Some(address) = pool.allocate(variable.kind) else {
+                return Err(Error::ZeropageExhausted {
+                    module: module.name.clone(),
+                    variable: variable.name.clone(),
+                });
+            };
+            allocations.push(ZeropageAllocation {
+                module: module.name.clone(),
+                name: variable.name.clone(),
+                kind: variable.kind,
+                address,
+            });
+        }
+    }
+
+    for allocation in &allocations {
+        application.define_address(&allocation.name, allocation.address);
+    }
+    application.zeropage_map = allocations;
+    Ok(())
+}
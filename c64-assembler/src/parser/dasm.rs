@@ -0,0 +1,836 @@
+use c64_assembler_6502::{instruction::InstructionDef, opcodes::*};
+
+use crate::{
+    builder::ApplicationBuilder,
+    generator::from_dasm_mnemonic,
+    instruction::{data::Word, operation::Operation, Instruction, SourceLocation},
+    memory::{
+        address_mode::{AddressMode, Immediate},
+        expression::{BinaryOperator, Expression},
+        label::AddressReference,
+        segment::{Segment, DEFAULT_SEGMENT},
+        Address,
+    },
+    validator::{AssemblerResult, Error},
+    Application, Function, Module,
+};
+
+use super::Parser;
+
+/// Name of the module that is used when the source doesn't contain module markers.
+const DEFAULT_MODULE_NAME: &str = "main";
+
+/// Dasm source code parser
+///
+/// Parses the dialect that [crate::generator::DasmGenerator] emits (`processor`, `seg`, `org`,
+/// defines, labels, `byte`, `word`, `ds`, `align` and all addressing modes) back into an [Application]. The first
+/// `org` without a `seg` directive is the entry point, the other ones create segments. The
+/// module and function markers that are written by the generator are used to restore
+/// the modules and functions of the application. Local labels (`.loop`) are scoped to the
+/// function or module they are defined in; `SUBROUTINE` directives are accepted.
+///
+/// ```
+/// use c64_assembler::parser::{DasmParser, Parser};
+///
+/// let application = DasmParser::default()
+///     .parse(
+///         "
+///   processor 6502
+/// VIC2_BORDER_COLOR = $D020
+///   org $0800
+/// main_entry_point:
+///   lda #$00               ; Load black color
+///   sta VIC2_BORDER_COLOR
+///   rts
+/// ",
+///     )
+///     .unwrap();
+/// assert_eq!(0x0800, application.entry_point);
+/// ```
+#[derive(Default)]
+pub struct DasmParser {
+    name: String,
+    entry_point: Option<Address>,
+    defines: Vec<(String, Address)>,
+    modules: Vec<Module>,
+    module: Option<Module>,
+    function: Option<Function>,
+    /// A function begin marker has been read, the next label is the name of the function.
+    function_pending: bool,
+    /// Comments that will be attached to the next instruction.
+    comments: Vec<String>,
+    /// Previous line was an instruction with a comment; indented comment lines continue it.
+    comments_continue: bool,
+    /// Address names that are referenced, including their location for error reporting.
+    references: Vec<(String, usize, usize)>,
+    segments: Vec<Segment>,
+    /// Name of the segment that instructions are placed in; `None` is the default segment.
+    segment: Option<String>,
+    /// Name set by a `seg` directive that is used by the next `org` directive.
+    segment_pending: Option<String>,
+}
+
+impl Parser for DasmParser {
+    type Input = str;
+
+    fn parse(mut self, input: &str) -> AssemblerResult<Application> {
+        for (line_index, line) in input.lines().enumerate() {
+            self.line(line_index + 1, line)?;
+        }
+        self.function_end();
+        self.module_end();
+        self.validate_references()?;
+
+        let mut builder = ApplicationBuilder::default();
+        builder.name(&self.name);
+        if let Some(entry_point) = self.entry_point {
+            builder.entry_point(entry_point);
+        }
+        for (name, address) in &self.defines {
+            builder.define_address(name, *address);
+        }
+        for segment in self.segments {
+            builder.segment(segment);
+        }
+        for module in self.modules {
+            builder.module(module);
+        }
+        builder.build()
+    }
+}
+
+impl DasmParser {
+    fn line(&mut self, line_number: usize, line: &str) -> AssemblerResult<()> {
+        let (code, comment) = match line.find(';') {
+            Some(index) => (&line[..index], Some(line[index + 1..].trim())),
+            None => (line, None),
+        };
+
+        if code.trim().is_empty() {
+            match comment {
+                Some(comment) => self.comment_line(code.len(), comment),
+                None => self.comments_continue = false,
+            }
+            return Ok(());
+        }
+
+        let mut cursor = Cursor::new(line_number, code);
+        if !code.starts_with(char::is_whitespace) {
+            let column = cursor.column();
+            let name = cursor
+                .name()
+                .ok_or_else(|| cursor.error(column, "expected a label or define name"))?;
+            cursor.skip_whitespace();
+            if cursor.eat('=') || cursor.eat_word("equ") {
+                let value_column = cursor.column();
+                let value = cursor.number()?;
+                if value > 0xFFFF {
+                    return Err(cursor.error(value_column, "define value does not fit in an address"));
+                }
+                cursor.expect_end()?;
+                self.defines.push((name, value as Address));
+                return Ok(());
+            }
+            cursor.eat(':');
+            let is_function = self.function_pending;
+            self.label(&cursor, column, name)?;
+            if cursor.is_end() {
+                if let (Some(comment), false) = (comment, is_function) {
+                    self.continue_comment(comment);
+                }
+                return Ok(());
+            }
+        }
+
+        cursor.skip_whitespace();
+        let column = cursor.column();
+        let word = cursor
+            .word()
+            .ok_or_else(|| cursor.error(column, "expected an instruction or directive"))?;
+        let instruction = match word.to_lowercase().as_str() {
+            "processor" => {
+                let processor_column = cursor.column();
+                let processor = cursor.number()?;
+                if processor != 6502 {
+                    return Err(cursor.error(processor_column, "only the 6502 processor is supported"));
+                }
+                cursor.expect_end()?;
+                return Ok(());
+            }
+            "seg" => {
+                cursor.skip_whitespace();
+                let name_column = cursor.column();
+                let name = cursor
+                    .identifier()
+                    .ok_or_else(|| cursor.error(name_column, "expected a segment name"))?;
+                cursor.expect_end()?;
+                self.function_end();
+                self.module_end();
+                if name == DEFAULT_SEGMENT {
+                    self.segment = None;
+                } else if self.segments.iter().any(|segment| segment.name == name) {
+                    self.segment = Some(name);
+                } else {
+                    self.segment_pending = Some(name);
+                }
+                return Ok(());
+            }
+            "org" => {
+                let address_column = cursor.column();
+                let address = cursor.number()?;
+                if address > 0xFFFF {
+                    return Err(cursor.error(address_column, "org address does not fit in an address"));
+                }
+                // The fill byte is an option of the generator and isn't part of the application.
+                if cursor.eat(',') {
+                    cursor.number()?;
+                }
+                cursor.expect_end()?;
+                self.function_end();
+                self.module_end();
+                let name = self.segment_pending.take();
+                if self.entry_point.is_none() && name.is_none() {
+                    self.entry_point = Some(address as Address);
+                    self.segment = None;
+                    return Ok(());
+                }
+                let name = name.unwrap_or_else(|| format!("segment_{address:04X}"));
+                if self.segments.iter().any(|segment| segment.name == name) {
+                    return Err(cursor.error(column, &format!("segment '{name}' has multiple org directives")));
+                }
+                self.segments.push(Segment::new(&name, address as Address));
+                self.segment = Some(name);
+                return Ok(());
+            }
+            // Relocated segments are assembled for another address than where they are loaded.
+            "rorg" => {
+                let address_column = cursor.column();
+                let address = cursor.number()?;
+                if address > 0xFFFF {
+                    return Err(cursor.error(address_column, "rorg address does not fit in an address"));
+                }
+                cursor.expect_end()?;
+                let segment = self
+                    .segment
+                    .as_ref()
+                    .and_then(|name| self.segments.iter_mut().find(|segment| segment.name == *name))
+                    .ok_or_else(|| cursor.error(column, "rorg is only supported after the org of a segment"))?;
+                segment.run_address = Some(address as Address);
+                return Ok(());
+            }
+            "rend" => {
+                cursor.expect_end()?;
+                return Ok(());
+            }
+            // Local labels are scoped to the function or module they are defined in.
+            "subroutine" => {
+                cursor.skip_whitespace();
+                cursor.identifier();
+                cursor.expect_end()?;
+                return Ok(());
+            }
+            "byte" | ".byte" => Instruction {
+                operation: self.bytes(&mut cursor)?,
+                address_mode: AddressMode::Implied,
+                comments: vec![],
+                source: None,
+            },
+            "word" | ".word" => Instruction {
+                operation: Operation::Words(self.words(&mut cursor)?),
+                address_mode: AddressMode::Implied,
+                comments: vec![],
+                source: None,
+            },
+            "ds" | "ds.b" => {
+                let count_column = cursor.column();
+                let count = cursor.number()?;
+                if count > 0xFFFF {
+                    return Err(cursor.error(count_column, "count does not fit in an address"));
+                }
+                let mut byte = 0;
+                cursor.skip_whitespace();
+                if cursor.eat(',') {
+                    byte = self.byte(&mut cursor)?;
+                }
+                cursor.expect_end()?;
+                Instruction {
+                    operation: Operation::Fill(count as Address, byte),
+                    address_mode: AddressMode::Implied,
+                    comments: vec![],
+                    source: None,
+                }
+            }
+            "align" => {
+                let alignment_column = cursor.column();
+                let alignment = cursor.number()?;
+                if alignment > 0xFFFF {
+                    return Err(cursor.error(alignment_column, "alignment does not fit in an address"));
+                }
+                cursor.skip_whitespace();
+                if cursor.eat(',') && self.byte(&mut cursor)? != 0 {
+                    return Err(cursor.error(alignment_column, "only aligning with zeros is supported"));
+                }
+                cursor.expect_end()?;
+                Instruction {
+                    operation: Operation::Align(alignment as Address),
+                    address_mode: AddressMode::Implied,
+                    comments: vec![],
+                    source: None,
+                }
+            }
+            mnemonic => {
+                let operation = Operation::from_instruction(&from_dasm_mnemonic(mnemonic))
+                    .ok_or_else(|| cursor.error(column, &format!("unknown instruction '{word}'")))?;
+                let definition = operation
+                    .definition()
+                    .ok_or_else(|| cursor.error(column, &format!("unknown instruction '{word}'")))?;
+                let address_mode = self.address_mode(&mut cursor, definition)?;
+                if !address_mode.is_supported_by(definition) {
+                    return Err(cursor.error(
+                        column,
+                        &format!("addressing mode is not supported by '{}'", definition.instruction),
+                    ));
+                }
+                Instruction {
+                    operation,
+                    address_mode,
+                    comments: vec![],
+                    source: None,
+                }
+            }
+        };
+        self.instruction(&cursor, column, instruction)?;
+        if let Some(comment) = comment {
+            self.continue_comment(comment);
+            self.comments_continue = true;
+        }
+        Ok(())
+    }
+
+    fn comment_line(&mut self, indent: usize, comment: &str) {
+        if let Some(marker) = comment.strip_prefix("--- ").and_then(|c| c.strip_suffix(" ---")) {
+            if let Some(name) = marker.strip_prefix("Application: ") {
+                self.name = name.to_string();
+                return;
+            }
+            if let Some(name) = marker.strip_prefix("Module begin: ") {
+                self.function_end();
+                self.module_end();
+                self.module = Some(Module {
+                    name: name.to_string(),
+                    segment: self.segment.clone(),
+                    ..Default::default()
+                });
+                return;
+            }
+            if marker.starts_with("Module end: ") {
+                self.function_end();
+                self.module_end();
+                return;
+            }
+            if marker.starts_with("Function begin: ") {
+                self.function_end();
+                self.function_pending = true;
+                self.comments.clear();
+                return;
+            }
+            if marker.starts_with("Function end: ") {
+                self.function_end();
+                return;
+            }
+        }
+        if comment == "NOTE: This file is generated, do not modify" {
+            return;
+        }
+
+        // Comments that are aligned with the comment column continue the comments of the
+        // previous instruction.
+        if self.comments_continue && indent > 2 {
+            self.continue_comment(comment);
+        } else {
+            self.comments_continue = false;
+            self.comments.push(comment.to_string());
+        }
+    }
+
+    fn label(&mut self, cursor: &Cursor, column: usize, label: String) -> AssemblerResult<()> {
+        if self.function_pending {
+            self.function_pending = false;
+            self.function = Some(Function {
+                name: label,
+                documentation: self.comments.drain(..).collect(),
+                ..Default::default()
+            });
+            return Ok(());
+        }
+        self.instruction(
+            cursor,
+            column,
+            Instruction {
+                operation: Operation::Label(label),
+                address_mode: AddressMode::Implied,
+                comments: vec![],
+                source: None,
+            },
+        )
+    }
+
+    fn instruction(&mut self, cursor: &Cursor, column: usize, mut instruction: Instruction) -> AssemblerResult<()> {
+        instruction.comments = self.comments.drain(..).collect();
+        instruction.source = Some(SourceLocation {
+            line: cursor.line,
+            column: column + 1,
+        });
+        self.comments_continue = false;
+
+        if let Some(function) = &mut self.function {
+            function.instructions.instructions.push(instruction);
+            return Ok(());
+        }
+        if self.function_pending {
+            return Err(cursor.error(column, "expected the label of the function"));
+        }
+        let module = self.module.get_or_insert_with(|| Module {
+            name: DEFAULT_MODULE_NAME.to_string(),
+            ..Default::default()
+        });
+        if !module.functions.is_empty() {
+            return Err(cursor.error(column, "module instructions must be placed before its functions"));
+        }
+        module.instructions.instructions.push(instruction);
+        Ok(())
+    }
+
+    fn last_instruction_mut(&mut self) -> Option<&mut Instruction> {
+        let instructions = match (&mut self.function, &mut self.module) {
+            (Some(function), _) => &mut function.instructions,
+            (None, Some(module)) => &mut module.instructions,
+            (None, None) => return None,
+        };
+        instructions.instructions.last_mut()
+    }
+
+    /// Add a comment to the previous instruction. Without a previous instruction the comment
+    /// is attached to the next instruction.
+    fn continue_comment(&mut self, comment: &str) {
+        match self.last_instruction_mut() {
+            Some(instruction) => instruction.comments.push(comment.to_string()),
+            None => self.comments.push(comment.to_string()),
+        }
+    }
+
+    fn function_end(&mut self) {
+        self.function_pending = false;
+        self.comments_continue = false;
+        if let Some(function) = self.function.take() {
+            let segment = self.segment.clone();
+            let module = self.module.get_or_insert_with(|| Module {
+                name: DEFAULT_MODULE_NAME.to_string(),
+                segment,
+                ..Default::default()
+            });
+            module.functions.push(function);
+        }
+    }
+
+    /// A module can be split over multiple segments. Its parts are merged back into a
+    /// single module, functions outside the segment of the module remember their segment.
+    fn module_end(&mut self) {
+        self.comments_continue = false;
+        let Some(mut module) = self.module.take() else {
+            return;
+        };
+        let Some(existing) = self.modules.iter_mut().find(|existing| existing.name == module.name) else {
+            self.modules.push(module);
+            return;
+        };
+        if module.segment != existing.segment {
+            let segment = module.segment.take().unwrap_or(DEFAULT_SEGMENT.to_string());
+            for function in &mut module.functions {
+                function.segment = Some(segment.clone());
+            }
+        }
+        existing
+            .instructions
+            .instructions
+            .extend(module.instructions.instructions);
+        existing.functions.extend(module.functions);
+    }
+
+    /// Byte list: either values or the low/high bytes of addresses (`<name`, `>name`).
+    fn bytes(&mut self, cursor: &mut Cursor) -> AssemblerResult<Operation> {
+        let column = cursor.column();
+        let mut bytes = vec![];
+        let mut low_bytes = vec![];
+        let mut high_bytes = vec![];
+        loop {
+            cursor.skip_whitespace();
+            let value_column = cursor.column();
+            match self.expression(cursor)? {
+                Expression::Number(value) if value > 0xFF => {
+                    return Err(cursor.error(value_column, "value does not fit in a byte"));
+                }
+                Expression::Number(value) => bytes.push(value as u8),
+                Expression::Low(expression) => low_bytes.push((*expression).into()),
+                Expression::High(expression) => high_bytes.push((*expression).into()),
+                _ => return Err(cursor.error(value_column, "expected a value or the low/high byte of an address")),
+            }
+            cursor.skip_whitespace();
+            if !cursor.eat(',') {
+                break;
+            }
+        }
+        cursor.expect_end()?;
+        match (bytes.is_empty(), low_bytes.is_empty(), high_bytes.is_empty()) {
+            (_, true, true) => Ok(Operation::Raw(bytes)),
+            (true, false, true) => Ok(Operation::LowBytes(low_bytes)),
+            (true, true, false) => Ok(Operation::HighBytes(high_bytes)),
+            _ => Err(cursor.error(column, "values, low bytes and high bytes cannot be mixed")),
+        }
+    }
+
+    fn byte(&mut self, cursor: &mut Cursor) -> AssemblerResult<u8> {
+        cursor.skip_whitespace();
+        let column = cursor.column();
+        let value = cursor.number()?;
+        if value > 0xFF {
+            return Err(cursor.error(column, "value does not fit in a byte"));
+        }
+        Ok(value as u8)
+    }
+
+    fn words(&mut self, cursor: &mut Cursor) -> AssemblerResult<Vec<Word>> {
+        let mut words = vec![];
+        loop {
+            cursor.skip_whitespace();
+            let column = cursor.column();
+            match self.expression(cursor)? {
+                Expression::Number(value) if value > 0xFFFF => {
+                    return Err(cursor.error(column, "value does not fit in a word"));
+                }
+                Expression::Number(value) => words.push(Word::Value(value as u16)),
+                expression => words.push(Word::Address(expression.into())),
+            }
+            cursor.skip_whitespace();
+            if !cursor.eat(',') {
+                break;
+            }
+        }
+        cursor.expect_end()?;
+        Ok(words)
+    }
+
+    fn address_mode(&mut self, cursor: &mut Cursor, definition: &InstructionDef) -> AssemblerResult<AddressMode> {
+        cursor.skip_whitespace();
+        if cursor.is_end() {
+            return Ok(
+                if definition.implied == NO_IMPLIED && definition.accumulator != NO_ACCUMULATOR {
+                    AddressMode::Accumulator
+                } else {
+                    AddressMode::Implied
+                },
+            );
+        }
+
+        let address_mode = if cursor.eat('#') {
+            cursor.skip_whitespace();
+            let column = cursor.column();
+            match self.expression(cursor)? {
+                Expression::Low(expression) => AddressMode::Immediate(Immediate::Low((*expression).into())),
+                Expression::High(expression) => AddressMode::Immediate(Immediate::High((*expression).into())),
+                Expression::Number(value) if value > 0xFF => {
+                    return Err(cursor.error(column, "value does not fit in a byte"));
+                }
+                Expression::Number(value) => AddressMode::Immediate(Immediate::Byte(value as u8)),
+                expression => AddressMode::Immediate(Immediate::Expression(expression)),
+            }
+        } else if cursor.eat('(') {
+            let address_reference = self.address_reference(cursor)?;
+            cursor.skip_whitespace();
+            if cursor.eat(',') {
+                cursor.expect_register('x')?;
+                cursor.skip_whitespace();
+                cursor.expect(')')?;
+                AddressMode::IndexedIndirect(address_reference)
+            } else {
+                cursor.expect(')')?;
+                cursor.skip_whitespace();
+                if cursor.eat(',') {
+                    cursor.expect_register('y')?;
+                    AddressMode::IndirectIndexed(address_reference)
+                } else {
+                    AddressMode::Indirect(address_reference)
+                }
+            }
+        } else if cursor.eat_word("a") {
+            AddressMode::Accumulator
+        } else {
+            let address_reference = self.address_reference(cursor)?;
+            cursor.skip_whitespace();
+            if cursor.eat(',') {
+                cursor.skip_whitespace();
+                if cursor.eat_word("x") {
+                    AddressMode::AbsoluteX(address_reference)
+                } else {
+                    cursor.expect_register('y')?;
+                    AddressMode::AbsoluteY(address_reference)
+                }
+            } else if definition.relative != NO_RELATIVE {
+                AddressMode::Relative(address_reference)
+            } else {
+                AddressMode::Absolute(address_reference)
+            }
+        };
+        cursor.expect_end()?;
+        Ok(address_mode)
+    }
+
+    fn address_reference(&mut self, cursor: &mut Cursor) -> AssemblerResult<AddressReference> {
+        Ok(self.expression(cursor)?.into())
+    }
+
+    /// Parse an expression using the operators and precedence of dasm; `[` and `]` group.
+    fn expression(&mut self, cursor: &mut Cursor) -> AssemblerResult<Expression> {
+        self.binary_expression(cursor, 0)
+    }
+
+    fn binary_expression(&mut self, cursor: &mut Cursor, min_precedence: u8) -> AssemblerResult<Expression> {
+        let mut left = self.unary_expression(cursor)?;
+        loop {
+            cursor.skip_whitespace();
+            let Some((operator, length)) = cursor.binary_operator() else {
+                break;
+            };
+            if operator.precedence() < min_precedence {
+                break;
+            }
+            cursor.position += length;
+            let right = self.binary_expression(cursor, operator.precedence() + 1)?;
+            left = Expression::binary(operator, left, right);
+        }
+        Ok(left)
+    }
+
+    fn unary_expression(&mut self, cursor: &mut Cursor) -> AssemblerResult<Expression> {
+        cursor.skip_whitespace();
+        let column = cursor.column();
+        if cursor.eat('<') {
+            return Ok(self.unary_expression(cursor)?.low());
+        }
+        if cursor.eat('>') {
+            return Ok(self.unary_expression(cursor)?.high());
+        }
+        if cursor.eat('-') {
+            return Ok(-self.unary_expression(cursor)?);
+        }
+        if cursor.eat('[') {
+            let expression = self.expression(cursor)?;
+            cursor.skip_whitespace();
+            cursor.expect(']')?;
+            return Ok(expression);
+        }
+        if cursor
+            .peek()
+            .is_some_and(|c| c == '$' || c == '%' || c.is_ascii_digit())
+        {
+            return Ok(Expression::Number(cursor.number()? as i64));
+        }
+        let Some(name) = cursor.name() else {
+            return Err(cursor.error(column, "expected an expression"));
+        };
+        self.references.push((name.clone(), cursor.line, column + 1));
+        Ok(Expression::Name(name))
+    }
+
+    fn validate_references(&self) -> AssemblerResult<()> {
+        let mut names = self.defines.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        for module in &self.modules {
+            let functions = module.functions.iter();
+            for instructions in
+                std::iter::once(&module.instructions).chain(functions.clone().map(|function| &function.instructions))
+            {
+                for instruction in &instructions.instructions {
+                    if let Operation::Label(label) = &instruction.operation {
+                        names.push(label);
+                    }
+                }
+            }
+            names.extend(functions.map(|function| &function.name));
+        }
+
+        for (name, line, column) in &self.references {
+            if !names.contains(&name) {
+                return Err(Error::Parse {
+                    line: *line,
+                    column: *column,
+                    message: format!("unknown address name '{name}'"),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cursor over the code part of a single source line.
+struct Cursor {
+    line: usize,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Cursor {
+    fn new(line: usize, code: &str) -> Cursor {
+        Cursor {
+            line,
+            chars: code.chars().collect(),
+            position: 0,
+        }
+    }
+
+    /// Create a parse error at the given (0-based) column.
+    fn error(&self, column: usize, message: &str) -> Error {
+        Error::Parse {
+            line: self.line,
+            column: column + 1,
+            message: message.to_string(),
+        }
+    }
+
+    fn column(&self) -> usize {
+        self.position
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn is_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.peek().is_none()
+    }
+
+    fn expect_end(&mut self) -> AssemblerResult<()> {
+        if self.is_end() {
+            Ok(())
+        } else {
+            Err(self.error(self.position, "unexpected characters at end of line"))
+        }
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> AssemblerResult<()> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(self.position, &format!("expected '{expected}'")))
+        }
+    }
+
+    fn expect_register(&mut self, register: char) -> AssemblerResult<()> {
+        self.skip_whitespace();
+        let column = self.position;
+        if self.eat_word(&register.to_string()) {
+            Ok(())
+        } else {
+            Err(self.error(column, &format!("expected register '{register}'")))
+        }
+    }
+
+    /// Consume the given word (case insensitive) when it is the next complete word.
+    fn eat_word(&mut self, word: &str) -> bool {
+        let start = self.position;
+        match self.word() {
+            Some(found) if found.eq_ignore_ascii_case(word) => true,
+            _ => {
+                self.position = start;
+                false
+            }
+        }
+    }
+
+    /// Read an identifier; identifiers start with a letter or underscore.
+    fn identifier(&mut self) -> Option<String> {
+        if self.peek().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') {
+            self.word()
+        } else {
+            None
+        }
+    }
+
+    /// Read a label or define name; local labels start with a dot (`.loop`).
+    fn name(&mut self) -> Option<String> {
+        let next = self.chars.get(self.position + 1).copied();
+        if self.peek() == Some('.') && next.is_some_and(|c| c.is_ascii_alphabetic() || c == '_') {
+            self.word()
+        } else {
+            self.identifier()
+        }
+    }
+
+    /// Read a word; words can start with a dot to support directives like `.byte`.
+    fn word(&mut self) -> Option<String> {
+        let start = self.position;
+        if self.peek() == Some('.') {
+            self.position += 1;
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+            self.position += 1;
+        }
+        if self.position == start {
+            None
+        } else {
+            Some(self.chars[start..self.position].iter().collect())
+        }
+    }
+
+    /// Binary operator at the cursor, including its length in characters.
+    fn binary_operator(&self) -> Option<(BinaryOperator, usize)> {
+        let next = self.chars.get(self.position + 1).copied();
+        let operator = match (self.peek()?, next) {
+            ('<', Some('<')) => return Some((BinaryOperator::ShiftLeft, 2)),
+            ('>', Some('>')) => return Some((BinaryOperator::ShiftRight, 2)),
+            ('+', _) => BinaryOperator::Add,
+            ('-', _) => BinaryOperator::Subtract,
+            ('*', _) => BinaryOperator::Multiply,
+            ('/', _) => BinaryOperator::Divide,
+            ('&', _) => BinaryOperator::And,
+            ('|', _) => BinaryOperator::Or,
+            ('^', _) => BinaryOperator::Xor,
+            _ => return None,
+        };
+        Some((operator, 1))
+    }
+
+    /// Read a number in hexadecimal (`$FF`), binary (`%1010`) or decimal (`255`) notation.
+    fn number(&mut self) -> AssemblerResult<u32> {
+        self.skip_whitespace();
+        let column = self.position;
+        let radix = if self.eat('$') {
+            16
+        } else if self.eat('%') {
+            2
+        } else {
+            10
+        };
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_digit(radix)) {
+            self.position += 1;
+        }
+        let digits = self.chars[start..self.position].iter().collect::<String>();
+        u32::from_str_radix(&digits, radix).map_err(|_| self.error(column, "expected a number"))
+    }
+}
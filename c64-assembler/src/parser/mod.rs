@@ -0,0 +1,16 @@
+//! Parsers to import source code into an application.
+use crate::{validator::AssemblerResult, Application};
+
+mod dasm;
+mod program;
+
+/// Parse an input into an application.
+pub trait Parser {
+    type Input: ?Sized;
+
+    /// Parse the given input into an application.
+    fn parse(self, input: &Self::Input) -> AssemblerResult<Application>;
+}
+
+pub use dasm::*;
+pub use program::*;
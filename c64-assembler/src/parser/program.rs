@@ -0,0 +1,427 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use c64_assembler_6502::{
+    instruction::{AddressingMode, InstructionDef},
+    isa_6502, isa_6502_illegal, opcode_table,
+};
+
+use crate::{
+    basic::{BASIC_START, TOKEN_SYS},
+    builder::{ApplicationBuilder, ModuleBuilder},
+    instruction::{operation::Operation, Instruction},
+    memory::{
+        address_mode::{AddressMode, Immediate},
+        label::AddressReference,
+        Address, ZeroPage,
+    },
+    validator::{AssemblerResult, Error},
+    Application, Instructions,
+};
+
+use super::Parser;
+
+const PROGRAM_HEADER_BYTE_SIZE: usize = 2;
+/// Address directly after the last byte of memory.
+const MEMORY_END: u32 = 0x10000;
+
+/// .PRG byte stream parser (6502 disassembler)
+///
+/// Reads the loading address and the byte code of a program and recovers the instructions.
+/// Starting at the entry points all reachable code is traced. Branch and jump targets inside
+/// the program get a generated label (`label_080D`), addresses outside the program are
+/// added as defines (`ADDR_D020`). Bytes that aren't reached are added as raw data.
+/// Illegal op-codes are only decoded when the `illegal-opcodes` feature is enabled.
+///
+/// When no entry points are given, the entry point is read from the BASIC `SYS` line at
+/// the start of the program. When there is no BASIC line the loading address is used.
+///
+/// Generating the parsed application with [crate::generator::ProgramGenerator] results in
+/// the same byte stream.
+///
+/// ```
+/// use c64_assembler::generator::{Generator, ProgramGenerator};
+/// use c64_assembler::parser::{Parser, ProgramParser};
+///
+/// let bytes = [0x00, 0x10, 0xA9, 0x00, 0x8D, 0x20, 0xD0, 0x60];
+/// let application = ProgramParser::default().parse(&bytes).unwrap();
+/// assert_eq!(0x1000, application.entry_point);
+/// assert_eq!(bytes.to_vec(), ProgramGenerator::default().generate(application).unwrap());
+/// ```
+pub struct ProgramParser {
+    name: String,
+    entry_points: Vec<Address>,
+    table: Vec<Option<(InstructionDef, AddressingMode)>>,
+}
+
+impl Default for ProgramParser {
+    fn default() -> Self {
+        let mut isa = isa_6502();
+        if cfg!(feature = "illegal-opcodes") {
+            isa.extend(isa_6502_illegal());
+        }
+        ProgramParser {
+            name: String::default(),
+            entry_points: vec![],
+            table: opcode_table(&isa),
+        }
+    }
+}
+
+impl ProgramParser {
+    /// Set the name of the parsed application.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Add an address where code execution can start.
+    ///
+    /// Can be called multiple times, for example to add interrupt handlers.
+    pub fn entry_point(mut self, address: Address) -> Self {
+        self.entry_points.push(address);
+        self
+    }
+}
+
+/// Decoded instruction at a specific address.
+struct DecodedInstruction {
+    definition: InstructionDef,
+    addressing_mode: AddressingMode,
+    operand: Address,
+}
+
+impl DecodedInstruction {
+    fn byte_size(&self) -> Address {
+        self.addressing_mode.byte_size()
+    }
+
+    /// Target address of the operand (branch target for relative addressing).
+    fn target(&self, address: Address) -> Option<Address> {
+        match self.addressing_mode {
+            AddressingMode::Implied | AddressingMode::Accumulator | AddressingMode::Immediate => None,
+            AddressingMode::Relative => Some(
+                address
+                    .wrapping_add(self.byte_size())
+                    .wrapping_add(self.operand as u8 as i8 as Address),
+            ),
+            _ => Some(self.operand),
+        }
+    }
+
+    /// Can execution continue with the next instruction.
+    fn continues(&self) -> bool {
+        !matches!(self.definition.instruction, "jmp" | "rts" | "rti" | "brk")
+    }
+
+    /// Would assembling the instruction result in the same byte code.
+    ///
+    /// Absolute addressing of a zeropage address would be assembled to its zeropage variant.
+    fn is_reproducible(&self) -> bool {
+        let zeropage_mode = match self.addressing_mode {
+            AddressingMode::Absolute => AddressingMode::Zeropage,
+            AddressingMode::AbsoluteX => AddressingMode::ZeropageX,
+            AddressingMode::AbsoluteY => AddressingMode::ZeropageY,
+            _ => return true,
+        };
+        !self.operand.is_zeropage() || self.definition.opcode(zeropage_mode).is_none()
+    }
+}
+
+impl Parser for ProgramParser {
+    type Input = [u8];
+
+    fn parse(self, input: &[u8]) -> AssemblerResult<Application> {
+        if input.len() < PROGRAM_HEADER_BYTE_SIZE {
+            return Err(Error::InvalidProgram(
+                "program doesn't contain a loading address".to_string(),
+            ));
+        }
+        let load_address = Address::from_le_bytes([input[0], input[1]]);
+        let bytes = &input[PROGRAM_HEADER_BYTE_SIZE..];
+        if load_address as usize + bytes.len() > MEMORY_END as usize {
+            return Err(Error::InvalidProgram("program doesn't fit in memory".to_string()));
+        }
+        let program = Program { load_address, bytes };
+
+        let mut entry_points = self.entry_points.clone();
+        if entry_points.is_empty() {
+            entry_points.push(basic_sys_address(&program).unwrap_or(load_address));
+        }
+
+        let instructions = self.trace(&program, &entry_points);
+        let labels = Labels::new(&program, &instructions, &entry_points);
+
+        let mut instructions_parsed = Instructions::default();
+        let mut defines = BTreeSet::new();
+        let mut next_address = load_address as u32;
+        while next_address < program.end() {
+            let address = next_address as Address;
+            if labels.at(address) {
+                instructions_parsed.instructions.push(Instruction {
+                    operation: Operation::Label(labels.name(address)),
+                    address_mode: AddressMode::Implied,
+                    comments: vec![],
+                    source: None,
+                });
+            }
+            if let Some(decoded) = instructions.get(&address) {
+                let instruction = self.instruction(address, decoded, &program, &labels, &mut defines)?;
+                instructions_parsed.instructions.push(instruction);
+                next_address += decoded.byte_size() as u32;
+            } else {
+                let mut end = next_address + 1;
+                while end < program.end() && !instructions.contains_key(&(end as Address)) && !labels.at(end as Address)
+                {
+                    end += 1;
+                }
+                instructions_parsed.instructions.push(Instruction {
+                    operation: Operation::Raw(program.slice(address, end).to_vec()),
+                    address_mode: AddressMode::Implied,
+                    comments: vec![],
+                    source: None,
+                });
+                next_address = end;
+            }
+        }
+
+        let mut builder = ApplicationBuilder::default();
+        builder.name(&self.name).entry_point(load_address);
+        for define in defines {
+            builder.define_address(&define_name(define), define);
+        }
+        builder
+            .module(
+                ModuleBuilder::default()
+                    .name("main")
+                    .instructions(instructions_parsed)
+                    .build(),
+            )
+            .build()
+    }
+}
+
+impl ProgramParser {
+    fn decode(&self, program: &Program, address: Address) -> Option<DecodedInstruction> {
+        let (definition, addressing_mode) = self.table[program.byte(address)? as usize]?;
+        let operand = match addressing_mode.byte_size() {
+            1 => 0,
+            2 => program.byte(address.checked_add(1)?)? as Address,
+            _ => Address::from_le_bytes([
+                program.byte(address.checked_add(1)?)?,
+                program.byte(address.checked_add(2)?)?,
+            ]),
+        };
+        Some(DecodedInstruction {
+            definition,
+            addressing_mode,
+            operand,
+        })
+    }
+
+    /// Trace all code that is reachable from the entry points.
+    fn trace(&self, program: &Program, entry_points: &[Address]) -> BTreeMap<Address, DecodedInstruction> {
+        let mut instructions = BTreeMap::<Address, DecodedInstruction>::new();
+        let mut code_bytes = BTreeSet::<Address>::new();
+        let mut addresses_to_visit = entry_points.to_vec();
+
+        while let Some(mut address) = addresses_to_visit.pop() {
+            while program.contains(address) && !code_bytes.contains(&address) {
+                let Some(decoded) = self.decode(program, address) else {
+                    break;
+                };
+                let instruction_bytes = address..=address + (decoded.byte_size() - 1);
+                if instruction_bytes
+                    .clone()
+                    .any(|byte_address| code_bytes.contains(&byte_address))
+                {
+                    break;
+                }
+                code_bytes.extend(instruction_bytes);
+
+                match (decoded.definition.instruction, decoded.addressing_mode) {
+                    (_, AddressingMode::Relative) | ("jsr", _) | ("jmp", AddressingMode::Absolute) => {
+                        addresses_to_visit.extend(decoded.target(address));
+                    }
+                    _ => {}
+                }
+
+                let next_address = address.checked_add(decoded.byte_size()).filter(|_| decoded.continues());
+                instructions.insert(address, decoded);
+                let Some(next_address) = next_address else {
+                    break;
+                };
+                address = next_address;
+            }
+        }
+        instructions
+    }
+
+    fn instruction(
+        &self,
+        address: Address,
+        decoded: &DecodedInstruction,
+        program: &Program,
+        labels: &Labels,
+        defines: &mut BTreeSet<Address>,
+    ) -> AssemblerResult<Instruction> {
+        if !decoded.is_reproducible() {
+            return Ok(Instruction {
+                operation: Operation::Raw(
+                    program
+                        .slice(address, address as u32 + decoded.byte_size() as u32)
+                        .to_vec(),
+                ),
+                address_mode: AddressMode::Implied,
+                comments: vec![format!(
+                    "{} ${:04X} (absolute addressing of a zeropage address)",
+                    decoded.definition.instruction, decoded.operand
+                )],
+                source: None,
+            });
+        }
+
+        let mut address_reference = || -> AssemblerResult<AddressReference> {
+            let target = decoded.target(address).ok_or(Error::InternalCompilerError)?;
+            if program.contains(target) {
+                Ok(labels.reference(target))
+            } else {
+                defines.insert(target);
+                Ok(AddressReference::new(&define_name(target)))
+            }
+        };
+        let address_mode = match decoded.addressing_mode {
+            AddressingMode::Implied => AddressMode::Implied,
+            AddressingMode::Accumulator => AddressMode::Accumulator,
+            AddressingMode::Immediate => AddressMode::Immediate(Immediate::Byte(decoded.operand as u8)),
+            AddressingMode::Absolute | AddressingMode::Zeropage => AddressMode::Absolute(address_reference()?),
+            AddressingMode::AbsoluteX | AddressingMode::ZeropageX => AddressMode::AbsoluteX(address_reference()?),
+            AddressingMode::AbsoluteY | AddressingMode::ZeropageY => AddressMode::AbsoluteY(address_reference()?),
+            AddressingMode::Relative => AddressMode::Relative(address_reference()?),
+            AddressingMode::Indirect => AddressMode::Indirect(address_reference()?),
+            AddressingMode::IndexedIndirect => AddressMode::IndexedIndirect(address_reference()?),
+            AddressingMode::IndirectIndexed => AddressMode::IndirectIndexed(address_reference()?),
+        };
+        Ok(Instruction {
+            operation: Operation::from_instruction(decoded.definition.instruction)
+                .ok_or(Error::InternalCompilerError)?,
+            address_mode,
+            comments: vec![],
+            source: None,
+        })
+    }
+}
+
+/// Byte code of a program together with its loading address.
+struct Program<'a> {
+    load_address: Address,
+    bytes: &'a [u8],
+}
+
+impl Program<'_> {
+    /// Address directly after the last byte of the program.
+    fn end(&self) -> u32 {
+        self.load_address as u32 + self.bytes.len() as u32
+    }
+
+    fn contains(&self, address: Address) -> bool {
+        address >= self.load_address && ((address - self.load_address) as usize) < self.bytes.len()
+    }
+
+    fn byte(&self, address: Address) -> Option<u8> {
+        if self.contains(address) {
+            Some(self.bytes[(address - self.load_address) as usize])
+        } else {
+            None
+        }
+    }
+
+    fn slice(&self, start: Address, end: u32) -> &[u8] {
+        &self.bytes[(start - self.load_address) as usize..(end - self.load_address as u32) as usize]
+    }
+}
+
+/// Labels that are generated for addresses inside the program.
+struct Labels {
+    addresses: BTreeSet<Address>,
+    /// Start address of the instruction for addresses that point inside an instruction.
+    instruction_starts: BTreeMap<Address, Address>,
+}
+
+impl Labels {
+    fn new(
+        program: &Program,
+        instructions: &BTreeMap<Address, DecodedInstruction>,
+        entry_points: &[Address],
+    ) -> Labels {
+        let mut instruction_starts = BTreeMap::new();
+        for (address, decoded) in instructions {
+            for offset in 1..decoded.byte_size() {
+                instruction_starts.insert(address + offset, *address);
+            }
+        }
+
+        let mut addresses = BTreeSet::new();
+        let targets = instructions
+            .iter()
+            .filter(|(_address, decoded)| decoded.is_reproducible())
+            .filter_map(|(address, decoded)| decoded.target(*address))
+            .chain(entry_points.iter().copied());
+        for target in targets.filter(|target| program.contains(*target)) {
+            addresses.insert(*instruction_starts.get(&target).unwrap_or(&target));
+        }
+
+        Labels {
+            addresses,
+            instruction_starts,
+        }
+    }
+
+    fn at(&self, address: Address) -> bool {
+        self.addresses.contains(&address)
+    }
+
+    fn name(&self, address: Address) -> String {
+        format!("label_{address:04X}")
+    }
+
+    fn reference(&self, address: Address) -> AddressReference {
+        match self.instruction_starts.get(&address) {
+            Some(start) => AddressReference::with_offset(&self.name(*start), address - start),
+            None => AddressReference::new(&self.name(address)),
+        }
+    }
+}
+
+fn define_name(address: Address) -> String {
+    format!("ADDR_{address:04X}")
+}
+
+/// Read the address of the SYS command when the program contains a BASIC line.
+fn basic_sys_address(program: &Program) -> Option<Address> {
+    // Skip pointer to the next basic line and the line number.
+    let mut address = BASIC_START + 4;
+    while program.byte(address)? == b' ' {
+        address = address.checked_add(1)?;
+    }
+    if program.byte(address)? != TOKEN_SYS {
+        return None;
+    }
+    address = address.checked_add(1)?;
+    while program.byte(address)? == b' ' {
+        address = address.checked_add(1)?;
+    }
+
+    // Stop as soon as the number doesn't fit in an address, the line can contain any number
+    // of digits.
+    let mut sys_address: Address = 0;
+    let mut digits = 0;
+    while let Some(digit @ b'0'..=b'9') = program.byte(address) {
+        sys_address = sys_address.checked_mul(10)?.checked_add((digit - b'0') as Address)?;
+        digits += 1;
+        address = address.checked_add(1)?;
+    }
+    if digits == 0 || !program.contains(sys_address) {
+        return None;
+    }
+    Some(sys_address)
+}
@@ -0,0 +1,142 @@
+//! Tape images to store the generated programs on.
+//!
+//! [t64::T64] is a container with a directory of programs, [tap::Tap] contains the pulses
+//! that the datasette reads from the tape.
+//!
+//! ```
+//! use c64_assembler::tape::{tap::Tap, t64::T64, TapeFile};
+//!
+//! let file = TapeFile::from_prg("demo", &[0x01, 0x08, 0x00, 0x00]).unwrap();
+//! let mut container = T64::new("demo tape").unwrap();
+//! container.add_file(file.clone());
+//!
+//! let mut tape = Tap::default();
+//! tape.add_file(&file);
+//! assert_eq!(vec![file], tape.files().unwrap());
+//! ```
+use std::fmt::Display;
+
+use crate::{
+    basic::petscii::{decode_text, encode_text},
+    disk::NAME_LENGTH,
+    memory::{Address, ZeroPage},
+};
+
+pub mod t64;
+pub mod tap;
+
+/// Names on tape are padded with spaces.
+const NAME_PADDING: u8 = 0x20;
+
+pub type TapeResult<T> = Result<T, Error>;
+
+/// Errors of reading and writing tape images. They convert into [crate::validator::Error], so
+/// they can be returned from functions that also generate the program.
+#[derive(Debug)]
+pub enum Error {
+    /// A file or tape name contains text that cannot be encoded as PETSCII.
+    InvalidName { name: String, reason: String },
+    /// A file or tape name doesn't fit in the header of a tape image.
+    NameTooLong { name: String, max_length: usize },
+    /// The file isn't stored on the tape image.
+    FileNotFound(String),
+    /// Byte stream could not be stored as a program.
+    InvalidProgram(String),
+    /// Byte stream could not be read as a tape image.
+    InvalidTapeImage(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidName { name, reason } => write!(f, "name '{name}' cannot be encoded: {reason}"),
+            Error::NameTooLong { name, max_length } => {
+                write!(f, "name '{name}' is longer than {max_length} characters")
+            }
+            Error::FileNotFound(name) => write!(f, "file '{name}' not found"),
+            Error::InvalidProgram(message) => write!(f, "invalid program: {message}"),
+            Error::InvalidTapeImage(message) => write!(f, "invalid tape image: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Program stored on a tape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TapeFile {
+    /// PETSCII name without padding.
+    pub name: Vec<u8>,
+    /// Address where the program is loaded.
+    pub start_address: Address,
+    /// Program without the load address.
+    pub data: Vec<u8>,
+}
+
+impl TapeFile {
+    /// Tape file of a `.PRG` file, for example the output of
+    /// [crate::generator::ProgramGenerator]. The first two bytes contain the load address.
+    ///
+    /// The name is encoded like [crate::disk::DiskFile] names.
+    pub fn from_prg(name: &str, prg: &[u8]) -> TapeResult<TapeFile> {
+        if prg.len() < 2 {
+            return Err(Error::InvalidProgram("load address is missing".to_string()));
+        }
+        let file = TapeFile {
+            name: encode_name(name, NAME_LENGTH)?,
+            start_address: Address::from_le_bytes([prg[0], prg[1]]),
+            data: prg[2..].to_vec(),
+        };
+        file.end_address()?;
+        Ok(file)
+    }
+
+    /// Name as text.
+    pub fn name(&self) -> String {
+        decode_text(&self.name)
+    }
+
+    /// Bytes of the `.PRG` file, starting with the load address.
+    pub fn prg(&self) -> Vec<u8> {
+        let mut result = vec![self.start_address.low(), self.start_address.high()];
+        result.extend(&self.data);
+        result
+    }
+
+    /// Address after the last byte of the program.
+    pub fn end_address(&self) -> TapeResult<Address> {
+        Address::try_from(self.start_address as usize + self.data.len())
+            .map_err(|_| Error::InvalidProgram("program doesn't fit in memory".to_string()))
+    }
+}
+
+/// Encode a file or tape name as PETSCII, like [crate::disk::DiskFile] names.
+fn encode_name(name: &str, max_length: usize) -> TapeResult<Vec<u8>> {
+    let encoded = encode_text(name).map_err(|error| Error::InvalidName {
+        name: name.to_string(),
+        reason: error.to_string(),
+    })?;
+    if encoded.len() > max_length {
+        return Err(Error::NameTooLong {
+            name: name.to_string(),
+            max_length,
+        });
+    }
+    Ok(encoded)
+}
+
+/// Name padded with spaces.
+fn padded_name(name: &[u8], length: usize) -> Vec<u8> {
+    let mut result = name.to_vec();
+    result.resize(length, NAME_PADDING);
+    result
+}
+
+/// Name without the trailing spaces.
+fn unpadded_name(name: &[u8]) -> Vec<u8> {
+    let length = name
+        .iter()
+        .rposition(|byte| *byte != NAME_PADDING)
+        .map_or(0, |index| index + 1);
+    name[..length].to_vec()
+}
@@ -0,0 +1,133 @@
+//! T64 tape archives, a container with a directory of programs used by emulators.
+use crate::{basic::petscii::decode_text, disk::NAME_LENGTH, memory::Address};
+
+use super::{encode_name, padded_name, unpadded_name, Error, TapeFile, TapeResult};
+
+/// Signature at the start of the container, padded with zeros to 32 bytes.
+const SIGNATURE: &[u8] = b"C64 tape image file";
+/// Size of the header and of a directory entry.
+const HEADER_SIZE: usize = 64;
+const ENTRY_SIZE: usize = 32;
+/// Version of the container format.
+const VERSION: u16 = 0x0101;
+/// Maximum length of the name of the container.
+const TAPE_NAME_LENGTH: usize = 24;
+/// Offset of the tape name in the header and of the file name in a directory entry.
+const TAPE_NAME_OFFSET: usize = 0x28;
+const FILE_NAME_OFFSET: usize = 0x10;
+/// Entry type of a tape file; free entries have type 0.
+const ENTRY_TYPE_FILE: u8 = 0x01;
+/// File type of a program, the same as the file type in the directory of a disk.
+const FILE_TYPE_PRG: u8 = 0x82;
+/// Minimum number of directory entries; some emulators expect room for 30 entries.
+const MIN_ENTRIES: usize = 30;
+
+/// T64 tape archive.
+///
+/// ```
+/// use c64_assembler::tape::{t64::T64, TapeFile};
+///
+/// let mut container = T64::new("demo tape").unwrap();
+/// container.add_file(TapeFile::from_prg("demo", &[0x01, 0x08, 0x00, 0x00]).unwrap());
+///
+/// let container = T64::from_bytes(&container.to_bytes()).unwrap();
+/// assert_eq!("demo tape", container.name());
+/// assert_eq!(vec![0x01, 0x08, 0x00, 0x00], container.read_file("demo").unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct T64 {
+    name: Vec<u8>,
+    pub files: Vec<TapeFile>,
+}
+
+impl T64 {
+    /// Empty container, the name has at most 24 characters.
+    pub fn new(name: &str) -> TapeResult<T64> {
+        Ok(T64 {
+            name: encode_name(name, TAPE_NAME_LENGTH)?,
+            files: vec![],
+        })
+    }
+
+    /// Name of the container.
+    pub fn name(&self) -> String {
+        decode_text(&self.name)
+    }
+
+    /// Add a program to the container.
+    pub fn add_file(&mut self, file: TapeFile) -> &mut Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Read a program, starting with its load address.
+    pub fn read_file(&self, name: &str) -> TapeResult<Vec<u8>> {
+        let encoded_name = encode_name(name, NAME_LENGTH)?;
+        self.files
+            .iter()
+            .find(|file| file.name == encoded_name)
+            .map(TapeFile::prg)
+            .ok_or_else(|| Error::FileNotFound(name.to_string()))
+    }
+
+    /// Bytes of the container.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let entries = self.files.len().max(MIN_ENTRIES);
+        let mut result = vec![0x00; HEADER_SIZE + entries * ENTRY_SIZE];
+        result[..SIGNATURE.len()].copy_from_slice(SIGNATURE);
+        result[0x20..0x22].copy_from_slice(&VERSION.to_le_bytes());
+        result[0x22..0x24].copy_from_slice(&(entries as u16).to_le_bytes());
+        result[0x24..0x26].copy_from_slice(&(self.files.len() as u16).to_le_bytes());
+        result[TAPE_NAME_OFFSET..HEADER_SIZE].copy_from_slice(&padded_name(&self.name, TAPE_NAME_LENGTH));
+
+        for (index, file) in self.files.iter().enumerate() {
+            let offset = result.len() as u32;
+            let end_address = file.start_address.wrapping_add(file.data.len() as Address);
+            let entry = &mut result[HEADER_SIZE + index * ENTRY_SIZE..HEADER_SIZE + (index + 1) * ENTRY_SIZE];
+            entry[0..2].copy_from_slice(&[ENTRY_TYPE_FILE, FILE_TYPE_PRG]);
+            entry[2..4].copy_from_slice(&file.start_address.to_le_bytes());
+            entry[4..6].copy_from_slice(&end_address.to_le_bytes());
+            entry[8..12].copy_from_slice(&offset.to_le_bytes());
+            entry[FILE_NAME_OFFSET..ENTRY_SIZE].copy_from_slice(&padded_name(&file.name, NAME_LENGTH));
+            result.extend(&file.data);
+        }
+        result
+    }
+
+    /// Read a container.
+    ///
+    /// Some tools store a wrong end address; the size of a program is limited to the bytes
+    /// that are available in the container.
+    pub fn from_bytes(bytes: &[u8]) -> TapeResult<T64> {
+        if bytes.len() < HEADER_SIZE || !bytes.starts_with(b"C64") {
+            return Err(Error::InvalidTapeImage("T64 signature is missing".to_string()));
+        }
+        let entries = u16::from_le_bytes([bytes[0x22], bytes[0x23]]) as usize;
+        let mut container = T64 {
+            name: unpadded_name(&bytes[TAPE_NAME_OFFSET..HEADER_SIZE]),
+            files: vec![],
+        };
+        for index in 0..entries {
+            let entry = bytes
+                .get(HEADER_SIZE + index * ENTRY_SIZE..HEADER_SIZE + (index + 1) * ENTRY_SIZE)
+                .ok_or_else(|| Error::InvalidTapeImage("T64 directory is truncated".to_string()))?;
+            if entry[0] != ENTRY_TYPE_FILE {
+                continue;
+            }
+            let start_address = Address::from_le_bytes([entry[2], entry[3]]);
+            let end_address = Address::from_le_bytes([entry[4], entry[5]]);
+            let offset = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]) as usize;
+            let size = end_address.wrapping_sub(start_address) as usize;
+            let data = bytes
+                .get(offset..)
+                .map(|data| &data[..size.min(data.len())])
+                .ok_or_else(|| Error::InvalidTapeImage(format!("T64 entry {index} points outside the container")))?;
+            container.files.push(TapeFile {
+                name: unpadded_name(&entry[FILE_NAME_OFFSET..ENTRY_SIZE]),
+                start_address,
+                data: data.to_vec(),
+            });
+        }
+        Ok(container)
+    }
+}
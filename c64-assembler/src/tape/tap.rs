@@ -0,0 +1,323 @@
+//! TAP tape images, the pulses that the datasette reads from the tape.
+use super::{padded_name, unpadded_name, Error, TapeFile, TapeResult};
+
+/// Signature at the start of the image.
+const SIGNATURE: &[u8] = b"C64-TAPE-RAW";
+/// Size of the header of the image.
+const HEADER_SIZE: usize = 20;
+/// Version 1 stores pulses longer than 255 * 8 cycles as 24 bit cycle count.
+const VERSION: u8 = 1;
+/// Pulses are stored in units of 8 cycles.
+const CYCLES_PER_UNIT: u32 = 8;
+
+/// Length in cycles of the pulses that the KERNAL writes.
+const SHORT_PULSE: u32 = 0x30 * CYCLES_PER_UNIT;
+const MEDIUM_PULSE: u32 = 0x42 * CYCLES_PER_UNIT;
+const LONG_PULSE: u32 = 0x56 * CYCLES_PER_UNIT;
+/// Pulses longer than this are pauses, not data.
+const MAX_PULSE: u32 = 0x70 * CYCLES_PER_UNIT;
+
+/// Number of short pulses before a header and before the data of a program.
+const HEADER_LEADER: usize = 0x6A00;
+const DATA_LEADER: usize = 0x1A00;
+/// Number of short pulses between the two copies of a block and after the second copy.
+const INTERRECORD_GAP: usize = 0x4F;
+const TRAILER: usize = 0x4E;
+
+/// Countdown bytes at the start of the first and the repeated copy of a block.
+const FIRST_COPY: [u8; 9] = [0x89, 0x88, 0x87, 0x86, 0x85, 0x84, 0x83, 0x82, 0x81];
+const REPEATED_COPY: [u8; 9] = [0x09, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01];
+
+/// Size of a header block and the header types of programs.
+const HEADER_BLOCK_SIZE: usize = 192;
+const HEADER_RELOCATABLE_PROGRAM: u8 = 0x01;
+const HEADER_PROGRAM: u8 = 0x03;
+/// Offset and length of the file name in a header block.
+const HEADER_NAME_OFFSET: usize = 5;
+const HEADER_NAME_LENGTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pulse {
+    Short,
+    Medium,
+    Long,
+}
+
+impl Pulse {
+    fn classify(cycles: u32) -> Option<Pulse> {
+        match cycles {
+            0 => None,
+            cycles if cycles < (SHORT_PULSE + MEDIUM_PULSE) / 2 => Some(Pulse::Short),
+            cycles if cycles < (MEDIUM_PULSE + LONG_PULSE) / 2 => Some(Pulse::Medium),
+            cycles if cycles < MAX_PULSE => Some(Pulse::Long),
+            _ => None,
+        }
+    }
+
+    fn cycles(&self) -> u32 {
+        match self {
+            Pulse::Short => SHORT_PULSE,
+            Pulse::Medium => MEDIUM_PULSE,
+            Pulse::Long => LONG_PULSE,
+        }
+    }
+}
+
+/// TAP tape image (version 1) with programs encoded in the KERNAL tape format.
+///
+/// Each program is stored as a header block with the name and addresses followed by a data
+/// block. Every block starts with a leader and is stored twice, so the KERNAL can recover
+/// from read errors. [Tap::files] decodes the programs again, using the repeated copy of a
+/// block when the first copy is damaged.
+///
+/// ```
+/// use c64_assembler::tape::{tap::Tap, TapeFile};
+///
+/// let mut tape = Tap::default();
+/// tape.add_file(&TapeFile::from_prg("demo", &[0x01, 0x08, 0x00, 0x00]).unwrap());
+///
+/// let tape = Tap::from_bytes(&tape.to_bytes()).unwrap();
+/// let files = tape.files().unwrap();
+/// assert_eq!("demo", files[0].name());
+/// assert_eq!(vec![0x01, 0x08, 0x00, 0x00], files[0].prg());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Tap {
+    /// Length of each pulse in cycles.
+    pub pulses: Vec<u32>,
+}
+
+impl Tap {
+    /// Add a program to the end of the tape.
+    pub fn add_file(&mut self, file: &TapeFile) -> &mut Self {
+        let end_address = file.start_address.wrapping_add(file.data.len() as u16);
+        let mut header = vec![HEADER_PROGRAM];
+        header.extend(file.start_address.to_le_bytes());
+        header.extend(end_address.to_le_bytes());
+        header.extend(padded_name(&file.name, HEADER_NAME_LENGTH));
+        header.resize(HEADER_BLOCK_SIZE, 0x20);
+
+        self.add_block(&header, HEADER_LEADER);
+        self.add_block(&file.data, DATA_LEADER);
+        self
+    }
+
+    /// Add a leader followed by both copies of the block.
+    fn add_block(&mut self, payload: &[u8], leader: usize) {
+        self.add_pulses(Pulse::Short, leader);
+        for (countdown, gap) in [(FIRST_COPY, INTERRECORD_GAP), (REPEATED_COPY, TRAILER)] {
+            let checksum = payload.iter().fold(0x00, |checksum, byte| checksum ^ byte);
+            for byte in countdown.iter().chain(payload).chain([&checksum]) {
+                self.add_byte(*byte);
+            }
+            self.add_pulse(Pulse::Long);
+            self.add_pulse(Pulse::Short);
+            self.add_pulses(Pulse::Short, gap);
+        }
+    }
+
+    /// Add a byte marker, the bits starting with the lowest bit and an odd parity bit.
+    fn add_byte(&mut self, byte: u8) {
+        self.add_pulse(Pulse::Long);
+        self.add_pulse(Pulse::Medium);
+        let bits = (0..8).map(|bit| byte & (1 << bit) != 0);
+        for bit in bits.chain([byte.count_ones().is_multiple_of(2)]) {
+            let pair = if bit {
+                [Pulse::Medium, Pulse::Short]
+            } else {
+                [Pulse::Short, Pulse::Medium]
+            };
+            pair.into_iter().for_each(|pulse| self.add_pulse(pulse));
+        }
+    }
+
+    fn add_pulse(&mut self, pulse: Pulse) {
+        self.pulses.push(pulse.cycles());
+    }
+
+    fn add_pulses(&mut self, pulse: Pulse, count: usize) {
+        self.pulses.extend(std::iter::repeat_n(pulse.cycles(), count));
+    }
+
+    /// Bytes of the image.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![];
+        for cycles in &self.pulses {
+            let units = cycles / CYCLES_PER_UNIT;
+            if (1..=0xFF).contains(&units) {
+                data.push(units as u8);
+            } else {
+                data.push(0x00);
+                data.extend(&cycles.to_le_bytes()[0..3]);
+            }
+        }
+
+        let mut result = SIGNATURE.to_vec();
+        result.extend([VERSION, 0x00, 0x00, 0x00]);
+        result.extend((data.len() as u32).to_le_bytes());
+        result.extend(data);
+        result
+    }
+
+    /// Read an image of version 0 or 1.
+    pub fn from_bytes(bytes: &[u8]) -> TapeResult<Tap> {
+        if bytes.len() < HEADER_SIZE || !bytes.starts_with(SIGNATURE) {
+            return Err(Error::InvalidTapeImage("TAP signature is missing".to_string()));
+        }
+        let version = bytes[12];
+        let size = u32::from_le_bytes([bytes[16], bytes[17], bytes[18], bytes[19]]) as usize;
+        let data = bytes
+            .get(HEADER_SIZE..HEADER_SIZE + size)
+            .ok_or_else(|| Error::InvalidTapeImage("TAP data is truncated".to_string()))?;
+
+        let mut tap = Tap::default();
+        let mut index = 0;
+        while index < data.len() {
+            let cycles = match data[index] {
+                0x00 if version == 0 => 0x100 * CYCLES_PER_UNIT,
+                0x00 => {
+                    let cycles = data
+                        .get(index + 1..index + 4)
+                        .ok_or_else(|| Error::InvalidTapeImage("TAP data is truncated".to_string()))?;
+                    index += 3;
+                    u32::from_le_bytes([cycles[0], cycles[1], cycles[2], 0x00])
+                }
+                units => units as u32 * CYCLES_PER_UNIT,
+            };
+            tap.pulses.push(cycles);
+            index += 1;
+        }
+        Ok(tap)
+    }
+
+    /// Decode the programs on the tape.
+    pub fn files(&self) -> TapeResult<Vec<TapeFile>> {
+        let mut result = vec![];
+        let mut header: Option<Vec<u8>> = None;
+        for record in self.records() {
+            let Some(payload) = record else {
+                return Err(Error::InvalidTapeImage(
+                    "both copies of a block are damaged".to_string(),
+                ));
+            };
+            match header.take() {
+                None => {
+                    let is_program_header = payload.len() == HEADER_BLOCK_SIZE
+                        && [HEADER_RELOCATABLE_PROGRAM, HEADER_PROGRAM].contains(&payload[0]);
+                    if is_program_header {
+                        header = Some(payload);
+                    }
+                }
+                Some(header) => {
+                    let start_address = u16::from_le_bytes([header[1], header[2]]);
+                    let end_address = u16::from_le_bytes([header[3], header[4]]);
+                    let size = end_address.wrapping_sub(start_address) as usize;
+                    let name = unpadded_name(&header[HEADER_NAME_OFFSET..HEADER_NAME_OFFSET + HEADER_NAME_LENGTH]);
+                    if payload.len() != size {
+                        return Err(Error::InvalidTapeImage(format!(
+                            "data block of program at ${start_address:04X} has {} bytes instead of {size}",
+                            payload.len()
+                        )));
+                    }
+                    result.push(TapeFile {
+                        name,
+                        start_address,
+                        data: payload,
+                    });
+                }
+            }
+        }
+        if header.is_some() {
+            return Err(Error::InvalidTapeImage(
+                "data block of the last program is missing".to_string(),
+            ));
+        }
+        Ok(result)
+    }
+
+    /// Payload of each block, taken from the first copy when its checksum is valid and
+    /// otherwise from the repeated copy. `None` when both copies are damaged.
+    fn records(&self) -> Vec<Option<Vec<u8>>> {
+        let mut result: Vec<Option<Vec<u8>>> = vec![];
+        let mut expect_repeated_copy = false;
+        for block in self.blocks() {
+            let is_first_copy = block.starts_with(&FIRST_COPY);
+            let is_repeated_copy = block.starts_with(&REPEATED_COPY);
+            if !is_first_copy && !is_repeated_copy {
+                continue;
+            }
+            let valid_payload = block[FIRST_COPY.len()..].split_last().and_then(|(checksum, payload)| {
+                let expected = payload.iter().fold(0x00, |checksum, byte| checksum ^ byte);
+                (expected == *checksum).then(|| payload.to_vec())
+            });
+
+            if is_repeated_copy && expect_repeated_copy {
+                let last = result.len() - 1;
+                if result[last].is_none() {
+                    result[last] = valid_payload;
+                }
+            } else {
+                result.push(valid_payload);
+            }
+            expect_repeated_copy = is_first_copy;
+        }
+        result
+    }
+
+    /// Bytes of each block, including the countdown and the checksum. A block ends at the
+    /// end-of-data marker or at a pulse that doesn't fit the KERNAL tape format; damaged
+    /// blocks are cut off at the damaged byte.
+    fn blocks(&self) -> Vec<Vec<u8>> {
+        let pulses = self
+            .pulses
+            .iter()
+            .map(|cycles| Pulse::classify(*cycles))
+            .collect::<Vec<_>>();
+        let mut result = vec![];
+        let mut block = vec![];
+        let mut index = 0;
+        while index + 1 < pulses.len() {
+            let marker = (pulses[index], pulses[index + 1]);
+            let byte = (marker == (Some(Pulse::Long), Some(Pulse::Medium)))
+                .then(|| Tap::decode_byte(&pulses[index + 2..]))
+                .flatten();
+            match byte {
+                Some(byte) => {
+                    block.push(byte);
+                    index += 20;
+                }
+                None => {
+                    if block.len() >= FIRST_COPY.len() {
+                        result.push(std::mem::take(&mut block));
+                    }
+                    block.clear();
+                    index += 1;
+                }
+            }
+        }
+        if block.len() >= FIRST_COPY.len() {
+            result.push(block);
+        }
+        result
+    }
+
+    /// Decode the bits and the parity bit of a byte, following the byte marker.
+    fn decode_byte(pulses: &[Option<Pulse>]) -> Option<u8> {
+        let mut byte = 0;
+        let mut ones = 0;
+        for bit in 0..9 {
+            let one = match (pulses.get(bit * 2)?, pulses.get(bit * 2 + 1)?) {
+                (Some(Pulse::Short), Some(Pulse::Medium)) => false,
+                (Some(Pulse::Medium), Some(Pulse::Short)) => true,
+                _ => return None,
+            };
+            if one {
+                ones += 1;
+                if bit < 8 {
+                    byte |= 1 << bit;
+                }
+            }
+        }
+        (ones % 2 == 1).then_some(byte)
+    }
+}
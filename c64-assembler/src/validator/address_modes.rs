@@ -0,0 +1,44 @@
+//! Check if the operation of each instruction can be encoded using its address mode.
+use crate::Application;
+
+use super::{diagnostic::visit_instructions, Diagnostic, Error};
+
+pub fn validate_address_modes(application: &Application, diagnostics: &mut Vec<Diagnostic>) {
+    visit_instructions(application, |instruction, location| {
+        let Some(definition) = instruction.operation.definition() else {
+            return;
+        };
+        let address_mode = &instruction.address_mode;
+        if !address_mode.is_supported_by(definition) {
+            diagnostics.push(Diagnostic {
+                error: Error::AddressModeNotSupported {
+                    instruction: definition.instruction.to_string(),
+                    address_mode: address_mode.name().to_string(),
+                },
+                location,
+            });
+            return;
+        }
+
+        let Some(address_reference) = address_mode.address_reference() else {
+            return;
+        };
+        if !address_mode.requires_zeropage(definition) {
+            return;
+        }
+        // Expressions that cannot be evaluated are reported when building the application.
+        let Ok(value) = address_reference.expression.evaluate(application) else {
+            return;
+        };
+        if !(0..=0xFF).contains(&value) {
+            diagnostics.push(Diagnostic {
+                error: Error::ZeropageAddressRequired {
+                    instruction: definition.instruction.to_string(),
+                    expression: address_reference.expression.to_string(),
+                    value,
+                },
+                location,
+            });
+        }
+    });
+}
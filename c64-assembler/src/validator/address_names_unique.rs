@@ -1,26 +1,19 @@
-use crate::{instruction::operation::Operation, Application, Instructions};
+use crate::{instruction::operation::Operation, Application};
 
-use super::{AssemblerResult, Error};
+use super::{diagnostic::visit_instructions, Diagnostic, Error};
 
-pub fn validate_address_names_unique(application: &Application) -> AssemblerResult<()> {
+pub fn validate_address_names_unique(application: &Application, diagnostics: &mut Vec<Diagnostic>) {
     let mut visited_names = vec![];
-    for module in &application.modules {
-        validate_instructions(&mut visited_names, &module.instructions)?;
-        for function in &module.functions {
-            validate_instructions(&mut visited_names, &function.instructions)?;
-        }
-    }
-    Ok(())
-}
-
-fn validate_instructions(visited_names: &mut Vec<String>, instructions: &Instructions) -> AssemblerResult<()> {
-    for instruction in &instructions.instructions {
+    visit_instructions(application, |instruction, location| {
         if let Operation::Label(label) = &instruction.operation {
             if visited_names.contains(label) {
-                return Err(Error::AddressNameNotUnique(label.to_string()));
+                diagnostics.push(Diagnostic {
+                    error: Error::AddressNameNotUnique(label.to_string()),
+                    location,
+                });
+                return;
             }
             visited_names.push(label.clone());
         }
-    }
-    Ok(())
+    });
 }
@@ -0,0 +1,114 @@
+use std::fmt::Display;
+
+use crate::{
+    instruction::{Instruction, SourceLocation},
+    Application,
+};
+
+use super::Error;
+
+/// Location of an instruction inside an application.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Location {
+    /// Name of the module.
+    pub module: Option<String>,
+    /// Name of the function; `None` when the instruction is part of the module instructions.
+    pub function: Option<String>,
+    /// Index of the instruction in the instructions of the module or function.
+    pub instruction_index: Option<usize>,
+    /// Location in the dasm source or macro, when the instruction has been parsed.
+    pub source: Option<SourceLocation>,
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = vec![];
+        if let Some(source) = &self.source {
+            parts.push(format!("line {}, column {}", source.line, source.column));
+        }
+        if let Some(module) = &self.module {
+            parts.push(format!("module '{module}'"));
+        }
+        if let Some(function) = &self.function {
+            parts.push(format!("function '{function}'"));
+        }
+        if let Some(instruction_index) = &self.instruction_index {
+            parts.push(format!("instruction {instruction_index}"));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Error together with the location where it was found.
+///
+/// ```
+/// use c64_assembler::builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder};
+/// use c64_assembler::validator::Validator;
+///
+/// let application = ApplicationBuilder::default()
+///     .module(
+///         ModuleBuilder::default()
+///             .name("main")
+///             .instructions(InstructionBuilder::default().label("loop").label("loop").build())
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// let diagnostics = application.diagnostics();
+/// assert_eq!(
+///     "address name 'loop' is defined multiple times (module 'main', instruction 1)",
+///     diagnostics[0].to_string()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub error: Error,
+    pub location: Location,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let location = self.location.to_string();
+        if location.is_empty() {
+            write!(f, "{}", self.error)
+        } else {
+            write!(f, "{} ({location})", self.error)
+        }
+    }
+}
+
+impl std::error::Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<Error> for Diagnostic {
+    fn from(error: Error) -> Diagnostic {
+        Diagnostic {
+            error,
+            location: Location::default(),
+        }
+    }
+}
+
+/// Call the visitor for each instruction of the application, together with its location.
+pub(crate) fn visit_instructions(application: &Application, mut visitor: impl FnMut(&Instruction, Location)) {
+    for module in &application.modules {
+        let mut visit = |function: Option<&String>, instructions: &[Instruction]| {
+            for (instruction_index, instruction) in instructions.iter().enumerate() {
+                let location = Location {
+                    module: Some(module.name.clone()),
+                    function: function.cloned(),
+                    instruction_index: Some(instruction_index),
+                    source: instruction.source,
+                };
+                visitor(instruction, location);
+            }
+        };
+        visit(None, &module.instructions.instructions);
+        for function in &module.functions {
+            visit(Some(&function.name), &function.instructions.instructions);
+        }
+    }
+}
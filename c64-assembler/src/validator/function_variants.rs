@@ -0,0 +1,39 @@
+use crate::{instruction::operation::Operation, Application, Instructions};
+
+use super::{Diagnostic, Error, Location};
+
+/// All variations of a function must start with the same entry label.
+pub fn validate_function_variants(application: &Application, diagnostics: &mut Vec<Diagnostic>) {
+    for module in &application.modules {
+        for function in &module.functions {
+            let function_entry_label = entry_label(&function.instructions);
+            for variant in &function.variants {
+                if entry_label(&variant.instructions) != function_entry_label {
+                    diagnostics.push(Diagnostic {
+                        error: Error::VariantEntryLabelMismatch {
+                            function: function.name.clone(),
+                            variant: variant.name.clone(),
+                        },
+                        location: Location {
+                            module: Some(module.name.clone()),
+                            function: Some(function.name.clone()),
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Label at the start of the instructions.
+fn entry_label(instructions: &Instructions) -> Option<&String> {
+    match instructions
+        .instructions
+        .first()
+        .map(|instruction| &instruction.operation)
+    {
+        Some(Operation::Label(label)) => Some(label),
+        _ => None,
+    }
+}
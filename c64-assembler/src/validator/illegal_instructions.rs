@@ -0,0 +1,17 @@
+use crate::Application;
+
+use super::{diagnostic::visit_instructions, Diagnostic, Error};
+
+pub fn validate_illegal_instructions(application: &Application, diagnostics: &mut Vec<Diagnostic>) {
+    if cfg!(feature = "illegal-opcodes") {
+        return;
+    }
+    visit_instructions(application, |instruction, location| {
+        if let (true, Some(definition)) = (instruction.operation.is_illegal(), instruction.operation.definition()) {
+            diagnostics.push(Diagnostic {
+                error: Error::IllegalInstruction(definition.instruction.to_string()),
+                location,
+            });
+        }
+    });
+}
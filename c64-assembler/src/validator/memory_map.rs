@@ -0,0 +1,21 @@
+//! Check that the modules and functions don't overlap and stay out of the forbidden regions.
+use crate::Application;
+
+use super::{Diagnostic, Location};
+
+pub fn validate_memory_map(application: &Application, diagnostics: &mut Vec<Diagnostic>) {
+    // Errors in the layout or in the expressions are reported when building the application.
+    let Ok(memory_map) = application.memory_map() else {
+        return;
+    };
+    for (range, error) in memory_map.errors() {
+        diagnostics.push(Diagnostic {
+            error,
+            location: Location {
+                module: Some(range.module.clone()),
+                function: range.function.clone(),
+                ..Default::default()
+            },
+        });
+    }
+}
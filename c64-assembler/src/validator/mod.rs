@@ -21,17 +21,43 @@
 //! ).unwrap();
 //! assert!(application.validate().is_ok());
 //! ```
+//!
+//! [Validator::validate] stops at the first error. Use [Validator::diagnostics] to collect all
+//! errors in a single pass, each together with the [Location] of the instruction.
+use std::fmt::Display;
+
+use address_modes::validate_address_modes;
 use address_names_exists::validate_address_names_exists;
 use address_names_unique::validate_address_names_unique;
+use function_variants::validate_function_variants;
+use illegal_instructions::validate_illegal_instructions;
+use memory_map::validate_memory_map;
+use relative_addressing::validate_relative_addressing;
 
-use crate::Application;
+use crate::{disk, memory::Address, tape, Application};
 
+mod address_modes;
 mod address_names_exists;
 mod address_names_unique;
+mod diagnostic;
+mod function_variants;
+mod illegal_instructions;
+mod memory_map;
 mod relative_addressing;
 
+pub use diagnostic::{Diagnostic, Location};
+
 pub trait Validator {
-    fn validate(&self) -> AssemblerResult<()>;
+    /// Validate the application, returns the first error that is found.
+    fn validate(&self) -> AssemblerResult<()> {
+        match self.diagnostics().into_iter().next() {
+            Some(diagnostic) => Err(diagnostic.error),
+            None => Ok(()),
+        }
+    }
+
+    /// Validate the application, returns all errors that are found.
+    fn diagnostics(&self) -> Vec<Diagnostic>;
 }
 
 pub type AssemblerResult<T> = Result<T, Error>;
@@ -42,14 +68,197 @@ pub enum Error {
     AddressNameUnknown(String),
     /// An address with the same name has been defined multiple times.
     AddressNameNotUnique(String),
+    /// An illegal instruction is used, but the `illegal-opcodes` feature isn't enabled.
+    IllegalInstruction(String),
+    /// The instruction doesn't have an op-code for the address mode.
+    AddressModeNotSupported { instruction: String, address_mode: String },
+    /// The instruction can only access the address using a zeropage op-code, but the address
+    /// isn't in the zeropage.
+    ZeropageAddressRequired {
+        instruction: String,
+        expression: String,
+        value: i64,
+    },
+    /// A module or function is assigned to a segment that doesn't exist.
+    SegmentUnknown(String),
+    /// The instructions placed in a segment exceed its maximum size.
+    SegmentOverflow {
+        segment: String,
+        size: usize,
+        max_size: Address,
+    },
+    /// Segments overlap and cannot be combined into a single program.
+    SegmentOverlap(String, String),
+    /// The instructions placed in a segment continue past the end of memory ($FFFF).
+    SegmentOutOfMemory(String),
+    /// Two modules or functions are placed at the same memory. Address is the first byte that
+    /// is used by both.
+    MemoryOverlap {
+        first: String,
+        second: String,
+        address: Address,
+    },
+    /// A module or function is placed in a forbidden memory region. Address is the first byte
+    /// inside the region.
+    ForbiddenRegion { region: String, address: Address },
+    /// Expression cannot be evaluated.
+    InvalidExpression(String),
+    /// Value of an expression doesn't fit in the operand of the instruction.
+    ExpressionOutOfRange { expression: String, value: i64 },
+    /// Target of a relative branch is outside -128..=127 bytes from the next instruction.
+    BranchOutOfRange { target: String, distance: i32 },
+    /// Addresses of labels still change after the maximum number of passes, instructions keep
+    /// switching between their zeropage and absolute op-codes.
+    LabelAddressesUnstable { passes: usize },
+    /// The selected variation of a function doesn't exist.
+    VariantUnknown { function: String, variant: String },
+    /// A variation of a function starts with another entry label than the selected variation.
+    VariantEntryLabelMismatch { function: String, variant: String },
+    /// A module parameter isn't bound or defined.
+    ModuleParameterUnbound { module: String, parameter: String },
+    /// A value is bound to a parameter that the module doesn't have.
+    ModuleParameterUnknown { module: String, parameter: String },
+    /// A zeropage module parameter is bound to an address outside the zeropage.
+    ModuleParameterNotZeropage {
+        module: String,
+        parameter: String,
+        value: Address,
+    },
+    /// The zeropage pool has no room left for a zeropage variable of a module.
+    ZeropageExhausted { module: String, variable: String },
+    /// Text contains a character that cannot be encoded.
+    UnsupportedCharacter(char),
+    /// A PETSCII control code is written between braces, but the name isn't known.
+    UnknownControlCode(String),
+    /// A disk image could not be read or written.
+    Disk(disk::Error),
+    /// A tape image could not be read or written.
+    Tape(tape::Error),
     /// Assembler did take a branch that it could not recover from.
     InternalCompilerError,
+    /// Byte stream could not be parsed as a program.
+    InvalidProgram(String),
+    /// Source code could not be parsed. Line and column are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::AddressNameUnknown(name) => write!(f, "address name '{name}' is unknown"),
+            Error::AddressNameNotUnique(name) => write!(f, "address name '{name}' is defined multiple times"),
+            Error::IllegalInstruction(name) => {
+                write!(f, "illegal instruction '{name}' requires the 'illegal-opcodes' feature")
+            }
+            Error::AddressModeNotSupported {
+                instruction,
+                address_mode,
+            } => write!(
+                f,
+                "instruction '{instruction}' doesn't support {address_mode} addressing"
+            ),
+            Error::ZeropageAddressRequired {
+                instruction,
+                expression,
+                value,
+            } => write!(
+                f,
+                "instruction '{instruction}' requires a zeropage address, but '{expression}' is {value}"
+            ),
+            Error::SegmentUnknown(name) => write!(f, "segment '{name}' is unknown"),
+            Error::SegmentOverflow {
+                segment,
+                size,
+                max_size,
+            } => write!(
+                f,
+                "segment '{segment}' uses {size} bytes, which exceeds its maximum size of {max_size} bytes"
+            ),
+            Error::SegmentOverlap(first, second) => write!(f, "segments '{first}' and '{second}' overlap"),
+            Error::SegmentOutOfMemory(name) => write!(f, "segment '{name}' doesn't fit in memory"),
+            Error::MemoryOverlap { first, second, address } => {
+                write!(f, "'{first}' and '{second}' overlap at ${address:04X}")
+            }
+            Error::ForbiddenRegion { region, address } => {
+                write!(f, "code or data is placed in the {region} region at ${address:04X}")
+            }
+            Error::InvalidExpression(message) => write!(f, "invalid expression: {message}"),
+            Error::ExpressionOutOfRange { expression, value } => {
+                write!(f, "value {value} of expression '{expression}' is out of range")
+            }
+            Error::BranchOutOfRange { target, distance } => write!(
+                f,
+                "branch to '{target}' is {distance} bytes away, only -128..=127 bytes can be reached"
+            ),
+            Error::LabelAddressesUnstable { passes } => {
+                write!(f, "label addresses are still changing after {passes} passes")
+            }
+            Error::VariantUnknown { function, variant } => {
+                write!(f, "function '{function}' has no variation '{variant}'")
+            }
+            Error::VariantEntryLabelMismatch { function, variant } => write!(
+                f,
+                "variation '{variant}' of function '{function}' doesn't start with the same entry label"
+            ),
+            Error::ModuleParameterUnbound { module, parameter } => {
+                write!(f, "parameter '{parameter}' of module '{module}' isn't bound")
+            }
+            Error::ModuleParameterUnknown { module, parameter } => {
+                write!(f, "module '{module}' has no parameter '{parameter}'")
+            }
+            Error::ModuleParameterNotZeropage {
+                module,
+                parameter,
+                value,
+            } => write!(
+                f,
+                "parameter '{parameter}' of module '{module}' requires a zeropage address, but is bound to ${value:04X}"
+            ),
+            Error::ZeropageExhausted { module, variable } => {
+                write!(
+                    f,
+                    "no free zeropage address for variable '{variable}' of module '{module}'"
+                )
+            }
+            Error::UnsupportedCharacter(character) => write!(f, "character {character:?} cannot be encoded"),
+            Error::UnknownControlCode(name) => write!(f, "control code '{{{name}}}' is unknown"),
+            Error::Disk(error) => write!(f, "{error}"),
+            Error::Tape(error) => write!(f, "{error}"),
+            Error::InternalCompilerError => write!(f, "internal compiler error"),
+            Error::InvalidProgram(message) => write!(f, "invalid program: {message}"),
+            Error::Parse { line, column, message } => write!(f, "line {line}, column {column}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<disk::Error> for Error {
+    fn from(error: disk::Error) -> Error {
+        Error::Disk(error)
+    }
+}
+
+impl From<tape::Error> for Error {
+    fn from(error: tape::Error) -> Error {
+        Error::Tape(error)
+    }
 }
 
 impl Validator for Application {
-    fn validate(&self) -> AssemblerResult<()> {
-        validate_address_names_exists(self)?;
-        validate_address_names_unique(self)?;
-        Ok(())
+    fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        validate_address_names_exists(self, &mut diagnostics);
+        validate_address_names_unique(self, &mut diagnostics);
+        validate_illegal_instructions(self, &mut diagnostics);
+        validate_address_modes(self, &mut diagnostics);
+        validate_relative_addressing(self, &mut diagnostics);
+        validate_function_variants(self, &mut diagnostics);
+        validate_memory_map(self, &mut diagnostics);
+        diagnostics
     }
 }
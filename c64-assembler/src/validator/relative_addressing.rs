@@ -1 +1,31 @@
 //! Check if all relative jumps are reachable from their instruction.
+use crate::{memory::address_mode::AddressMode, Application};
+
+use super::{Diagnostic, Error, Location};
+
+pub fn validate_relative_addressing(application: &Application, diagnostics: &mut Vec<Diagnostic>) {
+    // Errors in the layout or in the expressions are reported when building the application.
+    let _ = application.visit_placed_instructions(|placement, instruction_index, instruction, address| {
+        let (Ok(Some(distance)), AddressMode::Relative(address_reference)) = (
+            instruction.branch_distance(application, address),
+            &instruction.address_mode,
+        ) else {
+            return;
+        };
+        if (-128..=127).contains(&distance) {
+            return;
+        }
+        diagnostics.push(Diagnostic {
+            error: Error::BranchOutOfRange {
+                target: address_reference.expression.to_string(),
+                distance,
+            },
+            location: Location {
+                module: Some(placement.module().name.clone()),
+                function: placement.function().map(|function| function.name.clone()),
+                instruction_index: Some(instruction_index),
+                source: instruction.source,
+            },
+        });
+    });
+}
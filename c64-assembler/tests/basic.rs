@@ -0,0 +1,286 @@
+use c64_assembler::{
+    basic::{detokenize_line, tokenize_line, BasicProgram, BasicStub},
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    memory::{segment::Segment, Address},
+    parser::{DasmParser, Parser, ProgramParser},
+    validator::{AssemblerResult, Error, Validator},
+    Application, Instructions,
+};
+use c64_assembler_emulator::{
+    cpu::{Cpu, StopReason},
+    memory::{Bus, Ram},
+};
+
+fn application(entry_point: Address, instructions: Instructions) -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .entry_point(entry_point)
+        .module(ModuleBuilder::default().name("main").instructions(instructions).build())
+        .build()
+}
+
+#[test]
+fn basic_stub_matches_basic_header() -> AssemblerResult<()> {
+    let header = application(0x0800, InstructionBuilder::default().add_basic_header().rts().build())?;
+    let stub = application(
+        0x0800,
+        InstructionBuilder::default()
+            .basic_stub(&BasicStub::new("main"))
+            .label("main")
+            .rts()
+            .build(),
+    )?;
+    assert!(stub.validate().is_ok());
+    assert_eq!(
+        ProgramGenerator::default().generate(header)?,
+        ProgramGenerator::default().generate(stub)?
+    );
+    Ok(())
+}
+
+#[test]
+fn basic_stub_follows_label() -> AssemblerResult<()> {
+    let application = application(
+        0x0801,
+        InstructionBuilder::default()
+            .basic_stub(&BasicStub::new("main").line_number(20).rem(10, "demo"))
+            .label("data")
+            .fill(3, 0x00)
+            .label("main")
+            .rts()
+            .build(),
+    )?;
+    assert_eq!(0x081C, application.lookup_address(&"main".to_string())?);
+    let program = ProgramGenerator::default().generate(application.clone())?;
+    assert_eq!(
+        vec![
+            0x01, 0x08, // load address
+            0x0C, 0x08, 0x0A, 0x00, 0x8F, 0x20, 0x44, 0x45, 0x4D, 0x4F, 0x00, // 10 REM DEMO
+            0x17, 0x08, 0x14, 0x00, 0x9E, 0x20, 0x32, 0x30, 0x37, 0x36, 0x00, // 20 SYS 2076
+            0x00, 0x00, // end of program
+            0x00, 0x00, 0x00, 0x60,
+        ],
+        program
+    );
+
+    let source = DasmGenerator::default().generate(application)?;
+    assert!(source.contains("; 20 SYS main"));
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(program, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+/// The lines are linked for the address the stub is placed at.
+#[test]
+fn basic_stub_placement() -> AssemblerResult<()> {
+    for entry_point in [0x0800, 0x0801, 0x1C01] {
+        let application = application(
+            entry_point,
+            InstructionBuilder::default()
+                .basic_stub(&BasicStub::new("main"))
+                .label("main")
+                .lda_imm(0x00)
+                .sta_addr("result")
+                .rts()
+                .label("result")
+                .build(),
+        )?;
+        let main = application.lookup_address(&"main".to_string())?;
+        let result = application.lookup_address(&"result".to_string())?;
+        let program = ProgramGenerator::default().generate(application)?;
+        assert_eq!(
+            format!("10 sys {main}\n"),
+            BasicProgram::from_prg(&program)?.to_string()
+        );
+
+        let mut cpu = Cpu::<Ram>::default();
+        cpu.set_cycle_limit(Some(1_000));
+        cpu.bus.write(result, 0xFF);
+        cpu.load_program(&program).unwrap();
+        assert_eq!(Ok(StopReason::Return), cpu.call(main));
+        assert_eq!(0x00, cpu.bus.read(result));
+    }
+    Ok(())
+}
+
+#[test]
+fn basic_stub_five_digits() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .segment(Segment::new("code", 0xC000))
+        .module(
+            ModuleBuilder::default()
+                .instructions(
+                    InstructionBuilder::default()
+                        .basic_stub(&BasicStub::new("main"))
+                        .build(),
+                )
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .segment("code")
+                .instructions(InstructionBuilder::default().label("main").rts().build())
+                .build(),
+        )
+        .build()?;
+    let program = ProgramGenerator::default().generate(application)?;
+    assert_eq!(b"49152", &program[8..13]);
+
+    // The disassembler starts at the SYS address.
+    let parsed = ProgramParser::default().parse(&program)?;
+    assert!(parsed.lookup_address(&"label_C000".to_string()).is_ok());
+    Ok(())
+}
+
+#[test]
+fn tokenize() -> AssemblerResult<()> {
+    assert_eq!(
+        vec![0x81, 0x49, 0xB2, 0x31, 0xA4, 0x31, 0x30, 0x3A, 0x99, 0x49, 0x3A, 0x82],
+        tokenize_line("FOR I=1 TO 10:?I:NEXT")?
+            .into_iter()
+            .filter(|byte| *byte != 0x20)
+            .collect::<Vec<u8>>()
+    );
+    // Keywords inside strings, REM and DATA are kept as text.
+    assert_eq!(
+        vec![0x99, 0x22, 0x52, 0x55, 0x4E, 0x22, 0x3A, 0x83, 0x20, 0x45, 0x4E, 0x44, 0x3A, 0x80],
+        tokenize_line("print\"run\":data end:end")?
+    );
+    assert_eq!(vec![0x8F, 0x20, 0x47, 0x4F, 0x54, 0x4F], tokenize_line("rem goto")?);
+    assert_eq!(
+        vec![0x97, 0x35, 0x33, 0x32, 0x38, 0x30, 0x2C, 0x30],
+        tokenize_line("POKE53280,0")?
+    );
+    assert!(matches!(
+        tokenize_line("print \"{\""),
+        Err(Error::UnsupportedCharacter('{'))
+    ));
+    Ok(())
+}
+
+#[test]
+fn basic_program() -> AssemblerResult<()> {
+    let program = BasicProgram::parse(
+        "
+        20 goto 10
+        10 print \"hello\"
+        20 end
+        ",
+    )?;
+    assert_eq!(
+        vec![10, 20],
+        program.lines.iter().map(|line| line.number).collect::<Vec<_>>()
+    );
+
+    let application = application(
+        0x0800,
+        InstructionBuilder::default().basic_program(&program, 0x0800).build(),
+    )?;
+    assert_eq!(
+        vec![
+            0x00, 0x08, 0x00, // load address and zero byte before the program
+            0x0F, 0x08, 0x0A, 0x00, 0x99, 0x20, 0x22, 0x48, 0x45, 0x4C, 0x4C, 0x4F, 0x22,
+            0x00, // 10 PRINT "HELLO"
+            0x15, 0x08, 0x14, 0x00, 0x80, 0x00, // 20 END
+            0x00, 0x00,
+        ],
+        ProgramGenerator::default().generate(application)?
+    );
+
+    let Err(error) = BasicProgram::parse("10 print\nprint") else {
+        panic!("expected an error");
+    };
+    assert_eq!("line 2, column 1: line number expected", error.to_string());
+    assert!(matches!(BasicProgram::parse("64000 end"), Err(Error::Parse { .. })));
+    Ok(())
+}
+
+#[test]
+fn tokenize_abbreviations() -> AssemblerResult<()> {
+    assert_eq!(
+        tokenize_line("poke 53280,0:goto 10")?,
+        tokenize_line("pO 53280,0:gO 10")?
+    );
+    // Like on the C64 the first keyword that matches is used.
+    assert_eq!(vec![0x98], tokenize_line("pR")?);
+    assert_eq!(vec![0x99, 0x20, 0x41], tokenize_line("PRINT A")?);
+    Ok(())
+}
+
+#[test]
+fn control_codes() -> AssemblerResult<()> {
+    assert_eq!(
+        vec![0x99, 0x22, 0x93, 0x12, 0x1D, 0xC1, 0x92, 0x22],
+        tokenize_line("print\"{clr}{rvs on}{RIGHT}A{$92}\"")?
+    );
+    assert!(matches!(
+        tokenize_line("print\"{flash}\""),
+        Err(Error::UnknownControlCode(name)) if name == "flash"
+    ));
+    assert_eq!(
+        "print\"{clr}{rvon}{rght}A{rvof}\"",
+        detokenize_line(&tokenize_line("print\"{clear}{rvs on}{right}A{$92}\"")?)
+    );
+    assert_eq!("rem {$a6}π", detokenize_line(&[0x8F, 0x20, 0xA6, 0xFF]));
+    Ok(())
+}
+
+#[test]
+fn detokenize_prg() -> AssemblerResult<()> {
+    let text = "10 print\"{clr}hello\";:rem greeting
+20 for i=1 to 10:next i
+30 data run,\"a:b\",3
+40 if a$<>\"\" then goto 10
+";
+    let program = BasicProgram::parse(text)?;
+    assert_eq!(text, program.to_string());
+
+    for load_address in [0x0801, 0x1C01, 0x4001] {
+        let prg = program.prg(load_address);
+        assert_eq!(program, BasicProgram::from_prg(&prg)?);
+    }
+
+    let application = application(
+        0x0800,
+        InstructionBuilder::default().basic_program(&program, 0x0800).build(),
+    )?;
+    let prg = ProgramGenerator::default().generate(application)?;
+    assert_eq!(program, BasicProgram::from_prg(&prg)?);
+
+    assert!(matches!(
+        BasicProgram::from_prg(&prg[..prg.len() - 2]),
+        Err(Error::InvalidProgram(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn basic_segment_listing() -> AssemblerResult<()> {
+    let program = BasicProgram::parse("10 poke 53280,0\n20 sys 49152")?;
+    let application = ApplicationBuilder::default()
+        .segment(Segment::new("code", 0xC000))
+        .module(
+            ModuleBuilder::default()
+                .name("loader")
+                .instructions(InstructionBuilder::default().basic_program(&program, 0x0801).build())
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .segment("code")
+                .instructions(InstructionBuilder::default().rts().build())
+                .build(),
+        )
+        .build()?;
+    let source = DasmGenerator::default().generate(application.clone())?;
+    assert!(source.contains("; 10 poke 53280,0"));
+    assert!(source.contains("; 20 sys 49152"));
+
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(
+        ProgramGenerator::default().generate(application)?,
+        ProgramGenerator::default().generate(parsed)?
+    );
+    Ok(())
+}
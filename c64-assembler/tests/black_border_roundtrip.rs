@@ -2,12 +2,11 @@ use c64_assembler::{
     generator::{Generator, ProgramGenerator},
     validator::AssemblerResult,
 };
-use c64_assembler_macro::application;
-use mos6502::{
-    cpu::CPU,
-    instruction::Nmos6502,
-    memory::{Bus, Memory},
+use c64_assembler_emulator::{
+    cpu::{Cpu, StopReason},
+    memory::{Bus, Ram},
 };
+use c64_assembler_macro::application;
 
 #[test]
 fn set_black_border() -> AssemblerResult<()> {
@@ -29,17 +28,59 @@ fn set_black_border() -> AssemblerResult<()> {
     let bytes = ProgramGenerator::default().generate(application)?;
 
     // Emulate the program on 6502 CPU
-    let mut cpu = CPU::new(Memory::new(), Nmos6502);
+    let mut cpu = Cpu::<Ram>::default();
+    let load_address = cpu.load_program(&bytes).unwrap();
+    cpu.bus.write(0xd020, 0xFF);
+    cpu.registers.program_counter = load_address;
+
+    cpu.step().unwrap();
+    assert_eq!(0xFF, cpu.bus.read(0xd020));
+    cpu.step().unwrap();
+    assert_eq!(0x00, cpu.bus.read(0xd020));
+    cpu.step().unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn call_function() -> AssemblerResult<()> {
+    let application = application!(
+        name="Call function"
+        include_vic2_defines
+        module!(
+            name="main"
+            instructions!(
+            main_entry_point:
+                jsr clear_sprite_positions
+                rts
+            )
+            function!(
+                name="clear_sprite_positions"
+                instructions!(
+                    lda #$00
+                    ldx #$0F
+                loop:
+                    sta VIC2_SPRITE_0_X,x
+                    dex
+                    bpl loop
+                    rts
+                )
+            )
+        )
+    )?;
+
+    let function_address = application.lookup_address(&"clear_sprite_positions".to_string())?;
+    let bytes = ProgramGenerator::default().generate(application)?;
 
-    cpu.memory.set_bytes(0x0800, &bytes[2..]);
-    cpu.memory.set_byte(0xd020, 0xFF);
-    cpu.registers.program_counter = 0x0800;
+    let mut cpu = Cpu::<Ram>::default();
+    cpu.load_program(&bytes).unwrap();
+    cpu.bus.write_bytes(0xd000, &[0xFF; 16]);
+    cpu.set_cycle_limit(Some(1000));
 
-    cpu.single_step();
-    assert_eq!(0xFF, cpu.memory.get_byte(0xd020));
-    cpu.single_step();
-    assert_eq!(0x00, cpu.memory.get_byte(0xd020));
-    cpu.single_step();
+    assert_eq!(Ok(StopReason::Return), cpu.call(function_address));
+    assert!((0xd000..0xd010).all(|address| cpu.bus.read(address) == 0x00));
+    // lda + ldx + 16 * (sta + dex + bpl) - not taken branch + rts
+    assert_eq!(2 + 2 + 16 * (5 + 2 + 3) - 1 + 6, cpu.cycles());
 
     Ok(())
 }
@@ -43,3 +43,19 @@ fn dasm_v0_1_0_missing_y_indexing() -> AssemblerResult<()> {
     assert!(dasm_source.contains("lda label_a,y"));
     Ok(())
 }
+
+/// Indexed indirect addressing closed the parenthesis before the x-register.
+#[test]
+fn dasm_v0_2_0_indexed_indirect() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .define_address("POINTER", 0xFB)
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().lda_ind_x("POINTER").build())
+                .build(),
+        )
+        .build()?;
+    let dasm_source = DasmGenerator::default().generate(application)?;
+    assert!(dasm_source.contains("lda (POINTER,x)"));
+    Ok(())
+}
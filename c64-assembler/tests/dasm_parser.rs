@@ -0,0 +1,165 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    instruction::operation::Operation,
+    memory::{address_mode::AddressMode, label::AddressReference},
+    parser::{DasmParser, Parser},
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Parser roundtrip")
+        .include_vic2_defines()
+        .define_address("POINTER", 0xFE)
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .add_basic_header()
+                        .label("main_entry_point")
+                        .lda_imm(0x00)
+                        .comment("Load black color")
+                        .comment("Second line of the comment")
+                        .sta_addr("VIC2_BORDER_COLOR")
+                        .lda_imm_low("data")
+                        .sta_addr("POINTER")
+                        .lda_imm_high("data")
+                        .sta_addr_offs("POINTER", 1)
+                        .ldy_imm(0x01)
+                        .lda_ind_y("POINTER")
+                        .ldx_imm(0x00)
+                        .lda_ind_x("POINTER")
+                        .lda_addr_x("data")
+                        .ldx_addr_y("data")
+                        .asl_acc()
+                        .jsr_addr("clear_border")
+                        .jmp_ind("data")
+                        .label("data")
+                        .raw(&[0x01, 0x02, 0x03])
+                        .build(),
+                )
+                .function(
+                    FunctionBuilder::default()
+                        .name("clear_border")
+                        .doc(&["Set the border color to black"])
+                        .instructions(
+                            InstructionBuilder::default()
+                                .ldx_imm(0x10)
+                                .label("loop")
+                                .dex()
+                                .bne_addr("loop")
+                                .rts()
+                                .build(),
+                        )
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn roundtrip_dasm_source() -> AssemblerResult<()> {
+    let source = DasmGenerator::default().generate(test_application()?)?;
+    let application = DasmParser::default().parse(&source)?;
+    assert_eq!(source, DasmGenerator::default().generate(application)?);
+    Ok(())
+}
+
+#[test]
+fn roundtrip_program() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let program = ProgramGenerator::default().generate(application.clone())?;
+    let source = DasmGenerator::default().generate(application)?;
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(program, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+#[test]
+fn parse_handwritten_source() -> AssemblerResult<()> {
+    let application = DasmParser::default().parse(
+        "
+; Set the border color
+  processor 6502
+BORDER equ $d020
+  org $1000
+start lda #%00000110
+  sta BORDER
+loop: dex
+  bne loop
+  ROL
+  rts
+",
+    )?;
+    assert_eq!(0x1000, application.entry_point);
+    let instructions = &application.modules[0].instructions.instructions;
+    assert_eq!(Operation::Label("start".to_string()), instructions[0].operation);
+    assert_eq!(vec!["Set the border color".to_string()], instructions[0].comments);
+    assert_eq!(
+        AddressMode::Relative(AddressReference::new("loop")),
+        instructions[5].address_mode
+    );
+    assert_eq!(AddressMode::Accumulator, instructions[6].address_mode);
+
+    let program = ProgramGenerator::default().generate(application)?;
+    assert_eq!(
+        vec![0x00, 0x10, 0xA9, 0x06, 0x8D, 0x20, 0xD0, 0xCA, 0xD0, 0xFD, 0x2A, 0x60],
+        program
+    );
+    Ok(())
+}
+
+fn assert_parse_error(source: &str, expected_line: usize, expected_column: usize) {
+    match DasmParser::default().parse(source).map(|_| ()) {
+        Err(Error::Parse { line, column, .. }) => {
+            assert_eq!((expected_line, expected_column), (line, column));
+        }
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert_parse_error("  lda #$00\n  ldq #$00", 2, 3);
+    assert_parse_error("  lda #$100", 1, 8);
+    assert_parse_error("  sta #$10", 1, 3);
+    assert_parse_error("  jmp unknown_label", 1, 7);
+    assert_parse_error("  lda [$d020", 1, 13);
+    assert_parse_error("  lda (pointer),x\npointer:", 1, 17);
+}
+
+#[test]
+fn parse_comment_after_module_end() -> AssemblerResult<()> {
+    let application = DasmParser::default().parse(
+        "; --- Module begin: border ---
+  lda #$00 ; Load black color
+; --- Module end: border ---
+                    ; Next module
+  rts
+",
+    )?;
+    let instructions = &application.modules[0].instructions.instructions;
+    assert_eq!(vec!["Load black color".to_string()], instructions[0].comments);
+    let instructions = &application.modules[1].instructions.instructions;
+    assert_eq!(vec!["Next module".to_string()], instructions[0].comments);
+    Ok(())
+}
+
+#[test]
+fn parse_comment_after_function_end() -> AssemblerResult<()> {
+    let application = DasmParser::default().parse(
+        "; --- Function begin: clear_border ---
+clear_border
+  rts ; Return to caller
+; --- Function end: clear_border ---
+                    ; Trailing comment
+",
+    )?;
+    let instructions = &application.modules[0].functions[0].instructions.instructions;
+    assert_eq!(vec!["Return to caller".to_string()], instructions[0].comments);
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    parser::{DasmParser, Parser},
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Data")
+        .entry_point(0x1000)
+        .define_address("SCREEN", 0x0400)
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .rts()
+                        .label("vectors")
+                        .words_addr(&["SCREEN", "handler_b"])
+                        .words(&[0xBEEF])
+                        .split_address_table("handlers", &["handler_a", "handler_b"])
+                        .label("message")
+                        .text_petscii("Hi!")
+                        .text_screen_codes("@a")
+                        .fill(3, 0xEA)
+                        .align(0x10)
+                        .label("handler_a")
+                        .rts()
+                        .label("handler_b")
+                        .rts()
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn generate_data_bytes() -> AssemblerResult<()> {
+    let bytes = ProgramGenerator::default().generate(test_application()?)?;
+    #[rustfmt::skip]
+    let expected = vec![
+        0x00, 0x10,
+        0x60,
+        0x00, 0x04, 0x21, 0x10,
+        0xEF, 0xBE,
+        0x20, 0x21,
+        0x10, 0x10,
+        0xC8, 0x49, 0x21,
+        0x00, 0x01,
+        0xEA, 0xEA, 0xEA,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x60,
+        0x60,
+    ];
+    assert_eq!(expected, bytes);
+    assert_eq!(0x1020, test_application()?.lookup_address(&"handler_a".to_string())?);
+    Ok(())
+}
+
+#[test]
+fn generate_dasm_directives() -> AssemblerResult<()> {
+    let source = DasmGenerator::default().generate(test_application()?)?;
+    assert!(source.contains("SCREEN = $0400"));
+    assert!(source.contains("  word SCREEN, handler_b"));
+    assert!(source.contains("  word $BEEF"));
+    assert!(source.contains("  byte <handler_a, <handler_b"));
+    assert!(source.contains("  byte >handler_a, >handler_b"));
+    assert!(source.contains("  byte $C8, $49, $21"));
+    assert!(source.contains("  ds 3, $EA"));
+    assert!(source.contains("  align 16"));
+    Ok(())
+}
+
+#[test]
+fn dasm_roundtrip() -> AssemblerResult<()> {
+    let expected = ProgramGenerator::default().generate(test_application()?)?;
+    let source = DasmGenerator::default().generate(test_application()?)?;
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(expected, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+#[test]
+fn parse_address_byte_with_offset() -> AssemblerResult<()> {
+    let application = DasmParser::default().parse(
+        "
+  org $1000
+table:
+  byte <[table+2]
+  byte >[table+2]
+",
+    )?;
+    let bytes = ProgramGenerator::default().generate(application)?;
+    assert_eq!(vec![0x00, 0x10, 0x02, 0x10], bytes);
+
+    let result = DasmParser::default().parse("  org $1000\ntable:\n  byte <table, >table\n");
+    assert!(matches!(result, Err(Error::Parse { line: 3, .. })));
+    Ok(())
+}
+
+#[test]
+fn unsupported_character() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().text_screen_codes("{").build())
+                .build(),
+        )
+        .build()?;
+    assert!(matches!(
+        ProgramGenerator::default().generate(application),
+        Err(Error::UnsupportedCharacter('{'))
+    ));
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{Generator, ProgramGenerator},
+    instruction::{operation::Operation, Instruction, SourceLocation},
+    memory::{address_mode::AddressMode, label::AddressReference},
+    parser::{DasmParser, Parser},
+    validator::{AssemblerResult, Error, Location, Validator},
+    Instructions,
+};
+use c64_assembler_macro::application;
+
+#[test]
+fn collect_all_diagnostics() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(InstructionBuilder::default().label("loop").label("loop").build())
+                .function(
+                    FunctionBuilder::default()
+                        .name("clear")
+                        .instructions(InstructionBuilder::default().rts().label("loop").build())
+                        .build(),
+                )
+                .build(),
+        )
+        .build()?;
+
+    let diagnostics = application.diagnostics();
+    assert_eq!(2, diagnostics.len());
+    assert!(matches!(&diagnostics[0].error, Error::AddressNameNotUnique(name) if name == "loop"));
+    assert_eq!(
+        Location {
+            module: Some("main".to_string()),
+            function: None,
+            instruction_index: Some(1),
+            source: None,
+        },
+        diagnostics[0].location
+    );
+    assert_eq!(
+        "address name 'loop' is defined multiple times (module 'main', function 'clear', instruction 1)",
+        diagnostics[1].to_string()
+    );
+    assert!(matches!(application.validate(), Err(Error::AddressNameNotUnique(_))));
+    Ok(())
+}
+
+#[test]
+fn diagnostics_macro_source_location() -> AssemblerResult<()> {
+    let application = application!(module!(name = "main" instructions!(
+        duplicate:
+        duplicate:
+    )))?;
+
+    let diagnostics = application.diagnostics();
+    assert_eq!(1, diagnostics.len());
+    let source = diagnostics[0].location.source.unwrap();
+    assert_eq!(line!() - 6, source.line as u32);
+    assert_eq!(9, source.column);
+    Ok(())
+}
+
+#[test]
+fn diagnostics_dasm_source_location() -> AssemblerResult<()> {
+    let application = DasmParser::default().parse(
+        "
+  org $1000
+start:
+  nop
+start:
+  rts
+",
+    )?;
+
+    let diagnostics = application.diagnostics();
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(
+        Some(SourceLocation { line: 5, column: 1 }),
+        diagnostics[0].location.source
+    );
+    assert_eq!(
+        "address name 'start' is defined multiple times (line 5, column 1, module 'main', instruction 2)",
+        diagnostics[0].to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn error_is_std_error() {
+    fn validate() -> Result<(), Box<dyn std::error::Error>> {
+        let application = ApplicationBuilder::default()
+            .module(
+                ModuleBuilder::default()
+                    .instructions(InstructionBuilder::default().label("a").label("a").build())
+                    .build(),
+            )
+            .build()?;
+        application.validate()?;
+        Ok(())
+    }
+    assert_eq!(
+        "address name 'a' is defined multiple times",
+        validate().unwrap_err().to_string()
+    );
+}
+
+/// Instructions constructed using the public fields are not checked by the builder.
+#[test]
+fn generate_indexed_indirect_outside_zeropage() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .define_address("pointer", 0x1000)
+        .module(
+            ModuleBuilder::default()
+                .instructions(Instructions {
+                    instructions: vec![Instruction {
+                        operation: Operation::LDA,
+                        address_mode: AddressMode::IndirectIndexed(AddressReference::new("pointer")),
+                        ..Default::default()
+                    }],
+                })
+                .build(),
+        )
+        .build();
+    assert!(matches!(
+        application,
+        Err(Error::ExpressionOutOfRange { value: 0x1000, .. })
+    ));
+
+    let mut application = ApplicationBuilder::default()
+        .define_address("pointer", 0x0010)
+        .module(ModuleBuilder::default().build())
+        .build()?;
+    application.modules[0].instructions.instructions.push(Instruction {
+        operation: Operation::LDA,
+        address_mode: AddressMode::IndexedIndirect(AddressReference::new("pointer")),
+        ..Default::default()
+    });
+    application.address_lookup.insert("pointer".to_string(), 0x1000);
+    let result = ProgramGenerator::default().generate(application);
+    assert!(matches!(
+        result,
+        Err(Error::ZeropageAddressRequired { value: 0x1000, .. })
+    ));
+    Ok(())
+}
@@ -0,0 +1,336 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    disk::{d64::D64, d71::D71, d81::D81, DiskFile, DiskImage, DiskResult, Error, FileType},
+    generator::{Generator, ProgramGenerator},
+    validator::AssemblerResult,
+};
+
+const DIRECTORY_TRACK: u8 = 18;
+
+fn program() -> AssemblerResult<Vec<u8>> {
+    let application = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .instructions(
+                    InstructionBuilder::default()
+                        .add_basic_header()
+                        .lda_imm(0x00)
+                        .sta_addr("VIC2_BORDER_COLOR")
+                        .rts()
+                        .build(),
+                )
+                .build(),
+        )
+        .include_vic2_defines()
+        .build()?;
+    ProgramGenerator::default().generate(application)
+}
+
+#[test]
+fn d64_format() -> AssemblerResult<()> {
+    let disk = D64::new("disk 1", "fy")?;
+    assert_eq!(174848, disk.to_bytes().len());
+    assert_eq!(664, disk.blocks_free());
+    assert_eq!(17, disk.free_sectors(DIRECTORY_TRACK));
+    assert!(!disk.is_sector_free(DIRECTORY_TRACK, 0));
+    assert!(!disk.is_sector_free(DIRECTORY_TRACK, 1));
+    assert_eq!("0 \"disk 1          \" fy 2a\n664 blocks free.\n", disk.listing()?);
+
+    let bam = disk.sector(DIRECTORY_TRACK, 0)?;
+    assert_eq!(&[0x12, 0x01, 0x41, 0x00, 0x15, 0xFF, 0xFF, 0x1F], &bam[0..8]);
+    assert_eq!(&[0x11, 0xFC, 0xFF, 0x07], &bam[0x48..0x4C]);
+    assert_eq!(&[0x44, 0x49, 0x53, 0x4B, 0x20, 0x31, 0xA0], &bam[0x90..0x97]);
+
+    let disk = D64::with_tracks(40, "disk 2", "fy")?;
+    assert_eq!(196608, disk.to_bytes().len());
+    assert_eq!(749, disk.blocks_free());
+    assert!(matches!(
+        D64::with_tracks(42, "disk", "fy"),
+        Err(Error::InvalidDiskImage(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn d64_write_and_read() -> AssemblerResult<()> {
+    let program = program()?;
+    let data = (0..600).map(|value| value as u8).collect::<Vec<u8>>();
+    let mut disk = D64::new("disk 1", "fy")?;
+    let entry = disk.write_file(&DiskFile::prg("set black border", &program))?;
+    assert_eq!((17, 0, 1), (entry.track, entry.sector, entry.blocks));
+    disk.write_file(&DiskFile::seq("data", &data))?;
+    disk.write_file(&DiskFile::usr("fast", &data).interleave(1).locked())?;
+    disk.write_file(&DiskFile::usr("empty", &[]))?;
+
+    let directory = disk.directory()?;
+    assert_eq!(
+        vec![
+            ("set black border".to_string(), FileType::Prg, 1),
+            ("data".to_string(), FileType::Seq, 3),
+            ("fast".to_string(), FileType::Usr, 3),
+            ("empty".to_string(), FileType::Usr, 1),
+        ],
+        directory
+            .iter()
+            .map(|entry| (entry.name(), entry.file_type, entry.blocks))
+            .collect::<Vec<_>>()
+    );
+
+    // The sectors of a file are placed using the interleave.
+    assert_eq!(vec![(17, 1), (17, 11), (17, 2)], chain(&disk, "data")?);
+    assert_eq!(vec![(17, 3), (17, 4), (17, 5)], chain(&disk, "fast")?);
+    assert_eq!(656, disk.blocks_free());
+
+    let disk = D64::from_bytes(&disk.to_bytes())?;
+    assert_eq!(program, disk.read_file("set black border")?);
+    assert_eq!(data, disk.read_file("data")?);
+    assert_eq!(data, disk.read_file("fast")?);
+    assert!(disk.read_file("empty")?.is_empty());
+    assert_eq!(
+        "0 \"disk 1          \" fy 2a
+1    \"set black border\" prg
+3    \"data\"             seq
+3    \"fast\"             usr<
+1    \"empty\"            usr
+656 blocks free.
+",
+        disk.listing()?
+    );
+    Ok(())
+}
+
+fn chain(disk: &D64, name: &str) -> DiskResult<Vec<(u8, u8)>> {
+    let entry = disk.find_entry(name)?;
+    disk.sector_chain(entry.track, entry.sector)
+}
+
+#[test]
+fn d64_directory_art() -> AssemblerResult<()> {
+    let mut disk = D64::new("{rvon}  demo  {rvof}", "{$a6}{$a6}")?;
+    disk.write_file(&DiskFile::prg("demo", &program()?))?;
+    for line in 0..10 {
+        disk.write_file(&DiskFile::directory_art(&format!("{{$a6}}{{$a6}}{line}{{$a6}}{{$a6}}")))?;
+    }
+    let directory = disk.directory()?;
+    assert_eq!(11, directory.len());
+    assert_eq!(vec![0xA6, 0xA6, 0x30, 0xA6, 0xA6], directory[1].name);
+    assert_eq!((0, 0), (directory[1].track, directory[1].sector));
+    assert_eq!(FileType::Del, directory[1].file_type);
+
+    // The directory continues on the next sector using the directory interleave.
+    assert_eq!(vec![(18, 1), (18, 4)], disk.sector_chain(DIRECTORY_TRACK, 1)?);
+    assert_eq!(663, disk.blocks_free());
+    assert!(disk
+        .listing()?
+        .starts_with("0 \"{rvon}  demo  {rvof}\" {$a6}{$a6} 2a\n1    \"demo\""));
+    assert!(disk.listing()?.contains("\n0    \"{$a6}{$a6}0{$a6}{$a6}\" del\n"));
+    Ok(())
+}
+
+#[test]
+fn d64_40_tracks() -> AssemblerResult<()> {
+    let mut disk = D64::with_tracks(40, "disk", "fy")?;
+    let data = vec![0xEA; 700 * 254];
+    disk.write_file(&DiskFile::seq("big", &data))?;
+    assert_eq!(49, disk.blocks_free());
+    assert_eq!(0, disk.free_sectors(36));
+    assert_eq!(&[0x00, 0x00, 0x00, 0x00], &disk.sector(DIRECTORY_TRACK, 0)?[0xC0..0xC4]);
+    assert_eq!(&[0x11, 0xFF, 0xFF, 0x01], &disk.sector(DIRECTORY_TRACK, 0)?[0xD0..0xD4]);
+
+    let disk = D64::from_bytes(&disk.to_bytes())?;
+    assert_eq!(40, disk.tracks());
+    assert_eq!(data, disk.read_file("big")?);
+    Ok(())
+}
+
+#[test]
+fn d64_error_bytes() -> AssemblerResult<()> {
+    let mut disk = D64::new("disk", "fy")?;
+    assert_eq!(None, disk.error(1, 0));
+    disk.set_error(35, 16, 0x05);
+    let bytes = disk.to_bytes();
+    assert_eq!(175531, bytes.len());
+    assert_eq!(0x05, bytes[bytes.len() - 1]);
+
+    let disk = D64::from_bytes(&bytes)?;
+    assert_eq!(Some(0x01), disk.error(1, 0));
+    assert_eq!(Some(0x05), disk.error(35, 16));
+    assert!(matches!(D64::from_bytes(&bytes[1..]), Err(Error::InvalidDiskImage(_))));
+    Ok(())
+}
+
+#[test]
+fn d64_errors() -> AssemblerResult<()> {
+    let mut disk = D64::new("disk", "fy")?;
+    disk.write_file(&DiskFile::prg("demo", &[0x01, 0x08]))?;
+    assert!(matches!(
+        disk.write_file(&DiskFile::prg("demo", &[0x01, 0x08])),
+        Err(Error::FileExists(name)) if name == "demo"
+    ));
+    assert!(matches!(disk.read_file("other"), Err(Error::FileNotFound(_))));
+    assert!(matches!(
+        disk.write_file(&DiskFile::prg("a very long file name", &[])),
+        Err(Error::NameTooLong { max_length: 16, .. })
+    ));
+    assert!(matches!(
+        disk.write_file(&DiskFile::seq("big", &vec![0x00; 664 * 254])),
+        Err(Error::DiskFull(_))
+    ));
+    assert_eq!(663, disk.blocks_free());
+
+    for index in 0..143 {
+        disk.write_file(&DiskFile::directory_art(&format!("{index}")))?;
+    }
+    let Err(error) = disk.write_file(&DiskFile::directory_art("full")) else {
+        panic!("directory should be full");
+    };
+    assert_eq!("directory full, no entry left for file 'full'", error.to_string());
+    Ok(())
+}
+
+#[test]
+fn d71_format() -> AssemblerResult<()> {
+    let disk = D71::new("disk 1", "fy")?;
+    assert_eq!(349696, disk.to_bytes().len());
+    assert_eq!(70, disk.tracks());
+    assert_eq!(1328, disk.blocks_free());
+    assert_eq!(0, disk.free_sectors(53));
+
+    let bam = disk.sector(DIRECTORY_TRACK, 0)?;
+    assert_eq!(&[0x12, 0x01, 0x41, 0x80], &bam[0..4]);
+    assert_eq!(&[0x15, 0x15, 0x15], &bam[0xDD..0xE0]);
+    assert_eq!(0x00, bam[0xEE]);
+    assert_eq!(&[0xFF, 0xFF, 0x1F, 0xFF, 0xFF, 0x1F], &disk.sector(53, 0)?[0..6]);
+    assert_eq!("0 \"disk 1          \" fy 2a\n1328 blocks free.\n", disk.listing()?);
+    Ok(())
+}
+
+#[test]
+fn d71_write_and_read() -> AssemblerResult<()> {
+    let program = program()?;
+    let data = (0..700 * 254).map(|value| (value % 251) as u8).collect::<Vec<u8>>();
+    let mut disk = D71::new("disk 1", "fy")?.error_bytes();
+    disk.write_file(&DiskFile::prg("set black border", &program))?;
+    disk.write_file(&DiskFile::seq("big", &data))?;
+    assert_eq!(627, disk.blocks_free());
+    assert!(disk.free_sectors(36) < 21);
+    assert_eq!(disk.free_sectors(36), disk.sector(DIRECTORY_TRACK, 0)?[0xDD]);
+    disk.set_error(70, 16, 0x05);
+
+    let bytes = disk.to_bytes();
+    assert_eq!(351062, bytes.len());
+    let disk = D71::from_bytes(&bytes)?;
+    assert_eq!(Some(0x05), disk.error(70, 16));
+    assert_eq!(program, disk.read_file("set black border")?);
+    assert_eq!(data, disk.read_file("big")?);
+    assert!(matches!(
+        D71::from_bytes(&bytes[..174848]),
+        Err(Error::InvalidDiskImage(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn d81_format() -> AssemblerResult<()> {
+    let disk = D81::new("disk 1", "fy")?;
+    assert_eq!(819200, disk.to_bytes().len());
+    assert_eq!(3160, disk.blocks_free());
+    assert_eq!(36, disk.free_sectors(40));
+
+    let header = disk.sector(40, 0)?;
+    assert_eq!(&[0x28, 0x03, 0x44, 0x00, 0x44, 0x49, 0x53, 0x4B], &header[0..8]);
+    assert_eq!(&[0x46, 0x59, 0xA0, 0x33, 0x44, 0xA0, 0xA0], &header[0x16..0x1D]);
+    let bam = disk.sector(40, 1)?;
+    assert_eq!(&[0x28, 0x02, 0x44, 0xBB, 0x46, 0x59, 0xC0, 0x00], &bam[0..8]);
+    assert_eq!(&[0x28, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF], &bam[0x10..0x16]);
+    assert_eq!(&[0x24, 0xF0, 0xFF, 0xFF, 0xFF, 0xFF], &bam[0xFA..0x100]);
+    assert_eq!(&[0x00, 0xFF, 0x44, 0xBB], &disk.sector(40, 2)?[0..4]);
+    assert_eq!("0 \"disk 1          \" fy 3d\n3160 blocks free.\n", disk.listing()?);
+
+    let disk = D81::new("disk 1", "fy")?.error_bytes();
+    assert_eq!(822400, disk.to_bytes().len());
+    assert_eq!(Some(0x01), disk.error(80, 39));
+    Ok(())
+}
+
+#[test]
+fn d81_write_and_read() -> AssemblerResult<()> {
+    let program = program()?;
+    let data = (0..100 * 254).map(|value| (value % 251) as u8).collect::<Vec<u8>>();
+    let mut disk = D81::new("disk 1", "fy")?;
+    disk.write_file(&DiskFile::prg("set black border", &program))?;
+    disk.write_file(&DiskFile::seq("data", &data))?;
+    let entry = disk.find_entry("data")?;
+    let chain = disk.sector_chain(entry.track, entry.sector)?;
+    assert_eq!(&[(39, 1), (39, 2), (39, 3)], &chain[0..3]);
+    assert_eq!(&[(39, 39), (38, 0)], &chain[38..40]);
+
+    let disk = D81::from_bytes(&disk.to_bytes())?;
+    assert_eq!(program, disk.read_file("set black border")?);
+    assert_eq!(data, disk.read_file("data")?);
+    assert_eq!(3059, disk.blocks_free());
+    Ok(())
+}
+
+#[test]
+fn d81_sub_directories() -> AssemblerResult<()> {
+    let program = program()?;
+    let mut disk = D81::new("disk 1", "fy")?;
+    disk.write_file(&DiskFile::prg("main", &program))?;
+    let partition = disk.create_partition("raw", 1, 2)?;
+    assert_eq!((FileType::Cbm, 80), (partition.file_type, partition.blocks));
+    disk.create_sub_directory("games", 60, 10)?;
+    assert_eq!(3160 - 1 - 80 - 400, disk.blocks_free());
+
+    {
+        let mut games = disk.sub_directory("games")?;
+        assert_eq!(360, games.blocks_free());
+        games.write_file(&DiskFile::prg("game 1", &program))?;
+        let entry = games.find_entry("game 1")?;
+        assert_eq!((59 + 2, 0), (entry.track, entry.sector));
+        games.create_sub_directory("arcade", 65, 3)?;
+        games
+            .sub_directory("arcade")?
+            .write_file(&DiskFile::prg("game 2", &program))?;
+        assert!(matches!(
+            games.create_sub_directory("outside", 70, 3),
+            Err(Error::PartitionUnavailable { track: 70, .. })
+        ));
+        assert_eq!(
+            "0 \"games           \" fy 3d
+1    \"game 1\"           prg
+120  \"arcade\"           cbm
+239 blocks free.
+",
+            games.listing()?
+        );
+    }
+
+    let mut disk = D81::from_bytes(&disk.to_bytes())?;
+    assert_eq!(
+        "0 \"disk 1          \" fy 3d
+1    \"main\"             prg
+80   \"raw\"              cbm
+400  \"games\"            cbm
+2679 blocks free.
+",
+        disk.listing()?
+    );
+    assert_eq!(80 * 256, disk.read_file("raw")?.len());
+    assert_eq!(
+        program,
+        disk.sub_directory("games")?
+            .sub_directory("arcade")?
+            .read_file("game 2")?
+    );
+    assert!(matches!(disk.sub_directory("raw"), Err(Error::InvalidDiskImage(_))));
+    assert!(matches!(
+        disk.create_sub_directory("overlap", 38, 3),
+        Err(Error::PartitionUnavailable { track: 39, .. })
+    ));
+    assert!(matches!(
+        disk.create_partition("too far", 79, 3),
+        Err(Error::PartitionUnavailable { track: 79, .. })
+    ));
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    memory::{expression::Expression, segment::Segment},
+    parser::{DasmParser, Parser},
+    validator::{AssemblerResult, Error},
+    Application, Instructions,
+};
+use c64_assembler_macro::instructions;
+
+fn application(instructions: Instructions) -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Expressions")
+        .entry_point(0x1000)
+        .define_address("screen", 0x0400)
+        .define_address("row", 0x02)
+        .module(ModuleBuilder::default().name("main").instructions(instructions).build())
+        .build()
+}
+
+fn test_instructions() -> Instructions {
+    InstructionBuilder::default()
+        .label("start")
+        .lda_imm_low(Expression::name("table") + Expression::number(0x0100) * 2)
+        .sta_addr(Expression::name("screen") + Expression::number(40) * Expression::name("row"))
+        .ldx_imm_expr(Expression::name("end") - Expression::name("start"))
+        .lda_imm_high(Expression::name("handler") - 1)
+        .pha()
+        .lda_addr_x((Expression::name("screen") >> 8) | 0x20)
+        .ldy_imm_expr(-Expression::number(1))
+        .label("handler")
+        .rts()
+        .label("table")
+        .words_addr(&["handler"])
+        .label("end")
+        .build()
+}
+
+#[test]
+fn evaluate_expressions() -> AssemblerResult<()> {
+    let bytes = ProgramGenerator::default().generate(application(test_instructions())?)?;
+    #[rustfmt::skip]
+    let expected = vec![
+        0x00, 0x10,
+        0xA9, 0x0F,
+        0x8D, 0x50, 0x04,
+        0xA2, 0x11,
+        0xA9, 0x10,
+        0x48,
+        0xB5, 0x24,
+        0xA0, 0xFF,
+        0x60,
+        0x0E, 0x10,
+    ];
+    assert_eq!(expected, bytes);
+    Ok(())
+}
+
+#[test]
+fn dasm_roundtrip() -> AssemblerResult<()> {
+    let expected = ProgramGenerator::default().generate(application(test_instructions())?)?;
+    let source = DasmGenerator::default().generate(application(test_instructions())?)?;
+    assert!(source.contains("  lda #<[table+$0100*2]"));
+    assert!(source.contains("  sta screen+40*row"));
+    assert!(source.contains("  ldx #end-start"));
+    assert!(source.contains("  lda #>[handler-1]"));
+    assert!(source.contains("  lda screen>>8|32,x"));
+    assert!(source.contains("  ldy #-1"));
+
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(expected, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+#[test]
+fn macro_expressions() -> AssemblerResult<()> {
+    let instructions = instructions!(
+    start:
+        lda #<[table+$100*2]
+        sta screen+40*row
+        ldx #[end-start]
+        lda #>[handler-1]
+        pha
+        lda screen>>8|$20,x
+        ldy #-1
+    handler:
+        rts
+    );
+    let mut expected = test_instructions();
+    expected.instructions.truncate(instructions.instructions.len());
+    assert_eq!(expected.instructions, instructions.instructions);
+    Ok(())
+}
+
+#[test]
+fn labels_in_zeropage() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .entry_point(0x1000)
+        .segment(Segment::new("zeropage", 0x02))
+        .module(
+            ModuleBuilder::default()
+                .instructions(
+                    InstructionBuilder::default()
+                        .inc_addr("counter")
+                        .jmp_addr("end")
+                        .label("end")
+                        .build(),
+                )
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .segment("zeropage")
+                .instructions(InstructionBuilder::default().label("counter").raw(&[0x00]).build())
+                .build(),
+        )
+        .build()?;
+    assert_eq!(0x1005, application.lookup_address(&"end".to_string())?);
+    Ok(())
+}
+
+#[test]
+fn expression_out_of_range() {
+    let result = application(
+        InstructionBuilder::default()
+            .lda_imm_expr(Expression::number(0x100))
+            .build(),
+    );
+    assert!(matches!(result, Err(Error::ExpressionOutOfRange { value: 0x100, .. })));
+
+    let result = application(InstructionBuilder::default().lda_ind_y("screen").build());
+    assert!(matches!(result, Err(Error::ExpressionOutOfRange { value: 0x0400, .. })));
+
+    let result = application(
+        InstructionBuilder::default()
+            .lda_addr(Expression::name("screen") - 0x0401)
+            .build(),
+    );
+    assert!(matches!(result, Err(Error::ExpressionOutOfRange { value: -1, .. })));
+}
+
+#[test]
+fn division_by_zero() {
+    let result = application(
+        InstructionBuilder::default()
+            .lda_imm_expr(Expression::number(1) / Expression::number(0))
+            .build(),
+    );
+    assert!(matches!(result, Err(Error::InvalidExpression(_))));
+}
+
+/// Each instruction can only switch to its zeropage op-code after the previous one did, so every
+/// pass moves the labels by one byte.
+fn shrinking_instructions(count: usize) -> AssemblerResult<Application> {
+    let mut instructions = InstructionBuilder::default();
+    for index in 0..count {
+        instructions.lda_addr(format!("data_{index}").as_str());
+    }
+    for index in 0..count {
+        instructions.label(&format!("data_{index}")).raw(&[0x00]);
+    }
+    ApplicationBuilder::default()
+        .entry_point((0xFF - 3 * count) as u16)
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(instructions.build())
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn label_addresses_unstable() -> AssemblerResult<()> {
+    let application = shrinking_instructions(4)?;
+    assert_eq!(0x00F3 + 4 * 2, application.lookup_address(&"data_0".to_string())?);
+
+    let result = shrinking_instructions(16);
+    assert!(matches!(result, Err(Error::LabelAddressesUnstable { passes: 8 })));
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder, DEFAULT_VARIANT},
+    generator::{Generator, ProgramGenerator},
+    validator::{AssemblerResult, Error, Validator},
+    Application, Function, VariantReport,
+};
+use c64_assembler_macro::application;
+
+/// Fill 4 bytes of the screen using a loop, or unrolled for speed.
+fn fill_function() -> Function {
+    FunctionBuilder::default()
+        .name("fill")
+        .instructions(
+            InstructionBuilder::default()
+                .label("fill_entry")
+                .ldx_imm(0x03)
+                .label("fill_loop")
+                .sta_addr_x("SCREEN")
+                .dex()
+                .bpl_addr("fill_loop")
+                .rts()
+                .build(),
+        )
+        .variant(
+            "unrolled",
+            &["speed"],
+            InstructionBuilder::default()
+                .label("fill_entry")
+                .sta_addr("SCREEN")
+                .sta_addr_offs("SCREEN", 1)
+                .sta_addr_offs("SCREEN", 2)
+                .sta_addr_offs("SCREEN", 3)
+                .rts()
+                .build(),
+        )
+        .build()
+}
+
+fn test_application(builder: &mut ApplicationBuilder) -> AssemblerResult<Application> {
+    builder
+        .define_address("SCREEN", 0x0400)
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(InstructionBuilder::default().jsr_addr("fill_entry").rts().build())
+                .function(fill_function())
+                .build(),
+        )
+        .build()
+}
+
+fn selected_variant(application: &Application) -> Option<&str> {
+    application.modules[0].functions[0].variant.as_deref()
+}
+
+#[test]
+fn default_variant() -> AssemblerResult<()> {
+    let application = test_application(&mut ApplicationBuilder::default())?;
+    assert_eq!(None, selected_variant(&application));
+    assert!(application.validate().is_ok());
+    assert_eq!(4 + 9, ProgramGenerator::default().generate(application)?.len() - 2);
+    Ok(())
+}
+
+#[test]
+fn select_variant_by_tag() -> AssemblerResult<()> {
+    let application = test_application(ApplicationBuilder::default().variant_tag("size").variant_tag("speed"))?;
+    assert_eq!(Some("unrolled"), selected_variant(&application));
+    let function = &application.modules[0].functions[0];
+    assert_eq!(DEFAULT_VARIANT, function.variants[0].name);
+    assert!(application.validate().is_ok());
+    assert_eq!(4 + 13, ProgramGenerator::default().generate(application)?.len() - 2);
+    Ok(())
+}
+
+#[test]
+fn select_variant_by_function() -> AssemblerResult<()> {
+    let application = test_application(
+        ApplicationBuilder::default()
+            .variant_tag("speed")
+            .function_variant("fill", DEFAULT_VARIANT),
+    )?;
+    assert_eq!(None, selected_variant(&application));
+    Ok(())
+}
+
+#[test]
+fn select_unknown_variant() {
+    let result = test_application(ApplicationBuilder::default().function_variant("fill", "tiny"));
+    assert!(matches!(
+        result,
+        Err(Error::VariantUnknown { function, variant }) if function == "fill" && variant == "tiny"
+    ));
+
+    let result = test_application(ApplicationBuilder::default().function_variant("clear", "tiny"));
+    assert!(matches!(result, Err(Error::AddressNameUnknown(name)) if name == "clear"));
+}
+
+#[test]
+fn variant_report() -> AssemblerResult<()> {
+    let application = test_application(ApplicationBuilder::default().variant_tag("speed"))?;
+    let report = application.variant_report()?;
+    assert_eq!(
+        vec![
+            VariantReport {
+                module: "main".to_string(),
+                function: "fill".to_string(),
+                variant: "unrolled".to_string(),
+                selected: true,
+                byte_size: 13,
+                min_cycles: 22,
+                max_cycles: 22,
+            },
+            VariantReport {
+                module: "main".to_string(),
+                function: "fill".to_string(),
+                variant: DEFAULT_VARIANT.to_string(),
+                selected: false,
+                byte_size: 9,
+                min_cycles: 17,
+                max_cycles: 18,
+            },
+        ],
+        report
+    );
+    Ok(())
+}
+
+#[test]
+fn variant_entry_label_mismatch() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .function(
+                    FunctionBuilder::default()
+                        .name("init")
+                        .instructions(InstructionBuilder::default().label("init_entry").rts().build())
+                        .variant(
+                            "broken",
+                            &[],
+                            InstructionBuilder::default().label("start").rts().build(),
+                        )
+                        .build(),
+                )
+                .build(),
+        )
+        .build()?;
+    let diagnostics = application.diagnostics();
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(
+        "variation 'broken' of function 'init' doesn't start with the same entry label (module 'main', function 'init')",
+        diagnostics[0].to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn variant_tag_macro() -> AssemblerResult<()> {
+    let application = application!(
+        variant_tag="speed"
+        module!(
+            name="main"
+            instructions!(
+                rts
+            )
+        )
+    )?;
+    assert_eq!(1, application.modules.len());
+    Ok(())
+}
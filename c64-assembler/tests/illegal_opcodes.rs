@@ -0,0 +1,112 @@
+use c64_assembler::{
+    builder::ApplicationBuilder,
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    instruction::operation::Operation,
+    memory::{address_mode::*, label::AddressReference},
+    parser::{DasmParser, Parser, ProgramParser},
+    validator::{AssemblerResult, Validator},
+    Application,
+};
+use c64_assembler_macro::{instructions, module};
+
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Illegal opcodes")
+        .define_address("pointer", 0xFB)
+        .module(module!(
+            name="main"
+            instructions!(
+            main_entry_point:
+                lax data
+                lax data,y
+                sax data
+                dcp data,x
+                isc (pointer),y
+                slo (pointer,x)
+                anc #$0F
+                alr #$F0
+                las data,y
+                rts
+            data:
+            )
+        ))
+        .build()
+}
+
+#[test]
+fn macro_keywords() {
+    let instructions = instructions!(
+        lax data
+        isc data,x
+        sbx #$10
+    );
+    assert_eq!(Operation::LAX, instructions.instructions[0].operation);
+    assert_eq!(
+        AddressMode::AbsoluteX(AddressReference::new("data")),
+        instructions.instructions[1].address_mode
+    );
+    assert_eq!(
+        AddressMode::Immediate(Immediate::Byte(0x10)),
+        instructions.instructions[2].address_mode
+    );
+}
+
+#[test]
+fn generate_program() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let program = ProgramGenerator::default().generate(application)?;
+    assert_eq!(
+        vec![
+            0x00, 0x08, 0xAF, 0x18, 0x08, 0xBF, 0x18, 0x08, 0x8F, 0x18, 0x08, 0xDF, 0x18, 0x08, 0xF3, 0xFB, 0x03, 0xFB,
+            0x0B, 0x0F, 0x4B, 0xF0, 0xBB, 0x18, 0x08, 0x60
+        ],
+        program
+    );
+    Ok(())
+}
+
+#[test]
+fn dasm_mnemonics() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let source = DasmGenerator::default().generate(application.clone())?;
+    assert!(source.contains("  isb (pointer),y"));
+    assert!(source.contains("  asr #$F0"));
+
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(
+        ProgramGenerator::default().generate(application)?,
+        ProgramGenerator::default().generate(parsed)?
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "illegal-opcodes"))]
+#[test]
+fn validate_without_feature() -> AssemblerResult<()> {
+    let application = test_application()?;
+    assert!(
+        matches!(application.validate(), Err(c64_assembler::validator::Error::IllegalInstruction(name)) if name == "lax")
+    );
+
+    // Illegal op-codes are disassembled as data.
+    let application = ProgramParser::default().parse(&[0x00, 0x10, 0xA7, 0xFB, 0x60])?;
+    assert_eq!(
+        Operation::Raw(vec![0xA7, 0xFB, 0x60]),
+        application.modules[0].instructions.instructions[1].operation
+    );
+    Ok(())
+}
+
+#[cfg(feature = "illegal-opcodes")]
+#[test]
+fn validate_with_feature() -> AssemblerResult<()> {
+    let application = test_application()?;
+    application.validate()?;
+
+    let application = ProgramParser::default().parse(&[0x00, 0x10, 0xA7, 0xFB, 0x60])?;
+    assert_eq!(
+        Operation::LAX,
+        application.modules[0].instructions.instructions[1].operation
+    );
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{Generator, ListingGenerator, ProgramGenerator},
+    memory::segment::Segment,
+    validator::AssemblerResult,
+    Application,
+};
+
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Listing")
+        .include_vic2_defines()
+        .segment(Segment::with_max_size("data", 0x2000, 0x0100))
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .add_basic_header()
+                        .label("main_entry_point")
+                        .jsr_addr("clear")
+                        .rts()
+                        .build(),
+                )
+                .function(
+                    FunctionBuilder::default()
+                        .name("clear")
+                        .instructions(
+                            InstructionBuilder::default()
+                                .ldx_imm(0x00)
+                                .comment("Start with black")
+                                .comment("and count down")
+                                .label("loop")
+                                .stx_addr("VIC2_BORDER_COLOR")
+                                .dex()
+                                .bne_addr("loop")
+                                .rts()
+                                .build(),
+                        )
+                        .build(),
+                )
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("tables")
+                .segment("data")
+                .instructions(
+                    InstructionBuilder::default()
+                        .label("table")
+                        .raw(&[0x01, 0x02, 0x03, 0x04, 0x05])
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn listing() -> AssemblerResult<()> {
+    let listing = ListingGenerator::default().generate(test_application()?)?;
+    let expected = "; --- Application: LISTING ---
+
+; --- Segment: default $0800 ---
+
+; --- Module: MAIN ---
+0800  00 0C 08           byte $00, $0C, $08  ; New basic line
+0803  0A 00 9E           byte $0A, $00, $9E, $20, $32, $30, $36, $32 ; 10 SYS 2062
+0806  20 32 30
+0809  36 32
+080B  00 00 00           byte $00, $00, $00  ; End basic program
+080E                   main_entry_point:
+080E  20 12 08  [6]      jsr clear
+0811  60        [6]      rts
+
+; --- Function: CLEAR ---
+0812                   clear:
+0812  A2 00     [2]      ldx #$00            ; Start with black
+                                             ; and count down
+0814                   loop:
+0814  8E 20 D0  [4]      stx VIC2_BORDER_COLOR
+0817  CA        [2]      dex
+0818  D0 FA     [2-3]    bne loop
+081A  60        [6]      rts
+
+; --- Segment: data $2000 ---
+
+; --- Module: TABLES ---
+2000                   table:
+2000  01 02 03           byte $01, $02, $03, $04, $05
+2003  04 05
+
+; --- Segments ---
+; default      $0800-$081A     27 bytes used,  6117 bytes free
+; data         $2000-$2004      5 bytes used,   251 bytes free";
+    assert_eq!(expected, listing);
+    Ok(())
+}
+
+/// Every byte in the listing must match the generated program.
+#[test]
+fn listing_matches_program() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let listing = ListingGenerator::default().generate(application.clone())?;
+    let program = ProgramGenerator::default().generate(application)?;
+
+    let mut bytes_checked = 0;
+    for line in listing
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with(';') && !line.starts_with(' '))
+    {
+        let address = u16::from_str_radix(&line[0..4], 16).unwrap();
+        let bytes = line[6..line.len().min(14)]
+            .split_whitespace()
+            .map(|byte| u8::from_str_radix(byte, 16).unwrap())
+            .collect::<Vec<u8>>();
+        let offset = 2 + (address - 0x0800) as usize;
+        assert_eq!(&program[offset..offset + bytes.len()], bytes.as_slice(), "{line}");
+        bytes_checked += bytes.len();
+    }
+    assert_eq!(27 + 5, bytes_checked);
+    Ok(())
+}
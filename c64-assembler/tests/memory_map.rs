@@ -0,0 +1,185 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{Generator, MemoryMapFormat, MemoryMapGenerator},
+    memory::{memory_map::ForbiddenRegion, segment::Segment},
+    validator::{AssemblerResult, Error, Validator},
+    Application,
+};
+
+/// Main module with a data segment that starts inside the main module and a music segment in
+/// the I/O area.
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .segment(Segment::new("data", 0x0802))
+        .segment(Segment::new("music", 0xCFFE))
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(InstructionBuilder::default().jsr_addr("play").rts().build())
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("data")
+                .segment("data")
+                .instructions(InstructionBuilder::default().raw(&[0x00; 4]).build())
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("music")
+                .segment("music")
+                .function(
+                    FunctionBuilder::default()
+                        .name("play")
+                        .instructions(InstructionBuilder::default().lda_imm(0x00).rts().build())
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn memory_map_ranges() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let memory_map = application.memory_map()?;
+    let ranges = memory_map
+        .ranges
+        .iter()
+        .map(|range| (range.scoped_name(), range.start, range.last()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            ("main".to_string(), 0x0800, 0x0803),
+            ("data".to_string(), 0x0802, 0x0805),
+            ("music.play".to_string(), 0xCFFE, 0xD000),
+        ],
+        ranges
+    );
+    Ok(())
+}
+
+#[test]
+fn memory_map_diagnostics() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let diagnostics = application
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        vec![
+            "'main' and 'data' overlap at $0802 (module 'data')",
+            "code or data is placed in the I/O region at $D000 (module 'music', function 'play')",
+        ],
+        diagnostics
+    );
+    Ok(())
+}
+
+/// Relocated segments are mapped at the address where they run.
+#[test]
+fn memory_map_relocated_segment() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .segment(Segment::relocated("irq", 0x0803, 0xC000))
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(InstructionBuilder::default().jmp_addr("irq").build())
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("irq")
+                .segment("irq")
+                .instructions(InstructionBuilder::default().label("irq").lda_imm(0x00).rts().build())
+                .build(),
+        )
+        .build()?;
+    let memory_map = application.memory_map()?;
+    let ranges = memory_map
+        .ranges
+        .iter()
+        .map(|range| (range.scoped_name(), range.start, range.last()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            ("main".to_string(), 0x0800, 0x0802),
+            ("irq".to_string(), 0xC000, 0xC002)
+        ],
+        ranges
+    );
+    assert!(memory_map.errors().is_empty());
+    Ok(())
+}
+
+#[test]
+fn memory_map_configure_regions() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .allow_region("I/O")
+        .forbidden_region(ForbiddenRegion::new("screen", 0x0400, 0x07FF))
+        .segment(Segment::new("music", 0xD000))
+        .entry_point(0x07FF)
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().jsr_addr("music").rts().build())
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .segment("music")
+                .instructions(InstructionBuilder::default().label("music").rts().build())
+                .build(),
+        )
+        .build()?;
+    assert!(matches!(
+        application.validate(),
+        Err(Error::ForbiddenRegion { region, address: 0x07FF }) if region == "screen"
+    ));
+    assert_eq!(1, application.diagnostics().len());
+    Ok(())
+}
+
+#[test]
+fn memory_map_text() -> AssemblerResult<()> {
+    let report = MemoryMapGenerator::default().generate(test_application()?)?;
+    assert_eq!(
+        "; --- Memory map ---
+$0800-$0803      4  default      main
+$0802-$0805      4  data         data
+$CFFE-$D000      3  music        music.play
+
+; --- Problems ---
+; 'main' and 'data' overlap at $0802
+; code or data is placed in the I/O region at $D000
+",
+        report
+    );
+    Ok(())
+}
+
+#[test]
+fn memory_map_json() -> AssemblerResult<()> {
+    let report = MemoryMapGenerator::default()
+        .format(MemoryMapFormat::Json)
+        .generate(test_application()?)?;
+    assert_eq!(
+        r#"{
+  "ranges": [
+    {"module": "main", "function": null, "segment": "default", "start": 2048, "end": 2051, "byte_size": 4},
+    {"module": "data", "function": null, "segment": "data", "start": 2050, "end": 2053, "byte_size": 4},
+    {"module": "music", "function": "play", "segment": "music", "start": 53246, "end": 53248, "byte_size": 3}
+  ],
+  "overlaps": [
+    {"first": "main", "second": "data", "address": 2050}
+  ],
+  "forbidden_regions": [
+    {"name": "music.play", "region": "I/O", "address": 53248}
+  ]
+}
+"#,
+        report
+    );
+    Ok(())
+}
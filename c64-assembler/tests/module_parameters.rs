@@ -0,0 +1,150 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    memory::expression::Expression,
+    parser::{DasmParser, Parser},
+    validator::{AssemblerResult, Error, Validator},
+    Module, ParameterKind,
+};
+
+/// Copies the sprite positions from a table to the VIC2 registers.
+fn multiplexer() -> Module {
+    ModuleBuilder::default()
+        .name("multiplexer")
+        .parameter("SPRITE_TABLE", ParameterKind::Address)
+        .parameter("SPRITE_INDEX", ParameterKind::Zeropage)
+        .parameter("SPRITE_COUNT", ParameterKind::Constant)
+        .function(
+            FunctionBuilder::default()
+                .name("update")
+                .instructions(
+                    InstructionBuilder::default()
+                        .ldx_imm_expr(Expression::name("SPRITE_COUNT"))
+                        .label("update_loop")
+                        .lda_addr_x("SPRITE_TABLE")
+                        .sta_addr_x("VIC2_SPRITE_0_X")
+                        .stx_addr("SPRITE_INDEX")
+                        .dex()
+                        .bpl_addr("update_loop")
+                        .rts()
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+fn main_module() -> Module {
+    ModuleBuilder::default()
+        .name("main")
+        .instructions(
+            InstructionBuilder::default()
+                .jsr_addr("upper_update")
+                .jsr_addr("lower_update")
+                .rts()
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn include_module_twice() -> AssemblerResult<()> {
+    let multiplexer = multiplexer();
+    let application = ApplicationBuilder::default()
+        .include_vic2_defines()
+        .module(main_module())
+        .module_instance(
+            "upper",
+            &multiplexer,
+            &[("SPRITE_TABLE", 0x3000), ("SPRITE_INDEX", 0xFB), ("SPRITE_COUNT", 7)],
+        )
+        .module_instance(
+            "lower",
+            &multiplexer,
+            &[("SPRITE_TABLE", 0x3100), ("SPRITE_INDEX", 0xFC), ("SPRITE_COUNT", 3)],
+        )
+        .build()?;
+    assert!(application.validate().is_ok());
+    assert_eq!("upper_multiplexer", application.modules[1].name);
+    assert_eq!("lower_update", application.modules[2].functions[0].name);
+    assert_eq!(0x0807, application.lookup_address(&"upper_update".to_string())?);
+    assert_eq!(0x0815, application.lookup_address(&"lower_update".to_string())?);
+    assert_eq!(0x0809, application.lookup_address(&"upper_update_loop".to_string())?);
+
+    let program = ProgramGenerator::default().generate(application.clone())?;
+    assert_eq!(
+        [0xA2, 0x03, 0xBD, 0x00, 0x31, 0x9D, 0x00, 0xD0, 0x86, 0xFC, 0xCA, 0x10, 0xF5, 0x60],
+        program[2 + 0x15..]
+    );
+
+    let source = DasmGenerator::default().generate(application)?;
+    assert!(source.contains("upper_SPRITE_TABLE = $3000"));
+    assert!(source.contains("lower_SPRITE_INDEX = $FC"));
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(program, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+#[test]
+fn module_parameters_defined_by_application() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .include_vic2_defines()
+        .define_address("SPRITE_TABLE", 0x3000)
+        .define_address("SPRITE_INDEX", 0xFB)
+        .define_address("SPRITE_COUNT", 7)
+        .module(multiplexer())
+        .build()?;
+    assert_eq!(0x0800, application.lookup_address(&"update".to_string())?);
+    Ok(())
+}
+
+#[test]
+fn module_parameter_unbound() {
+    let result = ApplicationBuilder::default()
+        .include_vic2_defines()
+        .module_instance(
+            "upper",
+            &multiplexer(),
+            &[("SPRITE_TABLE", 0x3000), ("SPRITE_INDEX", 0xFB)],
+        )
+        .build();
+    let Err(error) = result else {
+        panic!("expected an error");
+    };
+    assert_eq!(
+        "parameter 'SPRITE_COUNT' of module 'upper_multiplexer' isn't bound",
+        error.to_string()
+    );
+
+    let result = ApplicationBuilder::default().module(multiplexer()).build();
+    assert!(matches!(
+        result,
+        Err(Error::ModuleParameterUnbound { parameter, .. }) if parameter == "SPRITE_TABLE"
+    ));
+}
+
+#[test]
+fn module_parameter_unknown() {
+    let result = ApplicationBuilder::default()
+        .module_instance("upper", &multiplexer(), &[("SPRITE_TABEL", 0x3000)])
+        .build();
+    assert!(matches!(
+        result,
+        Err(Error::ModuleParameterUnknown { parameter, .. }) if parameter == "SPRITE_TABEL"
+    ));
+}
+
+#[test]
+fn module_parameter_not_zeropage() {
+    let result = ApplicationBuilder::default()
+        .module_instance(
+            "upper",
+            &multiplexer(),
+            &[("SPRITE_TABLE", 0x3000), ("SPRITE_INDEX", 0x02FB), ("SPRITE_COUNT", 7)],
+        )
+        .build();
+    assert!(matches!(
+        result,
+        Err(Error::ModuleParameterNotZeropage { parameter, value: 0x02FB, .. }) if parameter == "SPRITE_INDEX"
+    ));
+}
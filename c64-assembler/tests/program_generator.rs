@@ -51,3 +51,21 @@ fn program_v0_1_0_zeropage_y_instructions() -> AssemblerResult<()> {
 
     Ok(())
 }
+
+/// The and (indirect),y instruction was assembled as and (indirect,x).
+#[test]
+fn program_v0_2_0_and_indirect_indexed() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .define_address("POINTER", 0xFB)
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().and_ind_y("POINTER").build())
+                .build(),
+        )
+        .build()?;
+    application.validate()?;
+    let program = ProgramGenerator::default().generate(application)?;
+    assert_eq!([0x00, 0x08, 0x31, 0xFB], program.as_slice());
+
+    Ok(())
+}
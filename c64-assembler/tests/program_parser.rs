@@ -0,0 +1,177 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    instruction::operation::Operation,
+    memory::{address_mode::AddressMode, label::AddressReference},
+    parser::{DasmParser, Parser, ProgramParser},
+    validator::{AssemblerResult, Error},
+};
+
+fn test_program() -> AssemblerResult<Vec<u8>> {
+    let application = ApplicationBuilder::default()
+        .name("Disassembler")
+        .include_vic2_defines()
+        .define_address("POINTER", 0xFB)
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .add_basic_header()
+                        .label("main_entry_point")
+                        .ldx_imm(0x00)
+                        .label("loop")
+                        .lda_addr_x("data")
+                        .sta_addr_x("VIC2_SPRITE_0_X")
+                        .inx()
+                        .cpx_imm(0x03)
+                        .bne_addr("loop")
+                        .lda_imm_low("data")
+                        .sta_addr("POINTER")
+                        .lda_imm_high("data")
+                        .sta_addr_offs("POINTER", 1)
+                        .ldy_imm(0x00)
+                        .lda_ind_y("POINTER")
+                        .ldx_addr_y("POINTER")
+                        .jsr_addr("sub_routine")
+                        .jmp_ind("vector")
+                        .label("sub_routine")
+                        .asl_acc()
+                        .rts()
+                        .label("vector")
+                        .raw(&[0x0D, 0x08])
+                        .label("data")
+                        .raw(&[0x01, 0x02, 0x03, 0xFF])
+                        .build(),
+                )
+                .build(),
+        )
+        .build()?;
+    ProgramGenerator::default().generate(application)
+}
+
+#[test]
+fn disassemble_roundtrip_program() -> AssemblerResult<()> {
+    let program = test_program()?;
+    let application = ProgramParser::default().parse(&program)?;
+    assert_eq!(0x0800, application.entry_point);
+    assert_eq!(program, ProgramGenerator::default().generate(application)?);
+    Ok(())
+}
+
+#[test]
+fn disassemble_roundtrip_dasm() -> AssemblerResult<()> {
+    let program = test_program()?;
+    let application = ProgramParser::default().parse(&program)?;
+    let source = DasmGenerator::default().generate(application)?;
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(program, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+#[test]
+fn disassemble_basic_sys_entry_point() -> AssemblerResult<()> {
+    let program = test_program()?;
+    let application = ProgramParser::default().parse(&program)?;
+    let instructions = &application.modules[0].instructions.instructions;
+
+    // BASIC header is kept as data, the code starts at the SYS address (2062).
+    assert!(matches!(instructions[0].operation, Operation::Raw(_)));
+    assert_eq!(Operation::Label("label_080E".to_string()), instructions[1].operation);
+    assert_eq!(Operation::LDX, instructions[2].operation);
+    assert_eq!(0x080E, application.lookup_address(&"label_080E".to_string())?);
+
+    // Addresses outside the program are added as defines.
+    assert_eq!(0xD000, application.lookup_address(&"ADDR_D000".to_string())?);
+    assert_eq!(0x00FB, application.lookup_address(&"ADDR_00FB".to_string())?);
+    Ok(())
+}
+
+#[test]
+fn disassemble_data_after_code() -> AssemblerResult<()> {
+    let program = [0x00, 0x10, 0xAD, 0x04, 0x10, 0x60, 0x42, 0x43];
+    let application = ProgramParser::default().parse(&program)?;
+    let instructions = &application.modules[0].instructions.instructions;
+    assert_eq!(
+        AddressMode::Absolute(AddressReference::new("label_1004")),
+        instructions[1].address_mode
+    );
+    assert_eq!(Operation::Raw(vec![0x42, 0x43]), instructions[4].operation);
+    assert_eq!(program.to_vec(), ProgramGenerator::default().generate(application)?);
+    Ok(())
+}
+
+/// Self modifying code refers to addresses inside instructions.
+#[test]
+fn disassemble_self_modifying_code() -> AssemblerResult<()> {
+    // inc $1004; lda #$00; rts
+    let program = [0x00, 0x10, 0xEE, 0x04, 0x10, 0xA9, 0x00, 0x60];
+    let application = ProgramParser::default().parse(&program)?;
+    let instructions = &application.modules[0].instructions.instructions;
+    assert_eq!(
+        AddressMode::Absolute(AddressReference::with_offset("label_1003", 1)),
+        instructions[1].address_mode
+    );
+    assert_eq!(program.to_vec(), ProgramGenerator::default().generate(application)?);
+    Ok(())
+}
+
+/// Absolute addressing of zeropage addresses cannot be reproduced and is kept as raw bytes.
+#[test]
+fn disassemble_absolute_zeropage() -> AssemblerResult<()> {
+    // lda $0002; ldx $0002,y; rts
+    let program = [0x00, 0x10, 0xAD, 0x02, 0x00, 0xBE, 0x02, 0x00, 0x60];
+    let application = ProgramParser::default().parse(&program)?;
+    let instructions = &application.modules[0].instructions.instructions;
+    assert_eq!(Operation::Raw(vec![0xAD, 0x02, 0x00]), instructions[1].operation);
+    assert_eq!(Operation::Raw(vec![0xBE, 0x02, 0x00]), instructions[2].operation);
+    assert_eq!(program.to_vec(), ProgramGenerator::default().generate(application)?);
+
+    // lda $0002,y has no zeropage variant.
+    let program = [0x00, 0x10, 0xB9, 0x02, 0x00, 0x60];
+    let application = ProgramParser::default().parse(&program)?;
+    assert_eq!(program.to_vec(), ProgramGenerator::default().generate(application)?);
+    Ok(())
+}
+
+#[test]
+fn disassemble_invalid_program() {
+    assert!(matches!(
+        ProgramParser::default().parse(&[0x00]),
+        Err(Error::InvalidProgram(_))
+    ));
+}
+
+/// A SYS address that doesn't fit in an address isn't used as entry point.
+#[test]
+fn disassemble_basic_sys_overflow() -> AssemblerResult<()> {
+    // 10 SYS 123456789012345
+    let mut program = vec![0x01, 0x08, 0x16, 0x08, 0x0A, 0x00, 0x9E, b' '];
+    program.extend(b"123456789012345");
+    program.extend([0x00, 0x00, 0x00]);
+    let application = ProgramParser::default().parse(&program)?;
+    assert_eq!(program, ProgramGenerator::default().generate(application)?);
+    Ok(())
+}
+
+/// A program can use the last byte of memory, but not more.
+#[test]
+fn disassemble_end_of_memory() -> AssemblerResult<()> {
+    // lda #$00; nop; rts
+    let program = [0xFC, 0xFF, 0xA9, 0x00, 0xEA, 0x60];
+    let application = ProgramParser::default().parse(&program)?;
+    assert_eq!(program.to_vec(), ProgramGenerator::default().generate(application)?);
+
+    // lda #$00; rts followed by data
+    let program = [0xFC, 0xFF, 0xA9, 0x00, 0x60, 0x42];
+    let application = ProgramParser::default().parse(&program)?;
+    let instructions = &application.modules[0].instructions.instructions;
+    assert_eq!(Operation::Raw(vec![0x42]), instructions[3].operation);
+    assert_eq!(program.to_vec(), ProgramGenerator::default().generate(application)?);
+
+    assert!(matches!(
+        ProgramParser::default().parse(&[0xFC, 0xFF, 0xA9, 0x00, 0xEA, 0x60, 0x00]),
+        Err(Error::InvalidProgram(_))
+    ));
+    Ok(())
+}
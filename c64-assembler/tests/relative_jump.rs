@@ -1,9 +1,21 @@
 use c64_assembler::{
-    generator::{print_hexdump, Generator, ProgramGenerator},
-    validator::AssemblerResult,
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{print_hexdump, DasmGenerator, Generator, ProgramGenerator},
+    validator::{AssemblerResult, Error, Validator},
+    Application, Instructions,
 };
 use c64_assembler_macro::application;
 
+fn application(instructions: Instructions, fix_out_of_range_branches: bool) -> AssemblerResult<Application> {
+    let mut builder = ApplicationBuilder::default();
+    if fix_out_of_range_branches {
+        builder.fix_out_of_range_branches();
+    }
+    builder
+        .module(ModuleBuilder::default().name("main").instructions(instructions).build())
+        .build()
+}
+
 /// Relative jumps are calculated from the start of the next instruction.
 #[test]
 fn relative_jump() -> AssemblerResult<()> {
@@ -24,3 +36,127 @@ fn relative_jump() -> AssemblerResult<()> {
     assert_eq!(&[0x00, 0x08, 0xD0, 0x01, 0x60], bytes.as_slice());
     Ok(())
 }
+
+/// Branches can reach -128 bytes back and 127 bytes forward from the next instruction.
+#[test]
+fn relative_jump_range() -> AssemblerResult<()> {
+    let application = application(
+        InstructionBuilder::default()
+            .label("back")
+            .fill(126, 0xEA)
+            .bne_addr("back")
+            .beq_addr("forward")
+            .fill(127, 0xEA)
+            .label("forward")
+            .build(),
+        false,
+    )?;
+    assert!(application.diagnostics().is_empty());
+
+    let bytes = ProgramGenerator::default().generate(application)?;
+    assert_eq!(&[0xD0, 0x80, 0xF0, 0x7F], &bytes[128..132]);
+    Ok(())
+}
+
+#[test]
+fn relative_jump_out_of_range() -> AssemblerResult<()> {
+    let application = application(
+        InstructionBuilder::default()
+            .label("back")
+            .fill(127, 0xEA)
+            .bne_addr("back")
+            .beq_addr("forward")
+            .fill(128, 0xEA)
+            .label("forward")
+            .build(),
+        false,
+    )?;
+
+    let diagnostics = application.diagnostics();
+    assert_eq!(2, diagnostics.len());
+    assert!(matches!(
+        &diagnostics[0].error,
+        Error::BranchOutOfRange { target, distance: -129 } if target == "back"
+    ));
+    assert_eq!(Some(2), diagnostics[0].location.instruction_index);
+    assert_eq!(
+        "branch to 'forward' is 128 bytes away, only -128..=127 bytes can be reached (module 'main', instruction 3)",
+        diagnostics[1].to_string()
+    );
+
+    let result = ProgramGenerator::default().generate(application);
+    assert!(matches!(result, Err(Error::BranchOutOfRange { distance: -129, .. })));
+    Ok(())
+}
+
+#[test]
+fn relative_jump_fix_out_of_range() -> AssemblerResult<()> {
+    let application = application(
+        InstructionBuilder::default()
+            .label("back")
+            .fill(127, 0xEA)
+            .bne_addr("back")
+            .bcc_addr("back")
+            .rts()
+            .build(),
+        true,
+    )?;
+    assert!(application.diagnostics().is_empty());
+
+    let source = DasmGenerator::default().generate(application.clone())?;
+    assert!(source.contains("  beq branch_fixup_0\n  jmp back\n\nbranch_fixup_0:\n  bcs branch_fixup_1\n"));
+
+    let bytes = ProgramGenerator::default().generate(application)?;
+    #[rustfmt::skip]
+    assert_eq!(
+        &[
+            0xF0, 0x03,
+            0x4C, 0x00, 0x08,
+            0xB0, 0x03,
+            0x4C, 0x00, 0x08,
+            0x60,
+        ],
+        &bytes[129..]
+    );
+    Ok(())
+}
+
+/// Fixing a branch moves the instructions after it, which can push other branches out of range.
+#[test]
+fn relative_jump_fix_cascade() -> AssemblerResult<()> {
+    let application = application(
+        InstructionBuilder::default()
+            .label("back")
+            .fill(100, 0xEA)
+            .bcc_addr("forward")
+            .fill(26, 0xEA)
+            .bne_addr("back")
+            .fill(97, 0xEA)
+            .label("forward")
+            .build(),
+        true,
+    )?;
+    assert!(application.diagnostics().is_empty());
+
+    let bytes = ProgramGenerator::default().generate(application)?;
+    assert_eq!(&[0xB0, 0x03, 0x4C, 0xE9, 0x08], &bytes[102..107]);
+    assert_eq!(&[0xF0, 0x03, 0x4C, 0x00, 0x08], &bytes[133..138]);
+    Ok(())
+}
+
+#[test]
+fn relative_jump_fix_macro() -> AssemblerResult<()> {
+    let application = application!(
+        fix_out_of_range_branches
+        module!(
+            name="main"
+            instructions!(
+        back:
+            bne back
+            )
+        )
+    )?;
+    let bytes = ProgramGenerator::default().generate(application)?;
+    assert_eq!(&[0x00, 0x08, 0xD0, 0xFE], bytes.as_slice());
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    parser::{DasmParser, Parser},
+    validator::{AssemblerResult, Error, Validator},
+    Application, Function, Instructions,
+};
+use c64_assembler_macro::{application, instructions};
+
+fn function(name: &str, instructions: Instructions) -> Function {
+    FunctionBuilder::default().name(name).instructions(instructions).build()
+}
+
+/// Two functions and the module use the same local label names.
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .define_address("SCREEN", 0x0400)
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .jsr_addr("clear")
+                        .jsr_addr("fill")
+                        .local_label("done")
+                        .rts()
+                        .build(),
+                )
+                .function(function(
+                    "clear",
+                    InstructionBuilder::default()
+                        .ldx_imm(0x00)
+                        .txa()
+                        .local_label("loop")
+                        .sta_addr_x("SCREEN")
+                        .dex()
+                        .bne_addr(".loop")
+                        .jmp_addr(".done")
+                        .build(),
+                ))
+                .function(function(
+                    "fill",
+                    InstructionBuilder::default()
+                        .ldx_imm(0x08)
+                        .local_label("loop")
+                        .sta_addr_x("SCREEN")
+                        .local_label("done")
+                        .dex()
+                        .bpl_addr(".loop")
+                        .bmi_addr(".done")
+                        .build(),
+                ))
+                .build(),
+        )
+        .build()
+}
+
+fn lookup(application: &Application, name: &str) -> AssemblerResult<u16> {
+    application.lookup_address(&name.to_string())
+}
+
+#[test]
+fn local_labels() -> AssemblerResult<()> {
+    let application = test_application()?;
+    assert!(application.validate().is_ok());
+    assert_eq!(0x0806, lookup(&application, "main__done")?);
+    assert_eq!(0x080A, lookup(&application, "clear__loop")?);
+    assert_eq!(0x0815, lookup(&application, "fill__loop")?);
+    // The function-local label is used before the module-local label with the same name.
+    assert_eq!(0x0818, lookup(&application, "fill__done")?);
+    assert!(lookup(&application, ".loop").is_err());
+
+    let function = &application.modules[0].functions[0];
+    assert_eq!(Some(&".loop".to_string()), function.local_labels.get("clear__loop"));
+    Ok(())
+}
+
+#[test]
+fn local_labels_dasm() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let program = ProgramGenerator::default().generate(application.clone())?;
+    let source = DasmGenerator::default().generate(application)?;
+    assert!(source.contains("clear:\n  SUBROUTINE\n"));
+    assert!(source.contains("\n.loop:\n"));
+    assert!(source.contains("  bne .loop\n"));
+    // Module-local labels are visible in all functions and keep their unique name.
+    assert!(source.contains("  jmp main__done\n"));
+
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(program, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+#[test]
+fn anonymous_labels() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .anonymous_label()
+                        .ldx_imm(0x00)
+                        .anonymous_label()
+                        .dex()
+                        .bne_addr("-")
+                        .beq_addr("++")
+                        .jmp_addr("--")
+                        .anonymous_label()
+                        .jmp_addr("+")
+                        .anonymous_label()
+                        .rts()
+                        .build(),
+                )
+                .build(),
+        )
+        .build()?;
+    assert!(application.validate().is_ok());
+    let program = ProgramGenerator::default().generate(application)?;
+    assert_eq!(
+        vec![0x00, 0x08, 0xA2, 0x00, 0xCA, 0xD0, 0xFD, 0xF0, 0x06, 0x4C, 0x00, 0x08, 0x4C, 0x0D, 0x08, 0x60],
+        program
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_local_label() {
+    let result = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .function(function(
+                    "clear",
+                    InstructionBuilder::default().local_label("loop").rts().build(),
+                ))
+                .function(function(
+                    "fill",
+                    InstructionBuilder::default().jmp_addr(".loop").build(),
+                ))
+                .build(),
+        )
+        .build();
+    assert!(matches!(result, Err(Error::AddressNameUnknown(name)) if name == ".loop"));
+
+    let result = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().anonymous_label().beq_addr("--").build())
+                .build(),
+        )
+        .build();
+    assert!(matches!(result, Err(Error::AddressNameUnknown(name)) if name == "--"));
+}
+
+#[test]
+fn local_labels_module_instances() -> AssemblerResult<()> {
+    let module = ModuleBuilder::default()
+        .name("wait")
+        .function(function(
+            "wait",
+            InstructionBuilder::default()
+                .local_label("loop")
+                .dex()
+                .bne_addr(".loop")
+                .rts()
+                .build(),
+        ))
+        .build();
+    let application = ApplicationBuilder::default()
+        .module_instance("first", &module, &[])
+        .module_instance("second", &module, &[])
+        .build()?;
+    assert!(application.validate().is_ok());
+    assert_eq!(0x0800, lookup(&application, "first_wait__loop")?);
+    assert_eq!(0x0804, lookup(&application, "second_wait__loop")?);
+    Ok(())
+}
+
+#[test]
+fn scoped_labels_macro() -> AssemblerResult<()> {
+    let macro_instructions = instructions!(
+        ldx #$08
+    .loop:
+        dex
+        bne .loop
+    -:
+        lda VIC2_RASTER
+        bne -
+        beq +
+        nop
+    +:
+        rts
+    );
+    let builder_instructions = InstructionBuilder::default()
+        .ldx_imm(0x08)
+        .local_label("loop")
+        .dex()
+        .bne_addr(".loop")
+        .anonymous_label()
+        .lda_addr("VIC2_RASTER")
+        .bne_addr("-")
+        .beq_addr("+")
+        .nop()
+        .label("+")
+        .rts()
+        .build();
+    assert_eq!(builder_instructions.instructions, macro_instructions.instructions);
+
+    let application = application!(
+        include_vic2_defines
+        module!(
+            name="main"
+            instructions!(
+            -:
+                dex
+                bne -
+                rts
+            )
+        )
+    )?;
+    assert!(application.validate().is_ok());
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator, SegmentProgramGenerator},
+    memory::segment::Segment,
+    parser::{DasmParser, Parser},
+    validator::{AssemblerResult, Error},
+    Application,
+};
+
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Segments")
+        .entry_point(0x0800)
+        .segment(Segment::new("data", 0x0810))
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .lda_addr("table")
+                        .jsr_addr("copy")
+                        .rts()
+                        .build(),
+                )
+                .function(
+                    FunctionBuilder::default()
+                        .name("copy")
+                        .instructions(InstructionBuilder::default().ldx_imm_low("table").rts().build())
+                        .build(),
+                )
+                .function(
+                    FunctionBuilder::default()
+                        .name("copy_table")
+                        .segment("data")
+                        .instructions(InstructionBuilder::default().raw(&[0x60]).build())
+                        .build(),
+                )
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("tables")
+                .segment("data")
+                .instructions(InstructionBuilder::default().label("table").raw(&[0x01, 0x02]).build())
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn labels_resolve_across_segments() -> AssemblerResult<()> {
+    let application = test_application()?;
+    assert_eq!(0x0807, application.lookup_address(&"copy".to_string())?);
+    assert_eq!(0x0810, application.lookup_address(&"copy_table".to_string())?);
+    assert_eq!(0x0811, application.lookup_address(&"table".to_string())?);
+    Ok(())
+}
+
+#[test]
+fn program_pads_gaps_between_segments() -> AssemblerResult<()> {
+    let bytes = ProgramGenerator::default()
+        .fill_byte(0xEA)
+        .generate(test_application()?)?;
+    #[rustfmt::skip]
+    let expected = vec![
+        0x00, 0x08,
+        0xAD, 0x11, 0x08, 0x20, 0x07, 0x08, 0x60, 0xA2, 0x11, 0x60,
+        0xEA, 0xEA, 0xEA, 0xEA, 0xEA, 0xEA,
+        0x60, 0x01, 0x02,
+    ];
+    assert_eq!(expected, bytes);
+    Ok(())
+}
+
+#[test]
+fn program_per_segment() -> AssemblerResult<()> {
+    let programs = SegmentProgramGenerator::default().generate(test_application()?)?;
+    assert_eq!(2, programs.len());
+    assert_eq!("default", programs[0].segment);
+    assert_eq!(12, programs[0].bytes.len());
+    assert_eq!("data", programs[1].segment);
+    assert_eq!(vec![0x10, 0x08, 0x60, 0x01, 0x02], programs[1].bytes);
+    Ok(())
+}
+
+#[test]
+fn dasm_roundtrip() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let expected = ProgramGenerator::default().generate(test_application()?)?;
+    let source = DasmGenerator::default().generate(application)?;
+    assert!(source.contains("  seg data\n  org $0810"));
+
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(2, parsed.modules.len());
+    assert_eq!(Some("data".to_string()), parsed.modules[0].functions[1].segment);
+    assert_eq!(expected, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+#[test]
+fn segment_exceeds_max_size() {
+    let result = ApplicationBuilder::default()
+        .segment(Segment::with_max_size("data", 0x1000, 1))
+        .module(
+            ModuleBuilder::default()
+                .segment("data")
+                .instructions(InstructionBuilder::default().raw(&[0x01, 0x02]).build())
+                .build(),
+        )
+        .build();
+    assert!(matches!(
+        result,
+        Err(Error::SegmentOverflow {
+            size: 2,
+            max_size: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn segment_unknown() {
+    let result = ApplicationBuilder::default()
+        .module(ModuleBuilder::default().segment("music").build())
+        .build();
+    assert!(matches!(result, Err(Error::SegmentUnknown(name)) if name == "music"));
+}
+
+#[test]
+fn segments_overlap() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .segment(Segment::new("data", 0x0801))
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().lda_imm(0x00).build())
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .segment("data")
+                .instructions(InstructionBuilder::default().raw(&[0x01]).build())
+                .build(),
+        )
+        .build()?;
+    assert!(matches!(
+        ProgramGenerator::default().generate(application),
+        Err(Error::SegmentOverlap(_, _))
+    ));
+    Ok(())
+}
+
+fn application_at_end_of_memory(size: usize) -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .entry_point(0xFFF0)
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().raw(&vec![0xEA; size]).build())
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn segment_out_of_memory() -> AssemblerResult<()> {
+    let bytes = ProgramGenerator::default().generate(application_at_end_of_memory(16)?)?;
+    assert_eq!(18, bytes.len());
+
+    assert!(matches!(
+        ProgramGenerator::default().generate(application_at_end_of_memory(33)?),
+        Err(Error::SegmentOutOfMemory(segment)) if segment == "default"
+    ));
+    assert!(matches!(
+        SegmentProgramGenerator::default().generate(application_at_end_of_memory(17)?),
+        Err(Error::SegmentOutOfMemory(_))
+    ));
+    Ok(())
+}
+
+fn relocated_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Relocated")
+        .entry_point(0x0800)
+        .segment(Segment::relocated("irq", 0x0810, 0xC000))
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(InstructionBuilder::default().jsr_addr("irq").rts().build())
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("irq")
+                .segment("irq")
+                .instructions(
+                    InstructionBuilder::default()
+                        .label("irq")
+                        .inc_addr("VIC2_BORDER_COLOR")
+                        .bne_addr("irq")
+                        .rts()
+                        .build(),
+                )
+                .build(),
+        )
+        .include_vic2_defines()
+        .build()
+}
+
+#[test]
+fn relocated_segment() -> AssemblerResult<()> {
+    let application = relocated_application()?;
+    assert_eq!(0xC000, application.lookup_address(&"irq".to_string())?);
+
+    let bytes = ProgramGenerator::default().generate(relocated_application()?)?;
+    #[rustfmt::skip]
+    let expected = vec![
+        0x00, 0x08,
+        0x20, 0x00, 0xC0, 0x60,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0xEE, 0x20, 0xD0, 0xD0, 0xFB, 0x60,
+    ];
+    assert_eq!(expected, bytes);
+
+    let source = DasmGenerator::default().generate(application)?;
+    assert!(source.contains("  seg irq\n  org $0810\n  rorg $C000\n"));
+    assert!(source.contains("  rend"));
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(Some(0xC000), parsed.segments[0].run_address);
+    assert_eq!(expected, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
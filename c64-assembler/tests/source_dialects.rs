@@ -0,0 +1,362 @@
+use std::{path::PathBuf, process::Command};
+
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{AcmeGenerator, Ca65Generator, DasmGenerator, Generator, KickAssemblerGenerator, ProgramGenerator},
+    memory::{expression::Expression, segment::Segment},
+    validator::AssemblerResult,
+    Application,
+};
+
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Dialects")
+        .include_vic2_defines()
+        .define_address("POINTER", 0xFE)
+        .define_address("screen", 0x0400)
+        .segment(Segment::new("music", 0x1000))
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .add_basic_header()
+                        .label("main_entry_point")
+                        .lda_imm(0x00)
+                        .comment("Load black color")
+                        .sta_addr("VIC2_BORDER_COLOR")
+                        .lda_imm_low("data")
+                        .sta_addr("POINTER")
+                        .lda_imm_high("data")
+                        .sta_addr_offs("POINTER", 1)
+                        .ldy_imm(0x01)
+                        .lda_ind_y("POINTER")
+                        .ldx_imm(0x00)
+                        .lda_ind_x("POINTER")
+                        .lda_addr_x("data")
+                        .ldx_addr_y("data")
+                        .asl_acc()
+                        .lda_addr_x((Expression::name("screen") >> 8) | 0x20)
+                        .ldx_imm_expr((Expression::name("end") - Expression::name("data")) * 2)
+                        .jsr_addr("clear_border")
+                        .bne_addr("loop")
+                        .jmp_ind("data")
+                        .label("data")
+                        .raw(&[0x01, 0x02, 0x03])
+                        .words_addr(&["main_entry_point"])
+                        .low_bytes_addr(&["data", "end"])
+                        .fill(2, 0xEA)
+                        .align(16)
+                        .text_petscii("HI")
+                        .label("end")
+                        .build(),
+                )
+                .function(
+                    FunctionBuilder::default()
+                        .name("clear_border")
+                        .doc(&["Set the border color to black"])
+                        .instructions(
+                            InstructionBuilder::default()
+                                .ldx_imm(0x10)
+                                .label("loop")
+                                .dex()
+                                .bne_addr("loop")
+                                .rts()
+                                .build(),
+                        )
+                        .build(),
+                )
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("music")
+                .segment("music")
+                .instructions(InstructionBuilder::default().label("tune").raw(&[0x10, 0x20]).build())
+                .build(),
+        )
+        .build()
+}
+
+fn assert_lines(source: &str, lines: &[&str]) {
+    for line in lines {
+        assert!(source.lines().any(|l| l == *line), "missing line '{line}' in\n{source}");
+    }
+}
+
+#[test]
+fn dasm_source_unchanged() -> AssemblerResult<()> {
+    let source = DasmGenerator::default().generate(test_application()?)?;
+    assert_lines(
+        &source,
+        &[
+            "  processor 6502",
+            "  seg default",
+            "  org $0800",
+            "  lda (POINTER),y",
+            "  lda (POINTER,x)",
+            "  asl A",
+            "  lda screen>>8|32,x",
+            "  ldx #[end-data]*2",
+            "  bne loop",
+            "  jmp (data)",
+            "  ds 2, $EA",
+            "  align 16",
+            "clear_border:",
+        ],
+    );
+    Ok(())
+}
+
+#[test]
+fn ca65_source() -> AssemblerResult<()> {
+    let source = Ca65Generator::default().generate(test_application()?)?;
+    assert_lines(
+        &source,
+        &[
+            "; --- Application: DIALECTS ---",
+            "  .setcpu \"6502\"",
+            "VIC2_BORDER_COLOR = $D020",
+            "  .segment \"CODE\"",
+            "  .org $0800",
+            "  .byte $00, $0C, $08    ; New basic line",
+            "main_entry_point:",
+            "  lda #$00               ; Load black color",
+            "  sta VIC2_BORDER_COLOR",
+            "  lda #<data",
+            "  sta POINTER",
+            "  lda #>data",
+            "  sta POINTER+1",
+            "  ldy #$01",
+            "  lda (POINTER),y",
+            "  lda (POINTER,x)",
+            "  lda data,x",
+            "  ldx data,y",
+            "  asl a",
+            "  lda 0+(screen>>8)|32,x",
+            "  ldx #(end-data)*2",
+            "  jsr clear_border",
+            "  bne clear_border::loop",
+            "  jmp (data)",
+            "  .byte $01, $02, $03",
+            "  .word main_entry_point",
+            "  .byte <data, <end",
+            "  .res 2, $EA",
+            "  .res 1, $00",
+            "; Set the border color to black",
+            ".proc clear_border",
+            "  bne loop",
+            ".endproc",
+            "  .segment \"music\"",
+            "  .org $1000",
+        ],
+    );
+    Ok(())
+}
+
+#[test]
+fn ca65_default_segment() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().rts().build())
+                .build(),
+        )
+        .build()?;
+    let source = Ca65Generator::default().generate(application)?;
+    assert_lines(&source, &["  .segment \"CODE\"", "  .org $0800"]);
+    Ok(())
+}
+
+#[test]
+fn acme_source() -> AssemblerResult<()> {
+    let source = AcmeGenerator::default().generate(test_application()?)?;
+    assert_lines(
+        &source,
+        &[
+            "  !cpu 6502",
+            "POINTER = $FE",
+            "; --- Segment: DEFAULT ---",
+            "  * = $0800",
+            "main_entry_point",
+            "  lda #$00               ; Load black color",
+            "  lda (POINTER),y",
+            "  lda (POINTER,x)",
+            "  asl",
+            "  lda 0+(screen>>8)|32,x",
+            "  bne loop",
+            "  !byte $01, $02, $03",
+            "  !word main_entry_point",
+            "  !byte <data, <end",
+            "  !fill 2, $EA",
+            "  !align 15, 0",
+            "clear_border",
+            "loop",
+            "; --- Segment: MUSIC ---",
+            "  * = $1000",
+        ],
+    );
+    Ok(())
+}
+
+#[test]
+fn kick_assembler_source() -> AssemblerResult<()> {
+    let source = KickAssemblerGenerator::default().generate(test_application()?)?;
+    assert_lines(
+        &source,
+        &[
+            "// --- Application: DIALECTS ---",
+            "  .cpu _6502NoIllegals",
+            ".label VIC2_BORDER_COLOR = $D020",
+            "  * = $0800 \"default\"",
+            "// --- Module begin: MAIN ---",
+            "  lda #$00               // Load black color",
+            "  lda (POINTER),y",
+            "  asl",
+            "  lda 0+(screen>>8)|32,x",
+            "  .byte $01, $02, $03",
+            "  .word main_entry_point",
+            "  .fill 2, $EA",
+            "  .align 16",
+            "clear_border:",
+            "  * = $1000 \"music\"",
+        ],
+    );
+    Ok(())
+}
+
+#[test]
+fn relocated_segment() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .segment(Segment::relocated("irq", 0x0810, 0xC000))
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().rts().build())
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("irq")
+                .segment("irq")
+                .instructions(InstructionBuilder::default().label("irq").jmp_addr("irq").build())
+                .build(),
+        )
+        .build()?;
+    let source = AcmeGenerator::default().generate(application.clone())?;
+    assert_lines(&source, &["  * = $0810", "  !pseudopc $C000 {", "  }"]);
+    let source = KickAssemblerGenerator::default().generate(application.clone())?;
+    assert_lines(&source, &["  * = $0810 \"irq\"", "  .pseudopc $C000 {", "  }"]);
+    let source = Ca65Generator::default().generate(application)?;
+    assert_lines(&source, &["  .segment \"irq\"", "  .org $C000"]);
+    Ok(())
+}
+
+#[test]
+fn operator_precedence_is_explicit() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .define_address("table", 0x1234)
+        .module(
+            ModuleBuilder::default()
+                .instructions(
+                    InstructionBuilder::default()
+                        .lda_imm_expr((Expression::name("table") + 1) & 0x0F)
+                        .lda_imm_expr((Expression::name("table") & 0xFF) ^ (Expression::number(1) << 4))
+                        .build(),
+                )
+                .build(),
+        )
+        .build()?;
+    let source = AcmeGenerator::default().generate(application.clone())?;
+    assert_lines(&source, &["  lda #(table+1)&15", "  lda #(table&255) XOR (1<<4)"]);
+    let source = DasmGenerator::default().generate(application)?;
+    assert_lines(&source, &["  lda #table+1&15", "  lda #table&255^1<<4"]);
+    Ok(())
+}
+
+/// Directory for the files of an external assembler.
+fn work_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("c64-assembler-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn run(command: &mut Command) {
+    let output = command.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{command:?} failed\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// The program assembled by ca65 must match the program generated by the [ProgramGenerator].
+#[test]
+#[ignore = "requires ca65 and ld65"]
+fn roundtrip_ca65() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let expected = ProgramGenerator::default().generate(application.clone())?;
+    let source = Ca65Generator::default().generate(application)?;
+
+    let directory = work_directory("ca65");
+    std::fs::write(directory.join("program.s"), source).unwrap();
+    std::fs::write(
+        directory.join("program.cfg"),
+        "MEMORY { MAIN: start = $0800, size = $F800, file = %O; }
+SEGMENTS {
+  CODE: load = MAIN, type = rw;
+  music: load = MAIN, type = rw, start = $1000;
+}
+",
+    )
+    .unwrap();
+    run(Command::new("ca65")
+        .current_dir(&directory)
+        .args(["-o", "program.o", "program.s"]));
+    run(Command::new("ld65")
+        .current_dir(&directory)
+        .args(["-C", "program.cfg", "-o", "program.bin", "program.o"]));
+
+    let mut program = vec![0x00, 0x08];
+    program.extend(std::fs::read(directory.join("program.bin")).unwrap());
+    assert_eq!(expected, program);
+    Ok(())
+}
+
+/// The program assembled by ACME must match the program generated by the [ProgramGenerator].
+#[test]
+#[ignore = "requires acme"]
+fn roundtrip_acme() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let expected = ProgramGenerator::default().generate(application.clone())?;
+    let source = AcmeGenerator::default().generate(application)?;
+
+    let directory = work_directory("acme");
+    std::fs::write(directory.join("program.a"), source).unwrap();
+    run(Command::new("acme")
+        .current_dir(&directory)
+        .args(["-f", "cbm", "-o", "program.prg", "program.a"]));
+
+    assert_eq!(expected, std::fs::read(directory.join("program.prg")).unwrap());
+    Ok(())
+}
+
+/// The program assembled by KickAssembler must match the program generated by the
+/// [ProgramGenerator]. Set `KICKASS_JAR` to the location of `KickAss.jar` to run it.
+#[test]
+#[ignore = "requires java and KICKASS_JAR"]
+fn roundtrip_kick_assembler() -> AssemblerResult<()> {
+    let jar = std::env::var("KICKASS_JAR").expect("KICKASS_JAR must point to KickAss.jar");
+    let application = test_application()?;
+    let expected = ProgramGenerator::default().generate(application.clone())?;
+    let source = KickAssemblerGenerator::default().generate(application)?;
+
+    let directory = work_directory("kickassembler");
+    std::fs::write(directory.join("program.asm"), source).unwrap();
+    run(Command::new("java")
+        .current_dir(&directory)
+        .args(["-jar", &jar, "program.asm", "-o", "program.prg"]));
+
+    assert_eq!(expected, std::fs::read(directory.join("program.prg")).unwrap());
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    parser::{DasmParser, Parser},
+    validator::AssemblerResult,
+    Application, Function, Instructions, RemovedFunction,
+};
+use c64_assembler_macro::application;
+
+fn function(name: &str, instructions: Instructions) -> Function {
+    FunctionBuilder::default().name(name).instructions(instructions).build()
+}
+
+fn test_application(strip_unused_functions: bool) -> AssemblerResult<Application> {
+    let mut builder = ApplicationBuilder::default();
+    if strip_unused_functions {
+        builder.strip_unused_functions();
+    }
+    builder
+        .name("Strip functions")
+        .include_vic2_defines()
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .add_basic_header()
+                        .label("main_entry_point")
+                        .jsr_addr("init")
+                        .jmp_addr("loop")
+                        .build(),
+                )
+                .function(function(
+                    "init",
+                    InstructionBuilder::default().jsr_addr("clear").rts().build(),
+                ))
+                .function(function(
+                    "clear",
+                    InstructionBuilder::default()
+                        .lda_imm(0x00)
+                        .sta_addr("VIC2_BORDER_COLOR")
+                        .rts()
+                        .build(),
+                ))
+                .function(function(
+                    "unused",
+                    InstructionBuilder::default()
+                        .jsr_addr("unused_helper")
+                        .sta_addr("VIC2_BACKGROUND_COLOR")
+                        .rts()
+                        .build(),
+                ))
+                .function(function(
+                    "unused_helper",
+                    InstructionBuilder::default().lda_imm(0x01).rts().build(),
+                ))
+                .function(function(
+                    "main_loop",
+                    InstructionBuilder::default().label("loop").jmp_addr("loop").build(),
+                ))
+                .build(),
+        )
+        .module(
+            ModuleBuilder::default()
+                .name("library")
+                .function(function("library_unused", InstructionBuilder::default().rts().build()))
+                .build(),
+        )
+        .build()
+}
+
+fn function_names(application: &Application) -> Vec<&str> {
+    application
+        .modules
+        .iter()
+        .flat_map(|module| &module.functions)
+        .map(|function| function.name.as_str())
+        .collect()
+}
+
+#[test]
+fn keep_all_functions_by_default() -> AssemblerResult<()> {
+    let application = test_application(false)?;
+    assert_eq!(6, function_names(&application).len());
+    assert!(application.removed_functions.is_empty());
+    assert_eq!(0, application.removed_bytes());
+    Ok(())
+}
+
+#[test]
+fn strip_unused_functions() -> AssemblerResult<()> {
+    let application = test_application(true)?;
+    assert_eq!(vec!["init", "clear", "main_loop"], function_names(&application));
+    assert_eq!(
+        vec![
+            RemovedFunction {
+                module: "main".to_string(),
+                name: "unused".to_string(),
+                byte_size: 7,
+            },
+            RemovedFunction {
+                module: "main".to_string(),
+                name: "unused_helper".to_string(),
+                byte_size: 3,
+            },
+            RemovedFunction {
+                module: "library".to_string(),
+                name: "library_unused".to_string(),
+                byte_size: 1,
+            },
+        ],
+        application.removed_functions
+    );
+    assert_eq!(11, application.removed_bytes());
+    assert!(application.lookup_address(&"unused".to_string()).is_err());
+    Ok(())
+}
+
+#[test]
+fn strip_unused_functions_program() -> AssemblerResult<()> {
+    let program = ProgramGenerator::default().generate(test_application(false)?)?;
+    let application = test_application(true)?;
+    let removed_bytes = application.removed_bytes();
+    let stripped_program = ProgramGenerator::default().generate(application)?;
+    assert_eq!(program.len() - removed_bytes, stripped_program.len());
+    Ok(())
+}
+
+/// The dasm source contains the same functions as the program.
+#[test]
+fn strip_unused_functions_dasm() -> AssemblerResult<()> {
+    let application = test_application(true)?;
+    let program = ProgramGenerator::default().generate(application.clone())?;
+    let source = DasmGenerator::default().generate(application)?;
+    assert!(!source.contains("unused"));
+    // Defines that were only used by the removed functions aren't emitted.
+    assert!(!source.contains("VIC2_BACKGROUND_COLOR"));
+
+    let parsed = DasmParser::default().parse(&source)?;
+    assert_eq!(program, ProgramGenerator::default().generate(parsed)?);
+    Ok(())
+}
+
+#[test]
+fn strip_unused_functions_macro() -> AssemblerResult<()> {
+    let application = application!(
+        strip_unused_functions
+        module!(
+            name="main"
+            instructions!(
+                rts
+            )
+            function!(
+                name="unused"
+                instructions!(
+                    rts
+                )
+            )
+        )
+    )?;
+    assert!(application.modules[0].functions.is_empty());
+    assert_eq!(1, application.removed_bytes());
+    Ok(())
+}
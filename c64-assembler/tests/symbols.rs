@@ -0,0 +1,132 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{Generator, SymbolFormat, SymbolGenerator, SymbolKind},
+    validator::AssemblerResult,
+    Application,
+};
+
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .name("Symbols")
+        .define_address("VIC2_BORDER_COLOR", 0xD020)
+        .define_address("counter", 0xFB)
+        .module(
+            ModuleBuilder::default()
+                .name("main")
+                .instructions(
+                    InstructionBuilder::default()
+                        .add_basic_header()
+                        .label("main_entry_point")
+                        .jsr_addr("clear")
+                        .rts()
+                        .build(),
+                )
+                .function(
+                    FunctionBuilder::default()
+                        .name("clear")
+                        .instructions(
+                            InstructionBuilder::default()
+                                .ldx_imm(0x00)
+                                .label("loop")
+                                .stx_addr("VIC2_BORDER_COLOR")
+                                .dex()
+                                .bne_addr("loop")
+                                .rts()
+                                .build(),
+                        )
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn symbols() -> AssemblerResult<()> {
+    let symbols = test_application()?.symbols();
+    assert_eq!(5, symbols.len());
+    assert_eq!("counter", symbols[0].name);
+    assert_eq!(SymbolKind::Define, symbols[0].kind);
+    assert_eq!("clear", symbols[2].name);
+    assert_eq!(0x0812, symbols[2].address);
+    assert_eq!(SymbolKind::Function, symbols[2].kind);
+    assert_eq!("main.clear.loop", symbols[3].scoped_name());
+    Ok(())
+}
+
+#[test]
+fn vice_labels() -> AssemblerResult<()> {
+    let labels = SymbolGenerator::default().generate(test_application()?)?;
+    assert_eq!(
+        "al C:00FB .counter
+al C:080E .main_entry_point
+al C:0812 .clear
+al C:0814 .loop
+al C:D020 .VIC2_BORDER_COLOR
+",
+        labels
+    );
+    Ok(())
+}
+
+#[test]
+fn vice_labels_scoped() -> AssemblerResult<()> {
+    let labels = SymbolGenerator::default().scoped(true).generate(test_application()?)?;
+    assert_eq!(
+        "al C:00FB .counter
+al C:080E .main.main_entry_point
+al C:0812 .main.clear
+al C:0814 .main.clear.loop
+al C:D020 .VIC2_BORDER_COLOR
+",
+        labels
+    );
+    Ok(())
+}
+
+#[test]
+fn plain_symbols() -> AssemblerResult<()> {
+    let symbols = SymbolGenerator::default()
+        .format(SymbolFormat::Plain)
+        .generate(test_application()?)?;
+    assert_eq!(
+        "counter = $00FB
+main_entry_point = $080E
+clear = $0812
+loop = $0814
+VIC2_BORDER_COLOR = $D020
+",
+        symbols
+    );
+    Ok(())
+}
+
+#[test]
+fn json_symbols() -> AssemblerResult<()> {
+    let symbols = SymbolGenerator::default()
+        .format(SymbolFormat::Json)
+        .scoped(true)
+        .generate(test_application()?)?;
+    assert_eq!(
+        r#"[
+  {"name": "counter", "address": 251, "kind": "define", "module": null, "function": null},
+  {"name": "main.main_entry_point", "address": 2062, "kind": "label", "module": "main", "function": null},
+  {"name": "main.clear", "address": 2066, "kind": "function", "module": "main", "function": null},
+  {"name": "main.clear.loop", "address": 2068, "kind": "label", "module": "main", "function": "clear"},
+  {"name": "VIC2_BORDER_COLOR", "address": 53280, "kind": "define", "module": null, "function": null}
+]
+"#,
+        symbols
+    );
+    Ok(())
+}
+
+#[test]
+fn json_symbols_empty() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default().build()?;
+    let symbols = SymbolGenerator::default()
+        .format(SymbolFormat::Json)
+        .generate(application)?;
+    assert_eq!("[]\n", symbols);
+    Ok(())
+}
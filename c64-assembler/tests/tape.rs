@@ -0,0 +1,142 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{Generator, ProgramGenerator},
+    tape::{t64::T64, tap::Tap, Error, TapeFile},
+    validator::AssemblerResult,
+};
+
+fn program(color: u8) -> AssemblerResult<Vec<u8>> {
+    let application = ApplicationBuilder::default()
+        .module(
+            ModuleBuilder::default()
+                .instructions(
+                    InstructionBuilder::default()
+                        .add_basic_header()
+                        .lda_imm(color)
+                        .sta_addr("VIC2_BORDER_COLOR")
+                        .rts()
+                        .build(),
+                )
+                .build(),
+        )
+        .include_vic2_defines()
+        .build()?;
+    ProgramGenerator::default().generate(application)
+}
+
+fn files() -> AssemblerResult<Vec<TapeFile>> {
+    Ok(vec![
+        TapeFile::from_prg("black border", &program(0)?)?,
+        TapeFile::from_prg("white border", &program(1)?)?,
+    ])
+}
+
+#[test]
+fn t64_write_and_read() -> AssemblerResult<()> {
+    let mut container = T64::new("borders")?;
+    for file in files()? {
+        container.add_file(file);
+    }
+    let bytes = container.to_bytes();
+    assert_eq!(64 + 30 * 32 + 2 * 20, bytes.len());
+    assert_eq!(b"C64 tape image file\0", &bytes[0..20]);
+    assert_eq!(&[0x01, 0x01, 0x1E, 0x00, 0x02, 0x00], &bytes[0x20..0x26]);
+    assert_eq!(b"BORDERS                 ", &bytes[0x28..0x40]);
+    assert_eq!(
+        &[0x01, 0x82, 0x00, 0x08, 0x14, 0x08, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00],
+        &bytes[0x40..0x4C]
+    );
+    assert_eq!(b"BLACK BORDER    ", &bytes[0x50..0x60]);
+    assert_eq!(&[0x14, 0x04], &bytes[0x68..0x6A]);
+
+    let container = T64::from_bytes(&bytes)?;
+    assert_eq!("borders", container.name());
+    assert_eq!(files()?, container.files);
+    assert_eq!(program(1)?, container.read_file("white border")?);
+    assert!(matches!(container.read_file("red border"), Err(Error::FileNotFound(_))));
+    assert!(matches!(
+        T64::from_bytes(&bytes[0..32]),
+        Err(Error::InvalidTapeImage(_))
+    ));
+    Ok(())
+}
+
+/// Number of pulses of a block with the given number of bytes.
+fn block_pulses(leader: usize, bytes: usize) -> usize {
+    leader + 2 * ((9 + bytes + 1) * 20 + 2) + 0x4F + 0x4E
+}
+
+#[test]
+fn tap_write_and_read() -> AssemblerResult<()> {
+    let mut tape = Tap::default();
+    for file in files()? {
+        tape.add_file(&file);
+    }
+    assert_eq!(
+        2 * (block_pulses(0x6A00, 192) + block_pulses(0x1A00, 20)),
+        tape.pulses.len()
+    );
+
+    let bytes = tape.to_bytes();
+    assert_eq!(b"C64-TAPE-RAW\x01\0\0\0", &bytes[0..16]);
+    assert_eq!(
+        (bytes.len() - 20) as u32,
+        u32::from_le_bytes([bytes[16], bytes[17], bytes[18], bytes[19]])
+    );
+    assert_eq!(&[0x30, 0x30], &bytes[20..22]);
+
+    let tape = Tap::from_bytes(&bytes)?;
+    assert_eq!(files()?, tape.files()?);
+    assert!(matches!(
+        Tap::from_bytes(&bytes[0..12]),
+        Err(Error::InvalidTapeImage(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn tap_long_pulses() -> AssemblerResult<()> {
+    let file = TapeFile::from_prg("black border", &program(0)?)?;
+    let mut tape = Tap::default();
+    tape.add_file(&file);
+    tape.pulses.push(985248);
+    tape.add_file(&file);
+
+    let bytes = tape.to_bytes();
+    let pause = 20 + tape.pulses.len() / 2;
+    assert_eq!(&[0x00, 0xA0, 0x08, 0x0F], &bytes[pause..pause + 4]);
+
+    let tape = Tap::from_bytes(&bytes)?;
+    assert_eq!(985248, tape.pulses[tape.pulses.len() / 2]);
+    assert_eq!(vec![file.clone(), file], tape.files()?);
+    Ok(())
+}
+
+#[test]
+fn tap_repeated_copy() -> AssemblerResult<()> {
+    let file = TapeFile::from_prg("black border", &program(0)?)?;
+    let mut tape = Tap::default();
+    tape.add_file(&file);
+
+    // Damage the first byte after the countdown of the first copy of the data block.
+    let first_data_byte = block_pulses(0x6A00, 192) + 0x1A00 + 9 * 20;
+    tape.pulses[first_data_byte + 2] = 0x56 * 8;
+    assert_eq!(vec![file.clone()], tape.files()?);
+
+    // Damage the repeated copy as well.
+    let repeated_data_byte = first_data_byte + (9 + 20 + 1) * 20 + 2 + 0x4F;
+    tape.pulses[repeated_data_byte + 2] = 0x56 * 8;
+    let Err(error) = tape.files() else {
+        panic!("both copies are damaged");
+    };
+    assert_eq!(
+        "invalid tape image: both copies of a block are damaged",
+        error.to_string()
+    );
+
+    let mut tape = Tap::default();
+    tape.add_file(&file);
+    tape.pulses.truncate(block_pulses(0x6A00, 192));
+    assert!(matches!(tape.files(), Err(Error::InvalidTapeImage(_))));
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, FunctionBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ProgramGenerator},
+    validator::AssemblerResult,
+    Application,
+};
+use c64_assembler_emulator::{
+    cpu::{Cpu, StopReason},
+    memory::Ram,
+};
+
+/// Copy loop where the branch back to the start of the loop crosses a page.
+fn test_application() -> AssemblerResult<Application> {
+    ApplicationBuilder::default()
+        .entry_point(0x08F8)
+        .define_address("SCREEN", 0x0400)
+        .module(
+            ModuleBuilder::default()
+                .instructions(InstructionBuilder::default().jsr_addr("copy").rts().build())
+                .function(
+                    FunctionBuilder::default()
+                        .name("copy")
+                        .instructions(
+                            InstructionBuilder::default()
+                                .ldx_imm(0x07)
+                                .label("copy_loop")
+                                .lda_addr_x("data")
+                                .sta_addr_x("SCREEN")
+                                .dex()
+                                .bpl_addr("copy_loop")
+                                .rts()
+                                .label("data")
+                                .raw(&[1, 2, 3, 4, 5, 6, 7, 8])
+                                .build(),
+                        )
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn function_timing() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let timing = application.modules[0].functions[0].timing(&application)?;
+
+    let blocks = timing
+        .blocks
+        .iter()
+        .map(|block| (block.address, block.min_cycles, block.max_cycles))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(0x08FC, 2, 2), (0x08FE, 13, 16), (0x0907, 6, 6)], blocks);
+    assert_eq!(2..6, timing.blocks[1].instructions);
+
+    let branches = timing.page_crossing_branches().collect::<Vec<_>>();
+    assert_eq!(1, branches.len());
+    assert_eq!(0x0905, branches[0].address);
+    Ok(())
+}
+
+#[test]
+fn timing_matches_emulator() -> AssemblerResult<()> {
+    let application = test_application()?;
+    let timing = application.modules[0].functions[0].timing(&application)?;
+    let function_address = application.lookup_address(&"copy".to_string())?;
+    let program = ProgramGenerator::default().generate(application)?;
+
+    let mut cpu = Cpu::<Ram>::default();
+    cpu.load_program(&program).unwrap();
+    cpu.set_cycle_limit(Some(1000));
+    assert_eq!(Ok(StopReason::Return), cpu.call(function_address));
+
+    // Data doesn't cross a page: the loop takes its maximum cycles minus the page penalty
+    // of the indexed read. The last branch isn't taken.
+    let loop_block = &timing.blocks[1];
+    let expected = timing.blocks[0].max_cycles + 8 * (loop_block.max_cycles - 1) - 2 + timing.blocks[2].max_cycles;
+    assert_eq!(expected as u64, cpu.cycles());
+    Ok(())
+}
+
+#[test]
+fn dasm_cycle_annotations() -> AssemblerResult<()> {
+    let source = DasmGenerator::default()
+        .annotate_cycles(true)
+        .generate(test_application()?)?;
+    assert!(source.contains("  lda data,x             ; [4-5]"));
+    assert!(source.contains("  bpl copy_loop          ; [2-4]"));
+    assert!(!source.contains("byte $01, $02, $03, $04, $05, $06, $07, $08 ;"));
+    Ok(())
+}
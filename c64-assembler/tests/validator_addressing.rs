@@ -1,9 +1,31 @@
 use c64_assembler::{
-    instruction::operation::Operation,
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{Generator, ProgramGenerator},
+    instruction::{operation::Operation, Instruction},
+    memory::{
+        address_mode::{AddressMode, Immediate},
+        label::AddressReference,
+    },
     validator::{AssemblerResult, Error, Validator},
+    Application,
 };
 use c64_assembler_macro::application;
 
+/// Application with a single instruction that is constructed using the public fields.
+fn application_with_instruction(operation: Operation, address_mode: AddressMode) -> AssemblerResult<Application> {
+    let mut application = ApplicationBuilder::default()
+        .define_address("zeropage", 0x0010)
+        .define_address("screen", 0x0400)
+        .module(ModuleBuilder::default().name("main").build())
+        .build()?;
+    application.modules[0].instructions.instructions.push(Instruction {
+        operation,
+        address_mode,
+        ..Default::default()
+    });
+    Ok(application)
+}
+
 #[test]
 fn address_names_not_exist() -> AssemblerResult<()> {
     let application = application!(module!(instruction!(
@@ -136,3 +158,98 @@ fn label_at_end_of_instructions() -> AssemblerResult<()> {
     }
     Ok(())
 }
+
+#[test]
+fn address_mode_not_supported() -> AssemblerResult<()> {
+    let application = application_with_instruction(Operation::STA, AddressMode::Immediate(Immediate::Byte(0x00)))?;
+    let diagnostics = application.diagnostics();
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(
+        "instruction 'sta' doesn't support immediate addressing (module 'main', instruction 0)",
+        diagnostics[0].to_string()
+    );
+
+    let result = ProgramGenerator::default().generate(application);
+    assert!(matches!(
+        result,
+        Err(Error::AddressModeNotSupported { instruction, address_mode }) if instruction == "sta" && address_mode == "immediate"
+    ));
+    Ok(())
+}
+
+#[test]
+fn address_mode_implied_not_supported() -> AssemblerResult<()> {
+    let application = application_with_instruction(Operation::LDA, AddressMode::Implied)?;
+    assert!(matches!(
+        application.validate(),
+        Err(Error::AddressModeNotSupported { instruction, .. }) if instruction == "lda"
+    ));
+    assert!(ProgramGenerator::default().generate(application).is_err());
+    Ok(())
+}
+
+/// stx only has a zeropage op-code for the y-indexed address mode.
+#[test]
+fn address_mode_missing_absolute_variant() -> AssemblerResult<()> {
+    let application =
+        application_with_instruction(Operation::STX, AddressMode::AbsoluteY(AddressReference::new("screen")))?;
+    assert!(matches!(
+        application.validate(),
+        Err(Error::ZeropageAddressRequired { instruction, value: 0x0400, .. }) if instruction == "stx"
+    ));
+    assert!(matches!(
+        ProgramGenerator::default().generate(application),
+        Err(Error::ZeropageAddressRequired { .. })
+    ));
+
+    let application = application_with_instruction(
+        Operation::STX,
+        AddressMode::AbsoluteY(AddressReference::new("zeropage")),
+    )?;
+    assert!(application.validate().is_ok());
+    assert_eq!(
+        vec![0x00, 0x08, 0x96, 0x10],
+        ProgramGenerator::default().generate(application)?
+    );
+    Ok(())
+}
+
+#[test]
+fn address_mode_indirect_requires_zeropage() -> AssemblerResult<()> {
+    for address_mode in [
+        AddressMode::IndexedIndirect(AddressReference::new("screen")),
+        AddressMode::IndirectIndexed(AddressReference::new("screen")),
+    ] {
+        let application = application_with_instruction(Operation::LDA, address_mode)?;
+        let diagnostics = application.diagnostics();
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(
+            "instruction 'lda' requires a zeropage address, but 'screen' is 1024 (module 'main', instruction 0)",
+            diagnostics[0].to_string()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn address_modes_of_builder_are_supported() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .define_address("zeropage", 0x0010)
+        .module(
+            ModuleBuilder::default()
+                .instructions(
+                    InstructionBuilder::default()
+                        .lda_ind_y("zeropage")
+                        .sta_ind_x("zeropage")
+                        .ldx_addr_y("zeropage")
+                        .stx_addr_y("zeropage")
+                        .asl_acc()
+                        .jmp_ind("zeropage")
+                        .build(),
+                )
+                .build(),
+        )
+        .build()?;
+    assert!(application.diagnostics().is_empty());
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use c64_assembler::{
+    builder::{ApplicationBuilder, InstructionBuilder, ModuleBuilder},
+    generator::{DasmGenerator, Generator, ListingGenerator, ProgramGenerator},
+    memory::zeropage::{ZeropageAllocation, ZeropageKind, ZeropagePool},
+    validator::{AssemblerResult, Error, Validator},
+    Module, ParameterKind,
+};
+
+/// Copies a byte via a zeropage pointer.
+fn copy_module() -> Module {
+    ModuleBuilder::default()
+        .name("copy")
+        .zeropage("source", ZeropageKind::Pointer)
+        .zeropage("counter", ZeropageKind::Byte)
+        .instructions(
+            InstructionBuilder::default()
+                .lda_ind_y("source")
+                .stx_addr("counter")
+                .rts()
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn allocate_zeropage() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .module(copy_module())
+        .module(
+            ModuleBuilder::default()
+                .name("music")
+                .zeropage("music_pointer", ZeropageKind::Word)
+                .instructions(InstructionBuilder::default().sta_addr("music_pointer").build())
+                .build(),
+        )
+        .build()?;
+    assert!(application.validate().is_ok());
+    assert_eq!(
+        vec![
+            ZeropageAllocation {
+                module: "copy".to_string(),
+                name: "source".to_string(),
+                kind: ZeropageKind::Pointer,
+                address: 0xFB,
+            },
+            ZeropageAllocation {
+                module: "copy".to_string(),
+                name: "counter".to_string(),
+                kind: ZeropageKind::Byte,
+                address: 0x02,
+            },
+            ZeropageAllocation {
+                module: "music".to_string(),
+                name: "music_pointer".to_string(),
+                kind: ZeropageKind::Word,
+                address: 0xFD,
+            },
+        ],
+        application.zeropage_map
+    );
+
+    let program = ProgramGenerator::default().generate(application.clone())?;
+    assert_eq!(vec![0x00, 0x08, 0xB1, 0xFB, 0x86, 0x02, 0x60, 0x85, 0xFD], program);
+
+    let source = DasmGenerator::default().generate(application.clone())?;
+    assert!(source.contains("source = $FB"));
+    assert!(source.contains("  lda (source),y\n"));
+
+    let listing = ListingGenerator::default().generate(application)?;
+    assert!(listing.contains("; --- Zeropage ---\n"));
+    assert!(listing.contains("; $FB-$FC  pointer  source (copy)\n"));
+    assert!(listing.contains("; $02      byte     counter (copy)\n"));
+    Ok(())
+}
+
+#[test]
+fn allocate_zeropage_module_instances() -> AssemblerResult<()> {
+    let module = copy_module();
+    let mut builder = ApplicationBuilder::default();
+    builder
+        .zeropage_pool(ZeropagePool::without_basic())
+        .module_instance("first", &module, &[])
+        .module_instance("second", &module, &[]);
+    let application = builder.build()?;
+    assert_eq!(0x02, application.lookup_address(&"first_source".to_string())?);
+    assert_eq!(0x04, application.lookup_address(&"first_counter".to_string())?);
+    assert_eq!(0x05, application.lookup_address(&"second_source".to_string())?);
+    assert_eq!(0x07, application.lookup_address(&"second_counter".to_string())?);
+
+    // Building again assigns the same addresses without defining the variables twice.
+    let rebuilt = builder.build()?;
+    assert_eq!(application.zeropage_map, rebuilt.zeropage_map);
+    assert!(rebuilt.validate().is_ok());
+    Ok(())
+}
+
+#[test]
+fn allocate_zeropage_skips_bound_parameters() -> AssemblerResult<()> {
+    let module = ModuleBuilder::default()
+        .name("scroll")
+        .parameter("SCROLL_POINTER", ParameterKind::Zeropage)
+        .zeropage("scroll_offset", ZeropageKind::Word)
+        .build();
+    let application = ApplicationBuilder::default()
+        .zeropage_pool(ZeropagePool::empty().free(0xFB..=0xFE))
+        .module_instance("top", &module, &[("SCROLL_POINTER", 0xFB)])
+        .build()?;
+    assert_eq!(0xFD, application.lookup_address(&"top_scroll_offset".to_string())?);
+    Ok(())
+}
+
+#[test]
+fn allocate_zeropage_skips_defines() -> AssemblerResult<()> {
+    let application = ApplicationBuilder::default()
+        .zeropage_pool(ZeropagePool::empty().free(0xFB..=0xFE))
+        .define_address("POINTER", 0xFB)
+        .module(
+            ModuleBuilder::default()
+                .name("scroll")
+                .zeropage("scroll_offset", ZeropageKind::Byte)
+                .build(),
+        )
+        .build()?;
+    assert_eq!(0xFD, application.lookup_address(&"scroll_offset".to_string())?);
+    Ok(())
+}
+
+#[test]
+fn zeropage_exhausted() {
+    let result = ApplicationBuilder::default()
+        .module(copy_module())
+        .module(
+            ModuleBuilder::default()
+                .name("music")
+                .zeropage("music_pointer", ZeropageKind::Pointer)
+                .zeropage("music_tempo", ZeropageKind::Byte)
+                .build(),
+        )
+        .build();
+    let Err(error) = result else {
+        panic!("expected an error");
+    };
+    assert!(matches!(
+        &error,
+        Error::ZeropageExhausted { module, variable } if module == "music" && variable == "music_tempo"
+    ));
+    assert_eq!(
+        "no free zeropage address for variable 'music_tempo' of module 'music'",
+        error.to_string()
+    );
+}